@@ -64,9 +64,7 @@ pub(crate) fn new(tcx: TyCtxt<'tcx>, isa: &dyn TargetIsa) -> Self {
             cranelift_codegen::VERSION,
         );
         let comp_dir = tcx
-            .sess
-            .opts
-            .working_dir
+            .working_directory(())
             .to_string_lossy(FileNameDisplayPreference::Remapped)
             .into_owned();
         let (name, file_info) = match tcx.sess.local_crate_source_file.clone() {
@@ -134,9 +134,7 @@ fn new(tcx: TyCtxt<'_>, version: u32) -> Self {
             // compilation directory can be combined with the the relative paths
             // to get absolute paths, if needed.
             let working_dir = tcx
-                .sess
-                .opts
-                .working_dir
+                .working_directory(())
                 .remapped_path_if_available()
                 .to_string_lossy()
                 .to_string();
@@ -545,7 +545,7 @@ fn alloc_new_file_metadata<'ll>(
 
         let (directory, file_name) = match &source_file.name {
             FileName::Real(filename) => {
-                let working_directory = &cx.sess().opts.working_dir;
+                let working_directory = cx.tcx.working_directory(());
                 debug!(?working_directory);
 
                 let filename = cx
@@ -817,7 +817,7 @@ pub fn build_compile_unit_di_node<'ll, 'tcx>(
     let producer = format!("clang LLVM ({})", rustc_producer);
 
     let name_in_debuginfo = name_in_debuginfo.to_string_lossy();
-    let work_dir = tcx.sess.opts.working_dir.to_string_lossy(FileNameDisplayPreference::Remapped);
+    let work_dir = tcx.working_directory(()).to_string_lossy(FileNameDisplayPreference::Remapped);
     let flags = "\0";
     let output_filenames = tcx.output_filenames(());
     let split_name = if tcx.sess.target_can_use_split_dwarf() {
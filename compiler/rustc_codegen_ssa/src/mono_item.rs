@@ -6,6 +6,7 @@
 use rustc_middle::mir::mono::{Linkage, Visibility};
 use rustc_middle::ty;
 use rustc_middle::ty::layout::{HasTyCtxt, LayoutOf};
+use rustc_middle::ty::print::with_no_trimmed_paths;
 use rustc_middle::ty::Instance;
 
 pub trait MonoItemExt<'a, 'tcx> {
@@ -88,6 +89,10 @@ fn define<Bx: BuilderMethods<'a, 'tcx>>(&self, cx: &'a Bx::CodegenCx) {
                 }
             }
             MonoItem::Fn(instance) => {
+                let _prof_timer =
+                    cx.tcx().prof.generic_activity_with_arg_recorder("codegen_instance", |recorder| {
+                        recorder.record_arg(with_no_trimmed_paths!(instance.to_string()));
+                    });
                 base::codegen_instance::<Bx>(&cx, instance);
             }
         }
@@ -99,6 +99,11 @@
 use parking_lot::RwLock;
 use smallvec::SmallVec;
 
+mod time_report;
+
+pub use time_report::{TimeReport, Timing};
+use time_report::{ReportEvent, ReportGuard};
+
 bitflags::bitflags! {
     struct EventFilter: u32 {
         const GENERIC_ACTIVITIES  = 1 << 0;
@@ -163,6 +168,11 @@ pub struct SelfProfilerRef {
 
     // Print extra verbose generic activities to stdout
     print_extra_verbose_generic_activities: bool,
+
+    // This field is `None` unless `-Z time-report` is enabled. Unlike the
+    // `SelfProfiler`, it records query providers and generic activities
+    // whatever the event filter.
+    time_report: Option<Arc<TimeReport>>,
 }
 
 impl SelfProfilerRef {
@@ -170,6 +180,7 @@ pub fn new(
         profiler: Option<Arc<SelfProfiler>>,
         print_verbose_generic_activities: bool,
         print_extra_verbose_generic_activities: bool,
+        time_report: Option<Arc<TimeReport>>,
     ) -> SelfProfilerRef {
         // If there is no SelfProfiler then the filter mask is set to NONE,
         // ensuring that nothing ever tries to actually access it.
@@ -181,6 +192,7 @@ pub fn new(
             event_filter_mask,
             print_verbose_generic_activities,
             print_extra_verbose_generic_activities,
+            time_report,
         }
     }
 
@@ -211,6 +223,31 @@ fn cold_call<F>(profiler_ref: &SelfProfilerRef, f: F) -> TimingGuard<'_>
         }
     }
 
+    /// Makes `guard` also record the event built by `event` in the time
+    /// report, if there is one.
+    #[inline(always)]
+    fn report<'a>(
+        &'a self,
+        guard: TimingGuard<'a>,
+        event: impl FnOnce() -> ReportEvent,
+    ) -> TimingGuard<'a> {
+        #[inline(never)]
+        #[cold]
+        fn cold_report<'a>(
+            report: &'a TimeReport,
+            mut guard: TimingGuard<'a>,
+            event: impl FnOnce() -> ReportEvent,
+        ) -> TimingGuard<'a> {
+            guard.report = Some(ReportGuard::start(report, event()));
+            guard
+        }
+
+        match &self.time_report {
+            Some(report) => cold_report(report, guard, event),
+            None => guard,
+        }
+    }
+
     /// Start profiling a verbose generic activity. Profiling continues until the
     /// VerboseTimingGuard returned from this call is dropped. In addition to recording
     /// a measureme event, "verbose" generic activities also print a timing entry to
@@ -250,11 +287,12 @@ pub fn extra_verbose_generic_activity<'a, A>(
     /// TimingGuard returned from this call is dropped.
     #[inline(always)]
     pub fn generic_activity(&self, event_label: &'static str) -> TimingGuard<'_> {
-        self.exec(EventFilter::GENERIC_ACTIVITIES, |profiler| {
+        let guard = self.exec(EventFilter::GENERIC_ACTIVITIES, |profiler| {
             let event_label = profiler.get_or_alloc_cached_string(event_label);
             let event_id = EventId::from_label(event_label);
             TimingGuard::start(profiler, profiler.generic_activity_event_kind, event_id)
-        })
+        });
+        self.report(guard, || ReportEvent::Activity(event_label, Vec::new()))
     }
 
     /// Start profiling with some event filter for a given event. Profiling continues until the
@@ -277,7 +315,8 @@ pub fn generic_activity_with_arg<A>(
     where
         A: Borrow<str> + Into<String>,
     {
-        self.exec(EventFilter::GENERIC_ACTIVITIES, |profiler| {
+        let report_arg = self.time_report.as_ref().map(|_| event_arg.borrow().to_owned());
+        let guard = self.exec(EventFilter::GENERIC_ACTIVITIES, |profiler| {
             let builder = EventIdBuilder::new(&profiler.profiler);
             let event_label = profiler.get_or_alloc_cached_string(event_label);
             let event_id = if profiler.event_filter_mask.contains(EventFilter::FUNCTION_ARGS) {
@@ -287,7 +326,8 @@ pub fn generic_activity_with_arg<A>(
                 builder.from_label(event_label)
             };
             TimingGuard::start(profiler, profiler.generic_activity_event_kind, event_id)
-        })
+        });
+        self.report(guard, || ReportEvent::Activity(event_label, report_arg.into_iter().collect()))
     }
 
     /// Start profiling a generic activity, allowing costly arguments to be recorded. Profiling
@@ -320,7 +360,7 @@ pub fn generic_activity_with_arg_recorder<F>(
         F: FnMut(&mut EventArgRecorder<'_>),
     {
         // Ensure this event will only be recorded when self-profiling is turned on.
-        self.exec(EventFilter::GENERIC_ACTIVITIES, |profiler| {
+        let guard = self.exec(EventFilter::GENERIC_ACTIVITIES, |profiler| {
             let builder = EventIdBuilder::new(&profiler.profiler);
             let event_label = profiler.get_or_alloc_cached_string(event_label);
 
@@ -329,7 +369,11 @@ pub fn generic_activity_with_arg_recorder<F>(
             let event_id = if profiler.event_filter_mask.contains(EventFilter::FUNCTION_ARGS) {
                 // Set up the builder and call the user-provided closure to record potentially
                 // costly event arguments.
-                let mut recorder = EventArgRecorder { profiler, args: SmallVec::new() };
+                let mut recorder = EventArgRecorder {
+                    profiler: Some(profiler),
+                    args: SmallVec::new(),
+                    report_args: Vec::new(),
+                };
                 f(&mut recorder);
 
                 // It is expected that the closure will record at least one argument. If that
@@ -347,6 +391,12 @@ pub fn generic_activity_with_arg_recorder<F>(
                 builder.from_label(event_label)
             };
             TimingGuard::start(profiler, profiler.generic_activity_event_kind, event_id)
+        });
+        self.report(guard, || {
+            let mut recorder =
+                EventArgRecorder { profiler: None, args: SmallVec::new(), report_args: Vec::new() };
+            f(&mut recorder);
+            ReportEvent::Activity(event_label, recorder.report_args)
         })
     }
 
@@ -383,7 +433,7 @@ pub fn generic_activity_with_args(
         event_label: &'static str,
         event_args: &[String],
     ) -> TimingGuard<'_> {
-        self.exec(EventFilter::GENERIC_ACTIVITIES, |profiler| {
+        let guard = self.exec(EventFilter::GENERIC_ACTIVITIES, |profiler| {
             let builder = EventIdBuilder::new(&profiler.profiler);
             let event_label = profiler.get_or_alloc_cached_string(event_label);
             let event_id = if profiler.event_filter_mask.contains(EventFilter::FUNCTION_ARGS) {
@@ -396,16 +446,18 @@ pub fn generic_activity_with_args(
                 builder.from_label(event_label)
             };
             TimingGuard::start(profiler, profiler.generic_activity_event_kind, event_id)
-        })
+        });
+        self.report(guard, || ReportEvent::Activity(event_label, event_args.to_vec()))
     }
 
     /// Start profiling a query provider. Profiling continues until the
     /// TimingGuard returned from this call is dropped.
     #[inline(always)]
     pub fn query_provider(&self) -> TimingGuard<'_> {
-        self.exec(EventFilter::QUERY_PROVIDERS, |profiler| {
+        let guard = self.exec(EventFilter::QUERY_PROVIDERS, |profiler| {
             TimingGuard::start(profiler, profiler.query_event_kind, EventId::INVALID)
-        })
+        });
+        self.report(guard, || ReportEvent::Query(None))
     }
 
     /// Record a query in-memory cache hit.
@@ -503,19 +555,28 @@ pub fn llvm_recording_enabled(&self) -> bool {
     pub fn get_self_profiler(&self) -> Option<Arc<SelfProfiler>> {
         self.profiler.clone()
     }
+
+    /// The timings recorded for `-Z time-report`, if it is enabled.
+    pub fn time_report(&self) -> Option<&TimeReport> {
+        self.time_report.as_deref()
+    }
 }
 
 /// A helper for recording costly arguments to self-profiling events. Used with
 /// `SelfProfilerRef::generic_activity_with_arg_recorder`.
 pub struct EventArgRecorder<'p> {
-    /// The `SelfProfiler` used to intern the event arguments that users will ask to record.
-    profiler: &'p SelfProfiler,
+    /// The `SelfProfiler` used to intern the event arguments that users will ask to record, or
+    /// `None` when recording them for the time report.
+    profiler: Option<&'p SelfProfiler>,
 
     /// The interned event arguments to be recorded in the generic activity event.
     ///
     /// The most common case, when actually recording event arguments, is to have one argument. Then
     /// followed by recording two, in a couple places.
     args: SmallVec<[StringId; 2]>,
+
+    /// The event arguments to be recorded in the time report.
+    report_args: Vec<String>,
 }
 
 impl EventArgRecorder<'_> {
@@ -527,8 +588,10 @@ pub fn record_arg<A>(&mut self, event_arg: A)
     where
         A: Borrow<str> + Into<String>,
     {
-        let event_arg = self.profiler.get_or_alloc_cached_string(event_arg);
-        self.args.push(event_arg);
+        match self.profiler {
+            Some(profiler) => self.args.push(profiler.get_or_alloc_cached_string(event_arg)),
+            None => self.report_args.push(event_arg.into()),
+        }
     }
 }
 
@@ -680,7 +743,10 @@ pub fn event_id_builder(&self) -> EventIdBuilder<'_> {
 }
 
 #[must_use]
-pub struct TimingGuard<'a>(Option<measureme::TimingGuard<'a>>);
+pub struct TimingGuard<'a> {
+    guard: Option<measureme::TimingGuard<'a>>,
+    report: Option<ReportGuard<'a>>,
+}
 
 impl<'a> TimingGuard<'a> {
     #[inline]
@@ -693,12 +759,16 @@ pub fn start(
         let raw_profiler = &profiler.profiler;
         let timing_guard =
             raw_profiler.start_recording_interval_event(event_kind, event_id, thread_id);
-        TimingGuard(Some(timing_guard))
+        TimingGuard { guard: Some(timing_guard), report: None }
     }
 
     #[inline]
     pub fn finish_with_query_invocation_id(self, query_invocation_id: QueryInvocationId) {
-        if let Some(guard) = self.0 {
+        let TimingGuard { guard, mut report } = self;
+        if let Some(ReportGuard { event: ReportEvent::Query(id), .. }) = &mut report {
+            *id = Some(QueryInvocationId(query_invocation_id.0));
+        }
+        if let Some(guard) = guard {
             cold_path(|| {
                 let event_id = StringId::new_virtual(query_invocation_id.0);
                 let event_id = EventId::from_virtual(event_id);
@@ -709,7 +779,7 @@ pub fn finish_with_query_invocation_id(self, query_invocation_id: QueryInvocatio
 
     #[inline]
     pub fn none() -> TimingGuard<'a> {
-        TimingGuard(None)
+        TimingGuard { guard: None, report: None }
     }
 
     #[inline(always)]
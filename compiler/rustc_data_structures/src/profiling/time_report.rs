@@ -0,0 +1,142 @@
+//! In-memory aggregation of the self-profiling events, for `-Z time-report`.
+//!
+//! Unlike the events recorded by `measureme`, which need external tools to be
+//! analyzed, the events recorded here are summed up as they finish: by query
+//! invocation for query providers, and by label and arguments for generic
+//! activities. The self time of an event is its duration minus the duration
+//! of the events nested in it on the same thread.
+
+use super::QueryInvocationId;
+use crate::fx::FxHashMap;
+
+use parking_lot::Mutex;
+use std::cell::Cell;
+use std::mem;
+use std::time::{Duration, Instant};
+
+#[cfg(test)]
+mod tests;
+
+/// The time spent in some events.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct Timing {
+    pub count: u64,
+    pub self_time: Duration,
+    pub total_time: Duration,
+}
+
+impl Timing {
+    pub fn add(&mut self, other: Timing) {
+        self.count += other.count;
+        self.self_time += other.self_time;
+        self.total_time += other.total_time;
+    }
+}
+
+/// The timings of the events recorded during a compilation.
+#[derive(Default)]
+pub struct TimeReport {
+    /// The timings of the query provider executions not named yet, by `QueryInvocationId`.
+    query_invocations: Mutex<FxHashMap<u32, Timing>>,
+    /// The timings of the queries, by name.
+    queries: Mutex<FxHashMap<&'static str, Timing>>,
+    /// The timings of some queries, by name and item.
+    items: Mutex<FxHashMap<(&'static str, String), Timing>>,
+    /// The timings of the generic activities, by label and arguments.
+    activities: Mutex<FxHashMap<(&'static str, Vec<String>), Timing>>,
+}
+
+impl TimeReport {
+    /// Takes the timings of the query provider executions recorded so far, by
+    /// `QueryInvocationId`, so that the query engine can name them with
+    /// `record_query`.
+    pub fn take_query_invocations(&self) -> FxHashMap<u32, Timing> {
+        mem::take(&mut *self.query_invocations.lock())
+    }
+
+    /// Records the timing of a query invocation, under the path of the item it
+    /// is about if there is one.
+    pub fn record_query(&self, query_name: &'static str, item: Option<String>, timing: Timing) {
+        self.queries.lock().entry(query_name).or_default().add(timing);
+        if let Some(item) = item {
+            self.items.lock().entry((query_name, item)).or_default().add(timing);
+        }
+    }
+
+    /// The timings of the queries, by name.
+    pub fn queries(&self) -> Vec<(&'static str, Timing)> {
+        self.queries.lock().iter().map(|(&name, &timing)| (name, timing)).collect()
+    }
+
+    /// The timings of the items of the queries that recorded them, by query name.
+    pub fn items(&self) -> Vec<(&'static str, String, Timing)> {
+        self.items
+            .lock()
+            .iter()
+            .map(|((name, item), &timing)| (*name, item.clone(), timing))
+            .collect()
+    }
+
+    /// The timings of the generic activities, by label and arguments.
+    pub fn activities(&self) -> Vec<(&'static str, Vec<String>, Timing)> {
+        self.activities
+            .lock()
+            .iter()
+            .map(|((label, args), &timing)| (*label, args.clone(), timing))
+            .collect()
+    }
+
+    fn record(&self, event: ReportEvent, self_time: Duration, total_time: Duration) {
+        let timing = Timing { count: 1, self_time, total_time };
+        match event {
+            ReportEvent::Query(Some(QueryInvocationId(id))) => {
+                self.query_invocations.lock().entry(id).or_default().add(timing)
+            }
+            // The query didn't complete, e.g. because of a cycle error.
+            ReportEvent::Query(None) => {}
+            ReportEvent::Activity(label, args) => {
+                self.activities.lock().entry((label, args)).or_default().add(timing)
+            }
+        }
+    }
+}
+
+/// What a recorded event is doing.
+pub(super) enum ReportEvent {
+    /// The execution of a query provider, known once it finishes.
+    Query(Option<QueryInvocationId>),
+    /// A generic activity, with its label and arguments.
+    Activity(&'static str, Vec<String>),
+}
+
+thread_local! {
+    /// The time spent in the finished events nested in the innermost running
+    /// event of the current thread.
+    static NESTED_TIME: Cell<Duration> = const { Cell::new(Duration::ZERO) };
+}
+
+/// Records an event in a `TimeReport` when dropped.
+pub(super) struct ReportGuard<'a> {
+    report: &'a TimeReport,
+    pub(super) event: ReportEvent,
+    start: Instant,
+    /// The time spent in the events nested in the enclosing event before this one started.
+    outer_nested_time: Duration,
+}
+
+impl<'a> ReportGuard<'a> {
+    pub(super) fn start(report: &'a TimeReport, event: ReportEvent) -> ReportGuard<'a> {
+        let outer_nested_time = NESTED_TIME.with(|nested| nested.replace(Duration::ZERO));
+        ReportGuard { report, event, start: Instant::now(), outer_nested_time }
+    }
+}
+
+impl Drop for ReportGuard<'_> {
+    fn drop(&mut self) {
+        let total_time = self.start.elapsed();
+        let nested_time =
+            NESTED_TIME.with(|nested| nested.replace(self.outer_nested_time + total_time));
+        let event = mem::replace(&mut self.event, ReportEvent::Query(None));
+        self.report.record(event, total_time.saturating_sub(nested_time), total_time);
+    }
+}
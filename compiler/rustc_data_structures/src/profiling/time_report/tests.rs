@@ -0,0 +1,41 @@
+use super::*;
+
+fn activity(report: &TimeReport, label: &'static str) -> Timing {
+    let activities = report.activities();
+    let (_, _, timing) = activities.iter().find(|(l, _, _)| *l == label).unwrap();
+    *timing
+}
+
+#[test]
+fn nested_events() {
+    let report = TimeReport::default();
+    {
+        let _outer = ReportGuard::start(&report, ReportEvent::Activity("outer", vec![]));
+        for _ in 0..2 {
+            let _inner =
+                ReportGuard::start(&report, ReportEvent::Activity("inner", vec!["a".into()]));
+            std::thread::sleep(Duration::from_millis(1));
+        }
+    }
+    let outer = activity(&report, "outer");
+    let inner = activity(&report, "inner");
+    assert_eq!((outer.count, inner.count), (1, 2));
+    assert_eq!(inner.self_time, inner.total_time);
+    assert_eq!(outer.self_time + inner.total_time, outer.total_time);
+    assert_eq!(report.activities().len(), 2);
+}
+
+#[test]
+fn queries() {
+    let report = TimeReport::default();
+    drop(ReportGuard::start(&report, ReportEvent::Query(Some(QueryInvocationId(7)))));
+    drop(ReportGuard::start(&report, ReportEvent::Query(None)));
+    let invocations = report.take_query_invocations();
+    assert_eq!(invocations.keys().copied().collect::<Vec<_>>(), [7]);
+    assert!(report.take_query_invocations().is_empty());
+
+    report.record_query("typeck", Some("foo".to_owned()), invocations[&7]);
+    report.record_query("typeck", None, invocations[&7]);
+    assert_eq!(report.queries()[0].1.count, 2);
+    assert_eq!(report.items(), [("typeck", "foo".to_owned(), invocations[&7])]);
+}
@@ -19,6 +19,7 @@
 use rustc_codegen_ssa::{traits::CodegenBackend, CodegenResults};
 use rustc_data_structures::profiling::{get_resident_set_size, print_time_passes_entry};
 use rustc_data_structures::sync::SeqCst;
+use rustc_data_structures::OnDrop;
 use rustc_errors::registry::{InvalidErrorCode, Registry};
 use rustc_errors::{ErrorGuaranteed, PResult};
 use rustc_feature::find_gated_cfg;
@@ -28,7 +29,7 @@
 use rustc_log::stdout_isatty;
 use rustc_metadata::locator;
 use rustc_save_analysis as save;
-use rustc_save_analysis::DumpHandler;
+use rustc_save_analysis::{CodeIndexHandler, DumpHandler};
 use rustc_session::config::{nightly_options, CG_OPTIONS, Z_OPTIONS};
 use rustc_session::config::{ErrorOutputType, Input, OutputType, PrintRequest, TrimmedDefPaths};
 use rustc_session::cstore::MetadataLoader;
@@ -51,11 +52,13 @@
 use std::path::PathBuf;
 use std::process::{self, Command, Stdio};
 use std::str;
+use std::sync::atomic::AtomicUsize;
 use std::sync::LazyLock;
 use std::time::Instant;
 
 pub mod args;
 pub mod pretty;
+mod time_report;
 
 /// Exit status code used for successful compilation and help output.
 pub const EXIT_SUCCESS: i32 = 0;
@@ -188,9 +191,15 @@ pub fn run(self) -> interface::Result<()> {
             self.file_loader,
             self.emitter,
             self.make_codegen_backend,
+            1,
         )
     }
 }
+
+/// The maximum number of times the crate is compiled with `-Z apply-suggestions`, when the
+/// applied suggestions keep uncovering new ones.
+const MAX_APPLY_SUGGESTIONS_PASSES: usize = 4;
+
 fn run_compiler(
     at_args: &[String],
     callbacks: &mut (dyn Callbacks + Send),
@@ -199,7 +208,12 @@ fn run_compiler(
     make_codegen_backend: Option<
         Box<dyn FnOnce(&config::Options) -> Box<dyn CodegenBackend> + Send>,
     >,
+    pass: usize,
 ) -> interface::Result<()> {
+    // The crate is only compiled again after applying suggestions if this pass doesn't consume
+    // hooks that the next one would need.
+    let can_compile_again =
+        file_loader.is_none() && emitter.is_none() && make_codegen_backend.is_none();
     let args = args::arg_expand_all(at_args);
 
     let diagnostic_output = emitter.map_or(DiagnosticOutput::Default, DiagnosticOutput::Raw);
@@ -288,8 +302,34 @@ fn run_compiler(
         },
     };
 
-    interface::run_compiler(config, |compiler| {
+    let changed_files = AtomicUsize::new(0);
+    let may_compile_again = can_compile_again && pass < MAX_APPLY_SUGGESTIONS_PASSES;
+    let result = interface::run_compiler(config, |compiler| {
         let sess = compiler.session();
+        let apply_suggestions = sess.opts.unstable_opts.apply_suggestions.is_some();
+        // The next pass reports the diagnostics that weren't fixed again, so they are held back
+        // until it is known whether this one is the last.
+        if apply_suggestions && may_compile_again {
+            sess.diagnostic().hold_diagnostics();
+        }
+        let _apply_suggestions = OnDrop(|| {
+            if !apply_suggestions {
+                return;
+            }
+            let held = sess.diagnostic().take_held_diagnostics();
+            // Don't rewrite the source files while unwinding from an ICE or a fatal error, the
+            // compilation stopped halfway.
+            if std::thread::panicking() {
+                sess.diagnostic().emit_held_diagnostics(held);
+                return;
+            }
+            let changed = sess.apply_suggestions();
+            if changed == 0 {
+                sess.diagnostic().emit_held_diagnostics(held);
+            }
+            changed_files.store(changed, SeqCst);
+        });
+
         let should_stop = print_crate_info(
             &***compiler.codegen_backend(),
             sess,
@@ -392,6 +432,22 @@ fn run_compiler(
                         )
                     });
                 }
+                if let Some(format) = sess.opts.unstable_opts.emit_code_index {
+                    let crate_name = queries.crate_name()?.peek().clone();
+                    sess.time("emit_code_index", || {
+                        save::process_crate(
+                            tcx,
+                            &crate_name,
+                            compiler.input(),
+                            Some(CodeIndexHandler::config()),
+                            CodeIndexHandler::new(
+                                format,
+                                compiler.output_dir().as_ref().map(|p| &**p),
+                                &crate_name,
+                            ),
+                        )
+                    });
+                }
                 result
             })?;
 
@@ -405,6 +461,10 @@ fn run_compiler(
                 sess.code_stats.print_type_sizes();
             }
 
+            if let Some(path) = &sess.opts.unstable_opts.type_size_limits {
+                sess.code_stats.check_type_size_limits(sess, path);
+            }
+
             let linker = queries.linker()?;
             Ok(Some(linker))
         })?;
@@ -418,6 +478,12 @@ fn run_compiler(
             sess.print_perf_stats();
         }
 
+        if let (Some(path), Some(report)) =
+            (&sess.opts.unstable_opts.time_report, sess.prof.time_report())
+        {
+            time_report::write_time_report(sess, report, path);
+        }
+
         if sess.opts.unstable_opts.print_fuel.is_some() {
             eprintln!(
                 "Fuel used by {}: {}",
@@ -427,7 +493,13 @@ fn run_compiler(
         }
 
         Ok(())
-    })
+    });
+
+    // Check the fixed code, and apply the suggestions it uncovers.
+    if changed_files.into_inner() > 0 && may_compile_again {
+        return run_compiler(at_args, callbacks, None, None, None, pass + 1);
+    }
+    result
 }
 
 #[cfg(unix)]
@@ -685,39 +757,13 @@ fn print_crate_info(
                 }
             }
             Cfg => {
-                let mut cfgs = sess
-                    .parse_sess
-                    .config
-                    .iter()
-                    .filter_map(|&(name, value)| {
-                        // Note that crt-static is a specially recognized cfg
-                        // directive that's printed out here as part of
-                        // rust-lang/rust#37406, but in general the
-                        // `target_feature` cfg is gated under
-                        // rust-lang/rust#29717. For now this is just
-                        // specifically allowing the crt-static cfg and that's
-                        // it, this is intended to get into Cargo and then go
-                        // through to build scripts.
-                        if (name != sym::target_feature || value != Some(sym::crt_dash_static))
-                            && !sess.is_nightly_build()
-                            && find_gated_cfg(|cfg_sym| cfg_sym == name).is_some()
-                        {
-                            return None;
-                        }
-
-                        if let Some(value) = value {
-                            Some(format!("{}=\"{}\"", name, value))
-                        } else {
-                            Some(name.to_string())
-                        }
-                    })
-                    .collect::<Vec<String>>();
-
-                cfgs.sort();
-                for cfg in cfgs {
+                for cfg in collect_cfgs(sess) {
                     println!("{}", cfg);
                 }
             }
+            SessionConfigJson => {
+                println!("{}", serde_json::to_string_pretty(&session_config_json(sess)).unwrap());
+            }
             RelocationModels
             | CodeModels
             | TlsModels
@@ -734,6 +780,112 @@ fn print_crate_info(
     Compilation::Stop
 }
 
+/// Returns the `cfg`s of the session as `name` or `name="value"`, sorted, leaving out the
+/// feature-gated ones on stable.
+fn collect_cfgs(sess: &Session) -> Vec<String> {
+    let mut cfgs = sess
+        .parse_sess
+        .config
+        .iter()
+        .filter_map(|&(name, value)| {
+            // Note that crt-static is a specially recognized cfg
+            // directive that's printed out here as part of
+            // rust-lang/rust#37406, but in general the
+            // `target_feature` cfg is gated under
+            // rust-lang/rust#29717. For now this is just
+            // specifically allowing the crt-static cfg and that's
+            // it, this is intended to get into Cargo and then go
+            // through to build scripts.
+            if (name != sym::target_feature || value != Some(sym::crt_dash_static))
+                && !sess.is_nightly_build()
+                && find_gated_cfg(|cfg_sym| cfg_sym == name).is_some()
+            {
+                return None;
+            }
+
+            if let Some(value) = value {
+                Some(format!("{}=\"{}\"", name, value))
+            } else {
+                Some(name.to_string())
+            }
+        })
+        .collect::<Vec<String>>();
+    cfgs.sort();
+    cfgs
+}
+
+/// Returns the resolved target, cfg, codegen and linking configuration of the session, for
+/// `--print=session-config-json`.
+fn session_config_json(sess: &Session) -> serde_json::Value {
+    use rustc_session::config::{DebugInfo, Lto, OptLevel};
+    use rustc_session::search_paths::PathKind;
+
+    let mut target_features =
+        sess.target_features.iter().map(|feature| feature.to_string()).collect::<Vec<_>>();
+    target_features.sort();
+
+    let opt_level = match sess.opts.optimize {
+        OptLevel::No => "0",
+        OptLevel::Less => "1",
+        OptLevel::Default => "2",
+        OptLevel::Aggressive => "3",
+        OptLevel::Size => "s",
+        OptLevel::SizeMin => "z",
+    };
+    let debuginfo = match sess.opts.debuginfo {
+        DebugInfo::None => 0,
+        DebugInfo::Limited => 1,
+        DebugInfo::Full => 2,
+    };
+    let lto = match sess.lto() {
+        Lto::No => "no",
+        Lto::Thin => "thin",
+        Lto::ThinLocal => "thin-local",
+        Lto::Fat => "fat",
+    };
+    let search_paths = sess
+        .opts
+        .search_paths
+        .iter()
+        .map(|search_path| {
+            let kind = match search_path.kind {
+                PathKind::Native => "native",
+                PathKind::Crate => "crate",
+                PathKind::Dependency => "dependency",
+                PathKind::Framework => "framework",
+                PathKind::ExternFlag => "extern",
+                PathKind::All => "all",
+            };
+            serde_json::json!({ "kind": kind, "path": search_path.dir })
+        })
+        .collect::<Vec<_>>();
+    let (linker, linker_flavor) = rustc_codegen_ssa::back::link::linker_and_flavor(sess);
+
+    serde_json::json!({
+        "target": sess.target.to_json(),
+        "cfg": collect_cfgs(sess),
+        "target_features": target_features,
+        "codegen": {
+            "opt_level": opt_level,
+            "debuginfo": debuginfo,
+            "split_debuginfo": sess.split_debuginfo().to_json(),
+            "panic": sess.panic_strategy().to_json(),
+            "lto": lto,
+            "codegen_units": sess.codegen_units(),
+            "relocation_model": sess.relocation_model().to_json(),
+            "code_model": sess.code_model().to_json(),
+            "tls_model": sess.tls_model().to_json(),
+            "crt_static": sess.crt_static(None),
+            "target_cpu": sess.opts.cg.target_cpu.as_deref().unwrap_or(&*sess.target.cpu),
+        },
+        "linker": linker,
+        "linker_flavor": linker_flavor.to_json(),
+        "sysroot": sess.sysroot,
+        "target_libdir": sess.target_tlib_path.dir,
+        "search_paths": search_paths,
+    })
+}
+
 /// Prints version information
 pub fn version(binary: &str, matches: &getopts::Matches) {
     let verbose = matches.opt_present("verbose");
@@ -0,0 +1,170 @@
+//! The report of `-Z time-report`, summing up the timings recorded by the
+//! self-profiler: the queries taking the most time, the items taking the most
+//! time in some of them and in codegen, and the time spent on each codegen
+//! unit. The first entries of each table are printed, and all of them are
+//! written to a JSON file.
+
+use rustc_data_structures::fx::FxHashMap;
+use rustc_data_structures::profiling::{duration_to_secs_str, TimeReport, Timing};
+use rustc_session::Session;
+use serde_json::json;
+
+use std::path::Path;
+use std::time::Duration;
+
+/// The number of entries printed for each table of the report.
+const PRINTED_ENTRIES: usize = 20;
+
+/// The queries reported by item, with the description of their table.
+const ITEM_QUERIES: &[(&str, &str)] = &[
+    ("typeck", "type checking"),
+    ("mir_borrowck", "borrow checking"),
+    ("optimized_mir", "MIR optimizations"),
+];
+
+/// The time spent on a codegen unit.
+#[derive(Default)]
+struct CguTiming {
+    /// The time spent by rustc generating the LLVM IR of the codegen unit.
+    codegen_time: Duration,
+    /// The time spent by LLVM optimizing and emitting the codegen unit.
+    llvm_time: Duration,
+}
+
+pub(crate) fn write_time_report(sess: &Session, report: &TimeReport, path: &Path) {
+    let mut queries = report.queries();
+    queries.sort_by(|a, b| b.1.self_time.cmp(&a.1.self_time).then(a.0.cmp(b.0)));
+
+    let mut items = report.items();
+    items.sort_by(|a, b| b.2.total_time.cmp(&a.2.total_time).then_with(|| a.1.cmp(&b.1)));
+
+    let mut instances = Vec::new();
+    let mut cgus: FxHashMap<String, CguTiming> = FxHashMap::default();
+    for (label, args, timing) in report.activities() {
+        let Some(arg) = args.into_iter().next() else { continue };
+        if label == "codegen_instance" {
+            instances.push((arg, timing));
+        } else if label == "codegen_module" {
+            cgus.entry(arg).or_default().codegen_time += timing.total_time;
+        } else if label.starts_with("LLVM_") {
+            // The LLVM activities are nested in each other, so only their self time is summed.
+            cgus.entry(arg).or_default().llvm_time += timing.self_time;
+        }
+    }
+    instances.sort_by(|a, b| b.1.total_time.cmp(&a.1.total_time).then_with(|| a.0.cmp(&b.0)));
+    let mut cgus: Vec<_> = cgus.into_iter().collect();
+    cgus.sort_by(|a, b| {
+        (b.1.llvm_time + b.1.codegen_time)
+            .cmp(&(a.1.llvm_time + a.1.codegen_time))
+            .then_with(|| a.0.cmp(&b.0))
+    });
+
+    print_table(
+        "queries by self time",
+        &["self", "total", "count"],
+        "query",
+        queries.iter().map(|(name, timing)| {
+            (cells(&[timing.self_time, timing.total_time], Some(timing.count)), *name)
+        }),
+    );
+    for &(query, description) in ITEM_QUERIES {
+        print_table(
+            &format!("items by time spent in {}", description),
+            &["total", "self", "count"],
+            "item",
+            items.iter().filter(|(name, ..)| *name == query).map(|(_, item, timing)| {
+                (cells(&[timing.total_time, timing.self_time], Some(timing.count)), &item[..])
+            }),
+        );
+    }
+    print_table(
+        "instances by time spent generating LLVM IR",
+        &["total", "self", "count"],
+        "instance",
+        instances.iter().map(|(instance, timing)| {
+            (cells(&[timing.total_time, timing.self_time], Some(timing.count)), &instance[..])
+        }),
+    );
+    print_table(
+        "codegen units by time spent in LLVM",
+        &["llvm", "rustc"],
+        "codegen unit",
+        cgus.iter().map(|(name, timing)| {
+            (cells(&[timing.llvm_time, timing.codegen_time], None), &name[..])
+        }),
+    );
+
+    let timing_json = |timing: &Timing| {
+        json!({
+            "count": timing.count,
+            "self_time": timing.self_time.as_secs_f64(),
+            "total_time": timing.total_time.as_secs_f64(),
+        })
+    };
+    let mut item_tables = serde_json::Map::new();
+    for &(query, _) in ITEM_QUERIES {
+        let table = items
+            .iter()
+            .filter(|(name, ..)| *name == query)
+            .map(|(_, item, timing)| json!({ "item": item, "timing": timing_json(timing) }))
+            .collect();
+        item_tables.insert(query.to_owned(), serde_json::Value::Array(table));
+    }
+    let json = json!({
+        "queries": queries
+            .iter()
+            .map(|(name, timing)| json!({ "query": name, "timing": timing_json(timing) }))
+            .collect::<Vec<_>>(),
+        "items": item_tables,
+        "codegen_instances": instances
+            .iter()
+            .map(|(instance, timing)| {
+                json!({ "instance": instance, "timing": timing_json(timing) })
+            })
+            .collect::<Vec<_>>(),
+        "codegen_units": cgus
+            .iter()
+            .map(|(name, timing)| {
+                json!({
+                    "name": name,
+                    "codegen_time": timing.codegen_time.as_secs_f64(),
+                    "llvm_time": timing.llvm_time.as_secs_f64(),
+                })
+            })
+            .collect::<Vec<_>>(),
+    });
+    if let Err(err) = std::fs::write(path, serde_json::to_string_pretty(&json).unwrap()) {
+        sess.err(&format!("failed to write the time report `{}`: {}", path.display(), err));
+    }
+}
+
+/// The cells of a table row with some durations in seconds and a count.
+fn cells(durations: &[Duration], count: Option<u64>) -> Vec<String> {
+    let durations = durations.iter().map(|&duration| duration_to_secs_str(duration));
+    durations.chain(count.map(|count| count.to_string())).collect()
+}
+
+/// Prints the first entries of a table, with their cells and their name.
+fn print_table<'a>(
+    title: &str,
+    columns: &[&str],
+    name_column: &str,
+    entries: impl Iterator<Item = (Vec<String>, &'a str)>,
+) {
+    let mut entries = entries.take(PRINTED_ENTRIES).peekable();
+    if entries.peek().is_none() {
+        return;
+    }
+    eprintln!("{}:", title);
+    for column in columns {
+        eprint!("{:>10}", column);
+    }
+    eprintln!("  {}", name_column);
+    for (cells, name) in entries {
+        for cell in cells {
+            eprint!("{:>10}", cell);
+        }
+        eprintln!("  {}", name);
+    }
+    eprintln!();
+}
@@ -0,0 +1,152 @@
+//! Lint baselines, listing the lint warnings a crate is known to have so that only the new ones
+//! are reported, for `-Z lint-baseline`.
+//!
+//! A baseline is a text file with a line for each place a lint is emitted at: the name of the
+//! lint, a fingerprint of the code the lint points at, the number of times the lint is emitted
+//! for that code, and the file the code is in.
+//!
+//! ```text
+//! unused_variables 5d2ec1b0a4f1e3c7 1 src/lib.rs
+//! ```
+//!
+//! The fingerprint only depends on the code, with its whitespace normalized, so the baseline stays
+//! valid when the code moves around.
+
+use crate::{Diagnostic, DiagnosticId};
+
+use rustc_data_structures::fx::FxHashSet;
+use rustc_data_structures::stable_hasher::StableHasher;
+use rustc_span::source_map::SourceMap;
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::hash::Hash;
+
+#[cfg(test)]
+mod tests;
+
+/// A place a lint is emitted at.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+struct Entry {
+    file: String,
+    lint: String,
+    fingerprint: u64,
+}
+
+#[derive(Debug)]
+pub struct LintBaseline {
+    /// The number of times each lint of the baseline can still be suppressed.
+    known: BTreeMap<Entry, usize>,
+    /// If true, the lints are only recorded, to write a new baseline.
+    recording: bool,
+    /// The lints emitted and not suppressed during this compilation.
+    emitted: BTreeMap<Entry, usize>,
+}
+
+impl LintBaseline {
+    /// The baseline described by the contents of a baseline file, suppressing the lints it lists.
+    pub fn parse(src: &str) -> Result<LintBaseline, String> {
+        let mut known = BTreeMap::new();
+        for (i, line) in src.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut fields = line.splitn(4, ' ');
+            let (Some(lint), Some(fingerprint), Some(count), Some(file)) =
+                (fields.next(), fields.next(), fields.next(), fields.next())
+            else {
+                return Err(format!(
+                    "line {}: expected a lint name, a fingerprint, a count and a file name",
+                    i + 1
+                ));
+            };
+            let fingerprint = u64::from_str_radix(fingerprint, 16)
+                .map_err(|_| format!("line {}: invalid fingerprint `{}`", i + 1, fingerprint))?;
+            let count: usize =
+                count.parse().map_err(|_| format!("line {}: invalid count `{}`", i + 1, count))?;
+            let entry = Entry { file: file.to_owned(), lint: lint.to_owned(), fingerprint };
+            *known.entry(entry).or_default() += count;
+        }
+        Ok(LintBaseline { known, recording: false, emitted: BTreeMap::new() })
+    }
+
+    /// A baseline that doesn't suppress anything, recording the emitted lints to write them to a
+    /// new baseline file.
+    pub fn recording() -> LintBaseline {
+        LintBaseline { known: BTreeMap::new(), recording: true, emitted: BTreeMap::new() }
+    }
+
+    /// Records a lint diagnostic about to be emitted, and returns whether it must be suppressed.
+    /// Diagnostics whose primary span doesn't point at source code are never suppressed.
+    pub(crate) fn suppresses(&mut self, source_map: &SourceMap, diagnostic: &Diagnostic) -> bool {
+        let Some(entry) = entry(source_map, diagnostic) else { return false };
+        match self.known.get_mut(&entry) {
+            Some(remaining) if *remaining > 0 && !self.recording => {
+                *remaining -= 1;
+                true
+            }
+            _ => {
+                *self.emitted.entry(entry).or_default() += 1;
+                false
+            }
+        }
+    }
+
+    /// The number of lints of the baseline in the files of `source_map` that weren't emitted,
+    /// and could be removed from the baseline.
+    pub fn unmatched(&self, source_map: &SourceMap) -> usize {
+        let files = local_files(source_map);
+        self.known
+            .iter()
+            .filter(|(entry, _)| files.contains(&entry.file))
+            .map(|(_, &remaining)| remaining)
+            .sum()
+    }
+
+    /// The contents of a baseline file with the lints emitted during this compilation, and the
+    /// lints of `previous` in files that aren't part of `source_map`, so that a baseline can be
+    /// shared by several crates.
+    pub fn updated(&self, previous: &LintBaseline, source_map: &SourceMap) -> String {
+        let files = local_files(source_map);
+        let mut entries: BTreeMap<&Entry, usize> = previous
+            .known
+            .iter()
+            .filter(|(entry, _)| !files.contains(&entry.file))
+            .map(|(entry, &count)| (entry, count))
+            .collect();
+        entries.extend(self.emitted.iter().map(|(entry, &count)| (entry, count)));
+
+        let mut out = String::from("# lint fingerprint count file\n");
+        for (entry, count) in entries {
+            writeln!(out, "{} {:016x} {} {}", entry.lint, entry.fingerprint, count, entry.file)
+                .unwrap();
+        }
+        out
+    }
+}
+
+/// The names of the files compiled from source, in the form used by baselines.
+fn local_files(source_map: &SourceMap) -> FxHashSet<String> {
+    source_map
+        .files()
+        .iter()
+        .filter(|file| file.src.is_some())
+        .map(|file| file.name.prefer_remapped().to_string())
+        .collect()
+}
+
+fn entry(source_map: &SourceMap, diagnostic: &Diagnostic) -> Option<Entry> {
+    let Some(DiagnosticId::Lint { name, .. }) = &diagnostic.code else { return None };
+    let span = diagnostic.span.primary_span()?;
+    let snippet = source_map.span_to_snippet(span).ok()?;
+    let mut hasher = StableHasher::new();
+    for word in snippet.split_whitespace() {
+        word.hash(&mut hasher);
+    }
+    Some(Entry {
+        file: source_map.span_to_filename(span).prefer_remapped().to_string(),
+        lint: name.clone(),
+        fingerprint: hasher.finish(),
+    })
+}
@@ -0,0 +1,115 @@
+use super::*;
+
+use crate::Level;
+use rustc_span::source_map::FilePathMapping;
+use rustc_span::{BytePos, Span};
+
+use std::path::Path;
+
+fn lint(name: &str, lo: u32, hi: u32) -> Diagnostic {
+    let code = DiagnosticId::Lint {
+        name: name.to_owned(),
+        has_future_breakage: false,
+        is_force_warn: false,
+    };
+    let mut diagnostic = Diagnostic::new_with_code(Level::Warning(None), Some(code), "test");
+    diagnostic.set_span(Span::with_root_ctxt(BytePos(lo), BytePos(hi)));
+    diagnostic
+}
+
+/// Emits `lints` for a single file containing `code` through `baseline`, and returns which of
+/// them are suppressed, the updated baseline and the number of unmatched lints.
+fn check(
+    baseline: &mut LintBaseline,
+    code: &str,
+    lints: &[Diagnostic],
+) -> (Vec<bool>, String, usize) {
+    rustc_span::create_default_session_globals_then(|| {
+        let sm = SourceMap::new(FilePathMapping::empty());
+        sm.new_source_file(Path::new("test.rs").to_owned().into(), code.to_owned());
+        let suppressed = lints.iter().map(|lint| baseline.suppresses(&sm, lint)).collect();
+        let updated = baseline.updated(&LintBaseline::recording(), &sm);
+        (suppressed, updated, baseline.unmatched(&sm))
+    })
+}
+
+#[test]
+fn record_then_suppress() {
+    let code = "let x = 1;\nlet y = 2;\n";
+    let lints = [lint("unused_variables", 4, 5), lint("unused_variables", 15, 16)];
+    let mut recording = LintBaseline::recording();
+    let (suppressed, recorded, _) = check(&mut recording, code, &lints);
+    assert_eq!(suppressed, [false, false]);
+    assert_eq!(recorded.lines().count(), 3);
+
+    // Moving the code around doesn't invalidate the baseline.
+    let mut baseline = LintBaseline::parse(&recorded).unwrap();
+    let moved = "\n\nlet   x = 1;\nlet y = 2;\n";
+    let lints = [lint("unused_variables", 8, 9), lint("unused_variables", 19, 20)];
+    let (suppressed, updated, unmatched) = check(&mut baseline, moved, &lints);
+    assert_eq!(suppressed, [true, true]);
+    assert_eq!(updated.lines().count(), 1);
+    assert_eq!(unmatched, 0);
+}
+
+#[test]
+fn new_and_fixed_lints() {
+    let code = "let x = 1;\nlet z = 2;\n";
+    let mut recording = LintBaseline::recording();
+    let (_, recorded, _) = check(&mut recording, code, &[lint("unused_variables", 4, 5)]);
+
+    // A lint for other code, or a different lint for the same code, isn't suppressed.
+    let mut baseline = LintBaseline::parse(&recorded).unwrap();
+    let lints = [lint("unused_variables", 15, 16), lint("non_snake_case", 4, 5)];
+    let (suppressed, _, unmatched) = check(&mut baseline, code, &lints);
+    assert_eq!(suppressed, [false, false]);
+    assert_eq!(unmatched, 1);
+}
+
+#[test]
+fn repeated_lints() {
+    // Each lint of the baseline only suppresses as many lints as it was emitted for.
+    let code = "f(x); f(x); f(x);";
+    let mut recording = LintBaseline::recording();
+    let lints = [lint("unused_must_use", 0, 4), lint("unused_must_use", 6, 10)];
+    let (_, recorded, _) = check(&mut recording, code, &lints);
+    assert!(recorded.contains(" 2 test.rs"));
+
+    let mut baseline = LintBaseline::parse(&recorded).unwrap();
+    let lints = [
+        lint("unused_must_use", 0, 4),
+        lint("unused_must_use", 6, 10),
+        lint("unused_must_use", 12, 16),
+    ];
+    let (suppressed, _, _) = check(&mut baseline, code, &lints);
+    assert_eq!(suppressed, [true, true, false]);
+}
+
+#[test]
+fn other_files_are_kept() {
+    let previous = LintBaseline::parse("dead_code 00000000000000ff 1 other.rs\n").unwrap();
+    let (updated, unmatched) = rustc_span::create_default_session_globals_then(|| {
+        let sm = SourceMap::new(FilePathMapping::empty());
+        sm.new_source_file(Path::new("test.rs").to_owned().into(), "fn f() {}".to_owned());
+        (LintBaseline::recording().updated(&previous, &sm), previous.unmatched(&sm))
+    });
+    assert!(updated.contains("dead_code 00000000000000ff 1 other.rs\n"));
+    assert_eq!(unmatched, 0);
+}
+
+#[test]
+fn parse_errors() {
+    assert!(LintBaseline::parse("# comment\n\n").is_ok());
+    assert_eq!(
+        LintBaseline::parse("dead_code ff 1").unwrap_err(),
+        "line 1: expected a lint name, a fingerprint, a count and a file name"
+    );
+    assert_eq!(
+        LintBaseline::parse("\ndead_code xyz 1 a.rs").unwrap_err(),
+        "line 2: invalid fingerprint `xyz`"
+    );
+    assert_eq!(
+        LintBaseline::parse("dead_code ff -1 a.rs").unwrap_err(),
+        "line 1: invalid count `-1`"
+    );
+}
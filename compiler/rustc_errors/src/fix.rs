@@ -0,0 +1,135 @@
+//! Applies the suggestions of emitted diagnostics to the source files they come from, for
+//! `-Z apply-suggestions`.
+
+use crate::{Applicability, CodeSuggestion};
+
+use rustc_data_structures::sync::Lrc;
+use rustc_span::source_map::SourceMap;
+use rustc_span::{FileName, Pos};
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+#[cfg(test)]
+mod tests;
+
+/// Whether a suggestion is applied when applying the suggestions that are at least `level`
+/// applicable: `MachineApplicable` suggestions are always applied, and `MaybeIncorrect` ones
+/// only at the `MaybeIncorrect` level.
+pub fn is_applicable(applicability: Applicability, level: Applicability) -> bool {
+    match applicability {
+        Applicability::MachineApplicable => true,
+        Applicability::MaybeIncorrect => level == Applicability::MaybeIncorrect,
+        Applicability::HasPlaceholders | Applicability::Unspecified => false,
+    }
+}
+
+/// A source file with some suggestions applied to it.
+pub struct FixedFile {
+    pub path: PathBuf,
+    /// The source code the suggestions were made for.
+    pub original: Lrc<String>,
+    /// The source code with the suggestions applied.
+    pub fixed: String,
+    /// The number of suggestions applied to this file.
+    pub applied: usize,
+}
+
+/// The replacement of a byte range of a source file.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct Edit {
+    path: PathBuf,
+    lo: usize,
+    hi: usize,
+    snippet: String,
+}
+
+impl Edit {
+    /// Whether both edits touch the same code. Two insertions at the same position overlap, as
+    /// the order to apply them in would be ambiguous.
+    fn overlaps(&self, other: &Edit) -> bool {
+        self.path == other.path
+            && (self.lo == other.lo || (self.lo < other.hi && other.lo < self.hi))
+    }
+}
+
+/// Applies `suggestions` to the files they come from, and returns the files that changed.
+///
+/// Suggestions with several alternatives, or touching code that isn't in a local file, are
+/// skipped. When suggestions overlap, only the one starting first in its file is applied, so the
+/// result doesn't depend on the order the diagnostics were emitted in; compiling the fixed files
+/// again gives the other ones a chance to apply.
+pub fn fix_files(source_map: &SourceMap, suggestions: &[CodeSuggestion]) -> Vec<FixedFile> {
+    let mut sources = BTreeMap::new();
+    let mut candidates: Vec<Vec<Edit>> = suggestions
+        .iter()
+        .filter_map(|suggestion| resolve_edits(source_map, suggestion, &mut sources))
+        .collect();
+    candidates.sort();
+    candidates.dedup();
+
+    let mut accepted: Vec<Edit> = Vec::new();
+    let mut applied: BTreeMap<PathBuf, usize> = BTreeMap::new();
+    for edits in candidates {
+        if edits.iter().any(|edit| accepted.iter().any(|other| edit.overlaps(other))) {
+            continue;
+        }
+        let mut paths: Vec<_> = edits.iter().map(|edit| &edit.path).collect();
+        paths.dedup();
+        for path in paths {
+            *applied.entry(path.clone()).or_default() += 1;
+        }
+        accepted.extend(edits);
+    }
+
+    // Edit the files from the end, so the byte ranges of the remaining edits stay valid.
+    accepted.sort_by(|a, b| b.cmp(a));
+    applied
+        .into_iter()
+        .map(|(path, applied)| {
+            let original = sources.remove(&path).unwrap();
+            let mut fixed = String::clone(&original);
+            for edit in accepted.iter().filter(|edit| edit.path == path) {
+                fixed.replace_range(edit.lo..edit.hi, &edit.snippet);
+            }
+            FixedFile { path, original, fixed, applied }
+        })
+        .collect()
+}
+
+/// Resolves the parts of a suggestion with a single alternative to byte ranges of local files,
+/// recording the source code of these files in `sources`.
+fn resolve_edits(
+    source_map: &SourceMap,
+    suggestion: &CodeSuggestion,
+    sources: &mut BTreeMap<PathBuf, Lrc<String>>,
+) -> Option<Vec<Edit>> {
+    let [substitution] = &suggestion.substitutions[..] else { return None };
+    let mut edits = Vec::with_capacity(substitution.parts.len());
+    for part in &substitution.parts {
+        if part.span.is_dummy() {
+            return None;
+        }
+        let lo = source_map.lookup_byte_offset(part.span.lo());
+        let hi = source_map.lookup_byte_offset(part.span.hi());
+        if !Lrc::ptr_eq(&lo.sf, &hi.sf) {
+            return None;
+        }
+        let FileName::Real(name) = &lo.sf.name else { return None };
+        let path = name.local_path()?.to_path_buf();
+        let src = lo.sf.src.as_ref()?;
+        sources.entry(path.clone()).or_insert_with(|| src.clone());
+        edits.push(Edit {
+            path,
+            lo: lo.pos.to_usize(),
+            hi: hi.pos.to_usize(),
+            snippet: part.snippet.clone(),
+        });
+    }
+    edits.sort();
+    // The parts of a suggestion are applied together, so they can't overlap each other.
+    if edits.windows(2).any(|pair| pair[0].overlaps(&pair[1])) {
+        return None;
+    }
+    Some(edits)
+}
@@ -0,0 +1,125 @@
+use super::*;
+
+use crate::{Substitution, SubstitutionPart, SuggestionStyle};
+use rustc_span::source_map::FilePathMapping;
+use rustc_span::{BytePos, Span};
+
+use std::path::Path;
+
+fn suggestion(parts: &[(u32, u32, &str)], applicability: Applicability) -> CodeSuggestion {
+    CodeSuggestion {
+        substitutions: vec![Substitution {
+            parts: parts
+                .iter()
+                .map(|&(lo, hi, snippet)| SubstitutionPart {
+                    span: Span::with_root_ctxt(BytePos(lo), BytePos(hi)),
+                    snippet: snippet.to_owned(),
+                })
+                .collect(),
+        }],
+        msg: "test".into(),
+        style: SuggestionStyle::ShowCode,
+        applicability,
+    }
+}
+
+/// Applies `suggestions` to a single file containing `code`, and returns the fixed code with the
+/// number of applied suggestions.
+fn fix(code: &str, suggestions: &[CodeSuggestion]) -> Option<(String, usize)> {
+    rustc_span::create_default_session_globals_then(|| {
+        let sm = SourceMap::new(FilePathMapping::empty());
+        sm.new_source_file(Path::new("test.rs").to_owned().into(), code.to_owned());
+        let mut files = fix_files(&sm, suggestions);
+        assert!(files.len() <= 1);
+        files.pop().map(|file| {
+            assert_eq!(file.path, Path::new("test.rs"));
+            assert_eq!(*file.original, code);
+            (file.fixed, file.applied)
+        })
+    })
+}
+
+#[test]
+fn applicability_levels() {
+    use Applicability::*;
+
+    assert!(is_applicable(MachineApplicable, MachineApplicable));
+    assert!(!is_applicable(MaybeIncorrect, MachineApplicable));
+    assert!(is_applicable(MachineApplicable, MaybeIncorrect));
+    assert!(is_applicable(MaybeIncorrect, MaybeIncorrect));
+    assert!(!is_applicable(HasPlaceholders, MaybeIncorrect));
+    assert!(!is_applicable(Unspecified, MaybeIncorrect));
+}
+
+#[test]
+fn no_suggestions() {
+    assert_eq!(fix("let x = 1;", &[]), None);
+}
+
+#[test]
+fn replacements_and_insertions() {
+    let suggestions = [
+        suggestion(&[(4, 5, "_x")], Applicability::MachineApplicable),
+        suggestion(&[(3, 3, " mut")], Applicability::MachineApplicable),
+        suggestion(&[(9, 9, ";")], Applicability::MachineApplicable),
+    ];
+    assert_eq!(fix("let x = 1\n", &suggestions), Some(("let mut _x = 1;\n".to_owned(), 3)));
+}
+
+#[test]
+fn multipart_suggestion() {
+    let suggestions = [suggestion(&[(8, 8, "("), (13, 13, ")")], Applicability::MachineApplicable)];
+    assert_eq!(fix("let x = a + b;", &suggestions), Some(("let x = (a + b);".to_owned(), 1)));
+}
+
+#[test]
+fn overlapping_suggestions() {
+    // The suggestion starting first wins, whatever the order the suggestions are given in.
+    let first = suggestion(&[(4, 9, "y")], Applicability::MachineApplicable);
+    let second = suggestion(&[(6, 12, "z")], Applicability::MachineApplicable);
+    let expected = Some(("let y = 1;".to_owned(), 1));
+    assert_eq!(fix("let xx_xx = 1;", &[first.clone(), second.clone()]), expected);
+    assert_eq!(fix("let xx_xx = 1;", &[second, first]), expected);
+}
+
+#[test]
+fn insertions_at_the_same_position() {
+    let suggestions = [
+        suggestion(&[(9, 9, ";")], Applicability::MachineApplicable),
+        suggestion(&[(9, 9, ",")], Applicability::MachineApplicable),
+    ];
+    assert_eq!(fix("let x = 1", &suggestions), Some(("let x = 1,".to_owned(), 1)));
+}
+
+#[test]
+fn adjacent_suggestions() {
+    let suggestions = [
+        suggestion(&[(4, 5, "y")], Applicability::MachineApplicable),
+        suggestion(&[(5, 5, ": u8")], Applicability::MachineApplicable),
+    ];
+    assert_eq!(fix("let x = 1;", &suggestions), Some(("let y: u8 = 1;".to_owned(), 2)));
+}
+
+#[test]
+fn duplicated_suggestions() {
+    let suggestions = [
+        suggestion(&[(9, 9, ";")], Applicability::MachineApplicable),
+        suggestion(&[(9, 9, ";")], Applicability::MachineApplicable),
+    ];
+    assert_eq!(fix("let x = 1", &suggestions), Some(("let x = 1;".to_owned(), 1)));
+}
+
+#[test]
+fn skipped_suggestions() {
+    let mut alternatives = suggestion(&[(4, 5, "y")], Applicability::MachineApplicable);
+    alternatives.substitutions.push(Substitution {
+        parts: vec![SubstitutionPart {
+            span: Span::with_root_ctxt(BytePos(4), BytePos(5)),
+            snippet: "z".to_owned(),
+        }],
+    });
+    let self_overlapping =
+        suggestion(&[(4, 5, "y"), (4, 6, "z")], Applicability::MachineApplicable);
+    let dummy = suggestion(&[(0, 0, "")], Applicability::MachineApplicable);
+    assert_eq!(fix("let x = 1;", &[alternatives, self_overlapping, dummy]), None);
+}
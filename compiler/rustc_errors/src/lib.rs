@@ -25,6 +25,7 @@
 use rustc_lint_defs::LintExpectationId;
 use Level::*;
 
+use baseline::LintBaseline;
 use emitter::{is_case_difference, Emitter, EmitterWriter};
 use registry::Registry;
 use rustc_data_structures::fx::{FxHashMap, FxHashSet, FxIndexMap};
@@ -51,12 +52,15 @@
 use termcolor::{Color, ColorSpec};
 
 pub mod annotate_snippet_emitter_writer;
+pub mod baseline;
 mod diagnostic;
 mod diagnostic_builder;
 pub mod emitter;
+pub mod fix;
 pub mod json;
 mod lock;
 pub mod registry;
+pub mod sarif;
 mod snippet;
 mod styled_buffer;
 
@@ -430,6 +434,15 @@ struct HandlerInner {
 
     future_breakage_diagnostics: Vec<Diagnostic>,
 
+    /// The suggestions collected when [`HandlerFlags::collect_suggestions`] is set.
+    suggestions: Vec<CodeSuggestion>,
+
+    /// The diagnostics held back since [`Handler::hold_diagnostics`] was called, if it was.
+    held_diagnostics: Option<Vec<Diagnostic>>,
+
+    /// The baseline of known lints set with [`Handler::set_lint_baseline`].
+    lint_baseline: Option<LintBaseline>,
+
     /// The [`Self::unstable_expect_diagnostics`] should be empty when this struct is
     /// dropped. However, it can have values if the compilation is stopped early
     /// or is only partially executed. To avoid ICEs, like in rust#94953 we only
@@ -484,12 +497,22 @@ pub struct HandlerFlags {
     pub macro_backtrace: bool,
     /// If true, identical diagnostics are reported only once.
     pub deduplicate_diagnostics: bool,
+    /// If set, the suggestions of emitted diagnostics that are at least this applicable are
+    /// collected, to be applied to the source files.
+    /// (rustc: see `-Z apply-suggestions`)
+    pub collect_suggestions: Option<Applicability>,
 }
 
 impl Drop for HandlerInner {
     fn drop(&mut self) {
         self.emit_stashed_diagnostics();
 
+        if let Some(held) = self.held_diagnostics.take() {
+            for diagnostic in &held {
+                self.emit_deduplicated(diagnostic);
+            }
+        }
+
         if !self.has_errors() {
             let bugs = std::mem::replace(&mut self.delayed_span_bugs, Vec::new());
             self.flush_delayed(bugs, "no errors encountered even though `delay_span_bug` issued");
@@ -588,6 +611,9 @@ pub fn with_emitter_and_flags(
                 emitted_diagnostics: Default::default(),
                 stashed_diagnostics: Default::default(),
                 future_breakage_diagnostics: Vec::new(),
+                suggestions: Vec::new(),
+                held_diagnostics: None,
+                lint_baseline: None,
                 check_unstable_expect_diagnostics: false,
                 unstable_expect_diagnostics: Vec::new(),
                 fulfilled_expectations: Default::default(),
@@ -999,6 +1025,39 @@ pub fn take_future_breakage_diagnostics(&self) -> Vec<Diagnostic> {
         std::mem::take(&mut self.inner.borrow_mut().future_breakage_diagnostics)
     }
 
+    pub fn take_suggestions(&self) -> Vec<CodeSuggestion> {
+        std::mem::take(&mut self.inner.borrow_mut().suggestions)
+    }
+
+    /// Holds back the diagnostics emitted from now on instead of emitting them, until
+    /// [`Handler::take_held_diagnostics`] is called. They still count as errors and warnings,
+    /// but aren't part of the summary printed by [`Handler::print_error_count`] unless they
+    /// are emitted with [`Handler::emit_held_diagnostics`].
+    pub fn hold_diagnostics(&self) {
+        self.inner.borrow_mut().held_diagnostics = Some(Vec::new());
+    }
+
+    /// Stops holding back diagnostics, and returns the ones that were held.
+    pub fn take_held_diagnostics(&self) -> Vec<Diagnostic> {
+        self.inner.borrow_mut().held_diagnostics.take().unwrap_or_default()
+    }
+
+    pub fn emit_held_diagnostics(&self, held: Vec<Diagnostic>) {
+        let mut inner = self.inner.borrow_mut();
+        for diagnostic in &held {
+            inner.emit_deduplicated(diagnostic);
+        }
+    }
+
+    /// Suppresses the lint warnings listed in `baseline`, and records the other ones.
+    pub fn set_lint_baseline(&self, baseline: LintBaseline) {
+        self.inner.borrow_mut().lint_baseline = Some(baseline);
+    }
+
+    pub fn take_lint_baseline(&self) -> Option<LintBaseline> {
+        self.inner.borrow_mut().lint_baseline.take()
+    }
+
     pub fn abort_if_errors(&self) {
         self.inner.borrow_mut().abort_if_errors()
     }
@@ -1164,6 +1223,14 @@ fn emit_diagnostic(&mut self, diagnostic: &mut Diagnostic) -> Option<ErrorGuaran
             return None;
         }
 
+        if matches!(diagnostic.level, Warning(_) | Level::Error { lint: true })
+            && let Some(baseline) = &mut self.lint_baseline
+            && let Some(source_map) = self.emitter.source_map()
+            && baseline.suppresses(source_map, diagnostic)
+        {
+            return None;
+        }
+
         if let Some(ref code) = diagnostic.code {
             self.emitted_diagnostic_codes.insert(code.clone());
         }
@@ -1194,11 +1261,19 @@ fn emit_diagnostic(&mut self, diagnostic: &mut Diagnostic) -> Option<ErrorGuaran
 
             diagnostic.children.drain_filter(already_emitted_sub).for_each(|_| {});
 
-            self.emitter.emit_diagnostic(&diagnostic);
-            if diagnostic.is_error() {
-                self.deduplicated_err_count += 1;
-            } else if let Warning(_) = diagnostic.level {
-                self.deduplicated_warn_count += 1;
+            if let (Some(level), Ok(suggestions)) =
+                (self.flags.collect_suggestions, &diagnostic.suggestions)
+            {
+                self.suggestions.extend(
+                    suggestions
+                        .iter()
+                        .filter(|suggestion| fix::is_applicable(suggestion.applicability, level))
+                        .cloned(),
+                );
+            }
+            match &mut self.held_diagnostics {
+                Some(held) => held.push(diagnostic.clone()),
+                None => self.emit_deduplicated(&diagnostic),
             }
         }
         if diagnostic.is_error() {
@@ -1230,6 +1305,16 @@ fn treat_err_as_bug(&self) -> bool {
             .map_or(false, |c| self.err_count() + self.lint_err_count >= c.get())
     }
 
+    /// Emits a diagnostic that passed deduplication, and counts it for the final summary.
+    fn emit_deduplicated(&mut self, diagnostic: &Diagnostic) {
+        self.emitter.emit_diagnostic(diagnostic);
+        if diagnostic.is_error() {
+            self.deduplicated_err_count += 1;
+        } else if let Warning(_) = diagnostic.level {
+            self.deduplicated_warn_count += 1;
+        }
+    }
+
     fn print_error_count(&mut self, registry: &Registry) {
         self.emit_stashed_diagnostics();
 
@@ -0,0 +1,533 @@
+//! A SARIF emitter for errors.
+//!
+//! [SARIF] (Static Analysis Results Interchange Format) is the format consumed by most
+//! code-scanning services. Unlike the JSON emitter, which writes one object per diagnostic,
+//! a SARIF log is a single document. The emitter therefore buffers every diagnostic it is
+//! given and writes the whole log once it is dropped, i.e. when the `Handler` goes away.
+//! Nothing is written when it is dropped while unwinding from a compiler panic, so that the
+//! panic message still ends up on stderr instead of in the middle of a truncated log.
+//!
+//! It is selected with `--error-format=sarif`, or `--error-format=pretty-sarif` to render the
+//! log with indents and newlines.
+//!
+//! The mapping onto SARIF 2.1.0 is as follows:
+//!
+//! - every top-level diagnostic becomes a `result`,
+//! - its error code or lint name becomes the `ruleId` (and a `rule` in the tool driver, with
+//!   the `--explain` text as its description if the registry has one),
+//! - primary spans become `locations`, secondary spans and sub-diagnostics with spans become
+//!   `relatedLocations`,
+//! - sub-diagnostics without a span are appended to the result message, the same way the
+//!   human-readable emitter renders them below the main message,
+//! - every `CodeSuggestion` substitution becomes one entry in `fixes`.
+//!
+//! [SARIF]: https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html
+
+use rustc_span::source_map::{FilePathMapping, SourceMap};
+
+use crate::emitter::Emitter;
+use crate::registry::Registry;
+use crate::{CodeSuggestion, DiagnosticId, FluentBundle, LazyFallbackBundle, Level, SubDiagnostic};
+use rustc_lint_defs::Applicability;
+
+use rustc_data_structures::fx::FxIndexMap;
+use rustc_data_structures::sync::Lrc;
+use rustc_error_messages::FluentArgs;
+use rustc_span::Span;
+use std::io::{self, Write};
+
+use serde::Serialize;
+
+#[cfg(test)]
+mod tests;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+
+pub struct SarifEmitter {
+    dst: Box<dyn Write + Send>,
+    registry: Option<Registry>,
+    sm: Lrc<SourceMap>,
+    fluent_bundle: Option<Lrc<FluentBundle>>,
+    fallback_bundle: LazyFallbackBundle,
+    pretty: bool,
+    /// Name reported as the SARIF `tool.driver.name`, e.g. `rustc`, `clippy` or `rustdoc`.
+    tool_name: String,
+    /// Rules referenced by the buffered results, keyed by their id.
+    rules: FxIndexMap<String, Rule>,
+    /// Results buffered until the log is written out on drop.
+    results: Vec<SarifResult>,
+}
+
+impl SarifEmitter {
+    pub fn stderr(
+        registry: Option<Registry>,
+        source_map: Lrc<SourceMap>,
+        fluent_bundle: Option<Lrc<FluentBundle>>,
+        fallback_bundle: LazyFallbackBundle,
+        pretty: bool,
+    ) -> SarifEmitter {
+        SarifEmitter::new(
+            Box::new(io::BufWriter::new(io::stderr())),
+            registry,
+            source_map,
+            fluent_bundle,
+            fallback_bundle,
+            pretty,
+        )
+    }
+
+    pub fn basic(
+        pretty: bool,
+        fluent_bundle: Option<Lrc<FluentBundle>>,
+        fallback_bundle: LazyFallbackBundle,
+    ) -> SarifEmitter {
+        let file_path_mapping = FilePathMapping::empty();
+        SarifEmitter::stderr(
+            None,
+            Lrc::new(SourceMap::new(file_path_mapping)),
+            fluent_bundle,
+            fallback_bundle,
+            pretty,
+        )
+    }
+
+    pub fn new(
+        dst: Box<dyn Write + Send>,
+        registry: Option<Registry>,
+        source_map: Lrc<SourceMap>,
+        fluent_bundle: Option<Lrc<FluentBundle>>,
+        fallback_bundle: LazyFallbackBundle,
+        pretty: bool,
+    ) -> SarifEmitter {
+        SarifEmitter {
+            dst,
+            registry,
+            sm: source_map,
+            fluent_bundle,
+            fallback_bundle,
+            pretty,
+            tool_name: "rustc".to_owned(),
+            rules: Default::default(),
+            results: Vec::new(),
+        }
+    }
+
+    /// Overrides the tool name recorded in the log, for drivers such as clippy or rustdoc that
+    /// share the emitter plumbing with rustc.
+    pub fn tool_name(self, tool_name: impl Into<String>) -> Self {
+        Self { tool_name: tool_name.into(), ..self }
+    }
+
+    fn write_log(&mut self) -> io::Result<()> {
+        let log = SarifLog {
+            schema: SARIF_SCHEMA,
+            version: SARIF_VERSION,
+            runs: vec![Run {
+                tool: Tool {
+                    driver: ToolComponent {
+                        name: &self.tool_name,
+                        information_uri: "https://www.rust-lang.org/",
+                        version: option_env!("CFG_VERSION"),
+                        rules: self.rules.values().collect(),
+                    },
+                },
+                column_kind: "unicodeCodePoints",
+                results: &self.results,
+            }],
+        };
+        if self.pretty {
+            writeln!(&mut self.dst, "{}", serde_json::to_string_pretty(&log).unwrap())
+        } else {
+            writeln!(&mut self.dst, "{}", serde_json::to_string(&log).unwrap())
+        }
+        .and_then(|_| self.dst.flush())
+    }
+
+    fn rule_index(&mut self, code: Option<&DiagnosticId>) -> Option<(String, usize)> {
+        let (id, help_uri) = match code? {
+            DiagnosticId::Error(s) => {
+                (s.clone(), Some(format!("https://doc.rust-lang.org/error-index.html#{s}")))
+            }
+            DiagnosticId::Lint { name, .. } => (name.clone(), None),
+        };
+        let registry = &self.registry;
+        let entry = self.rules.entry(id.clone());
+        let index = entry.index();
+        entry.or_insert_with(|| {
+            let explanation = registry
+                .as_ref()
+                .and_then(|registry| registry.try_find_description(&id).ok().flatten());
+            Rule {
+                id: id.clone(),
+                full_description: explanation.map(|text| Message { text: text.to_owned() }),
+                help_uri,
+            }
+        });
+        Some((id, index))
+    }
+}
+
+impl Drop for SarifEmitter {
+    fn drop(&mut self) {
+        // Panicking again while unwinding from an ICE would abort and lose the ICE report.
+        if std::thread::panicking() {
+            return;
+        }
+        if let Err(e) = self.write_log() {
+            eprintln!("failed to print SARIF log: {e}");
+        }
+    }
+}
+
+impl Emitter for SarifEmitter {
+    fn emit_diagnostic(&mut self, diag: &crate::Diagnostic) {
+        let result = SarifResult::from_errors_diagnostic(diag, self);
+        self.results.push(result);
+    }
+
+    fn emit_future_breakage_report(&mut self, diags: Vec<crate::Diagnostic>) {
+        for mut diag in diags {
+            if diag.level == Level::Allow {
+                diag.level = Level::Warning(None);
+            }
+            let mut result = SarifResult::from_errors_diagnostic(&diag, self);
+            result.properties.future_incompat = true;
+            self.results.push(result);
+        }
+    }
+
+    fn source_map(&self) -> Option<&Lrc<SourceMap>> {
+        Some(&self.sm)
+    }
+
+    fn fluent_bundle(&self) -> Option<&Lrc<FluentBundle>> {
+        self.fluent_bundle.as_ref()
+    }
+
+    fn fallback_fluent_bundle(&self) -> &FluentBundle {
+        &**self.fallback_bundle
+    }
+
+    fn should_show_explain(&self) -> bool {
+        // The explanations are attached to the rules of the run instead.
+        false
+    }
+}
+
+// The following data types are provided just for serialisation.
+
+#[derive(Serialize)]
+struct SarifLog<'a> {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    version: &'static str,
+    runs: Vec<Run<'a>>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Run<'a> {
+    tool: Tool<'a>,
+    /// rustc reports columns in characters, not in the UTF-16 code units SARIF defaults to.
+    column_kind: &'static str,
+    results: &'a [SarifResult],
+}
+
+#[derive(Serialize)]
+struct Tool<'a> {
+    driver: ToolComponent<'a>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ToolComponent<'a> {
+    name: &'a str,
+    information_uri: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<&'static str>,
+    rules: Vec<&'a Rule>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Rule {
+    /// The error code (e.g. `E0308`) or lint name (e.g. `unused_variables`).
+    id: String,
+    /// The `--explain` text for the code, if there is one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    full_description: Option<Message>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    help_uri: Option<String>,
+}
+
+#[derive(Serialize)]
+struct Message {
+    text: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rule_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rule_index: Option<usize>,
+    /// "error", "warning", "note" or "none".
+    level: &'static str,
+    message: Message,
+    /// The primary spans of the diagnostic.
+    locations: Vec<Location>,
+    /// Secondary spans, and the spans of sub-diagnostics.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    related_locations: Vec<Location>,
+    /// One fix per suggested substitution.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    fixes: Vec<Fix>,
+    properties: ResultProperties,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ResultProperties {
+    /// The level as rustc would print it, e.g. "error: internal compiler error".
+    rustc_level: &'static str,
+    /// Whether this result comes from a future-incompatibility report.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    future_incompat: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Location {
+    physical_location: PhysicalLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<Message>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PhysicalLocation {
+    artifact_location: ArtifactLocation,
+    region: Region,
+}
+
+#[derive(Serialize)]
+struct ArtifactLocation {
+    uri: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Region {
+    /// 1-based.
+    start_line: usize,
+    /// 1-based, character offset.
+    start_column: usize,
+    end_line: usize,
+    /// 1-based, character offset, exclusive.
+    end_column: usize,
+    byte_offset: u32,
+    byte_length: u32,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Fix {
+    description: Message,
+    artifact_changes: Vec<ArtifactChange>,
+    properties: FixProperties,
+}
+
+#[derive(Serialize)]
+struct FixProperties {
+    applicability: Applicability,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ArtifactChange {
+    artifact_location: ArtifactLocation,
+    replacements: Vec<Replacement>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Replacement {
+    deleted_region: Region,
+    inserted_content: InsertedContent,
+}
+
+#[derive(Serialize)]
+struct InsertedContent {
+    text: String,
+}
+
+impl SarifResult {
+    fn from_errors_diagnostic(diag: &crate::Diagnostic, se: &mut SarifEmitter) -> SarifResult {
+        let args = se.to_fluent_args(diag.args());
+        let mut message = se.translate_messages(&diag.message, &args).into_owned();
+
+        let mut locations = vec![];
+        let mut related_locations = vec![];
+        for span_label in diag.span.span_labels() {
+            let label = span_label
+                .label
+                .as_ref()
+                .map(|m| Message { text: se.translate_message(m, &args).into_owned() });
+            let Some(physical_location) = PhysicalLocation::from_span(span_label.span, se) else {
+                continue;
+            };
+            let location = Location { physical_location, message: label };
+            if span_label.is_primary {
+                locations.push(location);
+            } else {
+                related_locations.push(location);
+            }
+        }
+
+        for child in &diag.children {
+            let child_message = se.translate_messages(&child.message, &args);
+            let text = format!("{}: {}", child.level.to_str(), child_message);
+            let child_locations = Location::from_sub_diagnostic(child, &text, se);
+            if child_locations.is_empty() {
+                message.push('\n');
+                message.push_str(&text);
+            } else {
+                related_locations.extend(child_locations);
+            }
+        }
+
+        let fixes = diag
+            .suggestions
+            .iter()
+            .flatten()
+            .flat_map(|sugg| Fix::from_suggestion(sugg, &args, se))
+            .collect();
+
+        let (rule_id, rule_index) = match se.rule_index(diag.code.as_ref()) {
+            Some((id, index)) => (Some(id), Some(index)),
+            None => (None, None),
+        };
+
+        SarifResult {
+            rule_id,
+            rule_index,
+            level: sarif_level(diag.level),
+            message: Message { text: message },
+            locations,
+            related_locations,
+            fixes,
+            properties: ResultProperties {
+                rustc_level: diag.level.to_str(),
+                future_incompat: false,
+            },
+        }
+    }
+}
+
+/// Maps a rustc diagnostic level onto the SARIF `level` property.
+fn sarif_level(level: Level) -> &'static str {
+    match level {
+        Level::Bug | Level::DelayedBug | Level::Fatal | Level::Error { .. } => "error",
+        Level::Warning(_) => "warning",
+        Level::Note | Level::OnceNote | Level::Help => "note",
+        Level::FailureNote | Level::Allow | Level::Expect(_) => "none",
+    }
+}
+
+impl Location {
+    fn from_sub_diagnostic(diag: &SubDiagnostic, text: &str, se: &SarifEmitter) -> Vec<Location> {
+        let span = diag.render_span.as_ref().unwrap_or(&diag.span);
+        span.primary_spans()
+            .iter()
+            .filter_map(|&span| PhysicalLocation::from_span(span, se))
+            .map(|physical_location| Location {
+                physical_location,
+                message: Some(Message { text: text.to_owned() }),
+            })
+            .collect()
+    }
+}
+
+impl PhysicalLocation {
+    fn from_span(span: Span, se: &SarifEmitter) -> Option<PhysicalLocation> {
+        if span.is_dummy() {
+            return None;
+        }
+        let (artifact_location, region) = ArtifactLocation::with_region(span, se);
+        Some(PhysicalLocation { artifact_location, region })
+    }
+}
+
+impl ArtifactLocation {
+    fn with_region(span: Span, se: &SarifEmitter) -> (ArtifactLocation, Region) {
+        let start = se.sm.lookup_char_pos(span.lo());
+        let end = se.sm.lookup_char_pos(span.hi());
+        let file_name = se.sm.filename_for_diagnostics(&start.file.name).to_string();
+        let byte_start = start.file.original_relative_byte_pos(span.lo()).0;
+        let byte_end = start.file.original_relative_byte_pos(span.hi()).0;
+        let region = Region {
+            start_line: start.line,
+            start_column: start.col.0 + 1,
+            end_line: end.line,
+            end_column: end.col.0 + 1,
+            byte_offset: byte_start,
+            byte_length: byte_end - byte_start,
+        };
+        (ArtifactLocation { uri: file_name_to_uri(&file_name) }, region)
+    }
+}
+
+/// Turns a file name as printed in diagnostics into a SARIF URI reference. Relative paths stay
+/// relative (SARIF consumers resolve them against the checkout root), absolute paths become
+/// `file://` URIs.
+fn file_name_to_uri(file_name: &str) -> String {
+    let path = file_name.replace('\\', "/");
+    if path.starts_with('/') {
+        format!("file://{path}")
+    } else if path.as_bytes().get(1) == Some(&b':') {
+        // A Windows path with a drive letter.
+        format!("file:///{path}")
+    } else {
+        path
+    }
+}
+
+impl Fix {
+    fn from_suggestion(
+        suggestion: &CodeSuggestion,
+        args: &FluentArgs<'_>,
+        se: &SarifEmitter,
+    ) -> Vec<Fix> {
+        let description = se.translate_message(&suggestion.msg, args).into_owned();
+        suggestion
+            .substitutions
+            .iter()
+            .map(|substitution| {
+                // Group the parts of the substitution by the file they apply to, keeping the
+                // order in which the files are first mentioned.
+                let mut changes: FxIndexMap<String, Vec<Replacement>> = Default::default();
+                for part in &substitution.parts {
+                    let (artifact_location, deleted_region) =
+                        ArtifactLocation::with_region(part.span, se);
+                    changes.entry(artifact_location.uri).or_default().push(Replacement {
+                        deleted_region,
+                        inserted_content: InsertedContent { text: part.snippet.clone() },
+                    });
+                }
+                Fix {
+                    description: Message { text: description.clone() },
+                    artifact_changes: changes
+                        .into_iter()
+                        .map(|(uri, replacements)| ArtifactChange {
+                            artifact_location: ArtifactLocation { uri },
+                            replacements,
+                        })
+                        .collect(),
+                    properties: FixProperties { applicability: suggestion.applicability },
+                }
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,144 @@
+use super::*;
+
+use crate::sarif::SarifEmitter;
+use rustc_span::source_map::{FilePathMapping, SourceMap};
+
+use crate::{Handler, MultiSpan};
+use rustc_span::{BytePos, Span};
+
+use std::path::Path;
+use std::str;
+use std::sync::{Arc, Mutex};
+
+struct Shared<T> {
+    data: Arc<Mutex<T>>,
+}
+
+impl<T: Write> Write for Shared<T> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.data.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.data.lock().unwrap().flush()
+    }
+}
+
+/// Emits diagnostics through `f` and returns the SARIF log written once the handler is dropped.
+fn emit_sarif(code: &str, f: impl FnOnce(&Handler)) -> serde_json::Value {
+    rustc_span::create_default_session_globals_then(|| {
+        let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+        sm.new_source_file(Path::new("test.rs").to_owned().into(), code.to_owned());
+        let fallback_bundle =
+            crate::fallback_fluent_bundle(rustc_error_messages::DEFAULT_LOCALE_RESOURCES, false);
+
+        let output = Arc::new(Mutex::new(Vec::new()));
+        let se = SarifEmitter::new(
+            Box::new(Shared { data: output.clone() }),
+            None,
+            sm,
+            None,
+            fallback_bundle,
+            false,
+        );
+
+        let handler = Handler::with_emitter(true, None, Box::new(se));
+        f(&handler);
+        drop(handler);
+
+        let bytes = output.lock().unwrap();
+        let actual_output = str::from_utf8(&bytes).unwrap();
+        serde_json::from_str(actual_output).unwrap()
+    })
+}
+
+fn span(lo: u32, hi: u32) -> Span {
+    Span::with_root_ctxt(BytePos(lo), BytePos(hi))
+}
+
+#[test]
+fn empty_log() {
+    let log = emit_sarif("", |_| {});
+    assert_eq!(log["version"], "2.1.0");
+    let run = &log["runs"][0];
+    assert_eq!(run["tool"]["driver"]["name"], "rustc");
+    assert_eq!(run["columnKind"], "unicodeCodePoints");
+    assert_eq!(run["results"].as_array().unwrap().len(), 0);
+}
+
+#[test]
+fn error_with_code() {
+    let log = emit_sarif("fn main() {}\n", |handler| {
+        handler
+            .struct_span_err_with_code(span(3, 7), "foo", DiagnosticId::Error("E0001".to_owned()))
+            .emit();
+    });
+    let run = &log["runs"][0];
+    assert_eq!(run["tool"]["driver"]["rules"][0]["id"], "E0001");
+
+    let result = &run["results"][0];
+    assert_eq!(result["ruleId"], "E0001");
+    assert_eq!(result["ruleIndex"], 0);
+    assert_eq!(result["level"], "error");
+    assert_eq!(result["message"]["text"], "foo");
+
+    let location = &result["locations"][0]["physicalLocation"];
+    assert_eq!(location["artifactLocation"]["uri"], "test.rs");
+    let region = &location["region"];
+    assert_eq!(region["startLine"], 1);
+    assert_eq!(region["startColumn"], 4);
+    assert_eq!(region["endLine"], 1);
+    assert_eq!(region["endColumn"], 8);
+    assert_eq!(region["byteOffset"], 3);
+    assert_eq!(region["byteLength"], 4);
+}
+
+#[test]
+fn children_and_secondary_spans() {
+    let log = emit_sarif("let a = b;\n", |handler| {
+        let mut multispan = MultiSpan::from_span(span(8, 9));
+        multispan.push_span_label(span(4, 5), "bound here");
+        let mut diag = handler.struct_warn("bar");
+        diag.set_span(multispan);
+        diag.span_note(span(0, 3), "spanned note");
+        diag.note("unspanned note");
+        diag.emit();
+    });
+    let result = &log["runs"][0]["results"][0];
+    assert_eq!(result["level"], "warning");
+    assert_eq!(result["message"]["text"], "bar\nnote: unspanned note");
+    assert_eq!(result["locations"].as_array().unwrap().len(), 1);
+
+    let related = result["relatedLocations"].as_array().unwrap();
+    assert_eq!(related.len(), 2);
+    assert_eq!(related[0]["message"]["text"], "bound here");
+    assert_eq!(related[1]["message"]["text"], "note: spanned note");
+    assert_eq!(related[1]["physicalLocation"]["region"]["startColumn"], 1);
+}
+
+#[test]
+fn suggestions_become_fixes() {
+    let log = emit_sarif("let a = b;\n", |handler| {
+        handler
+            .struct_span_err(span(8, 9), "baz")
+            .span_suggestion(span(8, 9), "try this", "c", Applicability::MachineApplicable)
+            .emit();
+    });
+    let fix = &log["runs"][0]["results"][0]["fixes"][0];
+    assert_eq!(fix["description"]["text"], "try this");
+    assert_eq!(fix["properties"]["applicability"], "MachineApplicable");
+
+    let change = &fix["artifactChanges"][0];
+    assert_eq!(change["artifactLocation"]["uri"], "test.rs");
+    let replacement = &change["replacements"][0];
+    assert_eq!(replacement["insertedContent"]["text"], "c");
+    assert_eq!(replacement["deletedRegion"]["byteOffset"], 8);
+    assert_eq!(replacement["deletedRegion"]["byteLength"], 1);
+}
+
+#[test]
+fn uris() {
+    assert_eq!(file_name_to_uri("src/lib.rs"), "src/lib.rs");
+    assert_eq!(file_name_to_uri("/home/user/lib.rs"), "file:///home/user/lib.rs");
+    assert_eq!(file_name_to_uri("C:\\src\\lib.rs"), "file:///C:/src/lib.rs");
+}
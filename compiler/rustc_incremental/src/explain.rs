@@ -0,0 +1,141 @@
+//! Support for `-Z incremental-explain`, which reports why an incremental
+//! compilation session recomputed queries and codegen units.
+//!
+//! This runs once all queries have completed, and walks the dep-graph of the
+//! previous session with the colors given to its nodes in this one. A node is
+//! red when it was recomputed with a different result, and it was recomputed
+//! because the first of its dependencies that `try_mark_green` couldn't mark
+//! green was red (or couldn't be recomputed at all). Following those
+//! dependencies leads back to the inputs that changed: the eval-always nodes
+//! that read the source files, the SVH of the dependencies and so on. Changes
+//! to the command-line arguments are reported when the dep-graph is loaded,
+//! as they prevent it from being reused at all.
+//!
+//! The report has two parts:
+//!
+//! - For each changed input, the queries whose results changed because they
+//!   read it directly, grouped by source file.
+//! - For each codegen unit that was recompiled, the chain of red nodes from
+//!   the codegen unit back to the input that changed.
+
+use rustc_middle::dep_graph::{
+    make_compile_codegen_unit, DepContext, DepKind, DepNode, DepNodeColor, DepNodeExt,
+};
+use rustc_middle::ty::TyCtxt;
+use rustc_span::symbol::Symbol;
+
+use std::collections::BTreeMap;
+
+const PREFIX: &str = "[incremental-explain]";
+
+/// Reports why the queries and codegen units recomputed in this session were.
+pub fn explain_recompilation(tcx: TyCtxt<'_>) {
+    if tcx.dep_graph.previous_node_count() == 0 {
+        eprintln!("{PREFIX} no previous dep-graph was loaded, compiling everything from scratch");
+        return;
+    }
+
+    explain_changed_inputs(tcx);
+    explain_recompiled_codegen_units(tcx);
+}
+
+/// Reports the queries invalidated by each changed input.
+fn explain_changed_inputs(tcx: TyCtxt<'_>) {
+    // Changed input -> source file -> invalidated queries.
+    let mut invalidated: BTreeMap<String, BTreeMap<String, Vec<String>>> = BTreeMap::new();
+
+    for dep_node in tcx.dep_graph.previous_red_nodes() {
+        if tcx.is_eval_always(dep_node.kind) {
+            continue;
+        }
+        let cause = match tcx.dep_graph.first_non_green_dependency(&dep_node) {
+            // The node was recomputed because another query was, it isn't a root change.
+            Some((dep, Some(DepNodeColor::Red))) if !tcx.is_eval_always(dep.kind) => continue,
+            Some((dep, Some(_))) => describe_input(tcx, &dep),
+            Some((dep, None)) => {
+                format!("`{:?}` no longer exists or couldn't be recomputed", dep.kind)
+            }
+            None => "no dependency changed, the queries were run without trying to reuse them"
+                .to_string(),
+        };
+        invalidated
+            .entry(cause)
+            .or_default()
+            .entry(source_file(tcx, &dep_node))
+            .or_default()
+            .push(format!("{dep_node:?}"));
+    }
+
+    for (cause, files) in invalidated {
+        let count: usize = files.values().map(Vec::len).sum();
+        eprintln!("{PREFIX} {cause}, invalidating {count} queries:");
+        for (file, dep_nodes) in files {
+            eprintln!("{PREFIX}     in {file}:");
+            for dep_node in dep_nodes {
+                eprintln!("{PREFIX}         {dep_node}");
+            }
+        }
+    }
+}
+
+/// Reports the chain of red nodes that led to each recompiled codegen unit.
+fn explain_recompiled_codegen_units(tcx: TyCtxt<'_>) {
+    let mut cgu_names: Vec<_> =
+        tcx.dep_graph.previous_work_products().values().map(|wp| &wp.cgu_name).collect();
+    cgu_names.sort();
+
+    for cgu_name in cgu_names {
+        let dep_node = make_compile_codegen_unit(tcx, Symbol::intern(cgu_name));
+        if !tcx.dep_graph.is_red(&dep_node) {
+            continue;
+        }
+
+        let mut chain = vec![];
+        let mut current = dep_node;
+        while let Some((dep, color)) = tcx.dep_graph.first_non_green_dependency(&current) {
+            match color {
+                Some(DepNodeColor::Red) if !tcx.is_eval_always(dep.kind) => {
+                    chain.push(format!("{dep:?}"));
+                    current = dep;
+                }
+                Some(_) => {
+                    chain.push(format!("{dep:?}"));
+                    break;
+                }
+                None => {
+                    chain.push(format!("{:?} (no longer exists)", dep.kind));
+                    break;
+                }
+            }
+        }
+
+        if chain.is_empty() {
+            eprintln!("{PREFIX} recompiled codegen unit `{cgu_name}`: no dependency changed");
+        } else {
+            eprintln!("{PREFIX} recompiled codegen unit `{cgu_name}`: {}", chain.join(" <- "));
+        }
+    }
+}
+
+/// Describes a changed input, naming the dependency for a changed crate hash.
+fn describe_input(tcx: TyCtxt<'_>, dep_node: &DepNode) -> String {
+    if dep_node.kind == DepKind::crate_hash {
+        if let Some(def_id) = dep_node.extract_def_id(tcx) {
+            if !def_id.is_local() {
+                return format!("dependency `{}` has a new SVH", tcx.crate_name(def_id.krate));
+            }
+        }
+    }
+    format!("`{dep_node:?}` changed")
+}
+
+/// Returns the source file of the definition a dep-node is about, if any.
+fn source_file(tcx: TyCtxt<'_>, dep_node: &DepNode) -> String {
+    match dep_node.extract_def_id(tcx) {
+        Some(def_id) => {
+            let span = tcx.def_span(def_id);
+            tcx.sess.source_map().span_to_filename(span).prefer_local().to_string()
+        }
+        None => "<no source file>".to_string(),
+    }
+}
@@ -13,9 +13,11 @@
 
 mod assert_dep_graph;
 pub mod assert_module_sources;
+mod explain;
 mod persist;
 
 use assert_dep_graph::assert_dep_graph;
+use explain::explain_recompilation;
 pub use persist::copy_cgu_workproduct_to_incr_comp_cache_dir;
 pub use persist::delete_workproduct_files;
 pub use persist::finalize_session_directory;
@@ -24,6 +26,7 @@
 pub use persist::in_incr_comp_dir_sess;
 pub use persist::load_query_result_cache;
 pub use persist::prepare_session_directory;
+pub use persist::restore_from_shared_cache;
 pub use persist::save_dep_graph;
 pub use persist::save_work_product_index;
 pub use persist::LoadResult;
@@ -22,8 +22,9 @@
 /// The first few bytes of files generated by incremental compilation.
 const FILE_MAGIC: &[u8] = b"RSIC";
 
-/// Change this if the header format changes.
-const HEADER_FORMAT_VERSION: u16 = 0;
+/// Change this if the header format changes, or what the dep-graph file
+/// stores right after it.
+const HEADER_FORMAT_VERSION: u16 = 1;
 
 /// A version string that hopefully is always different for compiler versions
 /// with different encodings of incremental compilation artifacts. Contains
@@ -179,7 +180,7 @@ fn report_format_mismatch(report_incremental_info: bool, file: &Path, message: &
     }
 }
 
-fn rustc_version(nightly_build: bool) -> String {
+pub(crate) fn rustc_version(nightly_build: bool) -> String {
     if nightly_build {
         if let Some(val) = env::var_os("RUSTC_FORCE_RUSTC_VERSION") {
             return val.to_string_lossy().into_owned();
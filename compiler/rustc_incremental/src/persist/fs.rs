@@ -118,6 +118,8 @@
 
 use rand::{thread_rng, RngCore};
 
+use super::shared_cache;
+
 #[cfg(test)]
 mod tests;
 
@@ -131,7 +133,7 @@
 // or hexadecimal numbers (we want short file and directory names). Since these
 // numbers will be used in file names, we choose an encoding that is not
 // case-sensitive (as opposed to base64, for example).
-const INT_ENCODE_BASE: usize = base_n::CASE_INSENSITIVE;
+pub(crate) const INT_ENCODE_BASE: usize = base_n::CASE_INSENSITIVE;
 
 /// Returns the path to a session's dependency graph.
 pub fn dep_graph_path(sess: &Session) -> PathBuf {
@@ -343,6 +345,10 @@ pub fn finalize_session_directory(sess: &Session, svh: Svh) {
         let lock_file_path = lock_file_path(&*incr_comp_session_dir);
         delete_session_dir_lock_file(sess, &lock_file_path);
         sess.mark_incr_comp_session_as_invalid();
+    } else {
+        // Publish the session directory to the shared cache while we still
+        // hold its lock.
+        shared_cache::publish_session_directory(sess, &incr_comp_session_dir);
     }
 
     debug!("finalize_session_directory() - session directory: {}", incr_comp_session_dir.display());
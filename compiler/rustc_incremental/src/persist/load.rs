@@ -8,11 +8,13 @@
 use rustc_serialize::Decodable;
 use rustc_session::config::IncrementalStateAssertion;
 use rustc_session::Session;
+use std::collections::BTreeMap;
 use std::path::Path;
 
 use super::data::*;
 use super::file_format;
 use super::fs::*;
+use super::shared_cache;
 use super::work_product;
 
 type WorkProductMap = FxHashMap<WorkProductId, WorkProduct>;
@@ -142,7 +144,12 @@ pub fn load_dep_graph(sess: &Session) -> DepGraphFuture {
     // Fortunately, we just checked that this isn't the case.
     let path = dep_graph_path(&sess);
     let report_incremental_info = sess.opts.unstable_opts.incremental_info;
-    let expected_hash = sess.opts.dep_tracking_hash(false);
+    let expected_hash = shared_cache::commandline_args_hash(sess);
+    let expected_hashes = sess
+        .opts
+        .unstable_opts
+        .incremental_explain
+        .then(|| shared_cache::commandline_args_hashes(sess));
 
     let mut prev_work_products = FxHashMap::default();
     let nightly_build = sess.is_nightly_build();
@@ -189,6 +196,7 @@ pub fn load_dep_graph(sess: &Session) -> DepGraphFuture {
             LoadResult::Ok { data: (bytes, start_pos) } => {
                 let mut decoder = MemDecoder::new(&bytes, start_pos);
                 let prev_commandline_args_hash = u64::decode(&mut decoder);
+                let prev_commandline_args_hashes = BTreeMap::<String, u64>::decode(&mut decoder);
 
                 if prev_commandline_args_hash != expected_hash {
                     if report_incremental_info {
@@ -197,6 +205,12 @@ pub fn load_dep_graph(sess: &Session) -> DepGraphFuture {
                                     differing commandline arguments"
                         );
                     }
+                    if let Some(expected_hashes) = &expected_hashes {
+                        explain_differing_commandline_args(
+                            &prev_commandline_args_hashes,
+                            expected_hashes,
+                        );
+                    }
                     // We can't reuse the cache, purge it.
                     debug!("load_dep_graph_new: differing commandline arg hashes");
 
@@ -212,6 +226,28 @@ pub fn load_dep_graph(sess: &Session) -> DepGraphFuture {
     }))
 }
 
+/// Reports the command-line arguments that changed since the previous session
+/// for `-Z incremental-explain`.
+fn explain_differing_commandline_args(
+    prev: &BTreeMap<String, u64>,
+    current: &BTreeMap<String, u64>,
+) {
+    eprintln!(
+        "[incremental-explain] recompiling from scratch because of differing \
+        commandline arguments:"
+    );
+    for (name, hash) in current {
+        match prev.get(name) {
+            Some(prev_hash) if prev_hash == hash => {}
+            Some(_) => eprintln!("[incremental-explain]     `{name}` changed"),
+            None => eprintln!("[incremental-explain]     `{name}` was added"),
+        }
+    }
+    for name in prev.keys().filter(|name| !current.contains_key(*name)) {
+        eprintln!("[incremental-explain]     `{name}` was removed");
+    }
+}
+
 /// Attempts to load the query result cache from disk
 ///
 /// If we are not in incremental compilation mode, returns `None`.
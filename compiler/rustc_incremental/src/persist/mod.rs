@@ -8,6 +8,7 @@
 mod fs;
 mod load;
 mod save;
+mod shared_cache;
 mod work_product;
 
 pub use fs::finalize_session_directory;
@@ -21,5 +22,6 @@
 pub use save::build_dep_graph;
 pub use save::save_dep_graph;
 pub use save::save_work_product_index;
+pub use shared_cache::restore_from_shared_cache;
 pub use work_product::copy_cgu_workproduct_to_incr_comp_cache_dir;
 pub use work_product::delete_workproduct_files;
@@ -11,6 +11,7 @@
 use super::dirty_clean;
 use super::file_format;
 use super::fs::*;
+use super::shared_cache;
 use super::work_product;
 
 /// Saves and writes the [`DepGraph`] to the file system.
@@ -43,6 +44,10 @@ pub fn save_dep_graph(tcx: TyCtxt<'_>) {
             tcx.dep_graph.print_incremental_info()
         }
 
+        if sess.opts.unstable_opts.incremental_explain {
+            crate::explain_recompilation(tcx);
+        }
+
         join(
             move || {
                 sess.time("incr_comp_persist_result_cache", || {
@@ -174,8 +179,10 @@ pub fn build_dep_graph(
 
     file_format::write_file_header(&mut encoder, sess.is_nightly_build());
 
-    // First encode the commandline arguments hash
-    sess.opts.dep_tracking_hash(false).encode(&mut encoder);
+    // First encode the commandline arguments hash, followed by the hash of
+    // each argument so that `-Z incremental-explain` can name the ones that changed
+    shared_cache::commandline_args_hash(sess).encode(&mut encoder);
+    shared_cache::commandline_args_hashes(sess).encode(&mut encoder);
 
     Some(DepGraph::new(
         &sess.prof,
@@ -0,0 +1,638 @@
+//! This module manages the incremental compilation cache shared between
+//! incremental compilation directories, enabled with
+//! `-Z incremental-shared-cache=<dir>`.
+//!
+//! When a session directory is finalized, its files (the dep-graph, the query
+//! result cache, the work product index and the saved work products like the
+//! object files of the codegen units) are published to the shared cache as a
+//! snapshot of the crate, identified by the fingerprint of the source files it
+//! was compiled from. Once the source files of a new session are known, i.e.
+//! after macro expansion and before the dep-graph is used, the snapshot of the
+//! crate matching most of them is restored into the session directory, unless
+//! the previous session in the same incremental compilation directory matches
+//! as many. This way, compiling a fresh checkout of a workspace, or switching
+//! branches or worktrees, starts from the closest state compiled before in any
+//! of them. The usual red-green marking then decides which of the query
+//! results and work products can be reused, exactly as if they came from a
+//! previous session in the same incremental compilation directory.
+//!
+//! ## Layout
+//!
+//! The shared cache directory contains:
+//!
+//! - `blobs/{fingerprint}`: the contents of the published files, named by the
+//!   fingerprint of their contents, so that the files that are the same in
+//!   several snapshots are only stored once;
+//! - `crates/{crate-name-and-disambiguator}-{key}/{fingerprint}`: the manifest
+//!   of a snapshot of a crate, listing the source files it was compiled from
+//!   with the hash of their contents, and mapping the names of its files in the
+//!   session directory to the blobs storing their contents. The key is a hash
+//!   of the compiler version and of the command-line arguments recorded in the
+//!   dep-graph, and the manifest is named by the fingerprint of the source
+//!   files;
+//! - `cache.lock`: the lock file synchronizing the compiler processes.
+//!
+//! The manifests are text files, so that the garbage collection of a compiler
+//! knows which blobs the manifests written by other compiler versions use.
+//!
+//! A copy of the manifest is kept in the session directory, with the size and
+//! modification time of the files. Only the files that changed since they
+//! were published or restored are read and hashed again when publishing, the
+//! other ones are known to be stored in their blob already.
+//!
+//! ## Synchronization
+//!
+//! Blobs and manifests are written to a temporary file first and then renamed,
+//! so they are never seen partially written. The processes restoring and
+//! publishing session directories hold a shared lock on the lock file, while
+//! the garbage collection holds an exclusive one, so that blobs aren't removed
+//! while they are being linked, or before the manifest using them is written.
+//! As for the session directories, blobs are hard-linked when possible, which
+//! works because the files of the incremental compilation cache are never
+//! modified in place.
+//!
+//! ## Garbage Collection
+//!
+//! After publishing a session directory, the compiler removes the blobs not
+//! used by any manifest, and then the least recently published snapshots and
+//! the blobs only they use, until the size of the blobs is below the limit set
+//! with `-Z incremental-shared-cache-size`. If another process is using the
+//! shared cache, this is left to a later compilation.
+//!
+//! ## Path remapping
+//!
+//! The dep-graph records a hash of the command-line arguments, and isn't
+//! reused if it changed. This hash includes the working directory, which
+//! differs between the checkouts of a workspace, so it is left out of the hash
+//! when a shared cache is used. Codegen reads the working directory, which
+//! ends up in debuginfo and coverage maps, through the `working_directory`
+//! query instead, so the codegen units that depend on it are compiled again
+//! when it changed, unless it is remapped to the same path with
+//! `--remap-path-prefix`. The path prefixes remapped with
+//! `--remap-path-prefix` are also made relative to the working directory in
+//! the hash, so that e.g. `--remap-path-prefix=$PWD=/src` is the same argument
+//! in all checkouts. Likewise, the paths of the source files are relative to
+//! the working directory in the manifests.
+
+use rustc_data_structures::fingerprint::Fingerprint;
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_data_structures::stable_hasher::StableHasher;
+use rustc_data_structures::{base_n, flock};
+use rustc_fs_util::link_or_copy;
+use rustc_session::config::Options;
+use rustc_session::Session;
+use rustc_span::{FileName, RealFileName};
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::ffi::OsStr;
+use std::fs as std_fs;
+use std::hash::Hash;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use rand::{thread_rng, RngCore};
+
+use super::file_format;
+use super::fs::{delete_all_session_dir_contents, INT_ENCODE_BASE};
+
+#[cfg(test)]
+mod tests;
+
+const BLOBS_DIR: &str = "blobs";
+const CRATES_DIR: &str = "crates";
+const CACHE_LOCK_FILENAME: &str = "cache.lock";
+const SESSION_MANIFEST_FILENAME: &str = "shared-cache-manifest.txt";
+const TEMP_FILE_EXT: &str = ".tmp";
+
+/// A snapshot of a crate published to the shared cache.
+#[derive(Debug, Default, PartialEq)]
+struct Manifest {
+    /// The paths of the source files the crate was compiled from, with the
+    /// hash of their contents, as returned by `source_files`.
+    sources: Vec<(String, String)>,
+    /// The files of the session directory.
+    files: Vec<PublishedFile>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct PublishedFile {
+    /// The name of the file in the session directory.
+    name: String,
+    /// The blob storing the contents of the file.
+    blob: String,
+    /// The size and modification time in nanoseconds of the file when it was
+    /// published or restored.
+    size: u64,
+    modified: u128,
+}
+
+/// Returns the hash of the command-line arguments recorded in the dep-graph,
+/// which must be the same for the dep-graph to be reused.
+///
+/// See the module documentation for the paths left out of it when a shared
+/// cache is used.
+pub fn commandline_args_hash(sess: &Session) -> u64 {
+    tracked_options(sess).dep_tracking_hash(false)
+}
+
+/// Returns the hash of each of the command-line arguments making up
+/// `commandline_args_hash`, by name.
+pub fn commandline_args_hashes(sess: &Session) -> BTreeMap<String, u64> {
+    tracked_options(sess).dep_tracking_hashes()
+}
+
+fn tracked_options(sess: &Session) -> Cow<'_, Options> {
+    if sess.opts.unstable_opts.incremental_shared_cache.is_none() {
+        return Cow::Borrowed(&sess.opts);
+    }
+
+    let mut opts = sess.opts.clone();
+    if let Some(working_dir) = sess.opts.working_dir.local_path() {
+        for (from, _) in &mut opts.remap_path_prefix {
+            if let Ok(relative) = from.strip_prefix(working_dir) {
+                *from = relative.to_path_buf();
+            }
+        }
+    }
+    // Tracked through the `working_directory` query instead.
+    opts.working_dir = RealFileName::LocalPath(PathBuf::new());
+    Cow::Owned(opts)
+}
+
+/// Restores the snapshot of the crate matching most of its source files into
+/// the session directory, if it matches more of them than the previous
+/// session in the same incremental compilation directory. Returns whether the
+/// contents of the session directory were replaced, in which case the
+/// dep-graph must be loaded again.
+pub fn restore_from_shared_cache(sess: &Session) -> bool {
+    let Some(cache_dir) = &sess.opts.unstable_opts.incremental_shared_cache else {
+        return false;
+    };
+
+    let _timer = sess.timer("incr_comp_restore_from_shared_cache");
+
+    let session_dir = sess.incr_comp_session_dir().clone();
+    let rustc_version = file_format::rustc_version(sess.is_nightly_build());
+    let sources: FxHashSet<_> = source_files(sess).into_iter().collect();
+    let matching_sources =
+        |manifest: &Manifest| manifest.sources.iter().filter(|s| sources.contains(*s)).count();
+
+    let previous_session =
+        read_manifest(&session_dir.join(SESSION_MANIFEST_FILENAME), &rustc_version);
+    let mut best_matching_sources = previous_session.as_ref().map_or(0, matching_sources);
+
+    let Some(_lock) = lock_cache(sess, cache_dir) else {
+        return false;
+    };
+
+    let crate_dir = session_dir.parent().unwrap();
+    let manifests_dir = manifests_dir(sess, cache_dir, crate_dir);
+    let entries = match manifests_dir.read_dir() {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return false,
+        Err(err) => {
+            sess.warn(&format!(
+                "failed to read incremental shared cache directory `{}`: {}",
+                manifests_dir.display(),
+                err
+            ));
+            return false;
+        }
+    };
+
+    let mut best = None;
+    for entry in entries.flatten() {
+        let manifest_path = entry.path();
+        if is_temp_file(&manifest_path) {
+            continue;
+        }
+        let Some(manifest) = read_manifest(&manifest_path, &rustc_version) else {
+            debug!("restore_from_shared_cache() - ignoring `{}`", manifest_path.display());
+            continue;
+        };
+        let matches = matching_sources(&manifest);
+        if matches > best_matching_sources {
+            best_matching_sources = matches;
+            best = Some((manifest_path, manifest));
+        }
+    }
+
+    let Some((manifest_path, manifest)) = best else {
+        return false;
+    };
+
+    if let Err(err) = restore_files(sess, cache_dir, &session_dir, &rustc_version, manifest) {
+        sess.warn(&format!(
+            "failed to restore `{}` from the incremental shared cache: {}",
+            manifest_path.display(),
+            err
+        ));
+        // Don't leave the session directory half-restored.
+        let _ = delete_all_session_dir_contents(sess);
+        return true;
+    }
+
+    if sess.opts.unstable_opts.incremental_info {
+        eprintln!(
+            "[incremental] shared cache: restored `{}`, matching {} of {} source files",
+            manifest_path.display(),
+            best_matching_sources,
+            sources.len()
+        );
+    }
+
+    true
+}
+
+/// Replaces the files of the session directory with the ones of a snapshot.
+fn restore_files(
+    sess: &Session,
+    cache_dir: &Path,
+    session_dir: &Path,
+    rustc_version: &str,
+    mut manifest: Manifest,
+) -> io::Result<()> {
+    delete_all_session_dir_contents(sess)?;
+    for file in &mut manifest.files {
+        let path = session_dir.join(&file.name);
+        link_or_copy(cache_dir.join(BLOBS_DIR).join(&file.blob), &path)?;
+        let metadata = std_fs::metadata(&path)?;
+        file.size = metadata.len();
+        file.modified = modified_nanos(&metadata);
+    }
+    std_fs::write(
+        session_dir.join(SESSION_MANIFEST_FILENAME),
+        write_manifest(rustc_version, &manifest),
+    )
+}
+
+/// Publishes the files of a session directory to the shared cache, as the
+/// snapshot of its crate for its source files, and then runs the garbage
+/// collection.
+pub fn publish_session_directory(sess: &Session, session_dir: &Path) {
+    let Some(cache_dir) = &sess.opts.unstable_opts.incremental_shared_cache else {
+        return;
+    };
+
+    let _timer = sess.timer("incr_comp_publish_to_shared_cache");
+
+    {
+        let Some(_lock) = lock_cache(sess, cache_dir) else {
+            return;
+        };
+
+        let crate_dir = session_dir.parent().unwrap();
+        let manifests_dir = manifests_dir(sess, cache_dir, crate_dir);
+        let rustc_version = file_format::rustc_version(sess.is_nightly_build());
+        let session_manifest_path = session_dir.join(SESSION_MANIFEST_FILENAME);
+        let previous_files = read_manifest(&session_manifest_path, &rustc_version)
+            .map_or_else(Vec::new, |manifest| manifest.files);
+        let sources = source_files(sess);
+        let manifest_path = manifests_dir.join(fingerprint_name(&sources));
+        let result = publish_files(cache_dir, session_dir, &previous_files).and_then(|files| {
+            let manifest = write_manifest(&rustc_version, &Manifest { sources, files });
+            std_fs::create_dir_all(&manifests_dir)?;
+            write_atomically(&manifest_path, |temp_path| std_fs::write(temp_path, &manifest))?;
+            std_fs::write(&session_manifest_path, &manifest)
+        });
+        if let Err(err) = result {
+            sess.warn(&format!(
+                "failed to publish incremental compilation session directory `{}` \
+                 to the shared cache: {}",
+                session_dir.display(),
+                err
+            ));
+            return;
+        }
+    }
+
+    if let Err(err) = garbage_collect_shared_cache(sess, cache_dir) {
+        sess.warn(&format!(
+            "failed to garbage collect incremental shared cache `{}`: {}",
+            cache_dir.display(),
+            err
+        ));
+    }
+}
+
+/// Stores the files of a session directory as blobs, given the files as they
+/// were last published or restored.
+fn publish_files(
+    cache_dir: &Path,
+    session_dir: &Path,
+    previous_files: &[PublishedFile],
+) -> io::Result<Vec<PublishedFile>> {
+    let previous_files: FxHashMap<_, _> =
+        previous_files.iter().map(|file| (&file.name[..], file)).collect();
+
+    let mut files = vec![];
+    for entry in session_dir.read_dir()? {
+        let entry = entry?;
+        if !entry.file_type()?.is_file() {
+            continue;
+        }
+        let name = entry.file_name().into_string().map_err(|name| {
+            io::Error::new(io::ErrorKind::InvalidData, format!("invalid file name {:?}", name))
+        })?;
+        if name == SESSION_MANIFEST_FILENAME {
+            continue;
+        }
+
+        let metadata = entry.metadata()?;
+        let size = metadata.len();
+        let modified = modified_nanos(&metadata);
+
+        // Don't read the files that didn't change since they were stored.
+        let unchanged = previous_files.get(&name[..]).filter(|file| {
+            file.size == size
+                && file.modified == modified
+                && cache_dir.join(BLOBS_DIR).join(&file.blob).exists()
+        });
+        let blob = match unchanged {
+            Some(file) => file.blob.clone(),
+            None => {
+                let mut hasher = StableHasher::new();
+                std_fs::read(entry.path())?.hash(&mut hasher);
+                let fingerprint: Fingerprint = hasher.finish();
+                let blob = encode_fingerprint(fingerprint);
+
+                let blob_path = cache_dir.join(BLOBS_DIR).join(&blob);
+                if !blob_path.exists() {
+                    debug!("publish_files() - storing `{}` as `{}`", name, blob);
+                    write_atomically(&blob_path, |temp_path| {
+                        link_or_copy(entry.path(), temp_path)
+                    })?;
+                }
+                blob
+            }
+        };
+        files.push(PublishedFile { name, blob, size, modified });
+    }
+    files.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(files)
+}
+
+/// Removes the blobs not used by any manifest, and then the oldest snapshots
+/// until the blobs fit in the size limit.
+fn garbage_collect_shared_cache(sess: &Session, cache_dir: &Path) -> io::Result<()> {
+    // Leave the garbage collection to a later compilation if the cache is in use.
+    let lock_path = cache_dir.join(CACHE_LOCK_FILENAME);
+    let Ok(_lock) = flock::Lock::new(&lock_path, false, true, true) else {
+        debug!("garbage_collect_shared_cache() - not collecting, still in use");
+        return Ok(());
+    };
+
+    let mut manifests = vec![];
+    for manifests_dir in cache_dir.join(CRATES_DIR).read_dir()? {
+        let manifests_dir = manifests_dir?;
+        if !manifests_dir.file_type()?.is_dir() {
+            continue;
+        }
+        for entry in manifests_dir.path().read_dir()? {
+            let entry = entry?;
+            let path = entry.path();
+            if is_temp_file(&path) {
+                // Left behind by a compiler process that didn't finish publishing.
+                std_fs::remove_file(&path)?;
+                continue;
+            }
+            let blobs = manifest_blobs(&std_fs::read_to_string(&path)?);
+            manifests.push((entry.metadata()?.modified()?, path, blobs));
+        }
+    }
+
+    let mut blob_sizes = FxHashMap::default();
+    for entry in cache_dir.join(BLOBS_DIR).read_dir()? {
+        let entry = entry?;
+        let path = entry.path();
+        if is_temp_file(&path) {
+            std_fs::remove_file(&path)?;
+            continue;
+        }
+        let blob = entry.file_name().to_string_lossy().into_owned();
+        blob_sizes.insert(blob, entry.metadata()?.len());
+    }
+
+    let limit = sess.opts.unstable_opts.incremental_shared_cache_size.saturating_mul(1 << 20);
+    let (removed_manifests, removed_blobs) = entries_to_collect(manifests, &blob_sizes, limit);
+    for path in removed_manifests {
+        debug!("garbage_collect_shared_cache() - deleting `{}`", path.display());
+        std_fs::remove_file(&path)?;
+        // Only succeeds once the last snapshot of the crate is removed.
+        let _ = std_fs::remove_dir(path.parent().unwrap());
+    }
+    for blob in removed_blobs {
+        debug!("garbage_collect_shared_cache() - deleting blob `{}`", blob);
+        std_fs::remove_file(cache_dir.join(BLOBS_DIR).join(blob))?;
+    }
+
+    Ok(())
+}
+
+/// Returns the manifests and blobs to remove so that the size of the remaining
+/// blobs is below `limit`, given the manifests with the time they were
+/// published and the blobs they use, and the size of the blobs.
+fn entries_to_collect(
+    mut manifests: Vec<(SystemTime, PathBuf, Vec<String>)>,
+    blob_sizes: &FxHashMap<String, u64>,
+    limit: u64,
+) -> (Vec<PathBuf>, Vec<String>) {
+    manifests.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.cmp(&b.1)));
+
+    let mut uses: FxHashMap<&str, usize> = FxHashMap::default();
+    for (_, _, blobs) in &manifests {
+        for blob in blobs {
+            *uses.entry(&blob[..]).or_default() += 1;
+        }
+    }
+
+    let mut removed_blobs = vec![];
+    let mut size = 0;
+    for (blob, &blob_size) in blob_sizes {
+        if uses.contains_key(&blob[..]) {
+            size += blob_size;
+        } else {
+            removed_blobs.push(blob.clone());
+        }
+    }
+
+    let mut removed_manifests = vec![];
+    for (_, path, blobs) in &manifests {
+        if size <= limit {
+            break;
+        }
+        removed_manifests.push(path.clone());
+        for blob in blobs {
+            let count = uses.get_mut(&blob[..]).unwrap();
+            *count -= 1;
+            if *count == 0 {
+                if let Some(blob_size) = blob_sizes.get(blob) {
+                    size -= *blob_size;
+                    removed_blobs.push(blob.clone());
+                }
+            }
+        }
+    }
+
+    removed_blobs.sort();
+    (removed_manifests, removed_blobs)
+}
+
+/// Creates the directories of the shared cache and acquires a shared lock on
+/// it, waiting for the garbage collection to finish if needed.
+fn lock_cache(sess: &Session, cache_dir: &Path) -> Option<flock::Lock> {
+    let result = std_fs::create_dir_all(cache_dir.join(BLOBS_DIR))
+        .and_then(|()| std_fs::create_dir_all(cache_dir.join(CRATES_DIR)))
+        .and_then(|()| flock::Lock::new(&cache_dir.join(CACHE_LOCK_FILENAME), true, true, false));
+    match result {
+        Ok(lock) => Some(lock),
+        Err(err) => {
+            sess.warn(&format!(
+                "failed to lock incremental shared cache `{}`: {}",
+                cache_dir.display(),
+                err
+            ));
+            None
+        }
+    }
+}
+
+/// Returns the path to the directory of the manifests of a crate, given the
+/// directory of the crate in the incremental compilation directory.
+fn manifests_dir(sess: &Session, cache_dir: &Path, crate_dir: &Path) -> PathBuf {
+    let mut hasher = StableHasher::new();
+    file_format::rustc_version(sess.is_nightly_build()).hash(&mut hasher);
+    commandline_args_hash(sess).hash(&mut hasher);
+    let key: u64 = hasher.finish();
+
+    let crate_dir_name = crate_dir.file_name().unwrap().to_string_lossy();
+    let dir_name = format!("{}-{}", crate_dir_name, base_n::encode(key as u128, INT_ENCODE_BASE));
+    cache_dir.join(CRATES_DIR).join(dir_name)
+}
+
+/// Returns the paths of the source files of the local crate relative to the
+/// working directory, with the hash of their contents, sorted.
+fn source_files(sess: &Session) -> Vec<(String, String)> {
+    let working_dir = sess.opts.working_dir.local_path();
+    let mut sources: Vec<(String, String)> = sess
+        .source_map()
+        .files()
+        .iter()
+        .filter(|file| !file.is_imported())
+        .filter_map(|file| {
+            let FileName::Real(name) = &file.name else { return None };
+            let path = name.remapped_path_if_available();
+            let path = working_dir.and_then(|dir| path.strip_prefix(dir).ok()).unwrap_or(path);
+            let path = path.to_str().filter(|path| !path.contains('\n'))?.to_owned();
+            let hash: String =
+                file.src_hash.hash_bytes().iter().map(|b| format!("{:02x}", b)).collect();
+            Some((path, hash))
+        })
+        .collect();
+    sources.sort();
+    sources.dedup();
+    sources
+}
+
+/// Returns the name of the manifest of a snapshot compiled from the given
+/// source files.
+fn fingerprint_name(sources: &[(String, String)]) -> String {
+    let mut hasher = StableHasher::new();
+    sources.hash(&mut hasher);
+    encode_fingerprint(hasher.finish())
+}
+
+fn encode_fingerprint(fingerprint: Fingerprint) -> String {
+    base_n::encode(u128::from_le_bytes(fingerprint.to_le_bytes()), INT_ENCODE_BASE)
+}
+
+fn modified_nanos(metadata: &std_fs::Metadata) -> u128 {
+    metadata
+        .modified()
+        .ok()
+        .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |modified| modified.as_nanos())
+}
+
+/// Writes a file through a temporary file renamed once it is complete.
+fn write_atomically<T>(path: &Path, write: impl FnOnce(&Path) -> io::Result<T>) -> io::Result<()> {
+    let mut temp_name = path.file_name().unwrap().to_os_string();
+    temp_name.push(format!(
+        "-{}{}",
+        base_n::encode(thread_rng().next_u32() as u128, INT_ENCODE_BASE),
+        TEMP_FILE_EXT
+    ));
+    let temp_path = path.with_file_name(temp_name);
+    let result = write(&temp_path).and_then(|_| std_fs::rename(&temp_path, path));
+    if result.is_err() {
+        let _ = std_fs::remove_file(&temp_path);
+    }
+    result
+}
+
+fn is_temp_file(path: &Path) -> bool {
+    path.to_string_lossy().ends_with(TEMP_FILE_EXT)
+}
+
+/// Formats a manifest: the compiler version on the first line, then the hash
+/// and path of each source file, and then the blob, size, modification time
+/// and name of each file.
+fn write_manifest(rustc_version: &str, manifest: &Manifest) -> String {
+    let mut contents = format!("{}\n", rustc_version);
+    for (path, hash) in &manifest.sources {
+        contents.push_str(&format!("source {} {}\n", hash, path));
+    }
+    for file in &manifest.files {
+        contents.push_str(&format!(
+            "file {} {} {} {}\n",
+            file.blob, file.size, file.modified, file.name
+        ));
+    }
+    contents
+}
+
+/// Parses a manifest, if it was written by the given compiler version.
+fn parse_manifest(contents: &str, rustc_version: &str) -> Option<Manifest> {
+    let mut lines = contents.lines();
+    if lines.next()? != rustc_version {
+        return None;
+    }
+    let mut manifest = Manifest::default();
+    for line in lines {
+        match line.split_once(' ')? {
+            ("source", rest) => {
+                let (hash, path) = rest.split_once(' ')?;
+                manifest.sources.push((path.to_owned(), hash.to_owned()));
+            }
+            ("file", rest) => {
+                let mut fields = rest.splitn(4, ' ');
+                let blob = fields.next()?.to_owned();
+                let size = fields.next()?.parse().ok()?;
+                let modified = fields.next()?.parse().ok()?;
+                let name = fields.next()?.to_owned();
+                // The files are restored into the session directory.
+                if Path::new(&name).file_name() != Some(OsStr::new(&name)) {
+                    return None;
+                }
+                manifest.files.push(PublishedFile { name, blob, size, modified });
+            }
+            _ => return None,
+        }
+    }
+    Some(manifest)
+}
+
+fn read_manifest(path: &Path, rustc_version: &str) -> Option<Manifest> {
+    parse_manifest(&std_fs::read_to_string(path).ok()?, rustc_version)
+}
+
+/// Returns the blobs used by a manifest, whatever the compiler version that
+/// wrote it.
+fn manifest_blobs(contents: &str) -> Vec<String> {
+    contents
+        .lines()
+        .filter_map(|line| Some(line.strip_prefix("file ")?.split(' ').next()?.to_owned()))
+        .collect()
+}
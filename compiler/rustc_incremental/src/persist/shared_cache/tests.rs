@@ -0,0 +1,61 @@
+use super::*;
+
+use std::time::{Duration, UNIX_EPOCH};
+
+#[test]
+fn test_manifest_roundtrip() {
+    let file = |name: &str, blob: &str| PublishedFile {
+        name: name.to_owned(),
+        blob: blob.to_owned(),
+        size: 42,
+        modified: 1_660_000_000_000_000_000,
+    };
+    let manifest = Manifest {
+        sources: vec![
+            ("src/main.rs".to_owned(), "9f86d081".to_owned()),
+            ("src/with space.rs".to_owned(), "60303ae2".to_owned()),
+        ],
+        files: vec![file("dep-graph.bin", "1a2b"), file("main.main.1f2e-cgu.0.o", "3c4d")],
+    };
+    let contents = write_manifest("1.65.0-nightly", &manifest);
+    assert_eq!(parse_manifest(&contents, "1.65.0-nightly"), Some(manifest));
+    assert_eq!(parse_manifest(&contents, "1.66.0-nightly"), None);
+    assert_eq!(manifest_blobs(&contents), ["1a2b", "3c4d"]);
+    assert_eq!(parse_manifest("1.65.0-nightly\nmalformed\n", "1.65.0-nightly"), None);
+    assert_eq!(parse_manifest("1.65.0-nightly\nfile 1a2b 42 0 ../x\n", "1.65.0-nightly"), None);
+}
+
+#[test]
+fn test_entries_to_collect() {
+    let manifest = |secs, name: &str, blobs: &[&str]| {
+        let blobs = blobs.iter().map(|blob| blob.to_string()).collect();
+        (UNIX_EPOCH + Duration::new(secs, 0), PathBuf::from(name), blobs)
+    };
+    let manifests = vec![
+        manifest(3, "c", &["shared", "c"]),
+        manifest(1, "a", &["shared", "a"]),
+        manifest(2, "b", &["shared", "b"]),
+    ];
+    let blob_sizes: FxHashMap<String, u64> =
+        [("shared", 10), ("a", 5), ("b", 5), ("c", 5), ("unused", 100)]
+            .into_iter()
+            .map(|(blob, size)| (blob.to_owned(), size))
+            .collect();
+
+    // Only the unused blobs are removed when the others fit in the limit.
+    assert_eq!(
+        entries_to_collect(manifests.clone(), &blob_sizes, 25),
+        (vec![], vec!["unused".to_owned()])
+    );
+
+    // The oldest manifests are removed first, and the shared blob is kept.
+    assert_eq!(
+        entries_to_collect(manifests.clone(), &blob_sizes, 20),
+        (vec![PathBuf::from("a")], vec!["a".to_owned(), "unused".to_owned()])
+    );
+
+    // Everything is removed when even the newest manifest doesn't fit.
+    let (removed_manifests, removed_blobs) = entries_to_collect(manifests, &blob_sizes, 0);
+    assert_eq!(removed_manifests, [PathBuf::from("a"), PathBuf::from("b"), PathBuf::from("c")]);
+    assert_eq!(removed_blobs.len(), blob_sizes.len());
+}
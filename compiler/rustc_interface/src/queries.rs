@@ -190,8 +190,14 @@ fn dep_graph(&self) -> Result<&Query<DepGraph>> {
             let future_opt = self.dep_graph_future()?.take();
             let dep_graph = future_opt
                 .and_then(|future| {
-                    let (prev_graph, prev_work_products) =
-                        sess.time("blocked_on_dep_graph_loading", || future.open().open(sess));
+                    let mut load_result =
+                        sess.time("blocked_on_dep_graph_loading", || future.open());
+                    // Now that the source files are known, a session published to the
+                    // incremental shared cache may match them better than the previous one.
+                    if rustc_incremental::restore_from_shared_cache(sess) {
+                        load_result = rustc_incremental::load_dep_graph(sess).open();
+                    }
+                    let (prev_graph, prev_work_products) = load_result.open(sess);
 
                     rustc_incremental::build_dep_graph(sess, prev_graph, prev_work_products)
                 })
@@ -390,6 +396,7 @@ pub fn enter<F, T>(&self, f: F) -> T
                     queries.session().prof.generic_activity("self_profile_alloc_query_strings");
                 gcx.enter(rustc_query_impl::alloc_self_profile_query_strings);
             }
+            gcx.enter(rustc_query_impl::record_time_report_queries);
 
             self.session()
                 .time("serialize_dep_graph", || gcx.enter(rustc_incremental::save_dep_graph));
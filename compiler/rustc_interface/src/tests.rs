@@ -1,7 +1,7 @@
 use crate::interface::parse_cfgspecs;
 
 use rustc_data_structures::fx::FxHashSet;
-use rustc_errors::{emitter::HumanReadableErrorType, registry, ColorConfig};
+use rustc_errors::{emitter::HumanReadableErrorType, registry, Applicability, ColorConfig};
 use rustc_session::config::InstrumentCoverage;
 use rustc_session::config::Strip;
 use rustc_session::config::{build_configuration, build_session_options, to_crate_config};
@@ -9,8 +9,8 @@
     rustc_optgroups, ErrorOutputType, ExternLocation, LocationDetail, Options, Passes,
 };
 use rustc_session::config::{
-    BranchProtection, Externs, OomStrategy, OutputType, OutputTypes, PAuthKey, PacRet,
-    SymbolManglingVersion, WasiExecModel,
+    BranchProtection, CodeIndexFormat, Externs, OomStrategy, OutputType, OutputTypes, PAuthKey,
+    PacRet, SymbolManglingVersion, WasiExecModel,
 };
 use rustc_session::config::{CFGuard, ExternEntry, LinkerPluginLto, LtoCli, SwitchWithOptPath};
 use rustc_session::lint::Level;
@@ -643,6 +643,7 @@ macro_rules! untracked {
 
     // Make sure that changing an [UNTRACKED] option leaves the hash unchanged.
     // This list is in alphabetical order.
+    untracked!(apply_suggestions, Some(Applicability::MachineApplicable));
     untracked!(assert_incr_state, Some(String::from("loaded")));
     untracked!(deduplicate_diagnostics, false);
     untracked!(dep_tasks, true);
@@ -655,16 +656,21 @@ macro_rules! untracked {
     untracked!(dump_mir_dir, String::from("abc"));
     untracked!(dump_mir_exclude_pass_number, true);
     untracked!(dump_mir_graphviz, true);
+    untracked!(emit_code_index, Some(CodeIndexFormat::Scip));
     untracked!(emit_stack_sizes, true);
     untracked!(future_incompat_test, true);
     untracked!(hir_stats, true);
     untracked!(identify_regions, true);
+    untracked!(incremental_explain, true);
     untracked!(incremental_ignore_spans, true);
     untracked!(incremental_info, true);
+    untracked!(incremental_shared_cache, Some(PathBuf::from("abc")));
+    untracked!(incremental_shared_cache_size, 1);
     untracked!(incremental_verify_ich, true);
     untracked!(input_stats, true);
     untracked!(keep_hygiene_data, true);
     untracked!(link_native_libraries, false);
+    untracked!(lint_baseline, Some(PathBuf::from("lint-baseline.txt")));
     untracked!(llvm_time_trace, true);
     untracked!(ls, true);
     untracked!(macro_backtrace, true);
@@ -681,6 +687,7 @@ macro_rules! untracked {
     untracked!(profile_closures, true);
     untracked!(print_llvm_passes, true);
     untracked!(print_mono_items, Some(String::from("abc")));
+    untracked!(print_mono_stats, true);
     untracked!(print_type_sizes, true);
     untracked!(proc_macro_backtrace, true);
     untracked!(query_dep_graph, true);
@@ -694,11 +701,14 @@ macro_rules! untracked {
     untracked!(time, true);
     untracked!(time_llvm_passes, true);
     untracked!(time_passes, true);
+    untracked!(time_report, Some(PathBuf::from("time-report.json")));
     untracked!(trace_macros, true);
     untracked!(trim_diagnostic_paths, false);
+    untracked!(type_size_limits, Some(PathBuf::from("type-size-limits.txt")));
     untracked!(ui_testing, true);
     untracked!(unpretty, Some("expanded".to_string()));
     untracked!(unstable_options, true);
+    untracked!(update_lint_baseline, true);
     untracked!(validate_mir, true);
     untracked!(verbose, true);
 
@@ -813,6 +823,28 @@ macro_rules! tracked_no_crate_hash {
     tracked_no_crate_hash!(no_codegen, true);
 }
 
+#[test]
+fn test_dep_tracking_hashes() {
+    let reference = Options::default();
+    let mut opts = reference.clone();
+    opts.cg.opt_level = String::from("3");
+    opts.unstable_opts.no_codegen = true;
+    opts.remap_path_prefix = vec![("/home/bors/rust".into(), "src".into())];
+    opts.unstable_opts.incremental_info = true;
+
+    let reference_hashes = reference.dep_tracking_hashes();
+    let hashes = opts.dep_tracking_hashes();
+    assert_eq!(reference_hashes.keys().collect::<Vec<_>>(), hashes.keys().collect::<Vec<_>>());
+    let changed: Vec<_> =
+        hashes.iter().filter(|&(name, hash)| reference_hashes[name] != *hash).collect();
+    // Untracked options aren't hashed, and the options of the sub-structs are
+    // named like on the command line.
+    assert_eq!(
+        changed.into_iter().map(|(name, _)| &name[..]).collect::<Vec<_>>(),
+        ["-C opt-level", "-Z no-codegen", "remap_path_prefix"]
+    );
+}
+
 #[test]
 fn test_edition_parsing() {
     // test default edition
@@ -11,8 +11,10 @@
     SerializedDepNodeIndex, WorkProduct, WorkProductId,
 };
 
-pub use dep_node::{label_strs, DepKind, DepKindStruct, DepNode, DepNodeExt};
-pub(crate) use dep_node::{make_compile_codegen_unit, make_compile_mono_item};
+pub use dep_node::{
+    label_strs, make_compile_codegen_unit, DepKind, DepKindStruct, DepNode, DepNodeExt,
+};
+pub(crate) use dep_node::make_compile_mono_item;
 
 pub type DepGraph = rustc_query_system::dep_graph::DepGraph<DepKind>;
 pub type TaskDeps = rustc_query_system::dep_graph::TaskDeps<DepKind>;
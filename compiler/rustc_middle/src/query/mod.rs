@@ -1795,6 +1795,15 @@
         desc { "output_filenames" }
     }
 
+    /// Return the working directory of the compiler, which ends up in debuginfo and coverage maps.
+    ///
+    /// Codegen reads it through this query rather than from the session options, as it isn't part
+    /// of the hash of the command-line arguments with `-Z incremental-shared-cache`.
+    query working_directory(_: ()) -> &'tcx RealFileName {
+        eval_always
+        desc { "getting the working directory" }
+    }
+
     /// Do not call this query directly: invoke `normalize` instead.
     query normalize_projection_ty(
         goal: CanonicalProjectionGoal<'tcx>
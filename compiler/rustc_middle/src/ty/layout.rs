@@ -1,8 +1,11 @@
 use crate::middle::codegen_fn_attrs::CodegenFnAttrFlags;
 use crate::mir::{GeneratorLayout, GeneratorSavedLocal};
 use crate::ty::normalize_erasing_regions::NormalizationError;
+use crate::ty::print::with_no_trimmed_paths;
 use crate::ty::subst::Subst;
-use crate::ty::{self, subst::SubstsRef, EarlyBinder, ReprOptions, Ty, TyCtxt, TypeVisitable};
+use crate::ty::{
+    self, subst::SubstsRef, DefIdTree, EarlyBinder, ReprOptions, Ty, TyCtxt, TypeVisitable,
+};
 use rustc_ast as ast;
 use rustc_attr as attr;
 use rustc_hir as hir;
@@ -10,7 +13,7 @@
 use rustc_hir::lang_items::LangItem;
 use rustc_index::bit_set::BitSet;
 use rustc_index::vec::{Idx, IndexVec};
-use rustc_session::{config::OptLevel, DataTypeKind, FieldInfo, SizeKind, VariantInfo};
+use rustc_session::{config::OptLevel, DataTypeKind, FieldInfo, FieldKind, SizeKind, VariantInfo};
 use rustc_span::symbol::Symbol;
 use rustc_span::{Span, DUMMY_SP};
 use rustc_target::abi::call::{
@@ -1912,9 +1915,11 @@ fn generator_layout(
     /// layout of each type.
     #[inline(always)]
     fn record_layout_for_printing(&self, layout: TyAndLayout<'tcx>) {
-        // If we are running with `-Zprint-type-sizes`, maybe record layouts
-        // for dumping later.
-        if self.tcx.sess.opts.unstable_opts.print_type_sizes {
+        // If we are running with `-Zprint-type-sizes` or `-Ztype-size-limits`,
+        // maybe record layouts for dumping or checking later.
+        if self.tcx.sess.opts.unstable_opts.print_type_sizes
+            || self.tcx.sess.opts.unstable_opts.type_size_limits.is_some()
+        {
             self.record_layout_for_printing_outlined(layout)
         }
     }
@@ -1928,8 +1933,19 @@ fn record_layout_for_printing_outlined(&self, layout: TyAndLayout<'tcx>) {
         }
 
         // (delay format until we actually need it)
-        let record = |kind, packed, opt_discr_size, variants| {
-            let type_desc = format!("{:?}", layout.ty);
+        let record = |kind, packed, opt_discr_size, variants, def_id: DefId| {
+            let type_desc = match *layout.ty.kind() {
+                // Name the futures of async functions after the function, as
+                // they have no other name users could refer to them by.
+                ty::Generator(def_id, ..)
+                    if self.tcx.generator_kind(def_id)
+                        == Some(hir::GeneratorKind::Async(hir::AsyncGeneratorKind::Fn)) =>
+                {
+                    let fn_def_id = self.tcx.parent(def_id);
+                    format!("async fn {}", with_no_trimmed_paths!(self.tcx.def_path_str(fn_def_id)))
+                }
+                _ => format!("{:?}", layout.ty),
+            };
             self.tcx.sess.code_stats.record_type_size(
                 kind,
                 type_desc,
@@ -1938,6 +1954,7 @@ fn record_layout_for_printing_outlined(&self, layout: TyAndLayout<'tcx>) {
                 packed,
                 opt_discr_size,
                 variants,
+                Some(self.tcx.def_span(def_id)),
             );
         };
 
@@ -1947,9 +1964,17 @@ fn record_layout_for_printing_outlined(&self, layout: TyAndLayout<'tcx>) {
                 adt_def
             }
 
-            ty::Closure(..) => {
+            ty::Closure(def_id, _) => {
                 debug!("print-type-size t: `{:?}` record closure", layout.ty);
-                record(DataTypeKind::Closure, false, None, vec![]);
+                record(DataTypeKind::Closure, false, None, vec![], def_id);
+                return;
+            }
+
+            ty::Generator(def_id, substs, _) => {
+                debug!("print-type-size t: `{:?}` record generator", layout.ty);
+                let (variants, opt_discr_size) =
+                    self.variant_info_for_generator(layout, def_id, substs);
+                record(DataTypeKind::Generator, false, opt_discr_size, variants, def_id);
                 return;
             }
 
@@ -1975,6 +2000,7 @@ fn record_layout_for_printing_outlined(&self, layout: TyAndLayout<'tcx>) {
                         min_size = field_end;
                     }
                     FieldInfo {
+                        kind: FieldKind::AdtField,
                         name,
                         offset: offset.bytes(),
                         size: field_layout.size.bytes(),
@@ -1989,6 +2015,7 @@ fn record_layout_for_printing_outlined(&self, layout: TyAndLayout<'tcx>) {
                 align: layout.align.abi.bytes(),
                 size: if min_size.bytes() == 0 { layout.size.bytes() } else { min_size.bytes() },
                 fields: field_info,
+                span: None,
             }
         };
 
@@ -2007,11 +2034,12 @@ fn record_layout_for_printing_outlined(&self, layout: TyAndLayout<'tcx>) {
                         adt_packed,
                         None,
                         vec![build_variant_info(Some(variant_def.name), &fields, layout)],
+                        adt_def.did(),
                     );
                 } else {
                     // (This case arises for *empty* enums; so give it
                     // zero variants.)
-                    record(adt_kind.into(), adt_packed, None, vec![]);
+                    record(adt_kind.into(), adt_packed, None, vec![], adt_def.did());
                 }
             }
 
@@ -2041,10 +2069,112 @@ fn record_layout_for_printing_outlined(&self, layout: TyAndLayout<'tcx>) {
                         _ => None,
                     },
                     variant_infos,
+                    adt_def.did(),
                 );
             }
         }
     }
+
+    /// Returns the variants of a generator for `record_layout_for_printing`,
+    /// one for each state, with its size and discriminant size.
+    ///
+    /// The fields of each state are the upvars and the locals held across its
+    /// suspension point.
+    fn variant_info_for_generator(
+        &self,
+        layout: TyAndLayout<'tcx>,
+        def_id: DefId,
+        substs: SubstsRef<'tcx>,
+    ) -> (Vec<VariantInfo>, Option<Size>) {
+        let Variants::Multiple { tag, ref tag_encoding, tag_field, .. } = layout.variants else {
+            return (vec![], None);
+        };
+        let tcx = self.tcx;
+        let generator = tcx.generator_layout(def_id).unwrap();
+        // The captures are only known for the generators of the local crate.
+        let upvar_names: Vec<_> = match def_id.as_local() {
+            Some(local_def_id) => tcx
+                .typeck(local_def_id)
+                .closure_min_captures_flattened(def_id)
+                .map(|captured_place| Symbol::intern(&captured_place.to_string(tcx)))
+                .collect(),
+            None => vec![],
+        };
+
+        let mut upvars_size = Size::ZERO;
+        let upvar_fields: Vec<_> = (0..substs.as_generator().upvar_tys().count())
+            .map(|field_idx| {
+                let field_layout = layout.field(self, field_idx);
+                let offset = layout.fields.offset(field_idx);
+                upvars_size = upvars_size.max(offset + field_layout.size);
+                FieldInfo {
+                    kind: FieldKind::Upvar,
+                    name: upvar_names
+                        .get(field_idx)
+                        .copied()
+                        .unwrap_or_else(|| Symbol::intern(&format!("upvar{field_idx}"))),
+                    offset: offset.bytes(),
+                    size: field_layout.size.bytes(),
+                    align: field_layout.align.abi.bytes(),
+                }
+            })
+            .collect();
+
+        let tag_size = match tag_encoding {
+            TagEncoding::Direct => tag.size(self),
+            _ => Size::ZERO,
+        };
+        let variant_infos = generator
+            .variant_fields
+            .iter_enumerated()
+            .map(|(variant_idx, variant_def)| {
+                let variant_layout = layout.for_variant(self, variant_idx);
+                let mut variant_size = Size::ZERO;
+                let fields = variant_def
+                    .iter()
+                    .enumerate()
+                    .map(|(field_idx, &local)| {
+                        let field_layout = variant_layout.field(self, field_idx);
+                        let offset = variant_layout.fields.offset(field_idx);
+                        variant_size = variant_size.max(offset + field_layout.size);
+                        FieldInfo {
+                            kind: FieldKind::GeneratorLocal,
+                            name: generator.field_names[local]
+                                .unwrap_or_else(|| Symbol::intern(&format!("{:?}", local))),
+                            offset: offset.bytes(),
+                            size: field_layout.size.bytes(),
+                            align: field_layout.align.abi.bytes(),
+                        }
+                    })
+                    .chain(upvar_fields.iter().cloned())
+                    .collect();
+
+                // A state without locals is as large as the upvars.
+                variant_size = variant_size.max(upvars_size);
+                // `print_type_sizes` counts the discriminant as part of the
+                // variants, which it is unless it is placed after their fields.
+                if layout.fields.offset(tag_field) >= variant_size {
+                    variant_size += tag_size;
+                }
+
+                // The first states are the unresumed, returned and poisoned
+                // ones, the others start at a suspension point.
+                let span = (variant_idx.as_usize() > ty::GeneratorSubsts::POISONED)
+                    .then(|| generator.variant_source_info[variant_idx].span);
+
+                VariantInfo {
+                    name: Some(Symbol::intern(&ty::GeneratorSubsts::variant_name(variant_idx))),
+                    kind: SizeKind::Exact,
+                    size: variant_size.bytes(),
+                    align: variant_layout.align.abi.bytes(),
+                    fields,
+                    span,
+                }
+            })
+            .collect();
+
+        (variant_infos, Some(tag_size).filter(|size| *size > Size::ZERO))
+    }
 }
 
 /// Type size "skeleton", i.e., the only information determining a type's size.
@@ -0,0 +1,106 @@
+//! The report of `-Z print-mono-stats`, grouping the instances of the generic
+//! functions by definition to find the ones bloating the generated code.
+
+use crate::collector::InliningMap;
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_hir::def_id::{CrateNum, DefId, LOCAL_CRATE};
+use rustc_middle::mir::mono::{CodegenUnit, MonoItem};
+use rustc_middle::ty::print::with_no_trimmed_paths;
+use rustc_middle::ty::{Instance, TyCtxt};
+use rustc_span::symbol::Symbol;
+use serde_json::json;
+
+/// The instances of a generic function.
+#[derive(Default)]
+struct DefinitionStats<'tcx> {
+    /// The instances, with their estimated size in MIR statements.
+    instances: Vec<(Instance<'tcx>, usize)>,
+    /// The crates containing the items that use the instances.
+    used_by: FxHashSet<CrateNum>,
+    /// The codegen units the instances are placed in.
+    cgus: FxHashSet<Symbol>,
+}
+
+/// Prints, as JSON, the generic functions of which instances are codegened in
+/// this crate, starting with the ones with the biggest total estimated size.
+pub(crate) fn print_mono_stats<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    items: &FxHashSet<MonoItem<'tcx>>,
+    inlining_map: &InliningMap<'tcx>,
+    codegen_units: &[CodegenUnit<'tcx>],
+) {
+    let mut users: FxHashMap<MonoItem<'tcx>, FxHashSet<CrateNum>> = Default::default();
+    inlining_map.iter_accesses(|accessor, accessed| {
+        for &item in accessed {
+            users.entry(item).or_default().insert(accessor.krate());
+        }
+    });
+
+    let mut definitions: FxHashMap<DefId, DefinitionStats<'tcx>> = Default::default();
+    for &item in items {
+        let MonoItem::Fn(instance) = item else { continue };
+        // The substs of closures always contain their kind, signature and
+        // upvar types, so whether they are generic depends on the definition
+        // they are in.
+        let generics = tcx.generics_of(tcx.typeck_root_def_id(instance.def_id()));
+        if !generics.requires_monomorphization(tcx) {
+            continue;
+        }
+        let stats = definitions.entry(instance.def_id()).or_default();
+        stats.instances.push((instance, item.size_estimate(tcx)));
+        stats.used_by.extend(users.get(&item).into_iter().flatten());
+    }
+    for cgu in codegen_units {
+        for item in cgu.items().keys() {
+            let MonoItem::Fn(instance) = item else { continue };
+            if let Some(stats) = definitions.get_mut(&instance.def_id()) {
+                stats.cgus.insert(cgu.name());
+            }
+        }
+    }
+
+    let mut definitions: Vec<_> = definitions
+        .into_iter()
+        .map(|(def_id, stats)| {
+            let size_estimate: usize = stats.instances.iter().map(|&(_, size)| size).sum();
+            (size_estimate, with_no_trimmed_paths!(tcx.def_path_str(def_id)), def_id, stats)
+        })
+        .collect();
+    definitions.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
+
+    let definitions: Vec<_> = definitions
+        .into_iter()
+        .map(|(size_estimate, path, def_id, stats)| {
+            let mut instances: Vec<_> = stats
+                .instances
+                .iter()
+                .map(|&(instance, size)| (size, with_no_trimmed_paths!(instance.to_string())))
+                .collect();
+            instances.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
+            let mut used_by: Vec<_> =
+                stats.used_by.iter().map(|&krate| tcx.crate_name(krate).to_string()).collect();
+            used_by.sort();
+            used_by.dedup();
+            let mut cgus: Vec<_> = stats.cgus.iter().map(|cgu| cgu.to_string()).collect();
+            cgus.sort();
+            json!({
+                "definition": path,
+                "crate": tcx.crate_name(def_id.krate).as_str(),
+                "instance_count": instances.len(),
+                "size_estimate": size_estimate,
+                "used_by_crates": used_by,
+                "codegen_units": cgus,
+                "instances": instances
+                    .into_iter()
+                    .map(|(size, instance)| json!({ "instance": instance, "size_estimate": size }))
+                    .collect::<Vec<_>>(),
+            })
+        })
+        .collect();
+
+    let stats = json!({
+        "crate": tcx.crate_name(LOCAL_CRATE).as_str(),
+        "definitions": definitions,
+    });
+    println!("{}", serde_json::to_string_pretty(&stats).unwrap());
+}
@@ -107,6 +107,7 @@
 
 use crate::collector::InliningMap;
 use crate::collector::{self, MonoItemCollectionMode};
+use crate::mono_stats;
 
 pub struct PartitioningCx<'a, 'tcx> {
     tcx: TyCtxt<'tcx>,
@@ -464,6 +465,10 @@ fn collect_and_partition_mono_items<'tcx>(
         }
     }
 
+    if tcx.sess.opts.unstable_opts.print_mono_stats {
+        mono_stats::print_mono_stats(tcx, &items, &inlining_map, codegen_units);
+    }
+
     (tcx.arena.alloc(mono_items), codegen_units)
 }
 
@@ -1,8 +1,9 @@
 use measureme::{StringComponent, StringId};
 use rustc_data_structures::fx::FxHashMap;
-use rustc_data_structures::profiling::SelfProfiler;
+use rustc_data_structures::profiling::{SelfProfiler, TimeReport, Timing};
 use rustc_hir::def_id::{CrateNum, DefId, DefIndex, LocalDefId, LOCAL_CRATE};
 use rustc_hir::definitions::DefPathData;
+use rustc_middle::ty::print::with_no_trimmed_paths;
 use rustc_middle::ty::{TyCtxt, WithOptConstParam};
 use rustc_query_system::query::QueryCache;
 use std::fmt::Debug;
@@ -322,3 +323,85 @@ macro_rules! alloc_once {
 
     rustc_query_append! { [alloc_once!][<'tcx>] }
 }
+
+/// The queries whose timings `-Z time-report` also records by item, for their
+/// keys implementing `TimeReportItem`.
+const TIME_REPORT_ITEM_QUERIES: &[&str] = &["typeck", "mir_borrowck", "optimized_mir"];
+
+trait TimeReportItem {
+    /// The path of the item this query key is about, if any.
+    fn time_report_item(&self, tcx: TyCtxt<'_>) -> Option<String>;
+}
+
+impl<T> TimeReportItem for T {
+    default fn time_report_item(&self, _tcx: TyCtxt<'_>) -> Option<String> {
+        None
+    }
+}
+
+impl TimeReportItem for DefId {
+    fn time_report_item(&self, tcx: TyCtxt<'_>) -> Option<String> {
+        Some(with_no_trimmed_paths!(tcx.def_path_str(*self)))
+    }
+}
+
+impl TimeReportItem for LocalDefId {
+    fn time_report_item(&self, tcx: TyCtxt<'_>) -> Option<String> {
+        self.to_def_id().time_report_item(tcx)
+    }
+}
+
+/// Names the query provider executions of a single query cache in the time
+/// report. This method is called from `record_time_report_queries` which knows
+/// all the queries via macro magic.
+fn record_time_report_queries_for_query_cache<'tcx, C>(
+    tcx: TyCtxt<'tcx>,
+    report: &TimeReport,
+    query_name: &'static str,
+    query_cache: &C,
+    invocations: &mut FxHashMap<u32, Timing>,
+) where
+    C: QueryCache,
+    C::Key: Clone,
+{
+    // As when allocating the query strings, the keys are copied out of the
+    // cache first, as getting the path of an item may need to run queries.
+    let mut executed = Vec::new();
+    query_cache.iter(&mut |key, _, dep_node_index| {
+        if let Some(timing) = invocations.remove(&dep_node_index.as_u32()) {
+            executed.push((key.clone(), timing));
+        }
+    });
+
+    let by_item = TIME_REPORT_ITEM_QUERIES.contains(&query_name);
+    for (key, timing) in executed {
+        let item = if by_item { key.time_report_item(tcx) } else { None };
+        report.record_query(query_name, item, timing);
+    }
+}
+
+/// The query provider executions recorded for `-Z time-report` are identified
+/// by `QueryInvocationId`, like the self-profiling events. This method names
+/// them with their query, and their item for the queries reporting them.
+pub fn record_time_report_queries(tcx: TyCtxt<'_>) {
+    let Some(report) = tcx.prof.time_report() else { return };
+    let mut invocations = report.take_query_invocations();
+
+    macro_rules! record_once {
+        (<$tcx:tt>
+            $($(#[$attr:meta])* [$($modifiers:tt)*] fn $name:ident($K:ty) -> $V:ty,)*
+        ) => {
+            $({
+                record_time_report_queries_for_query_cache(
+                    tcx,
+                    report,
+                    stringify!($name),
+                    &tcx.query_caches.$name,
+                    &mut invocations,
+                );
+            })*
+        }
+    }
+
+    rustc_query_append! { [record_once!][<'tcx>] }
+}
@@ -843,6 +843,45 @@ pub fn exec_cache_promotions<Ctxt: DepContext<DepKind = K>>(&self, tcx: Ctxt) {
         }
     }
 
+    /// Returns the number of nodes in the dep-graph of the previous session,
+    /// which is zero if there was none to load.
+    pub fn previous_node_count(&self) -> usize {
+        self.data.as_ref().map_or(0, |data| data.previous.node_count())
+    }
+
+    /// Returns the nodes of the previous dep-graph that were marked red in
+    /// this session, i.e. that were recomputed with a different result or
+    /// without a result hash to compare.
+    pub fn previous_red_nodes(&self) -> Vec<DepNode<K>> {
+        let Some(data) = &self.data else { return vec![] };
+        data.colors
+            .values
+            .indices()
+            .filter(|&prev_index| data.colors.get(prev_index) == Some(DepNodeColor::Red))
+            .map(|prev_index| data.previous.index_to_node(prev_index))
+            .collect()
+    }
+
+    /// Returns the first dependency that isn't green of a node of the previous
+    /// dep-graph, with its color, or `None` if it wasn't recomputed.
+    ///
+    /// As `try_mark_previous_green` checks the dependencies of a node in order
+    /// and gives up on the first one it can't mark green, this is the
+    /// dependency that caused the node to be recomputed.
+    pub fn first_non_green_dependency(
+        &self,
+        dep_node: &DepNode<K>,
+    ) -> Option<(DepNode<K>, Option<DepNodeColor>)> {
+        let data = self.data.as_ref()?;
+        let prev_index = data.previous.node_to_index_opt(dep_node)?;
+        data.previous.edge_targets_from(prev_index).iter().find_map(|&dep_index| {
+            match data.colors.get(dep_index) {
+                Some(DepNodeColor::Green(_)) => None,
+                color => Some((data.previous.index_to_node(dep_index), color)),
+            }
+        })
+    }
+
     pub fn print_incremental_info(&self) {
         if let Some(data) = &self.data {
             data.current.encoder.borrow().print_incremental_info(
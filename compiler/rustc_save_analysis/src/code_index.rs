@@ -0,0 +1,345 @@
+//! Writes the save-analysis results as a code-intelligence index, for `-Z emit-code-index`.
+//!
+//! The definitions, references, signatures and impl relations collected by the `DumpVisitor`
+//! are first turned into a [`CodeIndex`], a flat list of documents with their occurrences and
+//! symbols. That model is then serialized either as [SCIP] or as [LSIF].
+//!
+//! Symbols follow the SCIP symbol syntax in both formats: `rustc . <crate> . <descriptors>`,
+//! where the descriptors are built from the def-path of the item, so the same item gets the
+//! same symbol in the index of the crate that defines it and in the index of every crate that
+//! uses it. Local variables get a `local <n>` symbol that is only meaningful within the index.
+//!
+//! [SCIP]: https://github.com/sourcegraph/scip
+//! [LSIF]: https://microsoft.github.io/language-server-protocol/specifications/lsif/0.6.0/specification/
+
+use crate::{SaveContext, SaveHandler};
+
+use rls_data::config::Config;
+use rls_data::{Analysis, DefKind, RelationKind, SpanData};
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_hir::def::DefKind as HirDefKind;
+use rustc_hir::def_id::{CrateNum, DefId, DefIndex, LOCAL_CRATE};
+use rustc_middle::ty::{DefIdTree, TyCtxt};
+use rustc_session::config::CodeIndexFormat;
+use rustc_span::source_map::SourceMap;
+use rustc_span::BytePos;
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use tracing::info;
+
+mod lsif;
+mod scip;
+
+/// Write a code-intelligence index of the save-analysis results to a file.
+pub struct CodeIndexHandler<'a> {
+    format: CodeIndexFormat,
+    odir: Option<&'a Path>,
+    cratename: String,
+}
+
+impl<'a> CodeIndexHandler<'a> {
+    pub fn new(format: CodeIndexFormat, odir: Option<&'a Path>, cratename: &str) -> Self {
+        CodeIndexHandler { format, odir, cratename: cratename.to_owned() }
+    }
+
+    /// The save-analysis configuration the index needs: full docs and signatures for hovers.
+    pub fn config() -> Config {
+        Config { full_docs: true, signatures: true, ..Config::default() }
+    }
+
+    fn output_file(&self, ctx: &SaveContext<'_>) -> PathBuf {
+        let sess = &ctx.tcx.sess;
+        let mut root_path = match self.odir {
+            Some(val) => val.join("code-index"),
+            None => PathBuf::from("code-index-temp"),
+        };
+
+        if let Err(e) = std::fs::create_dir_all(&root_path) {
+            sess.fatal(&format!("Could not create directory {}: {}", root_path.display(), e));
+        }
+
+        let extension = match self.format {
+            CodeIndexFormat::Scip => "scip",
+            CodeIndexFormat::Lsif => "lsif",
+        };
+        root_path.push(format!("{}{}.{}", self.cratename, sess.opts.cg.extra_filename, extension));
+        root_path
+    }
+}
+
+impl SaveHandler for CodeIndexHandler<'_> {
+    fn save(&mut self, save_ctxt: &SaveContext<'_>, analysis: &Analysis) {
+        let sess = &save_ctxt.tcx.sess;
+        let index = CodeIndex::new(save_ctxt.tcx, analysis);
+        let contents = match self.format {
+            CodeIndexFormat::Scip => scip::encode(&index),
+            CodeIndexFormat::Lsif => lsif::encode(&index),
+        };
+
+        let file_name = self.output_file(save_ctxt);
+        info!("Writing code index to {}", file_name.display());
+        if let Err(e) = std::fs::write(&file_name, contents) {
+            sess.fatal(&format!("Could not write {}: {}", file_name.display(), e));
+        }
+
+        if sess.opts.json_artifact_notifications {
+            sess.parse_sess.span_diagnostic.emit_artifact_notification(&file_name, "code-index");
+        }
+    }
+}
+
+/// The index of a crate, independent of the format it is written in.
+struct CodeIndex {
+    /// The URI of the directory all document paths are relative to.
+    project_root: String,
+    documents: Vec<Document>,
+}
+
+/// A source file of the crate.
+#[derive(Default)]
+struct Document {
+    /// The path of the file relative to the project root, with `/` separators.
+    relative_path: String,
+    /// The occurrences in the file, sorted by range.
+    occurrences: Vec<Occurrence>,
+    /// The symbols defined in the file.
+    symbols: Vec<SymbolInformation>,
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+struct Occurrence {
+    range: Range,
+    symbol: String,
+    is_definition: bool,
+}
+
+/// A zero-based range. Columns count UTF-16 code units, as in the Language Server Protocol.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+struct Range {
+    start_line: u32,
+    start_col: u32,
+    end_line: u32,
+    end_col: u32,
+}
+
+impl Range {
+    fn from_span(source_map: &SourceMap, span: &SpanData) -> Range {
+        // Save-analysis columns count characters, which only match UTF-16 code units for the
+        // characters of the Basic Multilingual Plane.
+        Range {
+            start_line: span.line_start.0 - 1,
+            start_col: utf16_column(source_map, span.byte_start).unwrap_or(span.column_start.0 - 1),
+            end_line: span.line_end.0 - 1,
+            end_col: utf16_column(source_map, span.byte_end).unwrap_or(span.column_end.0 - 1),
+        }
+    }
+}
+
+/// Returns the zero-based column of `pos` in UTF-16 code units, or `None` if the source of its
+/// file isn't available.
+fn utf16_column(source_map: &SourceMap, pos: u32) -> Option<u32> {
+    let pos = BytePos(pos);
+    let file = source_map.lookup_source_file(pos);
+    let src = file.src.as_ref()?;
+    let line_start = file.line_bounds(file.lookup_line(pos)?).start;
+    let line = &src[(line_start - file.start_pos).to_usize()..(pos - file.start_pos).to_usize()];
+    Some(line.encode_utf16().count() as u32)
+}
+
+struct SymbolInformation {
+    symbol: String,
+    /// The signature of the symbol as a `rust` code block, followed by its docs.
+    documentation: Vec<String>,
+    /// The traits the symbol implements.
+    implements: Vec<String>,
+}
+
+impl CodeIndex {
+    fn new(tcx: TyCtxt<'_>, analysis: &Analysis) -> CodeIndex {
+        let root = tcx.sess.opts.working_dir.remapped_path_if_available().to_path_buf();
+        let mut builder = Builder {
+            tcx,
+            root: &root,
+            locals: FxHashMap::default(),
+            items: FxHashSet::default(),
+            documents: BTreeMap::new(),
+        };
+
+        for def in &analysis.defs {
+            let id = (def.id.krate, def.id.index);
+            if def.kind == DefKind::Local {
+                let local = builder.locals.len();
+                builder.locals.insert(id, local);
+            } else {
+                builder.items.insert(id);
+            }
+        }
+
+        // Relations point from the self type to the trait, for impls of traits.
+        let mut implements: FxHashMap<String, Vec<String>> = FxHashMap::default();
+        for relation in &analysis.relations {
+            if let RelationKind::Impl { .. } = relation.kind {
+                if let (Some(from), Some(to)) =
+                    (builder.symbol(relation.from), builder.symbol(relation.to))
+                {
+                    let traits = implements.entry(from).or_default();
+                    if !traits.contains(&to) {
+                        traits.push(to);
+                    }
+                }
+            }
+        }
+
+        for def in &analysis.defs {
+            let Some(symbol) = builder.symbol(def.id) else { continue };
+            let Some(document) = builder.document(&def.span) else { continue };
+            document.occurrences.push(Occurrence {
+                range: Range::from_span(tcx.sess.source_map(), &def.span),
+                symbol: symbol.clone(),
+                is_definition: true,
+            });
+
+            let mut documentation = vec![];
+            let signature = def.sig.as_ref().map_or(&def.value, |sig| &sig.text);
+            if !signature.is_empty() {
+                documentation.push(format!("```rust\n{}\n```", signature));
+            }
+            if !def.docs.is_empty() {
+                documentation.push(def.docs.clone());
+            }
+            let implements = implements.remove(&symbol).unwrap_or_default();
+            document.symbols.push(SymbolInformation { symbol, documentation, implements });
+        }
+
+        for reference in &analysis.refs {
+            let Some(symbol) = builder.symbol(reference.ref_id) else { continue };
+            let Some(document) = builder.document(&reference.span) else { continue };
+            document.occurrences.push(Occurrence {
+                range: Range::from_span(tcx.sess.source_map(), &reference.span),
+                symbol,
+                is_definition: false,
+            });
+        }
+
+        let mut documents: Vec<_> = builder.documents.into_values().collect();
+        for document in &mut documents {
+            document.occurrences.sort();
+            document.occurrences.dedup();
+        }
+
+        CodeIndex { project_root: path_to_uri(&root), documents }
+    }
+}
+
+struct Builder<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    root: &'a Path,
+    /// The number of each local variable, keyed by its save-analysis id.
+    locals: FxHashMap<(u32, u32), usize>,
+    /// The save-analysis ids of the local items that have a definition.
+    items: FxHashSet<(u32, u32)>,
+    documents: BTreeMap<String, Document>,
+}
+
+impl Builder<'_, '_> {
+    /// Returns the symbol for a save-analysis id, or `None` if the id does not refer to
+    /// anything that can be named.
+    fn symbol(&self, id: rls_data::Id) -> Option<String> {
+        let key = (id.krate, id.index);
+        if let Some(local) = self.locals.get(&key) {
+            return Some(format!("local {}", local));
+        }
+        // Ids of the local crate that have no definition may have been made up from a `HirId`
+        // (see `id_from_hir_id`), so they can't be turned back into a `DefId`.
+        let null_id = crate::null_id();
+        if key == (null_id.krate, null_id.index)
+            || id.krate == LOCAL_CRATE.as_u32() && !self.items.contains(&key)
+        {
+            return None;
+        }
+        let def_id =
+            DefId { krate: CrateNum::from_u32(id.krate), index: DefIndex::from_u32(id.index) };
+        Some(global_symbol(self.tcx, def_id))
+    }
+
+    /// Returns the document for the file of `span`, or `None` if the file is outside of the
+    /// project root.
+    fn document(&mut self, span: &SpanData) -> Option<&mut Document> {
+        let path = &span.file_name;
+        if path.to_str().map_or(true, |path| path.starts_with('<')) {
+            return None;
+        }
+        let relative = if path.is_absolute() { path.strip_prefix(self.root).ok()? } else { path };
+        let relative_path = relative
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        Some(self.documents.entry(relative_path.clone()).or_insert_with(|| Document {
+            relative_path,
+            ..Document::default()
+        }))
+    }
+}
+
+/// Returns the symbol of an item, from the crate that defines it and its def-path.
+fn global_symbol(tcx: TyCtxt<'_>, mut def_id: DefId) -> String {
+    // Constructors share the name of their struct or variant.
+    if let HirDefKind::Ctor(..) = tcx.def_kind(def_id) {
+        def_id = tcx.parent(def_id);
+    }
+
+    let crate_name = tcx.crate_name(def_id.krate);
+    let mut descriptors = vec![];
+    let mut current = def_id;
+    while !current.is_crate_root() {
+        descriptors.push(descriptor(tcx, current));
+        current = tcx.parent(current);
+    }
+    if descriptors.is_empty() {
+        descriptors.push(format!("{}/", escape_name(crate_name.as_str())));
+    }
+    descriptors.reverse();
+
+    format!("rustc . {} . {}", crate_name, descriptors.concat())
+}
+
+/// Returns the SCIP descriptor for the last segment of the def-path of `def_id`.
+fn descriptor(tcx: TyCtxt<'_>, def_id: DefId) -> String {
+    let name = escape_name(&tcx.def_key(def_id).disambiguated_data.to_string());
+    match tcx.def_kind(def_id) {
+        HirDefKind::Mod | HirDefKind::ForeignMod | HirDefKind::Impl => format!("{}/", name),
+        HirDefKind::Struct
+        | HirDefKind::Union
+        | HirDefKind::Enum
+        | HirDefKind::Trait
+        | HirDefKind::TraitAlias
+        | HirDefKind::TyAlias
+        | HirDefKind::ForeignTy
+        | HirDefKind::AssocTy
+        | HirDefKind::OpaqueTy => format!("{}#", name),
+        HirDefKind::Fn | HirDefKind::AssocFn => format!("{}().", name),
+        HirDefKind::Macro(..) => format!("{}!", name),
+        HirDefKind::TyParam | HirDefKind::ConstParam | HirDefKind::LifetimeParam => {
+            format!("[{}]", name)
+        }
+        _ => format!("{}.", name),
+    }
+}
+
+/// Wraps a descriptor name in backticks if it contains characters SCIP doesn't allow in a
+/// plain identifier, such as the braces of `{impl#0}`.
+fn escape_name(name: &str) -> String {
+    if !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || "_+-$".contains(c)) {
+        name.to_owned()
+    } else {
+        format!("`{}`", name.replace('`', "``"))
+    }
+}
+
+fn path_to_uri(path: &Path) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    if path.starts_with('/') { format!("file://{}", path) } else { format!("file:///{}", path) }
+}
@@ -0,0 +1,186 @@
+//! Encoding of a [`CodeIndex`] as an LSIF dump, one JSON vertex or edge per line.
+//!
+//! Every symbol gets a `resultSet` that all of its ranges point to with a `next` edge, and the
+//! definition, reference, hover, moniker and implementation results hang off that result set.
+//! The SCIP symbol is used as the moniker identifier, so dumps of different crates link up.
+
+use super::{CodeIndex, Range};
+
+use rustc_data_structures::fx::FxHashMap;
+use serde_json::{json, Value};
+
+#[derive(Default)]
+struct SymbolResults {
+    result_set: u64,
+    /// The `(document, range)` ids of the definitions of the symbol.
+    definitions: Vec<(u64, u64)>,
+    /// The `(document, range)` ids of the references to the symbol.
+    references: Vec<(u64, u64)>,
+}
+
+pub(super) fn encode(index: &CodeIndex) -> Vec<u8> {
+    let mut w = Writer::default();
+    w.vertex(
+        "metaData",
+        json!({
+            "version": "0.6.0",
+            "projectRoot": index.project_root,
+            "positionEncoding": "utf-16",
+            "toolInfo": {
+                "name": "rustc",
+                "version": option_env!("CFG_VERSION").unwrap_or("unknown version"),
+            },
+        }),
+    );
+    let project = w.vertex("project", json!({ "kind": "rust" }));
+
+    let mut symbols: Vec<(&str, SymbolResults)> = vec![];
+    let mut symbol_indices: FxHashMap<&str, usize> = FxHashMap::default();
+    let mut documents = vec![];
+    for document in &index.documents {
+        let uri = format!("{}/{}", index.project_root, document.relative_path);
+        let document_id = w.vertex("document", json!({ "uri": uri, "languageId": "rust" }));
+        documents.push(document_id);
+
+        let mut ranges = vec![];
+        for occurrence in &document.occurrences {
+            let range_id = w.vertex("range", range(occurrence.range));
+            ranges.push(range_id);
+
+            let symbol = occurrence.symbol.as_str();
+            let i = *symbol_indices.entry(symbol).or_insert_with(|| {
+                let result_set = w.vertex("resultSet", json!({}));
+                symbols.push((symbol, SymbolResults { result_set, ..SymbolResults::default() }));
+                symbols.len() - 1
+            });
+            let results = &mut symbols[i].1;
+            w.edge("next", json!({ "outV": range_id, "inV": results.result_set }));
+            if occurrence.is_definition {
+                results.definitions.push((document_id, range_id));
+            } else {
+                results.references.push((document_id, range_id));
+            }
+        }
+        if !ranges.is_empty() {
+            w.edge("contains", json!({ "outV": document_id, "inVs": ranges }));
+        }
+    }
+
+    // Hovers and exported monikers, for the symbols defined in this crate.
+    let mut implementations: FxHashMap<&str, Vec<&str>> = FxHashMap::default();
+    for symbol in index.documents.iter().flat_map(|document| &document.symbols) {
+        let Some(&i) = symbol_indices.get(symbol.symbol.as_str()) else { continue };
+        let result_set = symbols[i].1.result_set;
+        if !symbol.documentation.is_empty() {
+            let contents = symbol
+                .documentation
+                .iter()
+                .map(|value| json!({ "kind": "markdown", "value": value }))
+                .collect::<Vec<_>>();
+            let hover = w.vertex("hoverResult", json!({ "result": { "contents": contents } }));
+            w.edge("textDocument/hover", json!({ "outV": result_set, "inV": hover }));
+        }
+        for implemented in &symbol.implements {
+            implementations.entry(implemented.as_str()).or_default().push(symbol.symbol.as_str());
+        }
+    }
+
+    for (symbol, results) in &symbols {
+        if !symbol.starts_with("local ") {
+            let kind = if results.definitions.is_empty() { "import" } else { "export" };
+            let moniker = w.vertex(
+                "moniker",
+                json!({ "scheme": "scip", "identifier": symbol, "kind": kind }),
+            );
+            w.edge("moniker", json!({ "outV": results.result_set, "inV": moniker }));
+        }
+
+        if !results.definitions.is_empty() {
+            let definition_result = w.vertex("definitionResult", json!({}));
+            w.edge(
+                "textDocument/definition",
+                json!({ "outV": results.result_set, "inV": definition_result }),
+            );
+            w.items(definition_result, &results.definitions, None);
+        }
+
+        let reference_result = w.vertex("referenceResult", json!({}));
+        w.edge(
+            "textDocument/references",
+            json!({ "outV": results.result_set, "inV": reference_result }),
+        );
+        w.items(reference_result, &results.definitions, Some("definitions"));
+        w.items(reference_result, &results.references, Some("references"));
+
+        if let Some(implementors) = implementations.get(*symbol) {
+            let ranges = implementors
+                .iter()
+                .filter_map(|implementor| symbol_indices.get(implementor))
+                .flat_map(|&i| symbols[i].1.definitions.iter().copied())
+                .collect::<Vec<_>>();
+            let implementation_result = w.vertex("implementationResult", json!({}));
+            w.edge(
+                "textDocument/implementation",
+                json!({ "outV": results.result_set, "inV": implementation_result }),
+            );
+            w.items(implementation_result, &ranges, None);
+        }
+    }
+
+    if !documents.is_empty() {
+        w.edge("contains", json!({ "outV": project, "inVs": documents }));
+    }
+    w.out
+}
+
+fn range(range: Range) -> Value {
+    json!({
+        "start": { "line": range.start_line, "character": range.start_col },
+        "end": { "line": range.end_line, "character": range.end_col },
+    })
+}
+
+#[derive(Default)]
+struct Writer {
+    out: Vec<u8>,
+    next_id: u64,
+}
+
+impl Writer {
+    fn write(&mut self, kind: &str, label: &str, mut element: Value) -> u64 {
+        self.next_id += 1;
+        let id = self.next_id;
+        element["id"] = id.into();
+        element["type"] = kind.into();
+        element["label"] = label.into();
+        serde_json::to_writer(&mut self.out, &element).unwrap();
+        self.out.push(b'\n');
+        id
+    }
+
+    fn vertex(&mut self, label: &str, vertex: Value) -> u64 {
+        self.write("vertex", label, vertex)
+    }
+
+    fn edge(&mut self, label: &str, edge: Value) {
+        self.write("edge", label, edge);
+    }
+
+    /// Adds `item` edges from a result to ranges, one per document the ranges are in.
+    fn items(&mut self, result: u64, ranges: &[(u64, u64)], property: Option<&str>) {
+        let mut by_document: Vec<(u64, Vec<u64>)> = vec![];
+        for &(document, range) in ranges {
+            match by_document.iter_mut().find(|(d, _)| *d == document) {
+                Some((_, ranges)) => ranges.push(range),
+                None => by_document.push((document, vec![range])),
+            }
+        }
+        for (document, ranges) in by_document {
+            let mut edge = json!({ "outV": result, "inVs": ranges, "document": document });
+            if let Some(property) = property {
+                edge["property"] = property.into();
+            }
+            self.edge("item", edge);
+        }
+    }
+}
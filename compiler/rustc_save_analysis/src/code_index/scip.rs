@@ -0,0 +1,131 @@
+//! Protobuf encoding of a [`CodeIndex`] as a SCIP `Index` message.
+//!
+//! Only the handful of messages and fields we fill in are encoded, by hand, following
+//! `scip.proto`. Fields that hold their default value are omitted, as protobuf does.
+
+use super::{CodeIndex, Document, Occurrence, Range, SymbolInformation};
+
+/// `SymbolRole.Definition`.
+const SYMBOL_ROLE_DEFINITION: u64 = 0x1;
+/// `TextEncoding.UTF8`.
+const TEXT_ENCODING_UTF8: u64 = 1;
+/// `PositionEncoding.UTF16CodeUnitOffsetFromLineStart`.
+const POSITION_ENCODING_UTF16: u64 = 2;
+
+pub(super) fn encode(index: &CodeIndex) -> Vec<u8> {
+    let mut e = Encoder::default();
+    // Index.metadata
+    e.message(1, |e| {
+        // Metadata.tool_info
+        e.message(2, |e| {
+            e.string(1, "rustc");
+            e.string(2, option_env!("CFG_VERSION").unwrap_or("unknown version"));
+        });
+        e.string(3, &index.project_root);
+        e.uint(4, TEXT_ENCODING_UTF8);
+    });
+    for document in &index.documents {
+        // Index.documents
+        e.message(2, |e| encode_document(e, document));
+    }
+    e.buf
+}
+
+fn encode_document(e: &mut Encoder, document: &Document) {
+    e.string(1, &document.relative_path);
+    for occurrence in &document.occurrences {
+        e.message(2, |e| encode_occurrence(e, occurrence));
+    }
+    for symbol in &document.symbols {
+        e.message(3, |e| encode_symbol(e, symbol));
+    }
+    e.string(4, "rust");
+    e.uint(6, POSITION_ENCODING_UTF16);
+}
+
+fn encode_occurrence(e: &mut Encoder, occurrence: &Occurrence) {
+    e.packed(1, &range(occurrence.range));
+    e.string(2, &occurrence.symbol);
+    if occurrence.is_definition {
+        e.uint(3, SYMBOL_ROLE_DEFINITION);
+    }
+}
+
+fn encode_symbol(e: &mut Encoder, symbol: &SymbolInformation) {
+    e.string(1, &symbol.symbol);
+    for documentation in &symbol.documentation {
+        e.string(3, documentation);
+    }
+    for implemented in &symbol.implements {
+        // SymbolInformation.relationships
+        e.message(4, |e| {
+            e.string(1, implemented);
+            e.uint(3, 1);
+        });
+    }
+}
+
+/// SCIP ranges have three elements when they start and end on the same line.
+fn range(range: Range) -> Vec<u64> {
+    let Range { start_line, start_col, end_line, end_col } = range;
+    if start_line == end_line {
+        vec![start_line.into(), start_col.into(), end_col.into()]
+    } else {
+        vec![start_line.into(), start_col.into(), end_line.into(), end_col.into()]
+    }
+}
+
+#[derive(Default)]
+struct Encoder {
+    buf: Vec<u8>,
+}
+
+impl Encoder {
+    const VARINT: u64 = 0;
+    const LENGTH_DELIMITED: u64 = 2;
+
+    fn varint(&mut self, mut value: u64) {
+        while value >= 0x80 {
+            self.buf.push(value as u8 | 0x80);
+            value >>= 7;
+        }
+        self.buf.push(value as u8);
+    }
+
+    fn key(&mut self, field: u64, wire_type: u64) {
+        self.varint(field << 3 | wire_type);
+    }
+
+    fn bytes(&mut self, field: u64, bytes: &[u8]) {
+        self.key(field, Self::LENGTH_DELIMITED);
+        self.varint(bytes.len() as u64);
+        self.buf.extend_from_slice(bytes);
+    }
+
+    fn uint(&mut self, field: u64, value: u64) {
+        if value != 0 {
+            self.key(field, Self::VARINT);
+            self.varint(value);
+        }
+    }
+
+    fn string(&mut self, field: u64, value: &str) {
+        if !value.is_empty() {
+            self.bytes(field, value.as_bytes());
+        }
+    }
+
+    fn packed(&mut self, field: u64, values: &[u64]) {
+        let mut packed = Encoder::default();
+        for &value in values {
+            packed.varint(value);
+        }
+        self.bytes(field, &packed.buf);
+    }
+
+    fn message(&mut self, field: u64, f: impl FnOnce(&mut Encoder)) {
+        let mut message = Encoder::default();
+        f(&mut message);
+        self.bytes(field, &message.buf);
+    }
+}
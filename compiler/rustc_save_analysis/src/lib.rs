@@ -4,6 +4,7 @@
 #![recursion_limit = "256"]
 #![allow(rustc::potential_query_instability)]
 
+mod code_index;
 mod dump_visitor;
 mod dumper;
 #[macro_use]
@@ -36,6 +37,7 @@
 use std::io::BufWriter;
 use std::path::{Path, PathBuf};
 
+pub use code_index::CodeIndexHandler;
 use dump_visitor::DumpVisitor;
 use span_utils::SpanUtils;
 
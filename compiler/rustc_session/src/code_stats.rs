@@ -1,8 +1,11 @@
-use rustc_data_structures::fx::FxHashSet;
+use crate::Session;
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_data_structures::sync::Lock;
-use rustc_span::Symbol;
+use rustc_errors::{DiagnosticBuilder, ErrorGuaranteed};
+use rustc_span::{Span, Symbol};
 use rustc_target::abi::{Align, Size};
 use std::cmp::{self, Ordering};
+use std::path::Path;
 
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub struct VariantInfo {
@@ -11,6 +14,8 @@ pub struct VariantInfo {
     pub size: u64,
     pub align: u64,
     pub fields: Vec<FieldInfo>,
+    /// The suspension point of the variants of generators.
+    pub span: Option<Span>,
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
@@ -19,8 +24,26 @@ pub enum SizeKind {
     Min,
 }
 
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum FieldKind {
+    AdtField,
+    Upvar,
+    GeneratorLocal,
+}
+
+impl FieldKind {
+    fn descr(self) -> &'static str {
+        match self {
+            FieldKind::AdtField => "field",
+            FieldKind::Upvar => "upvar",
+            FieldKind::GeneratorLocal => "local",
+        }
+    }
+}
+
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub struct FieldInfo {
+    pub kind: FieldKind,
     pub name: Symbol,
     pub offset: u64,
     pub size: u64,
@@ -33,6 +56,7 @@ pub enum DataTypeKind {
     Union,
     Enum,
     Closure,
+    Generator,
 }
 
 #[derive(PartialEq, Eq, Hash, Debug)]
@@ -44,8 +68,12 @@ pub struct TypeSizeInfo {
     pub packed: bool,
     pub opt_discr_size: Option<u64>,
     pub variants: Vec<VariantInfo>,
+    pub span: Option<Span>,
 }
 
+/// The number of fields or locals listed when a type exceeds its size limit.
+const MAX_LISTED_FIELDS: usize = 5;
+
 #[derive(Default)]
 pub struct CodeStats {
     type_sizes: Lock<FxHashSet<TypeSizeInfo>>,
@@ -61,6 +89,7 @@ pub fn record_type_size<S: ToString>(
         packed: bool,
         opt_discr_size: Option<Size>,
         mut variants: Vec<VariantInfo>,
+        span: Option<Span>,
     ) {
         // Sort variants so the largest ones are shown first. A stable sort is
         // used here so that source code order is preserved for all variants
@@ -74,6 +103,7 @@ pub fn record_type_size<S: ToString>(
             packed,
             opt_discr_size: opt_discr_size.map(|s| s.bytes()),
             variants,
+            span,
         };
         self.type_sizes.borrow_mut().insert(info);
     }
@@ -114,10 +144,11 @@ pub fn print_type_sizes(&self) {
 
             let struct_like = match kind {
                 DataTypeKind::Struct | DataTypeKind::Closure => true,
-                DataTypeKind::Enum | DataTypeKind::Union => false,
+                DataTypeKind::Enum | DataTypeKind::Union | DataTypeKind::Generator => false,
             };
             for (i, variant_info) in variants.into_iter().enumerate() {
-                let VariantInfo { ref name, kind: _, align: _, size, ref fields } = *variant_info;
+                let VariantInfo { ref name, kind: _, align: _, size, ref fields, span: _ } =
+                    *variant_info;
                 let indent = if !struct_like {
                     let name = match name.as_ref() {
                         Some(name) => name.to_string(),
@@ -144,7 +175,8 @@ pub fn print_type_sizes(&self) {
                 fields.sort_by_key(|f| (f.offset, f.size));
 
                 for field in fields {
-                    let FieldInfo { ref name, offset, size, align } = field;
+                    let FieldInfo { kind, ref name, offset, size, align } = field;
+                    let kind = kind.descr();
 
                     if offset > min_offset {
                         let pad = offset - min_offset;
@@ -154,16 +186,16 @@ pub fn print_type_sizes(&self) {
                     if offset < min_offset {
                         // If this happens it's probably a union.
                         println!(
-                            "print-type-size {indent}field `.{name}`: {size} bytes, \
+                            "print-type-size {indent}{kind} `.{name}`: {size} bytes, \
                                   offset: {offset} bytes, \
                                   alignment: {align} bytes"
                         );
                     } else if info.packed || offset == min_offset {
-                        println!("print-type-size {indent}field `.{name}`: {size} bytes");
+                        println!("print-type-size {indent}{kind} `.{name}`: {size} bytes");
                     } else {
                         // Include field alignment in output only if it caused padding injection
                         println!(
-                            "print-type-size {indent}field `.{name}`: {size} bytes, \
+                            "print-type-size {indent}{kind} `.{name}`: {size} bytes, \
                                   alignment: {align} bytes"
                         );
                     }
@@ -179,4 +211,102 @@ pub fn print_type_sizes(&self) {
             }
         }
     }
+
+    /// Reports an error for each type that is larger than its limit in the
+    /// `-Ztype-size-limits` file, with what contributes the most to its size.
+    pub fn check_type_size_limits(&self, sess: &Session, path: &Path) {
+        let limits = match std::fs::read_to_string(path) {
+            Ok(contents) => parse_type_size_limits(&contents).unwrap_or_else(|(line, msg)| {
+                sess.fatal(&format!(
+                    "invalid type size limit at `{}:{line}`: {msg}",
+                    path.display()
+                ))
+            }),
+            Err(err) => sess.fatal(&format!(
+                "failed to read type size limits from `{}`: {err}",
+                path.display()
+            )),
+        };
+
+        let type_sizes = self.type_sizes.borrow();
+        let mut exceeding: Vec<_> = type_sizes
+            .iter()
+            .filter_map(|info| {
+                let limit = *limits.get(&info.type_description)?;
+                (info.overall_size > limit).then_some((info, limit))
+            })
+            .collect();
+        exceeding.sort_by(|(info1, _), (info2, _)| {
+            info1
+                .type_description
+                .cmp(&info2.type_description)
+                .then(info2.overall_size.cmp(&info1.overall_size))
+        });
+
+        for (info, limit) in exceeding {
+            let TypeSizeInfo { type_description, overall_size, kind, variants, span, .. } = info;
+            let msg = format!(
+                "`{type_description}` is {overall_size} bytes, which exceeds its size limit \
+                of {limit} bytes"
+            );
+            let mut err = match *span {
+                Some(span) => sess.struct_span_err(span, &msg),
+                None => sess.struct_err(&msg),
+            };
+
+            // Variants are sorted from the largest to the smallest.
+            if let Some(variant) = variants.first() {
+                let VariantInfo { name, size, ref fields, span, .. } = *variant;
+                let name = name.map_or_else(String::new, |name| format!(" `{name}`"));
+                match kind {
+                    DataTypeKind::Struct | DataTypeKind::Union | DataTypeKind::Closure => {}
+                    DataTypeKind::Enum => {
+                        err.note(&format!("the largest variant{name} is {size} bytes"));
+                    }
+                    DataTypeKind::Generator => {
+                        let msg = format!("the largest state{name} is {size} bytes");
+                        match span {
+                            Some(span) => err.span_note(span, &msg),
+                            None => err.note(&msg),
+                        };
+                    }
+                }
+                note_largest_fields(&mut err, fields);
+            }
+            err.emit();
+        }
+    }
+}
+
+/// Lists the fields that contribute the most to the size of a variant.
+fn note_largest_fields(err: &mut DiagnosticBuilder<'_, ErrorGuaranteed>, fields: &[FieldInfo]) {
+    let mut fields: Vec<_> = fields.iter().filter(|field| field.size > 0).collect();
+    fields.sort_by(|field1, field2| field2.size.cmp(&field1.size));
+    for FieldInfo { kind, name, size, .. } in fields.iter().take(MAX_LISTED_FIELDS) {
+        err.note(&format!("{} `.{name}` is {size} bytes", kind.descr()));
+    }
+    if fields.len() > MAX_LISTED_FIELDS {
+        err.note(&format!("and {} smaller ones", fields.len() - MAX_LISTED_FIELDS));
+    }
+}
+
+/// Parses the lines of a `-Ztype-size-limits` file, each of which is a number
+/// of bytes followed by the type it limits the size of, as printed by
+/// `-Zprint-type-sizes`. Empty lines and lines starting with `#` are ignored.
+fn parse_type_size_limits(contents: &str) -> Result<FxHashMap<String, u64>, (usize, String)> {
+    let mut limits = FxHashMap::default();
+    for (i, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let Some((bytes, type_description)) = line.split_once(char::is_whitespace) else {
+            return Err((i + 1, "expected a number of bytes followed by a type".to_string()));
+        };
+        let Ok(bytes) = bytes.parse() else {
+            return Err((i + 1, format!("`{bytes}` is not a number of bytes")));
+        };
+        limits.insert(type_description.trim_start().to_string(), bytes);
+    }
+    Ok(limits)
 }
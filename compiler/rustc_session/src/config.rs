@@ -140,6 +140,15 @@ pub enum MirSpanview {
     Block,
 }
 
+/// The format of the code-intelligence index written by `-Z emit-code-index`.
+#[derive(Clone, Copy, PartialEq, Hash, Debug)]
+pub enum CodeIndexFormat {
+    /// `-Z emit-code-index=scip`, a protobuf-encoded SCIP index.
+    Scip,
+    /// `-Z emit-code-index=lsif`, an LSIF dump in JSON lines.
+    Lsif,
+}
+
 /// The different settings that the `-C instrument-coverage` flag can have.
 ///
 /// Coverage instrumentation now supports combining `-C instrument-coverage`
@@ -374,6 +383,11 @@ pub enum ErrorOutputType {
         /// human output.
         json_rendered: HumanReadableErrorType,
     },
+    /// A single SARIF 2.1.0 log, consumed by code-scanning services.
+    Sarif {
+        /// Render the log in a human readable way (with indents and newlines).
+        pretty: bool,
+    },
 }
 
 impl Default for ErrorOutputType {
@@ -545,6 +559,7 @@ pub enum PrintRequest {
     NativeStaticLibs,
     StackProtectorStrategies,
     LinkArgs,
+    SessionConfigJson,
 }
 
 pub enum Input {
@@ -791,6 +806,7 @@ pub fn diagnostic_handler_flags(&self, can_emit_warnings: bool) -> HandlerFlags
             report_delayed_bugs: self.report_delayed_bugs,
             macro_backtrace: self.macro_backtrace,
             deduplicate_diagnostics: self.deduplicate_diagnostics,
+            collect_suggestions: self.apply_suggestions,
         }
     }
 }
@@ -1358,7 +1374,7 @@ pub fn rustc_short_optgroups() -> Vec<RustcOptGroup> {
             "[crate-name|file-names|sysroot|target-libdir|cfg|target-list|\
              target-cpus|target-features|relocation-models|code-models|\
              tls-models|target-spec-json|native-static-libs|stack-protector-strategies|\
-             link-args]",
+             link-args|session-config-json]",
         ),
         opt::flagmulti_s("g", "", "Equivalent to -C debuginfo=2"),
         opt::flagmulti_s("O", "", "Equivalent to -C opt-level=2"),
@@ -1417,7 +1433,7 @@ pub fn rustc_optgroups() -> Vec<RustcOptGroup> {
             "",
             "error-format",
             "How errors and other messages are produced",
-            "human|json|short",
+            "human|json|short|sarif",
         ),
         opt::multi_s("", "json", "Configure the JSON output of the compiler", "CONFIG"),
         opt::opt_s(
@@ -1598,12 +1614,14 @@ pub fn parse_error_format(
             Some("json") => ErrorOutputType::Json { pretty: false, json_rendered },
             Some("pretty-json") => ErrorOutputType::Json { pretty: true, json_rendered },
             Some("short") => ErrorOutputType::HumanReadable(HumanReadableErrorType::Short(color)),
+            Some("sarif") => ErrorOutputType::Sarif { pretty: false },
+            Some("pretty-sarif") => ErrorOutputType::Sarif { pretty: true },
 
             Some(arg) => early_error(
                 ErrorOutputType::HumanReadable(HumanReadableErrorType::Default(color)),
                 &format!(
-                    "argument for `--error-format` must be `human`, `json` or \
-                     `short` (instead was `{arg}`)"
+                    "argument for `--error-format` must be `human`, `json`, `short`, \
+                     `sarif` or `pretty-sarif` (instead was `{arg}`)"
                 ),
             ),
         }
@@ -1680,6 +1698,16 @@ fn check_error_format_stability(
                 "`--error-format=human-annotate-rs` is unstable",
             );
         }
+        if let ErrorOutputType::Sarif { pretty } = error_format {
+            early_error(
+                ErrorOutputType::Json { pretty: false, json_rendered },
+                if pretty {
+                    "`--error-format=pretty-sarif` is unstable"
+                } else {
+                    "`--error-format=sarif` is unstable"
+                },
+            );
+        }
     }
 }
 
@@ -1815,6 +1843,17 @@ fn collect_print_requests(
             }
         }
         "link-args" => PrintRequest::LinkArgs,
+        "session-config-json" => {
+            if unstable_opts.unstable_options {
+                PrintRequest::SessionConfigJson
+            } else {
+                early_error(
+                    error_format,
+                    "the `-Z unstable-options` flag must also be passed to \
+                     enable the session-config-json print option",
+                );
+            }
+        }
         req => early_error(error_format, &format!("unknown print request `{req}`")),
     }));
 
@@ -2247,6 +2286,10 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
 
     let assert_incr_state = parse_assert_incr_state(&unstable_opts.assert_incr_state, error_format);
 
+    if unstable_opts.incremental_shared_cache.is_some() && incremental.is_none() {
+        early_error(error_format, "option `-Z incremental-shared-cache` requires `-C incremental`");
+    }
+
     if unstable_opts.profile && incremental.is_some() {
         early_error(
             error_format,
@@ -2280,6 +2323,10 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
         );
     }
 
+    if unstable_opts.update_lint_baseline && unstable_opts.lint_baseline.is_none() {
+        early_error(error_format, "option `-Z update-lint-baseline` requires `-Z lint-baseline`");
+    }
+
     // Handle both `-Z symbol-mangling-version` and `-C symbol-mangling-version`; the latter takes
     // precedence.
     match (cg.symbol_mangling_version, unstable_opts.symbol_mangling_version) {
@@ -2918,6 +2965,15 @@ fn hash(
         }
     }
 
+    /// Hashes a single option, to compare it with the same option in another session.
+    pub(crate) fn option_hash(option: &dyn DepTrackingHash, error_format: ErrorOutputType) -> u64 {
+        use std::hash::Hasher;
+
+        let mut hasher = DefaultHasher::new();
+        option.hash(&mut hasher, error_format, false);
+        hasher.finish()
+    }
+
     // This is a stable hash because BTreeMap is a sorted container
     pub(crate) fn stable_hash(
         sub_hashes: BTreeMap<&'static str, &dyn DepTrackingHash>,
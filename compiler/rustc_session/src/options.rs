@@ -4,7 +4,7 @@
 use crate::lint;
 use crate::search_paths::SearchPath;
 use crate::utils::NativeLib;
-use rustc_errors::LanguageIdentifier;
+use rustc_errors::{Applicability, LanguageIdentifier};
 use rustc_target::spec::{CodeModel, LinkerFlavor, MergeFunctions, PanicStrategy, SanitizerSet};
 use rustc_target::spec::{
     RelocModel, RelroLevel, SplitDebuginfo, StackProtector, TargetTriple, TlsModel,
@@ -59,6 +59,15 @@ macro_rules! hash_substruct {
     };
 }
 
+macro_rules! substruct_hashes {
+    ($opt_name:ident, $opt_expr:expr, $error_format:expr, $hashes:expr, [UNTRACKED]) => {{}};
+    ($opt_name:ident, $opt_expr:expr, $error_format:expr, $hashes:expr, [TRACKED]) => {{}};
+    ($opt_name:ident, $opt_expr:expr, $error_format:expr, $hashes:expr, [TRACKED_NO_CRATE_HASH]) => {{}};
+    ($opt_name:ident, $opt_expr:expr, $error_format:expr, $hashes:expr, [SUBSTRUCT]) => {{
+        $opt_expr.dep_tracking_hashes($error_format, $hashes);
+    }};
+}
+
 macro_rules! top_level_options {
     ( $( #[$top_level_attr:meta] )* pub struct Options { $(
         $( #[$attr:meta] )*
@@ -98,6 +107,35 @@ pub fn dep_tracking_hash(&self, for_crate_hash: bool) -> u64 {
                 })*
                 hasher.finish()
             }
+
+            /// Returns the hash of each option taken into account by
+            /// `dep_tracking_hash(false)`, by name, to find out which options
+            /// changed between two sessions.
+            pub fn dep_tracking_hashes(&self) -> BTreeMap<String, u64> {
+                let for_crate_hash = false;
+                let mut sub_hashes = BTreeMap::new();
+                $({
+                    hash_opt!($opt,
+                                &self.$opt,
+                                &mut sub_hashes,
+                                for_crate_hash,
+                                [$dep_tracking_marker]);
+                })*
+                let mut hashes: BTreeMap<String, u64> = sub_hashes
+                    .into_iter()
+                    .map(|(name, sub_hash)| {
+                        (name.to_owned(), dep_tracking::option_hash(sub_hash, self.error_format))
+                    })
+                    .collect();
+                $({
+                    substruct_hashes!($opt,
+                        &self.$opt,
+                        self.error_format,
+                        &mut hashes,
+                        [$dep_tracking_marker]);
+                })*
+                hashes
+            }
         }
     );
 }
@@ -282,6 +320,26 @@ fn dep_tracking_hash(&self, for_crate_hash: bool, error_format: ErrorOutputType)
                                         );
             hasher.finish()
         }
+
+        fn dep_tracking_hashes(
+            &self,
+            error_format: ErrorOutputType,
+            hashes: &mut BTreeMap<String, u64>,
+        ) {
+            let for_crate_hash = false;
+            let mut sub_hashes = BTreeMap::new();
+            $({
+                hash_opt!($opt,
+                            &self.$opt,
+                            &mut sub_hashes,
+                            for_crate_hash,
+                            [$dep_tracking_marker]);
+            })*
+            for (name, sub_hash) in sub_hashes {
+                let name = format!("-{} {}", $prefix, name.replace('_', "-"));
+                hashes.insert(name, dep_tracking::option_hash(sub_hash, error_format));
+            }
+        }
     }
 
     pub const $stat: OptionDescrs<$struct_name> =
@@ -385,6 +443,8 @@ mod desc {
     pub const parse_strip: &str = "either `none`, `debuginfo`, or `symbols`";
     pub const parse_linker_flavor: &str = ::rustc_target::spec::LinkerFlavor::one_of();
     pub const parse_optimization_fuel: &str = "crate=integer";
+    pub const parse_code_index_format: &str = "either `scip` or `lsif`";
+    pub const parse_apply_suggestions: &str = "either `machine-applicable` or `maybe-incorrect`";
     pub const parse_mir_spanview: &str = "`statement` (default), `terminator`, or `block`";
     pub const parse_instrument_coverage: &str =
         "`all` (default), `except-unused-generics`, `except-unused-functions`, or `off`";
@@ -800,6 +860,30 @@ pub(crate) fn parse_unpretty(slot: &mut Option<String>, v: Option<&str>) -> bool
         }
     }
 
+    pub(crate) fn parse_code_index_format(
+        slot: &mut Option<CodeIndexFormat>,
+        v: Option<&str>,
+    ) -> bool {
+        *slot = match v {
+            Some("scip") => Some(CodeIndexFormat::Scip),
+            Some("lsif") => Some(CodeIndexFormat::Lsif),
+            _ => return false,
+        };
+        true
+    }
+
+    pub(crate) fn parse_apply_suggestions(
+        slot: &mut Option<Applicability>,
+        v: Option<&str>,
+    ) -> bool {
+        *slot = match v {
+            Some("machine-applicable") => Some(Applicability::MachineApplicable),
+            Some("maybe-incorrect") => Some(Applicability::MaybeIncorrect),
+            _ => return false,
+        };
+        true
+    }
+
     pub(crate) fn parse_mir_spanview(slot: &mut Option<MirSpanview>, v: Option<&str>) -> bool {
         if v.is_some() {
             let mut bool_arg = None;
@@ -1199,6 +1283,10 @@ pub(crate) fn parse_branch_protection(
         "only allow the listed language features to be enabled in code (space separated)"),
     always_encode_mir: bool = (false, parse_bool, [TRACKED],
         "encode MIR of all functions into the crate metadata (default: no)"),
+    apply_suggestions: Option<Applicability> = (None, parse_apply_suggestions, [UNTRACKED],
+        "apply the suggestions of the emitted diagnostics to the source files, then compile \
+        them again until no more suggestions apply: only the `machine-applicable` ones, or the \
+        `maybe-incorrect` ones as well (default: no)"),
     assume_incomplete_release: bool = (false, parse_bool, [TRACKED],
         "make cfg(version) treat the current version as incomplete (default: no)"),
     asm_comments: bool = (false, parse_bool, [TRACKED],
@@ -1277,6 +1365,9 @@ pub(crate) fn parse_branch_protection(
         an additional `.html` file showing the computed coverage spans."),
     dwarf_version: Option<u32> = (None, parse_opt_number, [TRACKED],
         "version of DWARF debug information to emit (default: 2 or 4, depending on platform)"),
+    emit_code_index: Option<CodeIndexFormat> = (None, parse_code_index_format, [UNTRACKED],
+        "write a code-intelligence index of the crate in the given format (`scip` or `lsif`) \
+        to `<outdir>/code-index/` (default: no)"),
     emit_stack_sizes: bool = (false, parse_bool, [UNTRACKED],
         "emit a section containing stack size metadata (default: no)"),
     fewer_names: Option<bool> = (None, parse_opt_bool, [TRACKED],
@@ -1302,6 +1393,9 @@ pub(crate) fn parse_branch_protection(
         "generate human-readable, predictable names for codegen units (default: no)"),
     identify_regions: bool = (false, parse_bool, [UNTRACKED],
         "display unnamed regions as `'<id>`, using a non-ident unique id (default: no)"),
+    incremental_explain: bool = (false, parse_bool, [UNTRACKED],
+        "explain which changes caused incremental compilation to recompute queries and \
+        codegen units (default: no)"),
     incremental_ignore_spans: bool = (false, parse_bool, [UNTRACKED],
         "ignore spans during ICH computation -- used for testing (default: no)"),
     incremental_info: bool = (false, parse_bool, [UNTRACKED],
@@ -1309,6 +1403,12 @@ pub(crate) fn parse_branch_protection(
         (default: no)"),
     incremental_relative_spans: bool = (false, parse_bool, [TRACKED],
         "hash spans relative to their parent item for incr. comp. (default: no)"),
+    incremental_shared_cache: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "share the incremental compilation caches of the crates between incremental directories \
+        through a content-addressed cache in the given directory"),
+    incremental_shared_cache_size: u64 = (10240, parse_number, [UNTRACKED],
+        "the size in MiB above which the least recently published snapshots are removed from the \
+        incremental shared cache (default: 10240)"),
     incremental_verify_ich: bool = (false, parse_bool, [UNTRACKED],
         "verify incr. comp. hashes of green query instances (default: no)"),
     inline_mir: Option<bool> = (None, parse_opt_bool, [TRACKED],
@@ -1337,6 +1437,8 @@ pub(crate) fn parse_branch_protection(
         "link native libraries in the linker invocation (default: yes)"),
     link_only: bool = (false, parse_bool, [TRACKED],
         "link the `.rlink` file generated by `-Z no-link` (default: no)"),
+    lint_baseline: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "only report the lint warnings that aren't listed in this baseline file"),
     llvm_plugins: Vec<String> = (Vec::new(), parse_list, [TRACKED],
         "a list LLVM plugins to enable (space separated)"),
     llvm_time_trace: bool = (false, parse_bool, [UNTRACKED],
@@ -1430,6 +1532,9 @@ pub(crate) fn parse_branch_protection(
         "print the LLVM optimization passes being run (default: no)"),
     print_mono_items: Option<String> = (None, parse_opt_string, [UNTRACKED],
         "print the result of the monomorphization collection pass"),
+    print_mono_stats: bool = (false, parse_bool, [UNTRACKED],
+        "print the generic functions of which instances are codegened, by definition with their \
+        size, users and codegen units, as JSON (default: no)"),
     print_type_sizes: bool = (false, parse_bool, [UNTRACKED],
         "print layout information for each type encountered (default: no)"),
     proc_macro_backtrace: bool = (false, parse_bool, [UNTRACKED],
@@ -1551,6 +1656,9 @@ pub(crate) fn parse_branch_protection(
         "measure time of each LLVM pass (default: no)"),
     time_passes: bool = (false, parse_bool, [UNTRACKED],
         "measure time of each rustc pass (default: no)"),
+    time_report: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "print a report of the queries, items and codegen units that took the most time to \
+        compile, and write it as JSON to this path"),
     tls_model: Option<TlsModel> = (None, parse_tls_model, [TRACKED],
         "choose the TLS model to use (`rustc --print tls-models` for details)"),
     trace_macros: bool = (false, parse_bool, [UNTRACKED],
@@ -1563,6 +1671,9 @@ pub(crate) fn parse_branch_protection(
         "treat error number `val` that occurs as bug"),
     trim_diagnostic_paths: bool = (true, parse_bool, [UNTRACKED],
         "in diagnostics, use heuristics to shorten paths referring to items"),
+    type_size_limits: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "fail compilation when a type is larger than its limit in the given file, \
+        made of `<bytes> <type>` lines (default: no limits)"),
     ui_testing: bool = (false, parse_bool, [UNTRACKED],
         "emit compiler diagnostics in a form suitable for UI testing (default: no)"),
     uninit_const_chunk_threshold: usize = (16, parse_number, [TRACKED],
@@ -1587,6 +1698,9 @@ pub(crate) fn parse_branch_protection(
     /// this just allows "normal" options to be feature-gated.
     unstable_options: bool = (false, parse_bool, [UNTRACKED],
         "adds unstable command line options to rustc interface (default: no)"),
+    update_lint_baseline: bool = (false, parse_bool, [UNTRACKED],
+        "write the lint warnings emitted to the `-Z lint-baseline` file instead of \
+        suppressing them (default: no)"),
     use_ctors_section: Option<bool> = (None, parse_opt_bool, [TRACKED],
         "use legacy .ctors section for initializers rather than .init_array"),
     validate_mir: bool = (false, parse_bool, [UNTRACKED],
@@ -1,6 +1,6 @@
 use crate::cgu_reuse_tracker::CguReuseTracker;
 use crate::code_stats::CodeStats;
-pub use crate::code_stats::{DataTypeKind, FieldInfo, SizeKind, VariantInfo};
+pub use crate::code_stats::{DataTypeKind, FieldInfo, FieldKind, SizeKind, VariantInfo};
 use crate::config::{self, CrateType, OutputType, SwitchWithOptPath};
 use crate::parse::{add_feature_diagnostics, ParseSess};
 use crate::search_paths::{PathKind, SearchPath};
@@ -16,12 +16,14 @@
     self, AtomicU64, AtomicUsize, Lock, Lrc, OnceCell, OneThread, Ordering, Ordering::SeqCst,
 };
 use rustc_errors::annotate_snippet_emitter_writer::AnnotateSnippetEmitterWriter;
+use rustc_errors::baseline::LintBaseline;
 use rustc_errors::emitter::{Emitter, EmitterWriter, HumanReadableErrorType};
 use rustc_errors::json::JsonEmitter;
 use rustc_errors::registry::Registry;
+use rustc_errors::sarif::SarifEmitter;
 use rustc_errors::{
-    fallback_fluent_bundle, DiagnosticBuilder, DiagnosticId, DiagnosticMessage, EmissionGuarantee,
-    ErrorGuaranteed, FluentBundle, LazyFallbackBundle, MultiSpan,
+    fallback_fluent_bundle, fix, pluralize, DiagnosticBuilder, DiagnosticId, DiagnosticMessage,
+    EmissionGuarantee, ErrorGuaranteed, FluentBundle, LazyFallbackBundle, MultiSpan,
 };
 use rustc_macros::HashStable_Generic;
 pub use rustc_span::def_id::StableCrateId;
@@ -255,10 +257,64 @@ fn check_miri_unleashed_features(&self) {
     /// Invoked all the way at the end to finish off diagnostics printing.
     pub fn finish_diagnostics(&self, registry: &Registry) {
         self.check_miri_unleashed_features();
+        self.finish_lint_baseline();
         self.diagnostic().print_error_count(registry);
         self.emit_future_breakage();
     }
 
+    /// Writes the lints emitted for `-Z update-lint-baseline` to the baseline file, or reports the
+    /// lints of the `-Z lint-baseline` file that are no longer emitted.
+    fn finish_lint_baseline(&self) {
+        let Some(path) = &self.opts.unstable_opts.lint_baseline else { return };
+        let Some(baseline) = self.diagnostic().take_lint_baseline() else { return };
+        let update = self.opts.unstable_opts.update_lint_baseline;
+        // Errors stop the compilation before all the lints have run, so some of the lints of the
+        // baseline may just not have been checked yet.
+        if self.has_errors().is_some() {
+            if update {
+                self.warn(&format!(
+                    "not updating the lint baseline `{}` because of the errors",
+                    path.display()
+                ));
+            }
+            return;
+        }
+        if !update {
+            let unmatched = baseline.unmatched(self.source_map());
+            if unmatched > 0 {
+                self.struct_note_without_error(&format!(
+                    "{} lint warning{} of the baseline `{}` {} no longer emitted",
+                    unmatched,
+                    pluralize!(unmatched),
+                    path.display(),
+                    if unmatched == 1 { "is" } else { "are" },
+                ))
+                .help("remove them from the baseline with `-Z update-lint-baseline`")
+                .emit();
+            }
+            return;
+        }
+        if let Err(err) = self.update_lint_baseline(path, &baseline) {
+            self.err(&format!("failed to update the lint baseline `{}`: {}", path.display(), err));
+        }
+    }
+
+    fn update_lint_baseline(&self, path: &Path, baseline: &LintBaseline) -> Result<(), String> {
+        // Crates compiled in parallel may share the baseline, keeping the lints of each other's
+        // files in it, so the file is locked while it is updated.
+        let mut lock_path = path.as_os_str().to_owned();
+        lock_path.push(".lock");
+        let _lock =
+            flock::Lock::new(Path::new(&lock_path), true, true, true).map_err(|e| e.to_string())?;
+        let previous = match std::fs::read_to_string(path) {
+            Ok(src) => LintBaseline::parse(&src)?,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => LintBaseline::recording(),
+            Err(err) => return Err(err.to_string()),
+        };
+        std::fs::write(path, baseline.updated(&previous, self.source_map()))
+            .map_err(|e| e.to_string())
+    }
+
     fn emit_future_breakage(&self) {
         if !self.opts.json_future_incompat {
             return;
@@ -271,6 +327,42 @@ fn emit_future_breakage(&self) {
         self.parse_sess.span_diagnostic.emit_future_breakage_report(diags);
     }
 
+    /// Applies the suggestions collected for `-Z apply-suggestions` to the source files, and
+    /// returns the number of files that changed.
+    pub fn apply_suggestions(&self) -> usize {
+        let suggestions = self.diagnostic().take_suggestions();
+        let mut changed = 0;
+        for file in fix::fix_files(self.source_map(), &suggestions) {
+            let path = file.path.display();
+            // The source map holds the code with normalized line endings and without byte order
+            // mark, so the offsets of the suggestions may not match the file on disk.
+            if !std::fs::read_to_string(&file.path).map_or(false, |src| src == *file.original) {
+                self.struct_warn(&format!("not applying suggestions to `{}`", path))
+                    .note(
+                        "the file changed during compilation, or has CRLF line endings or a byte \
+                         order mark",
+                    )
+                    .emit();
+                continue;
+            }
+            match std::fs::write(&file.path, &file.fixed) {
+                Ok(()) => {
+                    self.note_without_error(&format!(
+                        "applied {} suggestion{} to `{}`",
+                        file.applied,
+                        pluralize!(file.applied),
+                        path
+                    ));
+                    changed += 1;
+                }
+                Err(err) => {
+                    self.err(&format!("failed to write `{}`: {}", path, err));
+                }
+            }
+        }
+        changed
+    }
+
     pub fn local_stable_crate_id(&self) -> StableCrateId {
         self.stable_crate_id.get().copied().unwrap()
     }
@@ -1219,6 +1311,21 @@ fn default_emitter(
             )
             .ui_testing(sopts.unstable_opts.ui_testing),
         ),
+        (config::ErrorOutputType::Sarif { pretty }, None) => Box::new(SarifEmitter::stderr(
+            Some(registry),
+            source_map,
+            bundle,
+            fallback_bundle,
+            pretty,
+        )),
+        (config::ErrorOutputType::Sarif { pretty }, Some(dst)) => Box::new(SarifEmitter::new(
+            dst,
+            Some(registry),
+            source_map,
+            bundle,
+            fallback_bundle,
+            pretty,
+        )),
     }
 }
 
@@ -1294,6 +1401,22 @@ pub fn build_session(
         emitter,
         sopts.unstable_opts.diagnostic_handler_flags(can_emit_warnings),
     );
+    if let Some(path) = &sopts.unstable_opts.lint_baseline {
+        let baseline = if sopts.unstable_opts.update_lint_baseline {
+            LintBaseline::recording()
+        } else {
+            std::fs::read_to_string(path)
+                .map_err(|err| err.to_string())
+                .and_then(|src| LintBaseline::parse(&src))
+                .unwrap_or_else(|err| {
+                    early_error(
+                        sopts.error_format,
+                        &format!("failed to read the lint baseline `{}`: {}", path.display(), err),
+                    )
+                })
+        };
+        span_diagnostic.set_lint_baseline(baseline);
+    }
 
     let self_profiler = if let SwitchWithOptPath::Enabled(ref d) = sopts.unstable_opts.self_profile
     {
@@ -1352,6 +1475,7 @@ pub fn build_session(
         self_profiler,
         sopts.unstable_opts.time_passes || sopts.unstable_opts.time,
         sopts.unstable_opts.time_passes,
+        sopts.unstable_opts.time_report.as_ref().map(|_| Default::default()),
     );
 
     let ctfe_backtrace = Lock::new(match env::var("RUSTC_CTFE_BACKTRACE") {
@@ -1547,6 +1671,9 @@ fn early_error_handler(output: config::ErrorOutputType) -> rustc_errors::Handler
         config::ErrorOutputType::Json { pretty, json_rendered } => {
             Box::new(JsonEmitter::basic(pretty, json_rendered, None, fallback_bundle, None, false))
         }
+        config::ErrorOutputType::Sarif { pretty } => {
+            Box::new(SarifEmitter::basic(pretty, None, fallback_bundle))
+        }
     };
     rustc_errors::Handler::with_emitter(true, None, emitter)
 }
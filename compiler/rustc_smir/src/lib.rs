@@ -14,4 +14,8 @@
 
 pub mod mir;
 
+pub mod rustc_internal;
+mod rustc_smir;
+pub mod stable_mir;
+
 pub mod very_unstable;
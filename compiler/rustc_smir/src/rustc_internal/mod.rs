@@ -0,0 +1,51 @@
+//! Module that runs the compiler and hands the resulting stable MIR to a tool.
+//!
+//! This is the only place a tool built on stable MIR needs to touch the compiler driver.
+
+use crate::stable_mir::Context;
+use crate::very_unstable::driver::{Callbacks, RunCompiler};
+use crate::very_unstable::interface::interface::{Compiler, Result};
+use crate::very_unstable::interface::Queries;
+use crate::very_unstable::middle::ty::TyCtxt;
+
+pub use crate::very_unstable::driver::Compilation;
+
+/// Runs the compiler with the given command-line `args` and calls `callback` with a [`Context`]
+/// for the crate once it has been analyzed successfully.
+///
+/// The return value of `callback` decides whether compilation continues to code generation.
+/// Returns `Err` if compilation failed, in which case `callback` may not have been called.
+pub fn run<F>(args: &[String], callback: F) -> Result<()>
+where
+    F: FnOnce(&mut Context<'_>) -> Compilation + Send,
+{
+    let mut callbacks = StableMirCallbacks { callback: Some(callback) };
+    RunCompiler::new(args, &mut callbacks).run()
+}
+
+/// Calls `f` with a [`Context`] for the crate `tcx` is compiling.
+///
+/// This is meant for tools that already drive rustc themselves and only want to convert
+/// bodies to stable MIR at some point.
+pub fn with_context<'tcx, R>(tcx: TyCtxt<'tcx>, f: impl FnOnce(&mut Context<'tcx>) -> R) -> R {
+    f(&mut Context::new(tcx))
+}
+
+struct StableMirCallbacks<F> {
+    callback: Option<F>,
+}
+
+impl<F> Callbacks for StableMirCallbacks<F>
+where
+    F: FnOnce(&mut Context<'_>) -> Compilation + Send,
+{
+    fn after_analysis<'tcx>(
+        &mut self,
+        compiler: &Compiler,
+        queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        compiler.session().abort_if_errors();
+        let callback = self.callback.take().expect("`after_analysis` called twice");
+        queries.global_ctxt().unwrap().peek_mut().enter(|tcx| with_context(tcx, callback))
+    }
+}
@@ -0,0 +1,681 @@
+//! Module that implements what will become the rustc side of Stable MIR.
+//!
+//! This module is responsible for building Stable MIR components from internal components.
+//!
+//! This module is not intended to be invoked directly by users. It will eventually
+//! become the public API of rustc that will be invoked by the `stable_mir` crate.
+//!
+//! For now, we are developing everything inside `rustc`, thus, we keep this module private.
+
+use crate::stable_mir::{self, mir as smir, ty as sty};
+use crate::very_unstable::data_structures::fx::FxHashMap;
+use crate::very_unstable::hir::def::DefKind;
+use crate::very_unstable::hir::def_id::{DefId, LOCAL_CRATE};
+use crate::very_unstable::hir::{self, Movability};
+use crate::very_unstable::middle::mir;
+use crate::very_unstable::middle::ty::{self, GenericArgKind, Ty, TyCtxt};
+use crate::very_unstable::span::Span;
+
+/// Gives access to the stable MIR of the crate being compiled.
+///
+/// A `Context` hands out [`stable_mir::DefId`]s for every item it mentions and remembers which
+/// rustc item each of them stands for, so ids can be passed back in to query more information.
+pub struct Context<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    def_ids: Vec<DefId>,
+    stable_def_ids: FxHashMap<DefId, stable_mir::DefId>,
+}
+
+impl<'tcx> Context<'tcx> {
+    pub(crate) fn new(tcx: TyCtxt<'tcx>) -> Self {
+        Context { tcx, def_ids: Vec::new(), stable_def_ids: FxHashMap::default() }
+    }
+
+    /// Returns the crate being compiled.
+    pub fn local_crate(&self) -> stable_mir::Crate {
+        self.smir_crate(LOCAL_CRATE)
+    }
+
+    /// Returns all the crates the crate being compiled depends on.
+    pub fn external_crates(&self) -> Vec<stable_mir::Crate> {
+        self.tcx.crates(()).iter().map(|&cnum| self.smir_crate(cnum)).collect()
+    }
+
+    /// Returns every item of the crate being compiled that has a MIR body, including closures,
+    /// generators and anonymous constants.
+    pub fn all_local_items(&mut self) -> stable_mir::CrateItems {
+        let tcx = self.tcx;
+        tcx.mir_keys(())
+            .iter()
+            .filter_map(|&local_def_id| {
+                let def_id = local_def_id.to_def_id();
+                let kind = item_kind(tcx.def_kind(def_id))?;
+                Some(stable_mir::CrateItem {
+                    def_id: self.stable_def_id(def_id),
+                    path: tcx.def_path_str(def_id),
+                    kind,
+                    span: self.span(tcx.def_span(def_id)),
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the optimized MIR of `item`, or the MIR used for const evaluation if `item` is a
+    /// constant or static.
+    pub fn mir_body(&mut self, item: &stable_mir::CrateItem) -> smir::Body {
+        let def_id = self.rustc_def_id(item.def_id);
+        let instance = ty::InstanceDef::Item(ty::WithOptConstParam::unknown(def_id));
+        self.tcx.instance_mir(instance).stable(self)
+    }
+
+    /// Returns the full path of the item identified by `def_id`, e.g. `std::vec::Vec`.
+    pub fn def_path_str(&self, def_id: stable_mir::DefId) -> String {
+        self.tcx.def_path_str(self.rustc_def_id(def_id))
+    }
+
+    /// Returns the crate that defines the item identified by `def_id`.
+    pub fn def_crate(&self, def_id: stable_mir::DefId) -> stable_mir::Crate {
+        self.smir_crate(self.rustc_def_id(def_id).krate)
+    }
+
+    fn rustc_def_id(&self, def_id: stable_mir::DefId) -> DefId {
+        self.def_ids[def_id.0]
+    }
+
+    fn stable_def_id(&mut self, def_id: DefId) -> stable_mir::DefId {
+        let def_ids = &mut self.def_ids;
+        *self.stable_def_ids.entry(def_id).or_insert_with(|| {
+            def_ids.push(def_id);
+            stable_mir::DefId(def_ids.len() - 1)
+        })
+    }
+
+    fn smir_crate(&self, crate_num: hir::def_id::CrateNum) -> stable_mir::Crate {
+        stable_mir::Crate {
+            id: crate_num.as_usize(),
+            name: self.tcx.crate_name(crate_num).to_string(),
+            is_local: crate_num == LOCAL_CRATE,
+        }
+    }
+
+    fn span(&self, span: Span) -> stable_mir::Span {
+        let source_map = self.tcx.sess.source_map();
+        let lo = source_map.lookup_char_pos(span.lo());
+        let hi = source_map.lookup_char_pos(span.hi());
+        stable_mir::Span {
+            file_name: source_map.filename_for_diagnostics(&lo.file.name).to_string(),
+            lo_line: lo.line,
+            lo_col: lo.col.0 + 1,
+            hi_line: hi.line,
+            hi_col: hi.col.0 + 1,
+        }
+    }
+}
+
+fn item_kind(kind: DefKind) -> Option<stable_mir::ItemKind> {
+    use stable_mir::ItemKind;
+    Some(match kind {
+        DefKind::Fn | DefKind::AssocFn => ItemKind::Fn,
+        DefKind::Const | DefKind::AssocConst => ItemKind::Const,
+        DefKind::Static(_) => ItemKind::Static,
+        DefKind::Closure => ItemKind::Closure,
+        DefKind::Generator => ItemKind::Generator,
+        DefKind::Ctor(..) => ItemKind::Ctor,
+        DefKind::AnonConst | DefKind::InlineConst => ItemKind::AnonConst,
+        _ => return None,
+    })
+}
+
+/// Trait used to convert between an internal MIR type to a Stable MIR type.
+pub(crate) trait Stable<'tcx> {
+    /// The stable representation of the type implementing Stable.
+    type T;
+    /// Converts an object to the equivalent Stable MIR representation.
+    fn stable(&self, cx: &mut Context<'tcx>) -> Self::T;
+}
+
+impl<'tcx, T: Stable<'tcx>> Stable<'tcx> for Vec<T> {
+    type T = Vec<T::T>;
+    fn stable(&self, cx: &mut Context<'tcx>) -> Self::T {
+        self.iter().map(|t| t.stable(cx)).collect()
+    }
+}
+
+impl<'tcx, T: Stable<'tcx>> Stable<'tcx> for Option<T> {
+    type T = Option<T::T>;
+    fn stable(&self, cx: &mut Context<'tcx>) -> Self::T {
+        self.as_ref().map(|t| t.stable(cx))
+    }
+}
+
+impl<'tcx> Stable<'tcx> for mir::Body<'tcx> {
+    type T = smir::Body;
+    fn stable(&self, cx: &mut Context<'tcx>) -> Self::T {
+        smir::Body {
+            blocks: self.basic_blocks().iter().map(|block| block.stable(cx)).collect(),
+            locals: self
+                .local_decls
+                .iter()
+                .map(|decl| smir::LocalDecl {
+                    ty: decl.ty.stable(cx),
+                    mutability: decl.mutability.stable(cx),
+                    span: cx.span(decl.source_info.span),
+                })
+                .collect(),
+            arg_count: self.arg_count,
+            span: cx.span(self.span),
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for mir::BasicBlockData<'tcx> {
+    type T = smir::BasicBlock;
+    fn stable(&self, cx: &mut Context<'tcx>) -> Self::T {
+        smir::BasicBlock {
+            statements: self.statements.stable(cx),
+            terminator: self.terminator().stable(cx),
+            is_cleanup: self.is_cleanup,
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for mir::Statement<'tcx> {
+    type T = smir::Statement;
+    fn stable(&self, cx: &mut Context<'tcx>) -> Self::T {
+        use mir::StatementKind::*;
+        let kind = match &self.kind {
+            Assign(assign) => smir::StatementKind::Assign(assign.0.stable(cx), assign.1.stable(cx)),
+            FakeRead(fake_read) => {
+                smir::StatementKind::FakeRead(fake_read.0.stable(cx), fake_read.1.stable(cx))
+            }
+            SetDiscriminant { place, variant_index } => smir::StatementKind::SetDiscriminant {
+                place: place.stable(cx),
+                variant_index: variant_index.as_usize(),
+            },
+            Deinit(place) => smir::StatementKind::Deinit(place.stable(cx)),
+            StorageLive(local) => smir::StatementKind::StorageLive(local.as_usize()),
+            StorageDead(local) => smir::StatementKind::StorageDead(local.as_usize()),
+            Retag(kind, place) => smir::StatementKind::Retag(kind.stable(cx), place.stable(cx)),
+            AscribeUserType(ascription, _) => {
+                smir::StatementKind::AscribeUserType(ascription.0.stable(cx))
+            }
+            Coverage(_) => smir::StatementKind::Coverage,
+            CopyNonOverlapping(copy) => smir::StatementKind::CopyNonOverlapping {
+                src: copy.src.stable(cx),
+                dst: copy.dst.stable(cx),
+                count: copy.count.stable(cx),
+            },
+            Nop => smir::StatementKind::Nop,
+        };
+        smir::Statement { kind, span: cx.span(self.source_info.span) }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for mir::FakeReadCause {
+    type T = smir::FakeReadCause;
+    fn stable(&self, _: &mut Context<'tcx>) -> Self::T {
+        use mir::FakeReadCause::*;
+        match self {
+            ForMatchGuard => smir::FakeReadCause::ForMatchGuard,
+            ForMatchedPlace(_) => smir::FakeReadCause::ForMatchedPlace,
+            ForGuardBinding => smir::FakeReadCause::ForGuardBinding,
+            ForLet(_) => smir::FakeReadCause::ForLet,
+            ForIndex => smir::FakeReadCause::ForIndex,
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for mir::RetagKind {
+    type T = smir::RetagKind;
+    fn stable(&self, _: &mut Context<'tcx>) -> Self::T {
+        use mir::RetagKind::*;
+        match self {
+            FnEntry => smir::RetagKind::FnEntry,
+            TwoPhase => smir::RetagKind::TwoPhase,
+            Raw => smir::RetagKind::Raw,
+            Default => smir::RetagKind::Default,
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for mir::Terminator<'tcx> {
+    type T = smir::Terminator;
+    fn stable(&self, cx: &mut Context<'tcx>) -> Self::T {
+        use mir::TerminatorKind::*;
+        let kind = match &self.kind {
+            Goto { target } => smir::TerminatorKind::Goto { target: target.as_usize() },
+            SwitchInt { discr, switch_ty: _, targets } => smir::TerminatorKind::SwitchInt {
+                discr: discr.stable(cx),
+                targets: targets.iter().map(|(value, target)| (value, target.as_usize())).collect(),
+                otherwise: targets.otherwise().as_usize(),
+            },
+            Resume => smir::TerminatorKind::Resume,
+            Abort => smir::TerminatorKind::Abort,
+            Return => smir::TerminatorKind::Return,
+            Unreachable => smir::TerminatorKind::Unreachable,
+            Drop { place, target, unwind } => smir::TerminatorKind::Drop {
+                place: place.stable(cx),
+                target: target.as_usize(),
+                unwind: unwind.map(|block| block.as_usize()),
+            },
+            DropAndReplace { place, value, target, unwind } => {
+                smir::TerminatorKind::DropAndReplace {
+                    place: place.stable(cx),
+                    value: value.stable(cx),
+                    target: target.as_usize(),
+                    unwind: unwind.map(|block| block.as_usize()),
+                }
+            }
+            Call { func, args, destination, target, cleanup, from_hir_call: _, fn_span: _ } => {
+                smir::TerminatorKind::Call {
+                    func: func.stable(cx),
+                    args: args.stable(cx),
+                    destination: destination.stable(cx),
+                    target: target.map(|block| block.as_usize()),
+                    cleanup: cleanup.map(|block| block.as_usize()),
+                }
+            }
+            Assert { cond, expected, msg, target, cleanup } => smir::TerminatorKind::Assert {
+                cond: cond.stable(cx),
+                expected: *expected,
+                msg: msg.stable(cx),
+                target: target.as_usize(),
+                cleanup: cleanup.map(|block| block.as_usize()),
+            },
+            Yield { value, resume, resume_arg, drop } => smir::TerminatorKind::Yield {
+                value: value.stable(cx),
+                resume: resume.as_usize(),
+                resume_arg: resume_arg.stable(cx),
+                drop: drop.map(|block| block.as_usize()),
+            },
+            GeneratorDrop => smir::TerminatorKind::GeneratorDrop,
+            FalseEdge { real_target, imaginary_target } => smir::TerminatorKind::FalseEdge {
+                real_target: real_target.as_usize(),
+                imaginary_target: imaginary_target.as_usize(),
+            },
+            FalseUnwind { real_target, unwind } => smir::TerminatorKind::FalseUnwind {
+                real_target: real_target.as_usize(),
+                unwind: unwind.map(|block| block.as_usize()),
+            },
+            InlineAsm { template, operands, options: _, line_spans: _, destination, cleanup } => {
+                smir::TerminatorKind::InlineAsm {
+                    template: format!("{:?}", template),
+                    operands: operands.stable(cx),
+                    destination: destination.map(|block| block.as_usize()),
+                    cleanup: cleanup.map(|block| block.as_usize()),
+                }
+            }
+        };
+        smir::Terminator { kind, span: cx.span(self.source_info.span) }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for mir::InlineAsmOperand<'tcx> {
+    type T = smir::InlineAsmOperand;
+    fn stable(&self, cx: &mut Context<'tcx>) -> Self::T {
+        use mir::InlineAsmOperand::*;
+        let (in_value, out_place) = match self {
+            In { value, .. } => (Some(value.stable(cx)), None),
+            Out { place, .. } => (None, place.stable(cx)),
+            InOut { in_value, out_place, .. } => (Some(in_value.stable(cx)), out_place.stable(cx)),
+            Const { .. } | SymFn { .. } | SymStatic { .. } => (None, None),
+        };
+        smir::InlineAsmOperand { in_value, out_place, rendered: format!("{:?}", self) }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for mir::AssertMessage<'tcx> {
+    type T = smir::AssertMessage;
+    fn stable(&self, cx: &mut Context<'tcx>) -> Self::T {
+        use mir::AssertKind::*;
+        match self {
+            BoundsCheck { len, index } => {
+                smir::AssertMessage::BoundsCheck { len: len.stable(cx), index: index.stable(cx) }
+            }
+            Overflow(op, lhs, rhs) => {
+                smir::AssertMessage::Overflow(op.stable(cx), lhs.stable(cx), rhs.stable(cx))
+            }
+            OverflowNeg(operand) => smir::AssertMessage::OverflowNeg(operand.stable(cx)),
+            DivisionByZero(operand) => smir::AssertMessage::DivisionByZero(operand.stable(cx)),
+            RemainderByZero(operand) => smir::AssertMessage::RemainderByZero(operand.stable(cx)),
+            ResumedAfterReturn(_) => smir::AssertMessage::ResumedAfterReturn,
+            ResumedAfterPanic(_) => smir::AssertMessage::ResumedAfterPanic,
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for mir::Rvalue<'tcx> {
+    type T = smir::Rvalue;
+    fn stable(&self, cx: &mut Context<'tcx>) -> Self::T {
+        use mir::Rvalue::*;
+        match self {
+            Use(operand) => smir::Rvalue::Use(operand.stable(cx)),
+            Repeat(operand, count) => smir::Rvalue::Repeat(operand.stable(cx), count.stable(cx)),
+            Ref(region, kind, place) => {
+                smir::Rvalue::Ref(region.stable(cx), kind.stable(cx), place.stable(cx))
+            }
+            ThreadLocalRef(def_id) => smir::Rvalue::ThreadLocalRef(cx.stable_def_id(*def_id)),
+            AddressOf(mutability, place) => {
+                smir::Rvalue::AddressOf(mutability.stable(cx), place.stable(cx))
+            }
+            Len(place) => smir::Rvalue::Len(place.stable(cx)),
+            Cast(kind, operand, ty) => {
+                smir::Rvalue::Cast(kind.stable(cx), operand.stable(cx), ty.stable(cx))
+            }
+            BinaryOp(op, operands) => smir::Rvalue::BinaryOp(
+                op.stable(cx),
+                operands.0.stable(cx),
+                operands.1.stable(cx),
+            ),
+            CheckedBinaryOp(op, operands) => smir::Rvalue::CheckedBinaryOp(
+                op.stable(cx),
+                operands.0.stable(cx),
+                operands.1.stable(cx),
+            ),
+            NullaryOp(op, ty) => smir::Rvalue::NullaryOp(op.stable(cx), ty.stable(cx)),
+            UnaryOp(op, operand) => smir::Rvalue::UnaryOp(op.stable(cx), operand.stable(cx)),
+            Discriminant(place) => smir::Rvalue::Discriminant(place.stable(cx)),
+            Aggregate(kind, operands) => {
+                smir::Rvalue::Aggregate(kind.stable(cx), operands.stable(cx))
+            }
+            ShallowInitBox(operand, ty) => {
+                smir::Rvalue::ShallowInitBox(operand.stable(cx), ty.stable(cx))
+            }
+            CopyForDeref(place) => smir::Rvalue::CopyForDeref(place.stable(cx)),
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for mir::BorrowKind {
+    type T = smir::BorrowKind;
+    fn stable(&self, _: &mut Context<'tcx>) -> Self::T {
+        use mir::BorrowKind::*;
+        match *self {
+            Shared => smir::BorrowKind::Shared,
+            Shallow => smir::BorrowKind::Shallow,
+            Unique => smir::BorrowKind::Unique,
+            Mut { allow_two_phase_borrow } => smir::BorrowKind::Mut { allow_two_phase_borrow },
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for mir::CastKind {
+    type T = smir::CastKind;
+    fn stable(&self, _: &mut Context<'tcx>) -> Self::T {
+        use mir::CastKind::*;
+        match self {
+            PointerExposeAddress => smir::CastKind::PointerExposeAddress,
+            PointerFromExposedAddress => smir::CastKind::PointerFromExposedAddress,
+            Pointer(_) => smir::CastKind::Pointer,
+            Misc => smir::CastKind::Misc,
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for mir::BinOp {
+    type T = smir::BinOp;
+    fn stable(&self, _: &mut Context<'tcx>) -> Self::T {
+        use mir::BinOp::*;
+        match self {
+            Add => smir::BinOp::Add,
+            Sub => smir::BinOp::Sub,
+            Mul => smir::BinOp::Mul,
+            Div => smir::BinOp::Div,
+            Rem => smir::BinOp::Rem,
+            BitXor => smir::BinOp::BitXor,
+            BitAnd => smir::BinOp::BitAnd,
+            BitOr => smir::BinOp::BitOr,
+            Shl => smir::BinOp::Shl,
+            Shr => smir::BinOp::Shr,
+            Eq => smir::BinOp::Eq,
+            Lt => smir::BinOp::Lt,
+            Le => smir::BinOp::Le,
+            Ne => smir::BinOp::Ne,
+            Ge => smir::BinOp::Ge,
+            Gt => smir::BinOp::Gt,
+            Offset => smir::BinOp::Offset,
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for mir::UnOp {
+    type T = smir::UnOp;
+    fn stable(&self, _: &mut Context<'tcx>) -> Self::T {
+        match self {
+            mir::UnOp::Not => smir::UnOp::Not,
+            mir::UnOp::Neg => smir::UnOp::Neg,
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for mir::NullOp {
+    type T = smir::NullOp;
+    fn stable(&self, _: &mut Context<'tcx>) -> Self::T {
+        match self {
+            mir::NullOp::SizeOf => smir::NullOp::SizeOf,
+            mir::NullOp::AlignOf => smir::NullOp::AlignOf,
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for mir::AggregateKind<'tcx> {
+    type T = smir::AggregateKind;
+    fn stable(&self, cx: &mut Context<'tcx>) -> Self::T {
+        use mir::AggregateKind::*;
+        match self {
+            Array(ty) => smir::AggregateKind::Array(ty.stable(cx)),
+            Tuple => smir::AggregateKind::Tuple,
+            Adt(def_id, variant_index, substs, _, _) => smir::AggregateKind::Adt(
+                cx.stable_def_id(*def_id),
+                variant_index.as_usize(),
+                substs.stable(cx),
+            ),
+            Closure(def_id, substs) => {
+                smir::AggregateKind::Closure(cx.stable_def_id(*def_id), substs.stable(cx))
+            }
+            Generator(def_id, substs, movability) => smir::AggregateKind::Generator(
+                cx.stable_def_id(*def_id),
+                substs.stable(cx),
+                movability.stable(cx),
+            ),
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for mir::Operand<'tcx> {
+    type T = smir::Operand;
+    fn stable(&self, cx: &mut Context<'tcx>) -> Self::T {
+        use mir::Operand::*;
+        match self {
+            Copy(place) => smir::Operand::Copy(place.stable(cx)),
+            Move(place) => smir::Operand::Move(place.stable(cx)),
+            Constant(constant) => smir::Operand::Constant(smir::Constant {
+                span: cx.span(constant.span),
+                ty: constant.ty().stable(cx),
+                literal: constant.to_string(),
+            }),
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for mir::Place<'tcx> {
+    type T = smir::Place;
+    fn stable(&self, cx: &mut Context<'tcx>) -> Self::T {
+        use mir::ProjectionElem::*;
+        smir::Place {
+            local: self.local.as_usize(),
+            projection: self
+                .projection
+                .iter()
+                .map(|elem| match elem {
+                    Deref => smir::ProjectionElem::Deref,
+                    Field(field, ty) => smir::ProjectionElem::Field(field.as_usize(), ty.stable(cx)),
+                    Index(local) => smir::ProjectionElem::Index(local.as_usize()),
+                    ConstantIndex { offset, min_length, from_end } => {
+                        smir::ProjectionElem::ConstantIndex { offset, min_length, from_end }
+                    }
+                    Subslice { from, to, from_end } => {
+                        smir::ProjectionElem::Subslice { from, to, from_end }
+                    }
+                    Downcast(name, variant_index) => smir::ProjectionElem::Downcast(
+                        name.map(|name| name.to_string()),
+                        variant_index.as_usize(),
+                    ),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for mir::Mutability {
+    type T = sty::Mutability;
+    fn stable(&self, _: &mut Context<'tcx>) -> Self::T {
+        match self {
+            mir::Mutability::Not => sty::Mutability::Not,
+            mir::Mutability::Mut => sty::Mutability::Mut,
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for Movability {
+    type T = sty::Movability;
+    fn stable(&self, _: &mut Context<'tcx>) -> Self::T {
+        match self {
+            Movability::Static => sty::Movability::Static,
+            Movability::Movable => sty::Movability::Movable,
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for ty::Region<'tcx> {
+    type T = sty::Region;
+    fn stable(&self, _: &mut Context<'tcx>) -> Self::T {
+        sty::Region(self.to_string())
+    }
+}
+
+impl<'tcx> Stable<'tcx> for ty::Const<'tcx> {
+    type T = sty::Const;
+    fn stable(&self, cx: &mut Context<'tcx>) -> Self::T {
+        sty::Const {
+            value: self
+                .try_eval_usize(cx.tcx, ty::ParamEnv::reveal_all())
+                .map(|value| value.into()),
+            rendered: self.to_string(),
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for ty::SubstsRef<'tcx> {
+    type T = sty::GenericArgs;
+    fn stable(&self, cx: &mut Context<'tcx>) -> Self::T {
+        self.iter()
+            .map(|arg| match arg.unpack() {
+                GenericArgKind::Lifetime(region) => sty::GenericArg::Lifetime(region.stable(cx)),
+                GenericArgKind::Type(ty) => sty::GenericArg::Type(ty.stable(cx)),
+                GenericArgKind::Const(ct) => sty::GenericArg::Const(ct.stable(cx)),
+            })
+            .collect()
+    }
+}
+
+impl<'tcx> Stable<'tcx> for ty::PolyFnSig<'tcx> {
+    type T = sty::FnSig;
+    fn stable(&self, cx: &mut Context<'tcx>) -> Self::T {
+        // Late-bound regions only matter to the type checker, so the binder is skipped.
+        let sig = self.skip_binder();
+        sty::FnSig {
+            inputs: sig.inputs().iter().map(|ty| ty.stable(cx)).collect(),
+            output: sig.output().stable(cx),
+            c_variadic: sig.c_variadic,
+            unsafety: match sig.unsafety {
+                hir::Unsafety::Unsafe => sty::Unsafety::Unsafe,
+                hir::Unsafety::Normal => sty::Unsafety::Normal,
+            },
+            abi: sig.abi.name().to_owned(),
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for Ty<'tcx> {
+    type T = sty::Ty;
+    fn stable(&self, cx: &mut Context<'tcx>) -> Self::T {
+        let kind = match *self.kind() {
+            ty::Bool => sty::TyKind::Bool,
+            ty::Char => sty::TyKind::Char,
+            ty::Int(int_ty) => sty::TyKind::Int(match int_ty {
+                ty::IntTy::Isize => sty::IntTy::Isize,
+                ty::IntTy::I8 => sty::IntTy::I8,
+                ty::IntTy::I16 => sty::IntTy::I16,
+                ty::IntTy::I32 => sty::IntTy::I32,
+                ty::IntTy::I64 => sty::IntTy::I64,
+                ty::IntTy::I128 => sty::IntTy::I128,
+            }),
+            ty::Uint(uint_ty) => sty::TyKind::Uint(match uint_ty {
+                ty::UintTy::Usize => sty::UintTy::Usize,
+                ty::UintTy::U8 => sty::UintTy::U8,
+                ty::UintTy::U16 => sty::UintTy::U16,
+                ty::UintTy::U32 => sty::UintTy::U32,
+                ty::UintTy::U64 => sty::UintTy::U64,
+                ty::UintTy::U128 => sty::UintTy::U128,
+            }),
+            ty::Float(float_ty) => sty::TyKind::Float(match float_ty {
+                ty::FloatTy::F32 => sty::FloatTy::F32,
+                ty::FloatTy::F64 => sty::FloatTy::F64,
+            }),
+            ty::Str => sty::TyKind::Str,
+            ty::Never => sty::TyKind::Never,
+            ty::Adt(adt_def, substs) => {
+                sty::TyKind::Adt(cx.stable_def_id(adt_def.did()), substs.stable(cx))
+            }
+            ty::Foreign(def_id) => sty::TyKind::Foreign(cx.stable_def_id(def_id)),
+            ty::Array(ty, len) => sty::TyKind::Array(ty.stable(cx), len.stable(cx)),
+            ty::Slice(ty) => sty::TyKind::Slice(ty.stable(cx)),
+            ty::RawPtr(ty::TypeAndMut { ty, mutbl }) => {
+                sty::TyKind::RawPtr(ty.stable(cx), mutbl.stable(cx))
+            }
+            ty::Ref(region, ty, mutbl) => {
+                sty::TyKind::Ref(region.stable(cx), ty.stable(cx), mutbl.stable(cx))
+            }
+            ty::FnDef(def_id, substs) => {
+                sty::TyKind::FnDef(cx.stable_def_id(def_id), substs.stable(cx))
+            }
+            ty::FnPtr(sig) => sty::TyKind::FnPtr(sig.stable(cx)),
+            ty::Dynamic(predicates, _) => sty::TyKind::Dynamic(
+                predicates
+                    .principal_def_id()
+                    .into_iter()
+                    .chain(predicates.auto_traits())
+                    .map(|def_id| cx.stable_def_id(def_id))
+                    .collect(),
+            ),
+            ty::Closure(def_id, substs) => {
+                sty::TyKind::Closure(cx.stable_def_id(def_id), substs.stable(cx))
+            }
+            ty::Generator(def_id, substs, movability) => sty::TyKind::Generator(
+                cx.stable_def_id(def_id),
+                substs.stable(cx),
+                movability.stable(cx),
+            ),
+            ty::Tuple(fields) => {
+                sty::TyKind::Tuple(fields.iter().map(|ty| ty.stable(cx)).collect())
+            }
+            ty::Projection(projection) => sty::TyKind::Projection(
+                cx.stable_def_id(projection.item_def_id),
+                projection.substs.stable(cx),
+            ),
+            ty::Opaque(def_id, substs) => {
+                sty::TyKind::Opaque(cx.stable_def_id(def_id), substs.stable(cx))
+            }
+            ty::Param(param) => {
+                sty::TyKind::Param(sty::ParamTy { index: param.index, name: param.name.to_string() })
+            }
+            ty::GeneratorWitness(_)
+            | ty::Bound(..)
+            | ty::Placeholder(_)
+            | ty::Infer(_)
+            | ty::Error(_) => sty::TyKind::Unsupported(self.to_string()),
+        };
+        kind.into()
+    }
+}
@@ -0,0 +1,312 @@
+use super::ty::{Const, GenericArgs, Movability, Mutability, Region, Ty};
+use super::{DefId, Span, Symbol};
+
+/// The MIR body of a function, constant or static.
+#[derive(Clone, Debug)]
+pub struct Body {
+    /// The basic blocks, indexed by [`BasicBlockIdx`]. The entry block is the first one.
+    pub blocks: Vec<BasicBlock>,
+    /// The local variables, indexed by [`Local`]. Local `0` is the return place, followed by
+    /// `arg_count` arguments, then user variables and temporaries.
+    pub locals: Vec<LocalDecl>,
+    pub arg_count: usize,
+    pub span: Span,
+}
+
+impl Body {
+    /// Returns the locals that hold the arguments of the body.
+    pub fn arg_locals(&self) -> &[LocalDecl] {
+        &self.locals[1..][..self.arg_count]
+    }
+
+    /// Returns the type of the return place.
+    pub fn ret_ty(&self) -> &Ty {
+        &self.locals[0].ty
+    }
+}
+
+pub type Local = usize;
+
+pub type BasicBlockIdx = usize;
+
+pub type FieldIdx = usize;
+
+pub type VariantIdx = usize;
+
+#[derive(Clone, Debug)]
+pub struct LocalDecl {
+    pub ty: Ty,
+    pub mutability: Mutability,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug)]
+pub struct BasicBlock {
+    pub statements: Vec<Statement>,
+    pub terminator: Terminator,
+    /// Whether this block is only reached while unwinding.
+    pub is_cleanup: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct Statement {
+    pub kind: StatementKind,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug)]
+pub enum StatementKind {
+    Assign(Place, Rvalue),
+    FakeRead(FakeReadCause, Place),
+    SetDiscriminant { place: Place, variant_index: VariantIdx },
+    Deinit(Place),
+    StorageLive(Local),
+    StorageDead(Local),
+    Retag(RetagKind, Place),
+    AscribeUserType(Place),
+    Coverage,
+    CopyNonOverlapping { src: Operand, dst: Operand, count: Operand },
+    Nop,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FakeReadCause {
+    ForMatchGuard,
+    ForMatchedPlace,
+    ForGuardBinding,
+    ForLet,
+    ForIndex,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RetagKind {
+    FnEntry,
+    TwoPhase,
+    Raw,
+    Default,
+}
+
+#[derive(Clone, Debug)]
+pub struct Terminator {
+    pub kind: TerminatorKind,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug)]
+pub enum TerminatorKind {
+    Goto {
+        target: BasicBlockIdx,
+    },
+    SwitchInt {
+        discr: Operand,
+        /// The values and the block to jump to for each of them.
+        targets: Vec<(u128, BasicBlockIdx)>,
+        otherwise: BasicBlockIdx,
+    },
+    Resume,
+    Abort,
+    Return,
+    Unreachable,
+    Drop {
+        place: Place,
+        target: BasicBlockIdx,
+        unwind: Option<BasicBlockIdx>,
+    },
+    DropAndReplace {
+        place: Place,
+        value: Operand,
+        target: BasicBlockIdx,
+        unwind: Option<BasicBlockIdx>,
+    },
+    Call {
+        func: Operand,
+        args: Vec<Operand>,
+        destination: Place,
+        target: Option<BasicBlockIdx>,
+        cleanup: Option<BasicBlockIdx>,
+    },
+    Assert {
+        cond: Operand,
+        expected: bool,
+        msg: AssertMessage,
+        target: BasicBlockIdx,
+        cleanup: Option<BasicBlockIdx>,
+    },
+    Yield {
+        value: Operand,
+        resume: BasicBlockIdx,
+        resume_arg: Place,
+        drop: Option<BasicBlockIdx>,
+    },
+    GeneratorDrop,
+    FalseEdge {
+        real_target: BasicBlockIdx,
+        imaginary_target: BasicBlockIdx,
+    },
+    FalseUnwind {
+        real_target: BasicBlockIdx,
+        unwind: Option<BasicBlockIdx>,
+    },
+    InlineAsm {
+        /// The assembly template as rustc renders it.
+        template: String,
+        operands: Vec<InlineAsmOperand>,
+        destination: Option<BasicBlockIdx>,
+        cleanup: Option<BasicBlockIdx>,
+    },
+}
+
+impl TerminatorKind {
+    /// Returns the blocks this terminator may transfer control to.
+    pub fn successors(&self) -> Vec<BasicBlockIdx> {
+        use TerminatorKind::*;
+        match self {
+            Goto { target } => vec![*target],
+            SwitchInt { targets, otherwise, .. } => {
+                targets.iter().map(|&(_, bb)| bb).chain(Some(*otherwise)).collect()
+            }
+            Resume | Abort | Return | Unreachable | GeneratorDrop => vec![],
+            Drop { target, unwind, .. } | DropAndReplace { target, unwind, .. } => {
+                Some(*target).into_iter().chain(*unwind).collect()
+            }
+            Call { target, cleanup, .. } => target.iter().chain(cleanup).copied().collect(),
+            Assert { target, cleanup, .. } => Some(*target).into_iter().chain(*cleanup).collect(),
+            Yield { resume, drop, .. } => Some(*resume).into_iter().chain(*drop).collect(),
+            FalseEdge { real_target, imaginary_target } => vec![*real_target, *imaginary_target],
+            FalseUnwind { real_target, unwind } => {
+                Some(*real_target).into_iter().chain(*unwind).collect()
+            }
+            InlineAsm { destination, cleanup, .. } => {
+                destination.iter().chain(cleanup).copied().collect()
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct InlineAsmOperand {
+    pub in_value: Option<Operand>,
+    pub out_place: Option<Place>,
+    /// The operand as rustc renders it, including its register class.
+    pub rendered: String,
+}
+
+#[derive(Clone, Debug)]
+pub enum AssertMessage {
+    BoundsCheck { len: Operand, index: Operand },
+    Overflow(BinOp, Operand, Operand),
+    OverflowNeg(Operand),
+    DivisionByZero(Operand),
+    RemainderByZero(Operand),
+    ResumedAfterReturn,
+    ResumedAfterPanic,
+}
+
+#[derive(Clone, Debug)]
+pub enum Rvalue {
+    Use(Operand),
+    Repeat(Operand, Const),
+    Ref(Region, BorrowKind, Place),
+    ThreadLocalRef(DefId),
+    AddressOf(Mutability, Place),
+    Len(Place),
+    Cast(CastKind, Operand, Ty),
+    BinaryOp(BinOp, Operand, Operand),
+    CheckedBinaryOp(BinOp, Operand, Operand),
+    NullaryOp(NullOp, Ty),
+    UnaryOp(UnOp, Operand),
+    Discriminant(Place),
+    Aggregate(AggregateKind, Vec<Operand>),
+    ShallowInitBox(Operand, Ty),
+    CopyForDeref(Place),
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BorrowKind {
+    Shared,
+    Shallow,
+    Unique,
+    Mut { allow_two_phase_borrow: bool },
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CastKind {
+    PointerExposeAddress,
+    PointerFromExposedAddress,
+    /// A pointer coercion, e.g. reifying a function item or unsizing a reference.
+    Pointer,
+    Misc,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BinOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    BitXor,
+    BitAnd,
+    BitOr,
+    Shl,
+    Shr,
+    Eq,
+    Lt,
+    Le,
+    Ne,
+    Ge,
+    Gt,
+    Offset,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum UnOp {
+    Not,
+    Neg,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum NullOp {
+    SizeOf,
+    AlignOf,
+}
+
+#[derive(Clone, Debug)]
+pub enum AggregateKind {
+    Array(Ty),
+    Tuple,
+    Adt(DefId, VariantIdx, GenericArgs),
+    Closure(DefId, GenericArgs),
+    Generator(DefId, GenericArgs, Movability),
+}
+
+#[derive(Clone, Debug)]
+pub enum Operand {
+    Copy(Place),
+    Move(Place),
+    Constant(Constant),
+}
+
+#[derive(Clone, Debug)]
+pub struct Constant {
+    pub span: Span,
+    pub ty: Ty,
+    /// The constant as rustc renders it, e.g. `const 1_i32` or `foo::<u8>`.
+    pub literal: String,
+}
+
+#[derive(Clone, Debug)]
+pub struct Place {
+    pub local: Local,
+    pub projection: Vec<ProjectionElem>,
+}
+
+#[derive(Clone, Debug)]
+pub enum ProjectionElem {
+    Deref,
+    Field(FieldIdx, Ty),
+    Index(Local),
+    ConstantIndex { offset: u64, min_length: u64, from_end: bool },
+    Subslice { from: u64, to: u64, from_end: bool },
+    Downcast(Option<Symbol>, VariantIdx),
+}
@@ -0,0 +1,76 @@
+//! Module that implements the public interface of stable MIR.
+//!
+//! The types in this module are plain data: they own all of their contents and do not refer to
+//! any rustc-internal type, so a tool built against a given [`VERSION`] keeps compiling when the
+//! compiler internals change underneath it. Conversion from the internal representation lives in
+//! [`crate::rustc_smir`], and tools get hold of a [`Context`] through
+//! [`crate::rustc_internal::run`].
+//!
+//! # Note
+//!
+//! Items that rustc does not have a stable representation for yet are kept as their rendered
+//! text, so nothing is silently dropped.
+
+pub mod mir;
+pub mod ty;
+
+pub use crate::rustc_smir::Context;
+
+/// The version of the stable MIR data types. This is bumped whenever a type in this module
+/// changes in a way that may break consumers.
+pub const VERSION: u32 = 1;
+
+/// Use String for now but we should replace it.
+pub type Symbol = String;
+
+/// The number that identifies a crate.
+pub type CrateNum = usize;
+
+/// A unique identification number for each item accessible for the current compilation unit.
+///
+/// The number is only meaningful within the [`Context`] that handed it out.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct DefId(pub(crate) usize);
+
+/// A list of crate items.
+pub type CrateItems = Vec<CrateItem>;
+
+/// Holds information about a crate.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Crate {
+    pub id: CrateNum,
+    pub name: Symbol,
+    pub is_local: bool,
+}
+
+/// Holds information about an item in the crate that has a MIR body.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct CrateItem {
+    pub def_id: DefId,
+    /// The path of the item, e.g. `foo::Bar::baz`.
+    pub path: String,
+    pub kind: ItemKind,
+    pub span: Span,
+}
+
+/// The kind of an item that has a MIR body.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ItemKind {
+    Fn,
+    Const,
+    Static,
+    Closure,
+    Generator,
+    Ctor,
+    AnonConst,
+}
+
+/// A region of source code, resolved to a file name and 1-based line and column numbers.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Span {
+    pub file_name: String,
+    pub lo_line: usize,
+    pub lo_col: usize,
+    pub hi_line: usize,
+    pub hi_col: usize,
+}
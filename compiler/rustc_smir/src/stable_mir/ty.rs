@@ -0,0 +1,131 @@
+use super::{DefId, Symbol};
+
+/// A type, owned as a tree of [`TyKind`]s.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Ty(pub Box<TyKind>);
+
+impl Ty {
+    pub fn kind(&self) -> &TyKind {
+        &self.0
+    }
+}
+
+impl From<TyKind> for Ty {
+    fn from(kind: TyKind) -> Ty {
+        Ty(Box::new(kind))
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum TyKind {
+    Bool,
+    Char,
+    Int(IntTy),
+    Uint(UintTy),
+    Float(FloatTy),
+    Str,
+    Never,
+    Adt(DefId, GenericArgs),
+    Foreign(DefId),
+    Array(Ty, Const),
+    Slice(Ty),
+    RawPtr(Ty, Mutability),
+    Ref(Region, Ty, Mutability),
+    FnDef(DefId, GenericArgs),
+    FnPtr(FnSig),
+    /// A trait object, with the traits it is made of (principal first, then auto traits).
+    Dynamic(Vec<DefId>),
+    Closure(DefId, GenericArgs),
+    Generator(DefId, GenericArgs, Movability),
+    Tuple(Vec<Ty>),
+    /// An associated type projection, e.g. `<T as Iterator>::Item`.
+    Projection(DefId, GenericArgs),
+    Opaque(DefId, GenericArgs),
+    Param(ParamTy),
+    /// A type that has no stable representation yet (inference variables, bound and
+    /// placeholder types, generator witnesses or errors), kept as its rendered text.
+    Unsupported(String),
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum IntTy {
+    Isize,
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum UintTy {
+    Usize,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FloatTy {
+    F32,
+    F64,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Mutability {
+    Not,
+    Mut,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Movability {
+    Static,
+    Movable,
+}
+
+/// A generic type parameter, e.g. the `T` in `fn foo<T>()`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ParamTy {
+    pub index: u32,
+    pub name: Symbol,
+}
+
+/// A lifetime. Regions are erased in optimized MIR, so only their rendered text is kept.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Region(pub String);
+
+/// A constant value used at the type level, e.g. an array length.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Const {
+    /// The evaluated value, if the constant is a fully known integer.
+    pub value: Option<u128>,
+    /// The constant as rustc renders it.
+    pub rendered: String,
+}
+
+pub type GenericArgs = Vec<GenericArg>;
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum GenericArg {
+    Lifetime(Region),
+    Type(Ty),
+    Const(Const),
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct FnSig {
+    pub inputs: Vec<Ty>,
+    pub output: Ty,
+    pub c_variadic: bool,
+    pub unsafety: Unsafety,
+    /// The ABI name, e.g. `"Rust"` or `"C"`.
+    pub abi: String,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Unsafety {
+    Unsafe,
+    Normal,
+}
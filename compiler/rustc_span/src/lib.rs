@@ -190,6 +190,13 @@ fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
     }
 }
 
+impl<CTX> HashStable<CTX> for RealFileName {
+    fn hash_stable(&self, _: &mut CTX, hasher: &mut StableHasher) {
+        // Like for `Hash`, only the path that ends up in build artifacts is hashed.
+        self.remapped_path_if_available().hash(hasher)
+    }
+}
+
 // This is functionally identical to #[derive(Encodable)], with the exception of
 // an added assert statement
 impl<S: Encoder> Encodable<S> for RealFileName {
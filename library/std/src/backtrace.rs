@@ -99,6 +99,7 @@
 use crate::env;
 use crate::ffi::c_void;
 use crate::fmt;
+use crate::path::PathBuf;
 use crate::sync::atomic::{AtomicUsize, Ordering::Relaxed};
 use crate::sync::Once;
 use crate::sys_common::backtrace::{lock, output_filename};
@@ -162,7 +163,11 @@ enum RawFrame {
     Fake,
 }
 
-struct BacktraceSymbol {
+/// A symbol that a [`BacktraceFrame`] has been resolved to.
+///
+/// Because of inlining, a single frame may resolve to several symbols.
+#[unstable(feature = "backtrace_frames", issue = "79676")]
+pub struct BacktraceSymbol {
     name: Option<Vec<u8>>,
     filename: Option<BytesOrWide>,
     lineno: Option<u32>,
@@ -248,6 +253,101 @@ fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
     }
 }
 
+#[unstable(feature = "backtrace_frames", issue = "79676")]
+impl BacktraceFrame {
+    /// Returns the instruction pointer of this frame.
+    ///
+    /// This is normally the next instruction to execute in the frame, i.e.
+    /// the return address, so it may point just past the call instruction.
+    /// The address is not adjusted for the base address the binary was
+    /// loaded at.
+    #[must_use]
+    pub fn ip(&self) -> *mut c_void {
+        self.frame.ip()
+    }
+
+    /// Returns the starting address of the function this frame is executing,
+    /// if it could be determined.
+    ///
+    /// This is found by unwinding rather than by resolving symbols, so it is
+    /// available for frames without symbols. Some platforms can't determine it
+    /// at all, in which case this returns the instruction pointer.
+    #[must_use]
+    pub fn symbol_address(&self) -> *mut c_void {
+        self.frame.symbol_address()
+    }
+
+    /// Returns the symbols this frame resolved to, innermost inlined function
+    /// first.
+    ///
+    /// This is empty if no symbol could be found for the frame, for example
+    /// because the binary was stripped.
+    #[must_use]
+    pub fn symbols(&self) -> &[BacktraceSymbol] {
+        &self.symbols
+    }
+}
+
+#[unstable(feature = "backtrace_frames", issue = "79676")]
+impl BacktraceSymbol {
+    /// Returns the demangled name of the function, without the trailing hash
+    /// of legacy Rust symbol names.
+    #[must_use]
+    pub fn name(&self) -> Option<String> {
+        self.name.as_ref().map(|b| format!("{:#}", backtrace_rs::SymbolName::new(b)))
+    }
+
+    /// Returns the name of the function as found in the binary, before
+    /// demangling.
+    #[must_use]
+    pub fn raw_name(&self) -> Option<&[u8]> {
+        self.name.as_deref()
+    }
+
+    /// Returns the path of the source file the function is defined in, as
+    /// recorded in the debug information.
+    ///
+    /// The path is not made relative to the current directory, unlike when
+    /// the backtrace is displayed.
+    #[must_use]
+    pub fn filename(&self) -> Option<PathBuf> {
+        self.filename.as_ref().map(BytesOrWide::to_path_buf)
+    }
+
+    /// Returns the line number in [`filename`](BacktraceSymbol::filename).
+    #[must_use]
+    pub fn lineno(&self) -> Option<u32> {
+        self.lineno
+    }
+
+    /// Returns the column number in [`filename`](BacktraceSymbol::filename).
+    #[must_use]
+    pub fn colno(&self) -> Option<u32> {
+        self.colno
+    }
+}
+
+impl BytesOrWide {
+    fn to_path_buf(&self) -> PathBuf {
+        match self {
+            #[cfg(unix)]
+            BytesOrWide::Bytes(b) => {
+                use crate::os::unix::ffi::OsStrExt;
+                crate::ffi::OsStr::from_bytes(b).into()
+            }
+            #[cfg(not(unix))]
+            BytesOrWide::Bytes(b) => String::from_utf8_lossy(b).into_owned().into(),
+            #[cfg(windows)]
+            BytesOrWide::Wide(w) => {
+                use crate::os::windows::ffi::OsStringExt;
+                crate::ffi::OsString::from_wide(w).into()
+            }
+            #[cfg(not(windows))]
+            BytesOrWide::Wide(w) => String::from_utf16_lossy(w).into(),
+        }
+    }
+}
+
 impl Backtrace {
     /// Returns whether backtrace captures are enabled through environment
     /// variables.
@@ -368,11 +468,28 @@ pub fn status(&self) -> BacktraceStatus {
 
 impl<'a> Backtrace {
     /// Returns an iterator over the backtrace frames.
+    ///
+    /// This resolves the symbols of the frames first, if that hasn't been
+    /// done yet.
     #[must_use]
     #[unstable(feature = "backtrace_frames", issue = "79676")]
     pub fn frames(&'a self) -> &'a [BacktraceFrame] {
         if let Inner::Captured(c) = &self.inner { &c.force().frames } else { &[] }
     }
+
+    /// Resolves the symbols of the frames, if that hasn't been done yet.
+    ///
+    /// Capturing a backtrace only records the instruction pointers of its
+    /// frames, and symbols are resolved the first time the backtrace is
+    /// formatted or its [`frames`](Backtrace::frames) are accessed. Resolving
+    /// is much slower than capturing, so this allows doing it ahead of time,
+    /// for example on a background thread, rather than on first use.
+    #[unstable(feature = "backtrace_frames", issue = "79676")]
+    pub fn resolve(&self) {
+        if let Inner::Captured(c) = &self.inner {
+            c.force();
+        }
+    }
 }
 
 impl fmt::Display for Backtrace {
@@ -496,4 +613,12 @@ fn ip(&self) -> *mut c_void {
             RawFrame::Fake => crate::ptr::invalid_mut(1),
         }
     }
+
+    fn symbol_address(&self) -> *mut c_void {
+        match self {
+            RawFrame::Actual(frame) => frame.symbol_address(),
+            #[cfg(test)]
+            RawFrame::Fake => crate::ptr::invalid_mut(1),
+        }
+    }
 }
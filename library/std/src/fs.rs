@@ -596,6 +596,229 @@ pub fn try_clone(&self) -> io::Result<File> {
     pub fn set_permissions(&self, perm: Permissions) -> io::Result<()> {
         self.inner.set_permissions(perm.0)
     }
+
+    /// Acquires an exclusive lock on the file, blocking until it can be acquired.
+    ///
+    /// At most one handle to a file can hold an exclusive lock at a time, and
+    /// no handle can hold a shared lock while it is held. The lock is released
+    /// by [`unlock`], or when the last handle to the underlying file description
+    /// is closed, including the ones created with [`try_clone`].
+    ///
+    /// Locks are advisory: they only exclude other handles that also lock the
+    /// file, and don't prevent reads or writes through handles that don't.
+    ///
+    /// If this handle already holds a lock, whether shared or exclusive, the
+    /// behavior is platform dependent. Call [`unlock`] first to change the kind
+    /// of lock that is held.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This function currently corresponds to the `flock` function on Unix
+    /// with the `LOCK_EX` flag, and the `LockFileEx` function on Windows with
+    /// the `LOCKFILE_EXCLUSIVE_LOCK` flag. Note that, this
+    /// [may change in the future][changes].
+    ///
+    /// On Solaris and illumos, which have no `flock`, a `fcntl` lock covering
+    /// the whole file is used. Such locks belong to the process rather than the
+    /// handle, so they don't exclude other handles of the same process, and are
+    /// released as soon as the process closes any handle to the file.
+    ///
+    /// On Windows, locks are mandatory rather than advisory: other handles
+    /// can't read or write the file while an exclusive lock is held.
+    ///
+    /// [changes]: io#platform-specific-behavior
+    /// [`unlock`]: File::unlock
+    /// [`try_clone`]: File::try_clone
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error with the [`Unsupported`] kind on
+    /// platforms that don't support file locking, such as WASI. It may also
+    /// return an error in other os-specific unspecified cases.
+    ///
+    /// [`Unsupported`]: io::ErrorKind::Unsupported
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(file_lock)]
+    /// use std::fs::File;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let f = File::create("foo.txt")?;
+    ///     f.lock()?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "file_lock", issue = "none")]
+    pub fn lock(&self) -> io::Result<()> {
+        self.inner.lock()
+    }
+
+    /// Acquires a shared lock on the file, blocking until it can be acquired.
+    ///
+    /// Any number of handles to a file can hold a shared lock at the same
+    /// time, as long as none holds an exclusive lock. See [`lock`] for the
+    /// lifetime of locks and for what happens if this handle already holds one.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This function currently corresponds to the `flock` function on Unix
+    /// with the `LOCK_SH` flag, and the `LockFileEx` function on Windows. Note
+    /// that, this [may change in the future][changes]. The other differences
+    /// between platforms are described in [`lock`].
+    ///
+    /// [changes]: io#platform-specific-behavior
+    /// [`lock`]: File::lock
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error with the [`Unsupported`] kind on
+    /// platforms that don't support file locking, such as WASI. It may also
+    /// return an error in other os-specific unspecified cases.
+    ///
+    /// [`Unsupported`]: io::ErrorKind::Unsupported
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(file_lock)]
+    /// use std::fs::File;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let f = File::open("foo.txt")?;
+    ///     f.lock_shared()?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "file_lock", issue = "none")]
+    pub fn lock_shared(&self) -> io::Result<()> {
+        self.inner.lock_shared()
+    }
+
+    /// Tries to acquire an exclusive lock on the file, without blocking.
+    ///
+    /// Returns `Ok(false)` if another handle holds a lock on the file, and
+    /// `Ok(true)` once the lock is acquired. See [`lock`] for the semantics of
+    /// the lock.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This function currently corresponds to the `flock` function on Unix
+    /// with the `LOCK_EX` and `LOCK_NB` flags, and the `LockFileEx` function on
+    /// Windows with the `LOCKFILE_EXCLUSIVE_LOCK` and `LOCKFILE_FAIL_IMMEDIATELY`
+    /// flags. Note that, this [may change in the future][changes].
+    ///
+    /// [changes]: io#platform-specific-behavior
+    /// [`lock`]: File::lock
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error with the [`Unsupported`] kind on
+    /// platforms that don't support file locking, such as WASI. It may also
+    /// return an error in other os-specific unspecified cases.
+    ///
+    /// [`Unsupported`]: io::ErrorKind::Unsupported
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(file_lock)]
+    /// use std::fs::File;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let f = File::create("foo.txt")?;
+    ///     if !f.try_lock()? {
+    ///         eprintln!("foo.txt is in use");
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "file_lock", issue = "none")]
+    pub fn try_lock(&self) -> io::Result<bool> {
+        self.inner.try_lock()
+    }
+
+    /// Tries to acquire a shared lock on the file, without blocking.
+    ///
+    /// Returns `Ok(false)` if another handle holds an exclusive lock on the
+    /// file, and `Ok(true)` once the lock is acquired. See [`lock_shared`] for
+    /// the semantics of the lock.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This function currently corresponds to the `flock` function on Unix
+    /// with the `LOCK_SH` and `LOCK_NB` flags, and the `LockFileEx` function on
+    /// Windows with the `LOCKFILE_FAIL_IMMEDIATELY` flag. Note that, this
+    /// [may change in the future][changes].
+    ///
+    /// [changes]: io#platform-specific-behavior
+    /// [`lock_shared`]: File::lock_shared
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error with the [`Unsupported`] kind on
+    /// platforms that don't support file locking, such as WASI. It may also
+    /// return an error in other os-specific unspecified cases.
+    ///
+    /// [`Unsupported`]: io::ErrorKind::Unsupported
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(file_lock)]
+    /// use std::fs::File;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let f = File::open("foo.txt")?;
+    ///     if !f.try_lock_shared()? {
+    ///         eprintln!("foo.txt is being written to");
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "file_lock", issue = "none")]
+    pub fn try_lock_shared(&self) -> io::Result<bool> {
+        self.inner.try_lock_shared()
+    }
+
+    /// Releases the lock held by this handle, if any.
+    ///
+    /// Unlocking a file that isn't locked by this handle is not an error.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This function currently corresponds to the `flock` function on Unix
+    /// with the `LOCK_UN` flag, and the `UnlockFile` function on Windows. Note
+    /// that, this [may change in the future][changes].
+    ///
+    /// [changes]: io#platform-specific-behavior
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error with the [`Unsupported`] kind on
+    /// platforms that don't support file locking, such as WASI. It may also
+    /// return an error in other os-specific unspecified cases.
+    ///
+    /// [`Unsupported`]: io::ErrorKind::Unsupported
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(file_lock)]
+    /// use std::fs::File;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let f = File::create("foo.txt")?;
+    ///     f.lock()?;
+    ///     f.unlock()?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "file_lock", issue = "none")]
+    pub fn unlock(&self) -> io::Result<()> {
+        self.inner.unlock()
+    }
 }
 
 // In addition to the `impl`s here, `File` also has `impl`s for
@@ -1233,6 +1233,46 @@ fn file_try_clone() {
     check!(f1.write_all(b"!"));
 }
 
+#[test]
+#[cfg(any(
+    windows,
+    target_os = "freebsd",
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "netbsd",
+    target_os = "openbsd",
+))]
+fn file_lock_multiple_handles() {
+    let tmpdir = tmpdir();
+    let path = tmpdir.join("lock");
+    let open = || check!(OpenOptions::new().read(true).write(true).create(true).open(&path));
+    let f1 = open();
+    let f2 = open();
+
+    // An exclusive lock excludes all other locks.
+    check!(f1.lock());
+    assert!(!check!(f2.try_lock()));
+    assert!(!check!(f2.try_lock_shared()));
+    check!(f1.unlock());
+
+    // Shared locks only exclude exclusive locks.
+    check!(f1.lock_shared());
+    assert!(check!(f2.try_lock_shared()));
+    check!(f2.unlock());
+    assert!(!check!(f2.try_lock()));
+    check!(f1.unlock());
+
+    // Unlocking twice is fine.
+    check!(f1.unlock());
+    assert!(check!(f2.try_lock()));
+    assert!(!check!(f1.try_lock()));
+
+    // Closing the handle releases its lock.
+    drop(f2);
+    assert!(check!(f1.try_lock()));
+    check!(f1.unlock());
+}
+
 #[test]
 #[cfg(not(windows))]
 fn unlink_readonly() {
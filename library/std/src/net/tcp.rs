@@ -503,6 +503,328 @@ pub fn nodelay(&self) -> io::Result<bool> {
         self.0.nodelay()
     }
 
+    /// Sets the value of the `SO_KEEPALIVE` option on this socket.
+    ///
+    /// If set, the system periodically sends probes on a connection that has
+    /// been idle for a while, and closes the connection if the peer stops
+    /// answering them. The timing of the probes can be tuned with
+    /// [`TcpStream::set_keepalive_time`], [`TcpStream::set_keepalive_interval`]
+    /// and [`TcpStream::set_keepalive_retries`], and otherwise follows the
+    /// system defaults, which typically wait for two hours of idleness.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(tcp_keepalive)]
+    ///
+    /// use std::net::TcpStream;
+    ///
+    /// let stream = TcpStream::connect("127.0.0.1:8080")
+    ///                        .expect("Couldn't connect to the server...");
+    /// stream.set_keepalive(true).expect("set_keepalive call failed");
+    /// ```
+    #[unstable(feature = "tcp_keepalive", issue = "none")]
+    pub fn set_keepalive(&self, keepalive: bool) -> io::Result<()> {
+        self.0.set_keepalive(keepalive)
+    }
+
+    /// Gets the value of the `SO_KEEPALIVE` option on this socket.
+    ///
+    /// For more information about this option, see [`TcpStream::set_keepalive`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(tcp_keepalive)]
+    ///
+    /// use std::net::TcpStream;
+    ///
+    /// let stream = TcpStream::connect("127.0.0.1:8080")
+    ///                        .expect("Couldn't connect to the server...");
+    /// stream.set_keepalive(true).expect("set_keepalive call failed");
+    /// assert_eq!(stream.keepalive().unwrap_or(false), true);
+    /// ```
+    #[unstable(feature = "tcp_keepalive", issue = "none")]
+    pub fn keepalive(&self) -> io::Result<bool> {
+        self.0.keepalive()
+    }
+
+    /// Sets how long the connection has to be idle before the first keepalive
+    /// probe is sent.
+    ///
+    /// The time is rounded up to whole seconds. This has no effect unless
+    /// keepalive is enabled with [`TcpStream::set_keepalive`].
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This function currently corresponds to the `TCP_KEEPIDLE` option on
+    /// Linux, Android, the BSDs and Windows, and the `TCP_KEEPALIVE` option on
+    /// macOS and iOS. Elsewhere, it returns an error of the [`Unsupported`]
+    /// kind. On Windows, the option requires Windows 10, version 1709 or
+    /// later, and older versions return an error. Note that, this
+    /// [may change in the future][changes].
+    ///
+    /// [`Unsupported`]: io::ErrorKind::Unsupported
+    /// [changes]: io#platform-specific-behavior
+    ///
+    /// # Errors
+    ///
+    /// An [`Err`] is returned if the zero [`Duration`] is passed to this
+    /// method.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(tcp_keepalive)]
+    ///
+    /// use std::net::TcpStream;
+    /// use std::time::Duration;
+    ///
+    /// let stream = TcpStream::connect("127.0.0.1:8080")
+    ///                        .expect("Couldn't connect to the server...");
+    /// stream.set_keepalive(true).expect("set_keepalive call failed");
+    /// stream.set_keepalive_time(Duration::from_secs(60)).expect("set_keepalive_time call failed");
+    /// ```
+    #[unstable(feature = "tcp_keepalive", issue = "none")]
+    pub fn set_keepalive_time(&self, time: Duration) -> io::Result<()> {
+        self.0.set_keepalive_time(time)
+    }
+
+    /// Gets how long the connection has to be idle before the first keepalive
+    /// probe is sent.
+    ///
+    /// For more information about this option, see [`TcpStream::set_keepalive_time`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(tcp_keepalive)]
+    ///
+    /// use std::net::TcpStream;
+    /// use std::time::Duration;
+    ///
+    /// let stream = TcpStream::connect("127.0.0.1:8080")
+    ///                        .expect("Couldn't connect to the server...");
+    /// stream.set_keepalive_time(Duration::from_secs(60)).expect("set_keepalive_time call failed");
+    /// assert_eq!(stream.keepalive_time().unwrap(), Duration::from_secs(60));
+    /// ```
+    #[unstable(feature = "tcp_keepalive", issue = "none")]
+    pub fn keepalive_time(&self) -> io::Result<Duration> {
+        self.0.keepalive_time()
+    }
+
+    /// Sets the time between two keepalive probes that go unanswered.
+    ///
+    /// The interval is rounded up to whole seconds. This has no effect unless
+    /// keepalive is enabled with [`TcpStream::set_keepalive`].
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This function currently corresponds to the `TCP_KEEPINTVL` option.
+    /// Where [`TcpStream::set_keepalive_time`] is unsupported, it returns an
+    /// error of the [`Unsupported`] kind. On Windows, the option requires
+    /// Windows 10, version 1709 or later, and older versions return an error.
+    /// Note that, this [may change in the future][changes].
+    ///
+    /// [`Unsupported`]: io::ErrorKind::Unsupported
+    /// [changes]: io#platform-specific-behavior
+    ///
+    /// # Errors
+    ///
+    /// An [`Err`] is returned if the zero [`Duration`] is passed to this
+    /// method.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(tcp_keepalive)]
+    ///
+    /// use std::net::TcpStream;
+    /// use std::time::Duration;
+    ///
+    /// let stream = TcpStream::connect("127.0.0.1:8080")
+    ///                        .expect("Couldn't connect to the server...");
+    /// stream.set_keepalive(true).expect("set_keepalive call failed");
+    /// stream.set_keepalive_interval(Duration::from_secs(10))
+    ///       .expect("set_keepalive_interval call failed");
+    /// ```
+    #[unstable(feature = "tcp_keepalive", issue = "none")]
+    pub fn set_keepalive_interval(&self, interval: Duration) -> io::Result<()> {
+        self.0.set_keepalive_interval(interval)
+    }
+
+    /// Gets the time between two keepalive probes that go unanswered.
+    ///
+    /// For more information about this option, see
+    /// [`TcpStream::set_keepalive_interval`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(tcp_keepalive)]
+    ///
+    /// use std::net::TcpStream;
+    /// use std::time::Duration;
+    ///
+    /// let stream = TcpStream::connect("127.0.0.1:8080")
+    ///                        .expect("Couldn't connect to the server...");
+    /// stream.set_keepalive_interval(Duration::from_secs(10))
+    ///       .expect("set_keepalive_interval call failed");
+    /// assert_eq!(stream.keepalive_interval().unwrap(), Duration::from_secs(10));
+    /// ```
+    #[unstable(feature = "tcp_keepalive", issue = "none")]
+    pub fn keepalive_interval(&self) -> io::Result<Duration> {
+        self.0.keepalive_interval()
+    }
+
+    /// Sets the number of unanswered keepalive probes after which the
+    /// connection is closed.
+    ///
+    /// This has no effect unless keepalive is enabled with
+    /// [`TcpStream::set_keepalive`].
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This function currently corresponds to the `TCP_KEEPCNT` option.
+    /// Where [`TcpStream::set_keepalive_time`] is unsupported, it returns an
+    /// error of the [`Unsupported`] kind. On Windows, the option requires
+    /// Windows 10, version 1709 or later, and older versions return an error.
+    /// Note that, this [may change in the future][changes].
+    ///
+    /// [`Unsupported`]: io::ErrorKind::Unsupported
+    /// [changes]: io#platform-specific-behavior
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(tcp_keepalive)]
+    ///
+    /// use std::net::TcpStream;
+    ///
+    /// let stream = TcpStream::connect("127.0.0.1:8080")
+    ///                        .expect("Couldn't connect to the server...");
+    /// stream.set_keepalive(true).expect("set_keepalive call failed");
+    /// stream.set_keepalive_retries(5).expect("set_keepalive_retries call failed");
+    /// ```
+    #[unstable(feature = "tcp_keepalive", issue = "none")]
+    pub fn set_keepalive_retries(&self, retries: u32) -> io::Result<()> {
+        self.0.set_keepalive_retries(retries)
+    }
+
+    /// Gets the number of unanswered keepalive probes after which the
+    /// connection is closed.
+    ///
+    /// For more information about this option, see
+    /// [`TcpStream::set_keepalive_retries`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(tcp_keepalive)]
+    ///
+    /// use std::net::TcpStream;
+    ///
+    /// let stream = TcpStream::connect("127.0.0.1:8080")
+    ///                        .expect("Couldn't connect to the server...");
+    /// stream.set_keepalive_retries(5).expect("set_keepalive_retries call failed");
+    /// assert_eq!(stream.keepalive_retries().unwrap_or(0), 5);
+    /// ```
+    #[unstable(feature = "tcp_keepalive", issue = "none")]
+    pub fn keepalive_retries(&self) -> io::Result<u32> {
+        self.0.keepalive_retries()
+    }
+
+    /// Sets the value of the `SO_SNDBUF` option on this socket.
+    ///
+    /// This is the size, in bytes, of the buffer the system uses for data that
+    /// has been written to the socket but not yet sent. The system may round
+    /// the size or clamp it to a configured range, and on Linux the value read
+    /// back with [`TcpStream::send_buffer_size`] is double the requested size, to
+    /// account for bookkeeping overhead.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(socket_buffer_size)]
+    ///
+    /// use std::net::TcpStream;
+    ///
+    /// let stream = TcpStream::connect("127.0.0.1:8080")
+    ///                        .expect("Couldn't connect to the server...");
+    /// stream.set_send_buffer_size(1 << 20).expect("set_send_buffer_size call failed");
+    /// ```
+    #[unstable(feature = "socket_buffer_size", issue = "none")]
+    pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        self.0.set_send_buffer_size(size)
+    }
+
+    /// Gets the value of the `SO_SNDBUF` option on this socket.
+    ///
+    /// For more information about this option, see [`TcpStream::set_send_buffer_size`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(socket_buffer_size)]
+    ///
+    /// use std::net::TcpStream;
+    ///
+    /// let stream = TcpStream::connect("127.0.0.1:8080")
+    ///                        .expect("Couldn't connect to the server...");
+    /// stream.set_send_buffer_size(1 << 20).expect("set_send_buffer_size call failed");
+    /// // The system may have doubled or clamped the requested size.
+    /// println!("send buffer size: {}", stream.send_buffer_size().unwrap());
+    /// ```
+    #[unstable(feature = "socket_buffer_size", issue = "none")]
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        self.0.send_buffer_size()
+    }
+
+    /// Sets the value of the `SO_RCVBUF` option on this socket.
+    ///
+    /// This is the size, in bytes, of the buffer the system uses for data that
+    /// has been received but not yet read from the socket. The system may
+    /// round the size or clamp it to a configured range, and on Linux the value
+    /// read back with [`TcpStream::recv_buffer_size`] is double the requested size.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(socket_buffer_size)]
+    ///
+    /// use std::net::TcpStream;
+    ///
+    /// let stream = TcpStream::connect("127.0.0.1:8080")
+    ///                        .expect("Couldn't connect to the server...");
+    /// stream.set_recv_buffer_size(1 << 20).expect("set_recv_buffer_size call failed");
+    /// ```
+    #[unstable(feature = "socket_buffer_size", issue = "none")]
+    pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        self.0.set_recv_buffer_size(size)
+    }
+
+    /// Gets the value of the `SO_RCVBUF` option on this socket.
+    ///
+    /// For more information about this option, see [`TcpStream::set_recv_buffer_size`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(socket_buffer_size)]
+    ///
+    /// use std::net::TcpStream;
+    ///
+    /// let stream = TcpStream::connect("127.0.0.1:8080")
+    ///                        .expect("Couldn't connect to the server...");
+    /// stream.set_recv_buffer_size(1 << 20).expect("set_recv_buffer_size call failed");
+    /// // The system may have doubled or clamped the requested size.
+    /// println!("recv buffer size: {}", stream.recv_buffer_size().unwrap());
+    /// ```
+    #[unstable(feature = "socket_buffer_size", issue = "none")]
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        self.0.recv_buffer_size()
+    }
+
     /// Sets the value for the `IP_TTL` option on this socket.
     ///
     /// This value sets the time-to-live field that is used in every packet sent
@@ -752,6 +1074,37 @@ pub fn bind<A: ToSocketAddrs>(addr: A) -> io::Result<TcpListener> {
         super::each_addr(addr, net_imp::TcpListener::bind).map(TcpListener)
     }
 
+    /// Creates a new `TcpListener` bound to the specified address, with the
+    /// `SO_REUSEPORT` option set on its socket before binding it.
+    ///
+    /// This allows several listeners, in this process or in others, to bind
+    /// to the same address and port as long as all of them set the option,
+    /// with the system distributing incoming connections between them.
+    /// Addresses are handled like in [`TcpListener::bind`].
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This option is available on Linux, Android, macOS, iOS, and the BSDs.
+    /// Elsewhere, including Windows, this function returns an error of the
+    /// [`Unsupported`] kind.
+    ///
+    /// [`Unsupported`]: io::ErrorKind::Unsupported
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(socket_reuse_port)]
+    ///
+    /// use std::net::TcpListener;
+    ///
+    /// let first = TcpListener::bind_reuse_port("127.0.0.1:8080").unwrap();
+    /// let second = TcpListener::bind_reuse_port("127.0.0.1:8080").unwrap();
+    /// ```
+    #[unstable(feature = "socket_reuse_port", issue = "none")]
+    pub fn bind_reuse_port<A: ToSocketAddrs>(addr: A) -> io::Result<TcpListener> {
+        super::each_addr(addr, net_imp::TcpListener::bind_reuse_port).map(TcpListener)
+    }
+
     /// Returns the local socket address of this listener.
     ///
     /// # Examples
@@ -929,6 +1282,113 @@ pub fn only_v6(&self) -> io::Result<bool> {
         self.0.only_v6()
     }
 
+    /// Sets the value of the `SO_SNDBUF` option on this socket.
+    ///
+    /// This is the size, in bytes, of the buffer the system uses for data that
+    /// has been written to the socket but not yet sent. The system may round
+    /// the size or clamp it to a configured range, and on Linux the value read
+    /// back with [`TcpListener::send_buffer_size`] is double the requested size, to
+    /// account for bookkeeping overhead.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(socket_buffer_size)]
+    ///
+    /// use std::net::TcpListener;
+    ///
+    /// let listener = TcpListener::bind("127.0.0.1:80").unwrap();
+    /// listener.set_send_buffer_size(1 << 20).expect("set_send_buffer_size call failed");
+    /// ```
+    #[unstable(feature = "socket_buffer_size", issue = "none")]
+    pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        self.0.set_send_buffer_size(size)
+    }
+
+    /// Gets the value of the `SO_SNDBUF` option on this socket.
+    ///
+    /// For more information about this option, see [`TcpListener::set_send_buffer_size`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(socket_buffer_size)]
+    ///
+    /// use std::net::TcpListener;
+    ///
+    /// let listener = TcpListener::bind("127.0.0.1:80").unwrap();
+    /// listener.set_send_buffer_size(1 << 20).expect("set_send_buffer_size call failed");
+    /// // The system may have doubled or clamped the requested size.
+    /// println!("send buffer size: {}", listener.send_buffer_size().unwrap());
+    /// ```
+    #[unstable(feature = "socket_buffer_size", issue = "none")]
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        self.0.send_buffer_size()
+    }
+
+    /// Sets the value of the `SO_RCVBUF` option on this socket.
+    ///
+    /// This is the size, in bytes, of the buffer the system uses for data that
+    /// has been received but not yet read from the socket. For a listener, it
+    /// is inherited by the accepted streams. The system may round the size or
+    /// clamp it to a configured range, and on Linux the value read back with
+    /// [`TcpListener::recv_buffer_size`] is double the requested size.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(socket_buffer_size)]
+    ///
+    /// use std::net::TcpListener;
+    ///
+    /// let listener = TcpListener::bind("127.0.0.1:80").unwrap();
+    /// listener.set_recv_buffer_size(1 << 20).expect("set_recv_buffer_size call failed");
+    /// ```
+    #[unstable(feature = "socket_buffer_size", issue = "none")]
+    pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        self.0.set_recv_buffer_size(size)
+    }
+
+    /// Gets the value of the `SO_RCVBUF` option on this socket.
+    ///
+    /// For more information about this option, see [`TcpListener::set_recv_buffer_size`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(socket_buffer_size)]
+    ///
+    /// use std::net::TcpListener;
+    ///
+    /// let listener = TcpListener::bind("127.0.0.1:80").unwrap();
+    /// listener.set_recv_buffer_size(1 << 20).expect("set_recv_buffer_size call failed");
+    /// // The system may have doubled or clamped the requested size.
+    /// println!("recv buffer size: {}", listener.recv_buffer_size().unwrap());
+    /// ```
+    #[unstable(feature = "socket_buffer_size", issue = "none")]
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        self.0.recv_buffer_size()
+    }
+
+    /// Gets the value of the `SO_REUSEPORT` option on this socket.
+    ///
+    /// For more information about this option, see [`TcpListener::bind_reuse_port`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(socket_reuse_port)]
+    ///
+    /// use std::net::TcpListener;
+    ///
+    /// let listener = TcpListener::bind_reuse_port("127.0.0.1:80").unwrap();
+    /// assert_eq!(listener.reuse_port().unwrap_or(false), true);
+    /// ```
+    #[unstable(feature = "socket_reuse_port", issue = "none")]
+    pub fn reuse_port(&self) -> io::Result<bool> {
+        self.0.reuse_port()
+    }
+
     /// Gets the value of the `SO_ERROR` option on this socket.
     ///
     /// This will retrieve the stored error in the underlying socket, clearing
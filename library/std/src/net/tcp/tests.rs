@@ -796,6 +796,65 @@ fn nodelay() {
     assert_eq!(false, t!(stream.nodelay()));
 }
 
+#[test]
+#[cfg_attr(target_env = "sgx", ignore)]
+fn keepalive() {
+    let addr = next_test_ip4();
+    let _listener = t!(TcpListener::bind(&addr));
+
+    let stream = t!(TcpStream::connect(&("localhost", addr.port())));
+
+    assert_eq!(false, t!(stream.keepalive()));
+    t!(stream.set_keepalive(true));
+    assert_eq!(true, t!(stream.keepalive()));
+
+    if cfg!(any(windows, target_os = "linux", target_os = "macos", target_os = "freebsd")) {
+        t!(stream.set_keepalive_time(Duration::from_secs(30)));
+        assert_eq!(Duration::from_secs(30), t!(stream.keepalive_time()));
+        t!(stream.set_keepalive_interval(Duration::from_millis(4500)));
+        assert_eq!(Duration::from_secs(5), t!(stream.keepalive_interval()));
+        t!(stream.set_keepalive_retries(3));
+        assert_eq!(3, t!(stream.keepalive_retries()));
+    }
+
+    let err = stream.set_keepalive_time(Duration::ZERO).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::InvalidInput | ErrorKind::Unsupported));
+
+    t!(stream.set_keepalive(false));
+    assert_eq!(false, t!(stream.keepalive()));
+}
+
+#[test]
+#[cfg_attr(target_env = "sgx", ignore)]
+fn buffer_sizes() {
+    let addr = next_test_ip4();
+    let listener = t!(TcpListener::bind(&addr));
+
+    t!(listener.set_recv_buffer_size(64 * 1024));
+    assert!(t!(listener.recv_buffer_size()) >= 64 * 1024);
+
+    let stream = t!(TcpStream::connect(&("localhost", addr.port())));
+
+    t!(stream.set_send_buffer_size(64 * 1024));
+    assert!(t!(stream.send_buffer_size()) >= 64 * 1024);
+    t!(stream.set_recv_buffer_size(64 * 1024));
+    assert!(t!(stream.recv_buffer_size()) >= 64 * 1024);
+}
+
+#[test]
+#[cfg(any(target_os = "linux", target_os = "macos", target_os = "freebsd"))]
+fn reuse_port() {
+    let addr = next_test_ip4();
+    let listener = t!(TcpListener::bind(&addr));
+    assert_eq!(false, t!(listener.reuse_port()));
+    drop(listener);
+
+    let first = t!(TcpListener::bind_reuse_port(&addr));
+    let second = t!(TcpListener::bind_reuse_port(&addr));
+    assert_eq!(true, t!(first.reuse_port()));
+    assert_eq!(true, t!(second.reuse_port()));
+}
+
 #[test]
 #[cfg_attr(target_env = "sgx", ignore)]
 fn ttl() {
@@ -95,6 +95,37 @@ pub fn bind<A: ToSocketAddrs>(addr: A) -> io::Result<UdpSocket> {
         super::each_addr(addr, net_imp::UdpSocket::bind).map(UdpSocket)
     }
 
+    /// Creates a UDP socket from the given address, with the `SO_REUSEPORT`
+    /// option set on it before binding it.
+    ///
+    /// This allows several sockets, in this process or in others, to bind to
+    /// the same address and port as long as all of them set the option, with
+    /// the system distributing incoming datagrams between them. Addresses are
+    /// handled like in [`UdpSocket::bind`].
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This option is available on Linux, Android, macOS, iOS, and the BSDs.
+    /// Elsewhere, including Windows, this function returns an error of the
+    /// [`Unsupported`] kind.
+    ///
+    /// [`Unsupported`]: io::ErrorKind::Unsupported
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(socket_reuse_port)]
+    ///
+    /// use std::net::UdpSocket;
+    ///
+    /// let first = UdpSocket::bind_reuse_port("127.0.0.1:34254").unwrap();
+    /// let second = UdpSocket::bind_reuse_port("127.0.0.1:34254").unwrap();
+    /// ```
+    #[unstable(feature = "socket_reuse_port", issue = "none")]
+    pub fn bind_reuse_port<A: ToSocketAddrs>(addr: A) -> io::Result<UdpSocket> {
+        super::each_addr(addr, net_imp::UdpSocket::bind_reuse_port).map(UdpSocket)
+    }
+
     /// Receives a single datagram message on the socket. On success, returns the number
     /// of bytes read and the origin.
     ///
@@ -407,6 +438,112 @@ pub fn broadcast(&self) -> io::Result<bool> {
         self.0.broadcast()
     }
 
+    /// Sets the value of the `SO_SNDBUF` option on this socket.
+    ///
+    /// This is the size, in bytes, of the buffer the system uses for data that
+    /// has been written to the socket but not yet sent. The system may round
+    /// the size or clamp it to a configured range, and on Linux the value read
+    /// back with [`UdpSocket::send_buffer_size`] is double the requested size, to
+    /// account for bookkeeping overhead.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(socket_buffer_size)]
+    ///
+    /// use std::net::UdpSocket;
+    ///
+    /// let socket = UdpSocket::bind("127.0.0.1:34254").expect("couldn't bind to address");
+    /// socket.set_send_buffer_size(1 << 20).expect("set_send_buffer_size call failed");
+    /// ```
+    #[unstable(feature = "socket_buffer_size", issue = "none")]
+    pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        self.0.set_send_buffer_size(size)
+    }
+
+    /// Gets the value of the `SO_SNDBUF` option on this socket.
+    ///
+    /// For more information about this option, see [`UdpSocket::set_send_buffer_size`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(socket_buffer_size)]
+    ///
+    /// use std::net::UdpSocket;
+    ///
+    /// let socket = UdpSocket::bind("127.0.0.1:34254").expect("couldn't bind to address");
+    /// socket.set_send_buffer_size(1 << 20).expect("set_send_buffer_size call failed");
+    /// // The system may have doubled or clamped the requested size.
+    /// println!("send buffer size: {}", socket.send_buffer_size().unwrap());
+    /// ```
+    #[unstable(feature = "socket_buffer_size", issue = "none")]
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        self.0.send_buffer_size()
+    }
+
+    /// Sets the value of the `SO_RCVBUF` option on this socket.
+    ///
+    /// This is the size, in bytes, of the buffer the system uses for data that
+    /// has been received but not yet read from the socket. The system may
+    /// round the size or clamp it to a configured range, and on Linux the value
+    /// read back with [`UdpSocket::recv_buffer_size`] is double the requested size.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(socket_buffer_size)]
+    ///
+    /// use std::net::UdpSocket;
+    ///
+    /// let socket = UdpSocket::bind("127.0.0.1:34254").expect("couldn't bind to address");
+    /// socket.set_recv_buffer_size(1 << 20).expect("set_recv_buffer_size call failed");
+    /// ```
+    #[unstable(feature = "socket_buffer_size", issue = "none")]
+    pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        self.0.set_recv_buffer_size(size)
+    }
+
+    /// Gets the value of the `SO_RCVBUF` option on this socket.
+    ///
+    /// For more information about this option, see [`UdpSocket::set_recv_buffer_size`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(socket_buffer_size)]
+    ///
+    /// use std::net::UdpSocket;
+    ///
+    /// let socket = UdpSocket::bind("127.0.0.1:34254").expect("couldn't bind to address");
+    /// socket.set_recv_buffer_size(1 << 20).expect("set_recv_buffer_size call failed");
+    /// // The system may have doubled or clamped the requested size.
+    /// println!("recv buffer size: {}", socket.recv_buffer_size().unwrap());
+    /// ```
+    #[unstable(feature = "socket_buffer_size", issue = "none")]
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        self.0.recv_buffer_size()
+    }
+
+    /// Gets the value of the `SO_REUSEPORT` option on this socket.
+    ///
+    /// For more information about this option, see [`UdpSocket::bind_reuse_port`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(socket_reuse_port)]
+    ///
+    /// use std::net::UdpSocket;
+    ///
+    /// let socket = UdpSocket::bind_reuse_port("127.0.0.1:34254").unwrap();
+    /// assert_eq!(socket.reuse_port().unwrap_or(false), true);
+    /// ```
+    #[unstable(feature = "socket_reuse_port", issue = "none")]
+    pub fn reuse_port(&self) -> io::Result<bool> {
+        self.0.reuse_port()
+    }
+
     /// Sets the value of the `IP_MULTICAST_LOOP` option for this socket.
     ///
     /// If enabled, multicast packets will be looped back to the local socket.
@@ -342,6 +342,33 @@ fn ttl() {
     assert_eq!(ttl, t!(stream.ttl()));
 }
 
+#[test]
+fn buffer_sizes() {
+    let addr = next_test_ip4();
+
+    let socket = t!(UdpSocket::bind(&addr));
+
+    t!(socket.set_send_buffer_size(64 * 1024));
+    assert!(t!(socket.send_buffer_size()) >= 64 * 1024);
+    t!(socket.set_recv_buffer_size(64 * 1024));
+    assert!(t!(socket.recv_buffer_size()) >= 64 * 1024);
+}
+
+#[test]
+#[cfg(any(target_os = "linux", target_os = "macos", target_os = "freebsd"))]
+fn reuse_port() {
+    let addr = next_test_ip4();
+
+    let socket = t!(UdpSocket::bind(&addr));
+    assert_eq!(false, t!(socket.reuse_port()));
+    drop(socket);
+
+    let first = t!(UdpSocket::bind_reuse_port(&addr));
+    let second = t!(UdpSocket::bind_reuse_port(&addr));
+    assert_eq!(true, t!(first.reuse_port()));
+    assert_eq!(true, t!(second.reuse_port()));
+}
+
 #[test]
 fn set_nonblocking() {
     each_ip(&mut |addr, _| {
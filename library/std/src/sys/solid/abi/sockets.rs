@@ -30,6 +30,9 @@
 pub const SO_REUSEADDR: i32 = 4;
 pub const SO_SNDTIMEO: i32 = 4101;
 pub const SO_LINGER: i32 = 128;
+pub const SO_KEEPALIVE: i32 = 8;
+pub const SO_SNDBUF: i32 = 4097;
+pub const SO_RCVBUF: i32 = 4098;
 pub const TCP_NODELAY: i32 = 1;
 pub const MSG_PEEK: c_int = 1;
 pub const FIONBIO: c_long = 0x8008667eu32 as c_long;
@@ -1029,6 +1029,106 @@ pub fn set_permissions(&self, perm: FilePermissions) -> io::Result<()> {
         cvt_r(|| unsafe { libc::fchmod(self.as_raw_fd(), perm.mode) })?;
         Ok(())
     }
+
+    pub fn lock(&self) -> io::Result<()> {
+        self.flock(FileLock::Exclusive, true).map(drop)
+    }
+
+    pub fn lock_shared(&self) -> io::Result<()> {
+        self.flock(FileLock::Shared, true).map(drop)
+    }
+
+    pub fn try_lock(&self) -> io::Result<bool> {
+        self.flock(FileLock::Exclusive, false)
+    }
+
+    pub fn try_lock_shared(&self) -> io::Result<bool> {
+        self.flock(FileLock::Shared, false)
+    }
+
+    pub fn unlock(&self) -> io::Result<()> {
+        self.flock(FileLock::Unlocked, false).map(drop)
+    }
+
+    /// Changes the lock held on the file, returning `false` if it is held by
+    /// someone else and `block` is not set.
+    #[cfg(any(
+        target_os = "android",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "fuchsia",
+        target_os = "ios",
+        target_os = "linux",
+        target_os = "macos",
+        target_os = "netbsd",
+        target_os = "openbsd",
+        target_os = "watchos",
+    ))]
+    fn flock(&self, lock: FileLock, block: bool) -> io::Result<bool> {
+        let mut operation = match lock {
+            FileLock::Exclusive => libc::LOCK_EX,
+            FileLock::Shared => libc::LOCK_SH,
+            FileLock::Unlocked => libc::LOCK_UN,
+        };
+        if !block {
+            operation |= libc::LOCK_NB;
+        }
+        match cvt_r(|| unsafe { libc::flock(self.as_raw_fd(), operation) }) {
+            Ok(_) => Ok(true),
+            Err(e) if e.raw_os_error() == Some(libc::EWOULDBLOCK) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Solaris and illumos have no `flock`, so `fcntl` record locks covering
+    /// the whole file are used instead. Unlike `flock` locks, these are owned
+    /// by the process rather than the open file description, so they are
+    /// released as soon as any descriptor of the file is closed by the process.
+    #[cfg(any(target_os = "solaris", target_os = "illumos"))]
+    fn flock(&self, lock: FileLock, block: bool) -> io::Result<bool> {
+        let mut flock: libc::flock = unsafe { mem::zeroed() };
+        flock.l_type = match lock {
+            FileLock::Exclusive => libc::F_WRLCK,
+            FileLock::Shared => libc::F_RDLCK,
+            FileLock::Unlocked => libc::F_UNLCK,
+        } as _;
+        flock.l_whence = libc::SEEK_SET as _;
+        // `l_start` and `l_len` are left at 0, which covers the whole file.
+        let cmd = if block { libc::F_SETLKW } else { libc::F_SETLK };
+        match cvt_r(|| unsafe { libc::fcntl(self.as_raw_fd(), cmd, &flock) }) {
+            Ok(_) => Ok(true),
+            Err(e) if matches!(e.raw_os_error(), Some(libc::EAGAIN | libc::EACCES)) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    #[cfg(not(any(
+        target_os = "android",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "fuchsia",
+        target_os = "illumos",
+        target_os = "ios",
+        target_os = "linux",
+        target_os = "macos",
+        target_os = "netbsd",
+        target_os = "openbsd",
+        target_os = "solaris",
+        target_os = "watchos",
+    )))]
+    fn flock(&self, _lock: FileLock, _block: bool) -> io::Result<bool> {
+        Err(io::const_io_error!(
+            io::ErrorKind::Unsupported,
+            "file locking is not supported on this platform",
+        ))
+    }
+}
+
+#[derive(Clone, Copy)]
+enum FileLock {
+    Exclusive,
+    Shared,
+    Unlocked,
 }
 
 impl DirBuilder {
@@ -536,6 +536,57 @@ pub fn set_permissions(&self, perm: FilePermissions) -> io::Result<()> {
         })?;
         Ok(())
     }
+
+    pub fn lock(&self) -> io::Result<()> {
+        self.lock_file(c::LOCKFILE_EXCLUSIVE_LOCK).map(drop)
+    }
+
+    pub fn lock_shared(&self) -> io::Result<()> {
+        self.lock_file(0).map(drop)
+    }
+
+    pub fn try_lock(&self) -> io::Result<bool> {
+        self.lock_file(c::LOCKFILE_EXCLUSIVE_LOCK | c::LOCKFILE_FAIL_IMMEDIATELY)
+    }
+
+    pub fn try_lock_shared(&self) -> io::Result<bool> {
+        self.lock_file(c::LOCKFILE_FAIL_IMMEDIATELY)
+    }
+
+    pub fn unlock(&self) -> io::Result<()> {
+        // Unlocking a file that isn't locked is not an error on other platforms.
+        let result = cvt(unsafe {
+            c::UnlockFile(self.handle.as_raw_handle(), 0, 0, c::DWORD::MAX, c::DWORD::MAX)
+        });
+        match result {
+            Err(e) if e.raw_os_error() == Some(c::ERROR_NOT_LOCKED as i32) => Ok(()),
+            result => result.map(drop),
+        }
+    }
+
+    /// Locks the whole file, returning `false` if it is locked by someone else
+    /// and `LOCKFILE_FAIL_IMMEDIATELY` is set.
+    fn lock_file(&self, flags: c::DWORD) -> io::Result<bool> {
+        // The lock starts at the offset in the `OVERLAPPED` structure, so the
+        // zeroed one together with the maximum length covers the whole file.
+        let mut overlapped: c::OVERLAPPED = unsafe { mem::zeroed() };
+        let result = cvt(unsafe {
+            c::LockFileEx(
+                self.handle.as_raw_handle(),
+                flags,
+                0,
+                c::DWORD::MAX,
+                c::DWORD::MAX,
+                &mut overlapped,
+            )
+        });
+        match result {
+            Ok(_) => Ok(true),
+            Err(e) if e.raw_os_error() == Some(c::ERROR_LOCK_VIOLATION as i32) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
     /// Get only basic file information such as attributes and file times.
     fn basic_info(&self) -> io::Result<c::FILE_BASIC_INFO> {
         unsafe {
@@ -53,6 +53,34 @@
     }
 }
 
+// The `IPPROTO_TCP` options setting the idle time, interval and number of
+// keepalive probes. Elsewhere, keepalive can only be switched on and off.
+cfg_if::cfg_if! {
+    if #[cfg(any(target_os = "ios", target_os = "macos", target_os = "watchos"))] {
+        const TCP_KEEPALIVE_OPTIONS: Option<(c_int, c_int, c_int)> =
+            Some((libc::TCP_KEEPALIVE, libc::TCP_KEEPINTVL, libc::TCP_KEEPCNT));
+    } else if #[cfg(any(
+        windows,
+        target_os = "android", target_os = "dragonfly", target_os = "freebsd",
+        target_os = "fuchsia", target_os = "linux", target_os = "netbsd"))] {
+        const TCP_KEEPALIVE_OPTIONS: Option<(c_int, c_int, c_int)> =
+            Some((c::TCP_KEEPIDLE, c::TCP_KEEPINTVL, c::TCP_KEEPCNT));
+    } else {
+        const TCP_KEEPALIVE_OPTIONS: Option<(c_int, c_int, c_int)> = None;
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(any(
+        target_os = "android", target_os = "dragonfly", target_os = "freebsd",
+        target_os = "ios", target_os = "linux", target_os = "macos",
+        target_os = "netbsd", target_os = "openbsd", target_os = "watchos"))] {
+        const SO_REUSEPORT: Option<c_int> = Some(libc::SO_REUSEPORT);
+    } else {
+        const SO_REUSEPORT: Option<c_int> = None;
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // sockaddr and misc bindings
 ////////////////////////////////////////////////////////////////////////////////
@@ -90,6 +118,58 @@ pub fn getsockopt<T: Copy>(sock: &Socket, level: c_int, option_name: c_int) -> i
     }
 }
 
+fn tcp_keepalive_options() -> io::Result<(c_int, c_int, c_int)> {
+    TCP_KEEPALIVE_OPTIONS.ok_or(io::const_io_error!(
+        ErrorKind::Unsupported,
+        "tuning TCP keepalive is not supported on this platform",
+    ))
+}
+
+/// Converts a keepalive time or interval to whole seconds, rounding up.
+fn keepalive_secs(dur: Duration) -> io::Result<c_int> {
+    if dur == Duration::ZERO {
+        return Err(io::const_io_error!(
+            ErrorKind::InvalidInput,
+            "cannot set a 0 duration keepalive time or interval",
+        ));
+    }
+    let secs = dur.as_secs().saturating_add((dur.subsec_nanos() > 0) as u64);
+    Ok(cmp::min(secs, c_int::MAX as u64) as c_int)
+}
+
+fn set_buffer_size(sock: &Socket, option_name: c_int, size: usize) -> io::Result<()> {
+    let size = cmp::min(size, c_int::MAX as usize) as c_int;
+    setsockopt(sock, c::SOL_SOCKET, option_name, size)
+}
+
+fn buffer_size(sock: &Socket, option_name: c_int) -> io::Result<usize> {
+    let raw: c_int = getsockopt(sock, c::SOL_SOCKET, option_name)?;
+    Ok(raw as usize)
+}
+
+fn set_reuse_port(sock: &Socket, reuse_port: bool) -> io::Result<()> {
+    match SO_REUSEPORT {
+        Some(option_name) => setsockopt(sock, c::SOL_SOCKET, option_name, reuse_port as c_int),
+        None => Err(io::const_io_error!(
+            ErrorKind::Unsupported,
+            "SO_REUSEPORT is not supported on this platform",
+        )),
+    }
+}
+
+fn reuse_port(sock: &Socket) -> io::Result<bool> {
+    match SO_REUSEPORT {
+        Some(option_name) => {
+            let raw: c_int = getsockopt(sock, c::SOL_SOCKET, option_name)?;
+            Ok(raw != 0)
+        }
+        None => Err(io::const_io_error!(
+            ErrorKind::Unsupported,
+            "SO_REUSEPORT is not supported on this platform",
+        )),
+    }
+}
+
 fn sockname<F>(f: F) -> io::Result<SocketAddr>
 where
     F: FnOnce(*mut c::sockaddr, *mut c::socklen_t) -> c_int,
@@ -327,6 +407,65 @@ pub fn nodelay(&self) -> io::Result<bool> {
         self.inner.nodelay()
     }
 
+    pub fn set_keepalive(&self, keepalive: bool) -> io::Result<()> {
+        setsockopt(&self.inner, c::SOL_SOCKET, c::SO_KEEPALIVE, keepalive as c_int)
+    }
+
+    pub fn keepalive(&self) -> io::Result<bool> {
+        let raw: c_int = getsockopt(&self.inner, c::SOL_SOCKET, c::SO_KEEPALIVE)?;
+        Ok(raw != 0)
+    }
+
+    pub fn set_keepalive_time(&self, time: Duration) -> io::Result<()> {
+        let (option_name, _, _) = tcp_keepalive_options()?;
+        setsockopt(&self.inner, c::IPPROTO_TCP, option_name, keepalive_secs(time)?)
+    }
+
+    pub fn keepalive_time(&self) -> io::Result<Duration> {
+        let (option_name, _, _) = tcp_keepalive_options()?;
+        let raw: c_int = getsockopt(&self.inner, c::IPPROTO_TCP, option_name)?;
+        Ok(Duration::from_secs(raw as u64))
+    }
+
+    pub fn set_keepalive_interval(&self, interval: Duration) -> io::Result<()> {
+        let (_, option_name, _) = tcp_keepalive_options()?;
+        setsockopt(&self.inner, c::IPPROTO_TCP, option_name, keepalive_secs(interval)?)
+    }
+
+    pub fn keepalive_interval(&self) -> io::Result<Duration> {
+        let (_, option_name, _) = tcp_keepalive_options()?;
+        let raw: c_int = getsockopt(&self.inner, c::IPPROTO_TCP, option_name)?;
+        Ok(Duration::from_secs(raw as u64))
+    }
+
+    pub fn set_keepalive_retries(&self, retries: u32) -> io::Result<()> {
+        let (_, _, option_name) = tcp_keepalive_options()?;
+        let retries = cmp::min(retries, c_int::MAX as u32) as c_int;
+        setsockopt(&self.inner, c::IPPROTO_TCP, option_name, retries)
+    }
+
+    pub fn keepalive_retries(&self) -> io::Result<u32> {
+        let (_, _, option_name) = tcp_keepalive_options()?;
+        let raw: c_int = getsockopt(&self.inner, c::IPPROTO_TCP, option_name)?;
+        Ok(raw as u32)
+    }
+
+    pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        set_buffer_size(&self.inner, c::SO_SNDBUF, size)
+    }
+
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        buffer_size(&self.inner, c::SO_SNDBUF)
+    }
+
+    pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        set_buffer_size(&self.inner, c::SO_RCVBUF, size)
+    }
+
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        buffer_size(&self.inner, c::SO_RCVBUF)
+    }
+
     pub fn set_ttl(&self, ttl: u32) -> io::Result<()> {
         setsockopt(&self.inner, c::IPPROTO_IP, c::IP_TTL, ttl as c_int)
     }
@@ -378,6 +517,17 @@ pub struct TcpListener {
 
 impl TcpListener {
     pub fn bind(addr: io::Result<&SocketAddr>) -> io::Result<TcpListener> {
+        Self::bind_with_reuse_port(addr, false)
+    }
+
+    pub fn bind_reuse_port(addr: io::Result<&SocketAddr>) -> io::Result<TcpListener> {
+        Self::bind_with_reuse_port(addr, true)
+    }
+
+    fn bind_with_reuse_port(
+        addr: io::Result<&SocketAddr>,
+        reuse_port: bool,
+    ) -> io::Result<TcpListener> {
         let addr = addr?;
 
         init();
@@ -394,6 +544,12 @@ pub fn bind(addr: io::Result<&SocketAddr>) -> io::Result<TcpListener> {
         #[cfg(not(windows))]
         setsockopt(&sock, c::SOL_SOCKET, c::SO_REUSEADDR, 1 as c_int)?;
 
+        // SO_REUSEPORT only lets sockets share a port if it was set on all of
+        // them before they were bound.
+        if reuse_port {
+            set_reuse_port(&sock, true)?;
+        }
+
         // Bind our new socket
         let (addrp, len) = addr.into_inner();
         cvt(unsafe { c::bind(sock.as_raw(), addrp, len as _) })?;
@@ -457,6 +613,26 @@ pub fn only_v6(&self) -> io::Result<bool> {
         Ok(raw != 0)
     }
 
+    pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        set_buffer_size(&self.inner, c::SO_SNDBUF, size)
+    }
+
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        buffer_size(&self.inner, c::SO_SNDBUF)
+    }
+
+    pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        set_buffer_size(&self.inner, c::SO_RCVBUF, size)
+    }
+
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        buffer_size(&self.inner, c::SO_RCVBUF)
+    }
+
+    pub fn reuse_port(&self) -> io::Result<bool> {
+        reuse_port(&self.inner)
+    }
+
     pub fn take_error(&self) -> io::Result<Option<io::Error>> {
         self.inner.take_error()
     }
@@ -495,11 +671,25 @@ pub struct UdpSocket {
 
 impl UdpSocket {
     pub fn bind(addr: io::Result<&SocketAddr>) -> io::Result<UdpSocket> {
+        Self::bind_with_reuse_port(addr, false)
+    }
+
+    pub fn bind_reuse_port(addr: io::Result<&SocketAddr>) -> io::Result<UdpSocket> {
+        Self::bind_with_reuse_port(addr, true)
+    }
+
+    fn bind_with_reuse_port(
+        addr: io::Result<&SocketAddr>,
+        reuse_port: bool,
+    ) -> io::Result<UdpSocket> {
         let addr = addr?;
 
         init();
 
         let sock = Socket::new(addr, c::SOCK_DGRAM)?;
+        if reuse_port {
+            set_reuse_port(&sock, true)?;
+        }
         let (addrp, len) = addr.into_inner();
         cvt(unsafe { c::bind(sock.as_raw(), addrp, len as _) })?;
         Ok(UdpSocket { inner: sock })
@@ -574,6 +764,26 @@ pub fn broadcast(&self) -> io::Result<bool> {
         Ok(raw != 0)
     }
 
+    pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        set_buffer_size(&self.inner, c::SO_SNDBUF, size)
+    }
+
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        buffer_size(&self.inner, c::SO_SNDBUF)
+    }
+
+    pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        set_buffer_size(&self.inner, c::SO_RCVBUF, size)
+    }
+
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        buffer_size(&self.inner, c::SO_RCVBUF)
+    }
+
+    pub fn reuse_port(&self) -> io::Result<bool> {
+        reuse_port(&self.inner)
+    }
+
     pub fn set_multicast_loop_v4(&self, multicast_loop_v4: bool) -> io::Result<()> {
         setsockopt(
             &self.inner,
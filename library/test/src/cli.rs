@@ -2,6 +2,7 @@
 
 use std::env;
 use std::path::PathBuf;
+use std::time::Duration;
 
 use super::helpers::isatty;
 use super::options::{ColorConfig, Options, OutputFormat, RunIgnored};
@@ -26,6 +27,14 @@ pub struct TestOpts {
     pub test_threads: Option<usize>,
     pub skip: Vec<String>,
     pub time_options: Option<TestTimeOptions>,
+    /// Tests running longer than this are stopped and reported as failed.
+    pub test_timeout: Option<Duration>,
+    /// Runs every test in its own process.
+    pub isolate: bool,
+    /// The number of shards the tests are split into.
+    pub shard_count: Option<usize>,
+    /// Runs only the tests of this shard, counting from 0.
+    pub shard_index: Option<usize>,
     pub options: Options,
 }
 
@@ -98,8 +107,10 @@ fn optgroups() -> getopts::Options {
             pretty = Print verbose output;
             terse  = Display one character per test;
             json   = Output a json document;
-            junit  = Output a JUnit document",
-            "pretty|terse|json|junit",
+            junit  = Output a JUnit document;
+            tap    = Output a TAP 14 stream;
+            github = Annotate failures for GitHub Actions",
+            "pretty|terse|json|junit|tap|github",
         )
         .optflag("", "show-output", "Show captured stdout of successful tests")
         .optopt(
@@ -138,6 +149,32 @@ fn optgroups() -> getopts::Options {
             `CRITICAL_TIME` here means the limit that should not be exceeded by test.
             ",
         )
+        .optopt(
+            "",
+            "test-timeout",
+            "Fail tests that run for longer than DURATION, e.g. `90s`, `500ms` or `2m`.
+            A plain number is a number of seconds. Tests run in a separate
+            process (see --isolate) are killed, other tests are abandoned.",
+            "DURATION",
+        )
+        .optflag(
+            "",
+            "isolate",
+            "Run each test in its own process, so that crashes, aborts and
+            timeouts only fail that test",
+        )
+        .optopt(
+            "",
+            "shard-count",
+            "Split the tests into COUNT shards, by hashing their names",
+            "COUNT",
+        )
+        .optopt(
+            "",
+            "shard-index",
+            "Run only the tests of shard INDEX (0-based); requires --shard-count",
+            "INDEX",
+        )
         .optflag("", "shuffle", "Run tests in random order")
         .optopt(
             "",
@@ -257,6 +294,9 @@ fn parse_opts_impl(matches: getopts::Matches) -> OptRes {
     let time_options = get_time_options(&matches, allow_unstable)?;
     let shuffle = get_shuffle(&matches, allow_unstable)?;
     let shuffle_seed = get_shuffle_seed(&matches, allow_unstable)?;
+    let test_timeout = get_test_timeout(&matches, allow_unstable)?;
+    let isolate = unstable_optflag!(matches, allow_unstable, "isolate");
+    let (shard_count, shard_index) = get_shard(&matches, allow_unstable)?;
 
     let include_ignored = matches.opt_present("include-ignored");
     let quiet = matches.opt_present("quiet");
@@ -295,6 +335,10 @@ fn parse_opts_impl(matches: getopts::Matches) -> OptRes {
         test_threads,
         skip,
         time_options,
+        test_timeout,
+        isolate,
+        shard_count,
+        shard_index,
         options,
     };
 
@@ -370,6 +414,71 @@ fn get_shuffle_seed(matches: &getopts::Matches, allow_unstable: bool) -> OptPart
     Ok(shuffle_seed)
 }
 
+fn get_test_timeout(
+    matches: &getopts::Matches,
+    allow_unstable: bool,
+) -> OptPartRes<Option<Duration>> {
+    let timeout = match unstable_optopt!(matches, allow_unstable, "test-timeout") {
+        Some(timeout) => timeout,
+        None => return Ok(None),
+    };
+
+    let (number, unit) = match timeout.find(|c: char| !c.is_ascii_digit() && c != '.') {
+        Some(i) => timeout.split_at(i),
+        None => (&*timeout, "s"),
+    };
+    let secs = match (number.parse::<f64>(), unit) {
+        (Ok(n), "ms") => n / 1000.0,
+        (Ok(n), "s") => n,
+        (Ok(n), "m") => n * 60.0,
+        _ => {
+            return Err(format!(
+                "argument for --test-timeout must be a duration such as `90s`, `500ms` \
+                 or `2m` (was {})",
+                timeout
+            ));
+        }
+    };
+    if secs <= 0.0 || !secs.is_finite() {
+        return Err(format!("argument for --test-timeout must be positive (was {})", timeout));
+    }
+    // `Duration::from_secs_f64` panics on values that don't fit in a `Duration`.
+    if secs >= u64::MAX as f64 {
+        return Err(format!("argument for --test-timeout is too large (was {})", timeout));
+    }
+
+    Ok(Some(Duration::from_secs_f64(secs)))
+}
+
+fn get_shard(
+    matches: &getopts::Matches,
+    allow_unstable: bool,
+) -> OptPartRes<(Option<usize>, Option<usize>)> {
+    let parse = |name: &str, value: Option<String>| -> OptPartRes<Option<usize>> {
+        value
+            .map(|n_str| {
+                n_str.parse::<usize>().map_err(|e| {
+                    format!("argument for --{} must be a number (error: {})", name, e)
+                })
+            })
+            .transpose()
+    };
+    let shard_count =
+        parse("shard-count", unstable_optopt!(matches, allow_unstable, "shard-count"))?;
+    let shard_index =
+        parse("shard-index", unstable_optopt!(matches, allow_unstable, "shard-index"))?;
+
+    match (shard_count, shard_index) {
+        (Some(0), _) => Err("argument for --shard-count must not be 0".to_string()),
+        (None, Some(_)) => Err("--shard-index requires --shard-count".to_string()),
+        (Some(count), Some(index)) if index >= count => Err(format!(
+            "argument for --shard-index must be less than --shard-count ({} >= {})",
+            index, count
+        )),
+        shard => Ok(shard),
+    }
+}
+
 fn get_test_threads(matches: &getopts::Matches) -> OptPartRes<Option<usize>> {
     let test_threads = match matches.opt_str("test-threads") {
         Some(n_str) => match n_str.parse::<usize>() {
@@ -410,9 +519,23 @@ fn get_format(
             }
             OutputFormat::Junit
         }
+        Some("tap") => {
+            if !allow_unstable {
+                return Err("The \"tap\" format is only accepted on the nightly compiler".into());
+            }
+            OutputFormat::Tap
+        }
+        Some("github") => {
+            if !allow_unstable {
+                return Err(
+                    "The \"github\" format is only accepted on the nightly compiler".into()
+                );
+            }
+            OutputFormat::Github
+        }
         Some(v) => {
             return Err(format!(
-                "argument for --format must be pretty, terse, json or junit (was \
+                "argument for --format must be pretty, terse, json, junit, tap or github (was \
                  {})",
                 v
             ));
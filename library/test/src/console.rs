@@ -10,8 +10,11 @@
     cli::TestOpts,
     event::{CompletedTest, TestEvent},
     filter_tests,
-    formatters::{JsonFormatter, JunitFormatter, OutputFormatter, PrettyFormatter, TerseFormatter},
-    helpers::{concurrency::get_concurrency, metrics::MetricMap},
+    formatters::{
+        GithubFormatter, JsonFormatter, JunitFormatter, OutputFormatter, PrettyFormatter,
+        TapFormatter, TerseFormatter,
+    },
+    helpers::{concurrency::get_concurrency, metrics::MetricMap, shard::get_shard},
     options::{Options, OutputFormat},
     run_tests, term,
     test_result::TestResult,
@@ -141,6 +144,12 @@ pub fn list_tests_console(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> io::Res
         Some(t) => OutputLocation::Pretty(t),
     };
 
+    if opts.format == OutputFormat::Json {
+        let json = list_tests_json(opts, tests)?;
+        output.write_all(&json)?;
+        return ConsoleTestState::new(opts)?.write_log(|| String::from_utf8_lossy(&json));
+    }
+
     let quiet = opts.format == OutputFormat::Terse;
     let mut st = ConsoleTestState::new(opts)?;
 
@@ -185,6 +194,34 @@ fn plural(count: u32, s: &str) -> String {
     Ok(())
 }
 
+// Lists the tests as JSON events, which are written both to the console and to the logfile.
+pub(crate) fn list_tests_json(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> io::Result<Vec<u8>> {
+    use crate::TestFn::*;
+
+    let mut json = Vec::new();
+    let mut out = JsonFormatter::new(OutputLocation::Raw(&mut json));
+    let mut ntest = 0;
+    let mut nbench = 0;
+
+    for test in filter_tests(opts, tests) {
+        let ty = match test.testfn {
+            StaticTestFn(..) | DynTestFn(..) => {
+                ntest += 1;
+                "test"
+            }
+            StaticBenchFn(..) | DynBenchFn(..) => {
+                nbench += 1;
+                "bench"
+            }
+        };
+        let shard = opts.shard_count.map(|count| get_shard(&test.desc.name, count));
+        out.write_discovered(ty, &test.desc, shard)?;
+    }
+
+    out.write_discovery_finish(ntest, nbench)?;
+    Ok(json)
+}
+
 // Updates `ConsoleTestState` depending on result of the test execution.
 fn handle_test_result(st: &mut ConsoleTestState, completed_test: CompletedTest) {
     let test = completed_test.desc;
@@ -281,6 +318,8 @@ pub fn run_tests_console(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> io::Resu
         }
         OutputFormat::Json => Box::new(JsonFormatter::new(output)),
         OutputFormat::Junit => Box::new(JunitFormatter::new(output)),
+        OutputFormat::Tap => Box::new(TapFormatter::new(output)),
+        OutputFormat::Github => Box::new(GithubFormatter::new(output)),
     };
     let mut st = ConsoleTestState::new(opts)?;
 
@@ -0,0 +1,186 @@
+use std::io::{self, prelude::Write};
+
+use super::OutputFormatter;
+use crate::{
+    console::{ConsoleTestState, OutputLocation},
+    test_result::TestResult,
+    time,
+    types::TestDesc,
+};
+
+/// Writes the results as plain test lines, with every failure also reported as a GitHub
+/// Actions [workflow command] so that it shows up as an annotation on the source line of the
+/// panic. The captured output of failed tests is written in a collapsed group.
+///
+/// [workflow command]: https://docs.github.com/en/actions/using-workflows/workflow-commands-for-github-actions
+pub(crate) struct GithubFormatter<T> {
+    out: OutputLocation<T>,
+}
+
+impl<T: Write> GithubFormatter<T> {
+    pub fn new(out: OutputLocation<T>) -> Self {
+        Self { out }
+    }
+
+    #[cfg(test)]
+    pub fn output_location(&self) -> &OutputLocation<T> {
+        &self.out
+    }
+
+    fn writeln_message(&mut self, s: &str) -> io::Result<()> {
+        self.out.write_all(s.as_bytes())?;
+        self.out.write_all(b"\n")
+    }
+
+    fn write_failure(
+        &mut self,
+        desc: &TestDesc,
+        message: Option<&str>,
+        stdout: &[u8],
+    ) -> io::Result<()> {
+        let stdout = String::from_utf8_lossy(stdout);
+        let panic = parse_panic(&stdout);
+
+        let mut properties = String::new();
+        if let Some(location) = panic.as_ref().map(|panic| &panic.location) {
+            properties.push_str(&format!(
+                "file={},line={},col={},",
+                escape_property(location.file),
+                location.line,
+                location.col
+            ));
+        }
+        properties.push_str(&format!("title={}", escape_property(desc.name.as_slice())));
+
+        let message = message
+            .or_else(|| panic.as_ref().map(|panic| panic.message))
+            .unwrap_or("test failed");
+        self.writeln_message(&format!("::error {}::{}", properties, escape_data(message)))?;
+
+        if !stdout.is_empty() {
+            self.writeln_message(&format!("::group::{} output", desc.name))?;
+            self.out.write_all(stdout.as_bytes())?;
+            if !stdout.ends_with('\n') {
+                self.out.write_all(b"\n")?;
+            }
+            self.writeln_message("::endgroup::")?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Write> OutputFormatter for GithubFormatter<T> {
+    fn write_run_start(&mut self, test_count: usize, shuffle_seed: Option<u64>) -> io::Result<()> {
+        let noun = if test_count != 1 { "tests" } else { "test" };
+        let shuffle_seed_msg = if let Some(shuffle_seed) = shuffle_seed {
+            format!(" (shuffle seed: {})", shuffle_seed)
+        } else {
+            String::new()
+        };
+        self.writeln_message(&format!("running {} {}{}", test_count, noun, shuffle_seed_msg))
+    }
+
+    fn write_test_start(&mut self, _desc: &TestDesc) -> io::Result<()> {
+        // Tests are reported once the result is known, so that parallel runs stay readable.
+        Ok(())
+    }
+
+    fn write_timeout(&mut self, desc: &TestDesc) -> io::Result<()> {
+        self.writeln_message(&format!(
+            "::warning title={}::test has been running for over {} seconds",
+            escape_property(desc.name.as_slice()),
+            time::TEST_WARN_TIMEOUT_S
+        ))
+    }
+
+    fn write_result(
+        &mut self,
+        desc: &TestDesc,
+        result: &TestResult,
+        _exec_time: Option<&time::TestExecTime>,
+        stdout: &[u8],
+        _state: &ConsoleTestState,
+    ) -> io::Result<()> {
+        match *result {
+            TestResult::TrOk => self.writeln_message(&format!("test {} ... ok", desc.name)),
+            TestResult::TrIgnored => {
+                self.writeln_message(&format!("test {} ... ignored", desc.name))
+            }
+            TestResult::TrBench(ref bs) => self.writeln_message(&format!(
+                "test {} ... bench: {}",
+                desc.name,
+                crate::bench::fmt_bench_samples(bs)
+            )),
+            TestResult::TrFailed => {
+                self.writeln_message(&format!("test {} ... FAILED", desc.name))?;
+                self.write_failure(desc, None, stdout)
+            }
+            TestResult::TrFailedMsg(ref m) => {
+                self.writeln_message(&format!("test {} ... FAILED", desc.name))?;
+                self.write_failure(desc, Some(m), stdout)
+            }
+            TestResult::TrTimedFail => {
+                let line = format!("test {} ... FAILED (time limit exceeded)", desc.name);
+                self.writeln_message(&line)?;
+                self.write_failure(desc, Some("time limit exceeded"), stdout)
+            }
+        }
+    }
+
+    fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
+        let success = state.failed == 0;
+        let mut summary = format!(
+            "test result: {}. {} passed; {} failed; {} ignored; {} measured; {} filtered out",
+            if success { "ok" } else { "FAILED" },
+            state.passed,
+            state.failed,
+            state.ignored,
+            state.measured,
+            state.filtered_out
+        );
+        if let Some(ref exec_time) = state.exec_time {
+            summary.push_str(&format!("; finished in {}", exec_time));
+        }
+        self.writeln_message(&summary)?;
+
+        Ok(success)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct Location<'a> {
+    pub file: &'a str,
+    pub line: u32,
+    pub col: u32,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct Panic<'a> {
+    pub message: &'a str,
+    pub location: Location<'a>,
+}
+
+/// Finds the first panic in the captured output of a test, as printed by the default panic
+/// hook: `thread '<name>' panicked at '<message>', <file>:<line>:<col>`. The message may span
+/// several lines.
+pub(crate) fn parse_panic(output: &str) -> Option<Panic<'_>> {
+    let (_, rest) = output.split_once(" panicked at '")?;
+    rest.match_indices("', ").find_map(|(end, _)| {
+        let location = rest[end + 3..].lines().next()?;
+        let mut parts = location.rsplitn(3, ':');
+        let col = parts.next()?.parse().ok()?;
+        let line = parts.next()?.parse().ok()?;
+        let file = parts.next()?;
+        Some(Panic { message: &rest[..end], location: Location { file, line, col } })
+    })
+}
+
+/// Escapes the message of a workflow command.
+fn escape_data(s: &str) -> String {
+    s.replace('%', "%25").replace('\r', "%0D").replace('\n', "%0A")
+}
+
+/// Escapes a property value of a workflow command.
+fn escape_property(s: &str) -> String {
+    escape_data(s).replace(':', "%3A").replace(',', "%2C")
+}
@@ -57,6 +57,25 @@ fn write_event(
         }
         self.writeln_message(" }")
     }
+
+    /// Writes a test found by `--list`, along with the shard it belongs to when
+    /// `--shard-count` is given.
+    pub fn write_discovered(
+        &mut self,
+        ty: &str,
+        desc: &TestDesc,
+        shard: Option<usize>,
+    ) -> io::Result<()> {
+        let shard = shard.map(|shard| format!(r#""shard": {}"#, shard));
+        self.write_event(ty, desc.name.as_slice(), "discovered", None, None, shard.as_deref())
+    }
+
+    pub fn write_discovery_finish(&mut self, tests: u32, benchmarks: u32) -> io::Result<()> {
+        self.writeln_message(&*format!(
+            r#"{{ "type": "suite", "event": "discovered", "test_count": {}, "bench_count": {} }}"#,
+            tests, benchmarks
+        ))
+    }
 }
 
 impl<T: Write> OutputFormatter for JsonFormatter<T> {
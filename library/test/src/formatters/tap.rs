@@ -0,0 +1,191 @@
+use std::io::{self, prelude::Write};
+
+use super::OutputFormatter;
+use crate::{
+    console::{ConsoleTestState, OutputLocation},
+    test_result::TestResult,
+    time,
+    types::TestDesc,
+};
+
+/// Writes the results as a [TAP 14](https://testanything.org/tap-version-14-specification.html)
+/// stream. Failures carry a YAML diagnostic block with the failure message and the captured
+/// output, and benchmarks are written as subtests with one test point per measurement.
+pub(crate) struct TapFormatter<T> {
+    out: OutputLocation<T>,
+    test_number: usize,
+}
+
+impl<T: Write> TapFormatter<T> {
+    pub fn new(out: OutputLocation<T>) -> Self {
+        Self { out, test_number: 0 }
+    }
+
+    #[cfg(test)]
+    pub fn output_location(&self) -> &OutputLocation<T> {
+        &self.out
+    }
+
+    fn writeln_message(&mut self, s: &str) -> io::Result<()> {
+        self.out.write_all(s.as_bytes())?;
+        self.out.write_all(b"\n")
+    }
+
+    fn write_test_point(&mut self, ok: bool, desc: &TestDesc, directive: &str) -> io::Result<()> {
+        self.test_number += 1;
+        self.writeln_message(&format!(
+            "{}ok {} - {}{}",
+            if ok { "" } else { "not " },
+            self.test_number,
+            EscapedDescription(desc.name.as_slice()),
+            directive
+        ))
+    }
+
+    /// Writes the YAML diagnostic block that follows a test point.
+    fn write_diagnostics(
+        &mut self,
+        message: Option<&str>,
+        exec_time: Option<&time::TestExecTime>,
+        stdout: &[u8],
+    ) -> io::Result<()> {
+        self.writeln_message("  ---")?;
+        if let Some(message) = message {
+            self.write_yaml_string("message", message)?;
+        }
+        if let Some(exec_time) = exec_time {
+            let duration_ms = exec_time.0.as_secs_f64() * 1000.0;
+            self.writeln_message(&format!("  duration_ms: {}", duration_ms))?;
+        }
+        if !stdout.is_empty() {
+            self.write_yaml_string("output", &String::from_utf8_lossy(stdout))?;
+        }
+        self.writeln_message("  ...")
+    }
+
+    /// Writes `key: value`, as a literal block scalar so that the value never needs escaping.
+    fn write_yaml_string(&mut self, key: &str, value: &str) -> io::Result<()> {
+        self.writeln_message(&format!("  {}: |-", key))?;
+        for line in value.lines() {
+            self.writeln_message(&format!("    {}", line))?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Write> OutputFormatter for TapFormatter<T> {
+    fn write_run_start(&mut self, test_count: usize, shuffle_seed: Option<u64>) -> io::Result<()> {
+        self.writeln_message("TAP version 14")?;
+        if let Some(shuffle_seed) = shuffle_seed {
+            self.writeln_message(&format!("# shuffle seed: {}", shuffle_seed))?;
+        }
+        self.writeln_message(&format!("1..{}", test_count))
+    }
+
+    fn write_test_start(&mut self, _desc: &TestDesc) -> io::Result<()> {
+        // Test points are written once the result is known.
+        Ok(())
+    }
+
+    fn write_timeout(&mut self, desc: &TestDesc) -> io::Result<()> {
+        self.writeln_message(&format!(
+            "# {} has been running for over {} seconds",
+            EscapedDescription(desc.name.as_slice()),
+            time::TEST_WARN_TIMEOUT_S
+        ))
+    }
+
+    fn write_result(
+        &mut self,
+        desc: &TestDesc,
+        result: &TestResult,
+        exec_time: Option<&time::TestExecTime>,
+        stdout: &[u8],
+        state: &ConsoleTestState,
+    ) -> io::Result<()> {
+        match *result {
+            TestResult::TrOk => {
+                self.write_test_point(true, desc, "")?;
+                if state.options.display_output && !stdout.is_empty() {
+                    self.write_diagnostics(None, exec_time, stdout)?;
+                }
+                Ok(())
+            }
+
+            TestResult::TrFailed => {
+                self.write_test_point(false, desc, "")?;
+                self.write_diagnostics(None, exec_time, stdout)
+            }
+
+            TestResult::TrFailedMsg(ref m) => {
+                self.write_test_point(false, desc, "")?;
+                self.write_diagnostics(Some(m), exec_time, stdout)
+            }
+
+            TestResult::TrTimedFail => {
+                self.write_test_point(false, desc, "")?;
+                self.write_diagnostics(Some("time limit exceeded"), exec_time, stdout)
+            }
+
+            TestResult::TrIgnored => {
+                let directive = match desc.ignore_message {
+                    Some(msg) => format!(" # SKIP {}", EscapedDescription(msg)),
+                    None => " # SKIP".to_owned(),
+                };
+                self.write_test_point(true, desc, &directive)
+            }
+
+            TestResult::TrBench(ref bs) => {
+                let median = bs.ns_iter_summ.median as usize;
+                let deviation = (bs.ns_iter_summ.max - bs.ns_iter_summ.min) as usize;
+
+                let mut measurements = vec![
+                    format!("median: {} ns/iter", median),
+                    format!("deviation: {} ns/iter", deviation),
+                ];
+                if bs.mb_s != 0 {
+                    measurements.push(format!("throughput: {} MB/s", bs.mb_s));
+                }
+
+                self.writeln_message(&format!(
+                    "# Subtest: {}",
+                    EscapedDescription(desc.name.as_slice())
+                ))?;
+                self.writeln_message(&format!("    1..{}", measurements.len()))?;
+                for (i, measurement) in measurements.iter().enumerate() {
+                    self.writeln_message(&format!("    ok {} - {}", i + 1, measurement))?;
+                }
+                self.write_test_point(true, desc, "")
+            }
+        }
+    }
+
+    fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
+        self.writeln_message(&format!(
+            "# {} passed; {} failed; {} ignored; {} measured; {} filtered out",
+            state.passed, state.failed, state.ignored, state.measured, state.filtered_out
+        ))?;
+        if let Some(ref exec_time) = state.exec_time {
+            self.writeln_message(&format!("# finished in {}", exec_time))?;
+        }
+
+        Ok(state.failed == 0)
+    }
+}
+
+/// A test point description, with the characters TAP gives a meaning to escaped.
+struct EscapedDescription<'a>(&'a str);
+
+impl std::fmt::Display for EscapedDescription<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for c in self.0.chars() {
+            match c {
+                '\\' => f.write_str("\\\\")?,
+                '#' => f.write_str("\\#")?,
+                '\n' => f.write_str(" ")?,
+                c => write!(f, "{}", c)?,
+            }
+        }
+        Ok(())
+    }
+}
@@ -5,4 +5,5 @@
 pub mod exit_code;
 pub mod isatty;
 pub mod metrics;
+pub mod shard;
 pub mod shuffle;
@@ -0,0 +1,23 @@
+use crate::cli::TestOpts;
+use crate::types::{TestDescAndFn, TestName};
+
+/// Returns the shard out of `shard_count` that the test belongs to.
+///
+/// The shard only depends on the name of the test, so adding or removing tests
+/// never moves other tests to a different shard. The name is hashed with FNV-1a
+/// rather than `DefaultHasher`, whose output may change between releases.
+pub fn get_shard(name: &TestName, shard_count: usize) -> usize {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in name.as_slice().bytes() {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    (hash % shard_count as u64) as usize
+}
+
+/// Removes the tests that don't belong to the shard selected with `--shard-index`.
+pub fn filter_shard(opts: &TestOpts, tests: &mut Vec<TestDescAndFn>) {
+    if let (Some(shard_index), Some(shard_count)) = (opts.shard_index, opts.shard_count) {
+        tests.retain(|test| get_shard(&test.desc.name, shard_count) == shard_index);
+    }
+}
@@ -53,9 +53,10 @@ pub mod test {
     collections::VecDeque,
     env, io,
     io::prelude::Write,
+    mem,
     panic::{self, catch_unwind, AssertUnwindSafe, PanicInfo},
     process::{self, Command, Termination},
-    sync::mpsc::{channel, Sender},
+    sync::mpsc::{channel, Receiver, Sender},
     sync::{Arc, Mutex},
     thread,
     time::{Duration, Instant},
@@ -127,6 +128,10 @@ pub fn test_main(args: &[String], tests: Vec<TestDescAndFn>, options: Option<Opt
 /// This is the entry point for the main function generated by `rustc --test`
 /// when panic=unwind.
 pub fn test_main_static(tests: &[&TestDescAndFn]) {
+    // If we're being run in SpawnedSecondary mode because of `--isolate`, run
+    // the test here. run_test will then exit the process.
+    run_spawned_test_if_requested(tests, false);
+
     let args = env::args().collect::<Vec<_>>();
     let owned_tests: Vec<_> = tests.iter().map(make_owned_test).collect();
     test_main(&args, owned_tests, None)
@@ -143,6 +148,16 @@ pub fn test_main_static(tests: &[&TestDescAndFn]) {
 pub fn test_main_static_abort(tests: &[&TestDescAndFn]) {
     // If we're being run in SpawnedSecondary mode, run the test here. run_test
     // will then exit the process.
+    run_spawned_test_if_requested(tests, true);
+
+    let args = env::args().collect::<Vec<_>>();
+    let owned_tests: Vec<_> = tests.iter().map(make_owned_test).collect();
+    test_main(&args, owned_tests, Some(Options::new().panic_abort(true)))
+}
+
+/// Runs the test named by `SECONDARY_TEST_INVOKER_VAR` and exits, if the
+/// variable is set because this process was spawned to run a single test.
+fn run_spawned_test_if_requested(tests: &[&TestDescAndFn], panic_abort: bool) {
     if let Ok(name) = env::var(SECONDARY_TEST_INVOKER_VAR) {
         env::remove_var(SECONDARY_TEST_INVOKER_VAR);
         let test = tests
@@ -156,12 +171,8 @@ pub fn test_main_static_abort(tests: &[&TestDescAndFn]) {
             StaticTestFn(f) => f,
             _ => panic!("only static tests are supported"),
         };
-        run_test_in_spawned_subprocess(desc, Box::new(testfn));
+        run_test_in_spawned_subprocess(desc, Box::new(testfn), panic_abort);
     }
-
-    let args = env::args().collect::<Vec<_>>();
-    let owned_tests: Vec<_> = tests.iter().map(make_owned_test).collect();
-    test_main(&args, owned_tests, Some(Options::new().panic_abort(true)))
 }
 
 /// Clones static values for putting into a dynamic vector, which test_main()
@@ -203,6 +214,10 @@ pub fn run_tests<F>(
 
     struct RunningTest {
         join_handle: Option<thread::JoinHandle<()>>,
+        desc: TestDesc,
+        /// When an in-process test is abandoned for running past `--test-timeout`.
+        /// Tests in a subprocess are killed by the thread that spawned them instead.
+        deadline: Option<Instant>,
     }
 
     // Use a deterministic hasher
@@ -259,11 +274,19 @@ struct TimeoutEntry {
     let mut pending = 0;
 
     let (tx, rx) = channel::<CompletedTest>();
-    let run_strategy = if opts.options.panic_abort && !opts.force_run_in_process {
+    let run_strategy = if (opts.options.panic_abort && !opts.force_run_in_process) || opts.isolate {
         RunStrategy::SpawnPrimary
     } else {
         RunStrategy::InProcess
     };
+    // Only static tests can be found again by a spawned process, so dynamic
+    // tests keep running in-process when isolation was merely requested.
+    let strategy_for = |test: &TestDescAndFn| match (run_strategy, &test.testfn) {
+        (RunStrategy::SpawnPrimary, DynTestFn(_)) if !opts.options.panic_abort => {
+            RunStrategy::InProcess
+        }
+        _ => run_strategy,
+    };
 
     let mut running_tests: TestMap = HashMap::default();
     let mut timeout_queue: VecDeque<TimeoutEntry> = VecDeque::new();
@@ -286,20 +309,48 @@ fn get_timed_out_tests(
         timed_out
     }
 
-    fn calc_timeout(timeout_queue: &VecDeque<TimeoutEntry>) -> Option<Duration> {
-        timeout_queue.front().map(|&TimeoutEntry { timeout: next_timeout, .. }| {
-            let now = Instant::now();
-            if next_timeout >= now { next_timeout - now } else { Duration::new(0, 0) }
-        })
+    fn calc_timeout(
+        timeout_queue: &VecDeque<TimeoutEntry>,
+        running_tests: &TestMap,
+    ) -> Option<Duration> {
+        let next_warning = timeout_queue.front().map(|entry| entry.timeout);
+        let next_deadline = running_tests.values().filter_map(|test| test.deadline).min();
+        let next_timeout = match (next_warning, next_deadline) {
+            (Some(warning), Some(deadline)) => warning.min(deadline),
+            (timeout, None) | (None, timeout) => timeout?,
+        };
+        let now = Instant::now();
+        Some(if next_timeout >= now { next_timeout - now } else { Duration::new(0, 0) })
     }
 
-    if concurrency == 1 {
+    fn take_overdue_tests(running_tests: &mut TestMap, timeout: Duration) -> Vec<CompletedTest> {
+        let now = Instant::now();
+        let overdue: Vec<TestId> = running_tests
+            .iter()
+            .filter(|(_, test)| test.deadline.map_or(false, |deadline| deadline <= now))
+            .map(|(&id, _)| id)
+            .collect();
+        overdue
+            .into_iter()
+            .map(|id| {
+                // The thread keeps running, but its result is ignored from now on.
+                let test = running_tests.remove(&id).unwrap();
+                let message = format!("test did not finish within {:?}", timeout);
+                CompletedTest::new(id, test.desc, TrFailedMsg(message), None, Vec::new())
+            })
+            .collect()
+    }
+
+    // In-process tests are only abandoned after `--test-timeout` when they run
+    // on their own thread, so the sequential runner is not used in that case.
+    if concurrency == 1 && opts.test_timeout.is_none() {
         while !remaining.is_empty() {
             let (id, test) = remaining.pop().unwrap();
             let event = TestEvent::TeWait(test.desc.clone());
             notify_about_test_event(event)?;
+            let strategy = strategy_for(&test);
             let join_handle =
-                run_test(opts, !opts.run_tests, id, test, run_strategy, tx.clone(), Concurrent::No);
+                run_test(opts, !opts.run_tests, id, test, strategy, tx.clone(), Concurrent::No);
             assert!(join_handle.is_none());
             let completed_test = rx.recv().unwrap();
 
@@ -315,23 +366,31 @@ fn calc_timeout(timeout_queue: &VecDeque<TimeoutEntry>) -> Option<Duration> {
 
                 let event = TestEvent::TeWait(desc.clone());
                 notify_about_test_event(event)?; //here no pad
+                let strategy = strategy_for(&test);
+                let deadline = match strategy {
+                    RunStrategy::InProcess => {
+                        opts.test_timeout.and_then(|t| Instant::now().checked_add(t))
+                    }
+                    RunStrategy::SpawnPrimary => None,
+                };
                 let join_handle = run_test(
                     opts,
                     !opts.run_tests,
                     id,
                     test,
-                    run_strategy,
+                    strategy,
                     tx.clone(),
                     Concurrent::Yes,
                 );
-                running_tests.insert(id, RunningTest { join_handle });
+                let running_test = RunningTest { join_handle, desc: desc.clone(), deadline };
+                running_tests.insert(id, running_test);
                 timeout_queue.push_back(TimeoutEntry { id, desc, timeout });
                 pending += 1;
             }
 
             let mut res;
             loop {
-                if let Some(timeout) = calc_timeout(&timeout_queue) {
+                if let Some(timeout) = calc_timeout(&timeout_queue, &running_tests) {
                     res = rx.recv_timeout(timeout);
                     for test in get_timed_out_tests(&running_tests, &mut timeout_queue) {
                         let event = TestEvent::TeTimeout(test);
@@ -339,6 +398,9 @@ fn calc_timeout(timeout_queue: &VecDeque<TimeoutEntry>) -> Option<Duration> {
                     }
 
                     match res {
+                        Ok(ref test) if !running_tests.contains_key(&test.id) => {
+                            // A late result of a test that was abandoned, ignore it.
+                        }
                         Err(RecvTimeoutError::Timeout) => {
                             // Result is not yet ready, continue waiting.
                         }
@@ -347,13 +409,32 @@ fn calc_timeout(timeout_queue: &VecDeque<TimeoutEntry>) -> Option<Duration> {
                             break;
                         }
                     }
+
+                    if let Some(timeout) = opts.test_timeout {
+                        let overdue = take_overdue_tests(&mut running_tests, timeout);
+                        if !overdue.is_empty() {
+                            for completed_test in overdue {
+                                let event = TestEvent::TeResult(completed_test);
+                                notify_about_test_event(event)?;
+                                pending -= 1;
+                            }
+                            // Start new tests in place of the abandoned ones.
+                            break;
+                        }
+                    }
                 } else {
                     res = rx.recv().map_err(|_| RecvTimeoutError::Disconnected);
                     break;
                 }
             }
 
-            let mut completed_test = res.unwrap();
+            let mut completed_test = match res {
+                Err(RecvTimeoutError::Timeout) => continue,
+                Ok(ref completed_test) if !running_tests.contains_key(&completed_test.id) => {
+                    continue;
+                }
+                res => res.unwrap(),
+            };
             let running_test = running_tests.remove(&completed_test.id).unwrap();
             if let Some(join_handle) = running_test.join_handle {
                 if let Err(_) = join_handle.join() {
@@ -421,6 +502,9 @@ pub fn filter_tests(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> Vec<TestDescA
         RunIgnored::No => {}
     }
 
+    // Only keep the tests of the selected shard
+    helpers::shard::filter_shard(opts, &mut filtered);
+
     // Sort the tests alphabetically
     filtered.sort_by(|t1, t2| t1.desc.name.as_slice().cmp(t2.desc.name.as_slice()));
 
@@ -473,6 +557,7 @@ struct TestRunOpts {
         pub nocapture: bool,
         pub concurrency: Concurrent,
         pub time: Option<time::TestTimeOptions>,
+        pub timeout: Option<Duration>,
     }
 
     fn run_test_inner(
@@ -502,6 +587,7 @@ fn run_test_inner(
                 opts.time.is_some(),
                 monitor_ch,
                 opts.time,
+                opts.timeout,
             ),
         };
 
@@ -529,8 +615,13 @@ fn run_test_inner(
         }
     }
 
-    let test_run_opts =
-        TestRunOpts { strategy, nocapture: opts.nocapture, concurrency, time: opts.time_options };
+    let test_run_opts = TestRunOpts {
+        strategy,
+        nocapture: opts.nocapture,
+        concurrency,
+        time: opts.time_options,
+        timeout: opts.test_timeout,
+    };
 
     match testfn {
         DynBenchFn(benchfn) => {
@@ -616,6 +707,7 @@ fn spawn_test_subprocess(
     report_time: bool,
     monitor_ch: Sender<CompletedTest>,
     time_opts: Option<time::TestTimeOptions>,
+    timeout: Option<Duration>,
 ) {
     let (result, test_output, exec_time) = (|| {
         let args = env::args().collect::<Vec<_>>();
@@ -626,10 +718,17 @@ fn spawn_test_subprocess(
         if nocapture {
             command.stdout(process::Stdio::inherit());
             command.stderr(process::Stdio::inherit());
+        } else {
+            command.stdout(process::Stdio::piped());
+            command.stderr(process::Stdio::piped());
         }
 
         let start = report_time.then(Instant::now);
-        let output = match command.output() {
+        let output = match timeout {
+            None => command.output().map(|output| (output, false)),
+            Some(timeout) => output_with_timeout(&mut command, timeout),
+        };
+        let output = match output {
             Ok(out) => out,
             Err(e) => {
                 let err = format!("Failed to spawn {} as child for test: {:?}", args[0], e);
@@ -641,11 +740,16 @@ fn spawn_test_subprocess(
             TestExecTime(duration)
         });
 
-        let std::process::Output { stdout, stderr, status } = output;
+        let (std::process::Output { stdout, stderr, status }, timed_out) = output;
         let mut test_output = stdout;
         formatters::write_stderr_delimiter(&mut test_output, &desc.name);
         test_output.extend_from_slice(&stderr);
 
+        if timed_out {
+            let message = format!("test did not finish within {:?}", timeout.unwrap());
+            return (TrFailedMsg(message), test_output, exec_time);
+        }
+
         let result = match (|| -> Result<TestResult, String> {
             let exit_code = get_exit_code(status)?;
             Ok(get_result_from_exit_code(&desc, exit_code, &time_opts, &exec_time))
@@ -664,7 +768,86 @@ fn spawn_test_subprocess(
     monitor_ch.send(message).unwrap();
 }
 
-fn run_test_in_spawned_subprocess(desc: TestDesc, testfn: Box<dyn FnOnce() + Send>) -> ! {
+/// Like `Command::output`, but kills the child once it has been running for longer than
+/// `timeout`. The returned flag is set if that happened, in which case the output holds
+/// whatever the child wrote until then.
+fn output_with_timeout(
+    command: &mut Command,
+    timeout: Duration,
+) -> io::Result<(process::Output, bool)> {
+    /// Output of a pipe read on a separate thread. `done` disconnects once the pipe is closed.
+    struct PipeReader {
+        buf: Arc<Mutex<Vec<u8>>>,
+        done: Receiver<()>,
+    }
+
+    fn read_to_end(mut pipe: impl io::Read + Send + 'static) -> PipeReader {
+        let buf = Arc::new(Mutex::new(Vec::new()));
+        let (done_tx, done) = channel();
+        let thread_buf = buf.clone();
+        thread::spawn(move || {
+            let _done_tx = done_tx;
+            let mut chunk = [0; 8192];
+            loop {
+                match pipe.read(&mut chunk) {
+                    Ok(0) => break,
+                    Ok(n) => thread_buf.lock().unwrap().extend_from_slice(&chunk[..n]),
+                    Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                    Err(_) => break,
+                }
+            }
+        });
+        PipeReader { buf, done }
+    }
+
+    let mut child = command.stdin(process::Stdio::null()).spawn()?;
+    let stdout = child.stdout.take().map(read_to_end);
+    let stderr = child.stderr.take().map(read_to_end);
+
+    let deadline = Instant::now().checked_add(timeout);
+    let (status, timed_out) = loop {
+        if let Some(status) = child.try_wait()? {
+            break (status, false);
+        }
+        if deadline.map_or(false, |deadline| Instant::now() >= deadline) {
+            // The child may exit on its own in the meantime, which is fine.
+            let _ = child.kill();
+            break (child.wait()?, true);
+        }
+        thread::sleep(Duration::from_millis(10));
+    };
+
+    // Processes spawned by the killed child may keep the pipes open indefinitely, so only
+    // give the readers a moment to drain them and keep whatever was read until then.
+    let collect = |reader: Option<PipeReader>| {
+        reader.map_or_else(Vec::new, |reader| {
+            if timed_out {
+                let _ = reader.done.recv_timeout(Duration::from_millis(100));
+            } else {
+                let _ = reader.done.recv();
+            }
+            mem::take(&mut *reader.buf.lock().unwrap_or_else(|e| e.into_inner()))
+        })
+    };
+    Ok((process::Output { status, stdout: collect(stdout), stderr: collect(stderr) }, timed_out))
+}
+
+fn run_test_in_spawned_subprocess(
+    desc: TestDesc,
+    testfn: Box<dyn FnOnce() + Send>,
+    panic_abort: bool,
+) -> ! {
+    if !panic_abort {
+        // Panics unwind, so tests may catch them themselves. Only a panic that
+        // escapes the test decides its result, like when running in-process.
+        let result = catch_unwind(AssertUnwindSafe(testfn));
+        let test_result = match result {
+            Ok(()) => calc_result(&desc, Ok(()), &None, &None),
+            Err(e) => calc_result(&desc, Err(e.as_ref()), &None, &None),
+        };
+        exit_with_test_result(&test_result);
+    }
+
     let builtin_panic_hook = panic::take_hook();
     let record_result = Arc::new(move |panic_info: Option<&'_ PanicInfo<'_>>| {
         let test_result = match panic_info {
@@ -672,21 +855,11 @@ fn run_test_in_spawned_subprocess(desc: TestDesc, testfn: Box<dyn FnOnce() + Sen
             None => calc_result(&desc, Ok(()), &None, &None),
         };
 
-        // We don't support serializing TrFailedMsg, so just
-        // print the message out to stderr.
-        if let TrFailedMsg(msg) = &test_result {
-            eprintln!("{msg}");
-        }
-
         if let Some(info) = panic_info {
             builtin_panic_hook(info);
         }
 
-        if let TrOk = test_result {
-            process::exit(test_result::TR_OK);
-        } else {
-            process::exit(test_result::TR_FAILED);
-        }
+        exit_with_test_result(&test_result);
     });
     let record_result2 = record_result.clone();
     panic::set_hook(Box::new(move |info| record_result2(Some(&info))));
@@ -694,3 +867,18 @@ fn run_test_in_spawned_subprocess(desc: TestDesc, testfn: Box<dyn FnOnce() + Sen
     record_result(None);
     unreachable!("panic=abort callback should have exited the process")
 }
+
+/// Exits a spawned test process with the exit code reporting `test_result`.
+fn exit_with_test_result(test_result: &TestResult) -> ! {
+    // We don't support serializing TrFailedMsg, so just
+    // print the message out to stderr.
+    if let TrFailedMsg(msg) = test_result {
+        eprintln!("{msg}");
+    }
+
+    if let TrOk = test_result {
+        process::exit(test_result::TR_OK);
+    } else {
+        process::exit(test_result::TR_FAILED);
+    }
+}
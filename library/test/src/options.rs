@@ -41,6 +41,10 @@ pub enum OutputFormat {
     Json,
     /// JUnit output
     Junit,
+    /// TAP 14 output
+    Tap,
+    /// GitHub Actions workflow annotations
+    Github,
 }
 
 /// Whether ignored test should be run or not
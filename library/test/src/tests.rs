@@ -3,7 +3,7 @@
 use crate::{
     bench::Bencher,
     console::OutputLocation,
-    formatters::PrettyFormatter,
+    formatters::{GithubFormatter, OutputFormatter, PrettyFormatter, TapFormatter},
     options::OutputFormat,
     test::{
         filter_tests,
@@ -50,6 +50,10 @@ fn new() -> TestOpts {
             test_threads: None,
             skip: vec![],
             time_options: None,
+            test_timeout: None,
+            isolate: false,
+            shard_count: None,
+            shard_index: None,
             options: Options::new(),
         }
     }
@@ -429,6 +433,52 @@ fn parse_include_ignored_flag() {
     assert_eq!(opts.run_ignored, RunIgnored::Yes);
 }
 
+#[test]
+fn parse_test_timeout_option() {
+    let parse = |timeout: &str| {
+        let args = ["progname", "-Zunstable-options", "--test-timeout", timeout];
+        let args = args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+        parse_opts(&args).unwrap().map(|opts| opts.test_timeout)
+    };
+    assert_eq!(parse("90"), Ok(Some(Duration::from_secs(90))));
+    assert_eq!(parse("500ms"), Ok(Some(Duration::from_millis(500))));
+    assert_eq!(parse("2m"), Ok(Some(Duration::from_secs(120))));
+    assert!(parse("0s").is_err());
+    assert!(parse("ten").is_err());
+    assert!(parse("5h").is_err());
+    assert!(parse("99999999999999999999999").is_err());
+}
+
+#[test]
+#[cfg(unix)]
+fn output_with_timeout_kills_the_child() {
+    // The background `sleep` keeps the pipes open after its parent is killed.
+    let mut command = Command::new("sh");
+    command.args(["-c", "echo started; sleep 60 & sleep 60"]);
+    command.stdout(process::Stdio::piped()).stderr(process::Stdio::piped());
+
+    let start = Instant::now();
+    let (output, timed_out) =
+        output_with_timeout(&mut command, Duration::from_millis(500)).unwrap();
+    assert!(timed_out);
+    assert!(!output.status.success());
+    assert_eq!(output.stdout, b"started\n");
+    assert!(start.elapsed() < Duration::from_secs(30));
+}
+
+#[test]
+#[cfg(unix)]
+fn output_with_timeout_waits_for_the_child() {
+    let mut command = Command::new("sh");
+    command.args(["-c", "echo done"]);
+    command.stdout(process::Stdio::piped()).stderr(process::Stdio::piped());
+
+    let (output, timed_out) = output_with_timeout(&mut command, Duration::from_secs(60)).unwrap();
+    assert!(!timed_out);
+    assert!(output.status.success());
+    assert_eq!(output.stdout, b"done\n");
+}
+
 #[test]
 pub fn filter_for_ignored_option() {
     // When we run ignored tests the test filter should filter out all the
@@ -692,6 +742,79 @@ pub fn order_depends_on_more_than_seed() {
     assert!(left.iter().zip(right).any(|(a, b)| a.0 != b.0));
 }
 
+#[test]
+pub fn shards_partition_the_tests() {
+    let names = |tests: Vec<TestDescAndFn>| {
+        tests.into_iter().map(|test| test.desc.name.to_string()).collect::<Vec<_>>()
+    };
+
+    let mut opts = TestOpts::new();
+    opts.shard_count = Some(3);
+    let all = names(filter_tests(&opts, sample_tests()));
+
+    let mut sharded = Vec::new();
+    for shard_index in 0..3 {
+        opts.shard_index = Some(shard_index);
+        let shard = names(filter_tests(&opts, sample_tests()));
+        // Tests stay in their shard when other tests are added or removed.
+        let mut fewer_tests = sample_tests();
+        fewer_tests.truncate(4);
+        for name in names(filter_tests(&opts, fewer_tests)) {
+            assert!(shard.contains(&name));
+        }
+        sharded.extend(shard);
+    }
+
+    sharded.sort();
+    assert_eq!(sharded, all);
+}
+
+#[test]
+fn list_tests_json_reports_shards() {
+    let mut opts = TestOpts::new();
+    opts.format = OutputFormat::Json;
+    opts.shard_count = Some(3);
+    opts.shard_index = Some(1);
+    let expected: Vec<_> =
+        filter_tests(&opts, sample_tests()).into_iter().map(|test| test.desc.name).collect();
+
+    let json = crate::console::list_tests_json(&opts, sample_tests()).unwrap();
+    let json = String::from_utf8(json).unwrap();
+    let mut lines: Vec<_> = json.lines().collect();
+    assert_eq!(
+        lines.pop(),
+        Some(&*format!(
+            r#"{{ "type": "suite", "event": "discovered", "test_count": {}, "bench_count": 0 }}"#,
+            expected.len()
+        ))
+    );
+    assert_eq!(lines.len(), expected.len());
+    for (line, name) in lines.iter().zip(&expected) {
+        assert_eq!(
+            *line,
+            format!(
+                r#"{{ "type": "test", "name": "{}", "event": "discovered", "shard": 1 }}"#,
+                name
+            )
+        );
+    }
+}
+
+#[test]
+fn parse_shard_options() {
+    let parse = |shard_args: &[&str]| {
+        let mut args = vec!["progname", "-Zunstable-options"];
+        args.extend_from_slice(shard_args);
+        let args = args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+        parse_opts(&args).unwrap().map(|opts| (opts.shard_count, opts.shard_index))
+    };
+    assert_eq!(parse(&["--shard-count", "4", "--shard-index", "3"]), Ok((Some(4), Some(3))));
+    assert_eq!(parse(&["--shard-count", "4"]), Ok((Some(4), None)));
+    assert!(parse(&["--shard-count", "4", "--shard-index", "4"]).is_err());
+    assert!(parse(&["--shard-count", "0"]).is_err());
+    assert!(parse(&["--shard-index", "0"]).is_err());
+}
+
 #[test]
 pub fn test_metricmap_compare() {
     let mut m1 = MetricMap::new();
@@ -821,3 +944,92 @@ fn should_sort_failures_before_printing_them() {
     let bpos = s.find("b").unwrap();
     assert!(apos < bpos);
 }
+
+fn formatter_test_state(failed: usize) -> console::ConsoleTestState {
+    console::ConsoleTestState {
+        log_out: None,
+        total: 0,
+        passed: 0,
+        failed,
+        ignored: 0,
+        filtered_out: 0,
+        measured: 0,
+        exec_time: None,
+        metrics: MetricMap::new(),
+        failures: Vec::new(),
+        options: Options::new(),
+        not_failures: Vec::new(),
+        time_failures: Vec::new(),
+    }
+}
+
+fn raw_output(out: &OutputLocation<Vec<u8>>) -> String {
+    match out {
+        OutputLocation::Raw(m) => String::from_utf8_lossy(&m[..]).into_owned(),
+        OutputLocation::Pretty(_) => unreachable!(),
+    }
+}
+
+#[test]
+fn tap_formatter_writes_diagnostics_for_failures() {
+    let desc = |name| TestDesc {
+        name: StaticTestName(name),
+        ignore: false,
+        ignore_message: None,
+        should_panic: ShouldPanic::No,
+        compile_fail: false,
+        no_run: false,
+        test_type: TestType::Unknown,
+    };
+    let st = formatter_test_state(1);
+
+    let mut out = TapFormatter::new(OutputLocation::Raw(Vec::new()));
+    out.write_run_start(3, None).unwrap();
+    out.write_result(&desc("a"), &TrOk, None, b"", &st).unwrap();
+    let failed = TestResult::TrFailedMsg("oops".into());
+    out.write_result(&desc("b#1"), &failed, None, b"out\n", &st).unwrap();
+    let ignored = TestDesc { ignore_message: Some("slow"), ..desc("c") };
+    out.write_result(&ignored, &TrIgnored, None, b"", &st).unwrap();
+
+    assert_eq!(
+        raw_output(out.output_location()),
+        "TAP version 14\n\
+         1..3\n\
+         ok 1 - a\n\
+         not ok 2 - b\\#1\n  \
+         ---\n  \
+         message: |-\n    \
+         oops\n  \
+         output: |-\n    \
+         out\n  \
+         ...\n\
+         ok 3 - c # SKIP slow\n"
+    );
+}
+
+#[test]
+fn github_formatter_annotates_panic_location() {
+    let desc = TestDesc {
+        name: StaticTestName("tests::b"),
+        ignore: false,
+        ignore_message: None,
+        should_panic: ShouldPanic::No,
+        compile_fail: false,
+        no_run: false,
+        test_type: TestType::UnitTest,
+    };
+    let stdout = b"thread 'tests::b' panicked at 'assertion failed: `(left == right)`\n  \
+                   left: `1`,\n right: `2`', src/lib.rs:9:5\n";
+
+    let mut out = GithubFormatter::new(OutputLocation::Raw(Vec::new()));
+    out.write_result(&desc, &TestResult::TrFailed, None, stdout, &formatter_test_state(1)).unwrap();
+
+    let output = raw_output(out.output_location());
+    assert!(output.starts_with(
+        "test tests::b ... FAILED\n\
+         ::error file=src/lib.rs,line=9,col=5,title=tests%3A%3Ab::\
+         assertion failed: `(left == right)`%0A  left: `1`,%0A right: `2`\n\
+         ::group::tests::b output\n"
+    ));
+    assert!(output.ends_with("::endgroup::\n"));
+}
@@ -673,6 +673,7 @@ macro_rules! describe {
                 test::RustdocTheme,
                 test::RustdocUi,
                 test::RustdocJson,
+                test::RustdocJsonDiff,
                 test::HtmlCheck,
                 // Run bootstrap close to the end as it's unlikely to fail
                 test::Bootstrap,
@@ -372,6 +372,7 @@ fn run(self, builder: &Builder<'_>) -> PathBuf {
     ExpandYamlAnchors, "src/tools/expand-yaml-anchors", "expand-yaml-anchors";
     LintDocs, "src/tools/lint-docs", "lint-docs";
     JsonDocCk, "src/tools/jsondocck", "jsondocck";
+    RustdocJsonDiff, "src/tools/rustdoc-json-diff", "rustdoc-json-diff";
     HtmlChecker, "src/tools/html-checker", "html-checker";
     BumpStage0, "src/tools/bump-stage0", "bump-stage0";
 );
@@ -35,6 +35,8 @@
 pub(crate) enum OutputFormat {
     Json,
     Html,
+    Markdown,
+    Man,
 }
 
 impl Default for OutputFormat {
@@ -56,6 +58,8 @@ fn try_from(value: &str) -> Result<Self, Self::Error> {
         match value {
             "json" => Ok(OutputFormat::Json),
             "html" => Ok(OutputFormat::Html),
+            "markdown" => Ok(OutputFormat::Markdown),
+            "man" => Ok(OutputFormat::Man),
             _ => Err(format!("unknown output format `{}`", value)),
         }
     }
@@ -138,6 +142,9 @@ pub(crate) struct Options {
     /// Whether to run the `calculate-doc-coverage` pass, which counts the number of public items
     /// with and without documentation.
     pub(crate) show_coverage: bool,
+    /// Whether to list the items missing documentation or examples, the unresolved intra-doc
+    /// links and the ignored doctests along with the `--show-coverage` totals.
+    pub(crate) coverage_report: bool,
 
     // Options that alter generated documentation pages
     /// Crate version to note on the sidebar of generated docs.
@@ -155,6 +162,9 @@ pub(crate) struct Options {
     pub(crate) json_unused_externs: JsonUnusedExterns,
     /// Whether to skip capturing stdout and stderr of tests.
     pub(crate) nocapture: bool,
+    /// Whether to compile compatible doctests together into a single binary instead of
+    /// building one binary per doctest.
+    pub(crate) merge_doctests: bool,
 
     /// Configuration for scraping examples from the current crate. If this option is Some(..) then
     /// the compiler will scrape examples and not generate documentation.
@@ -193,6 +203,7 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
             .field("test_run_directory", &self.test_run_directory)
             .field("persist_doctests", &self.persist_doctests)
             .field("show_coverage", &self.show_coverage)
+            .field("coverage_report", &self.coverage_report)
             .field("crate_version", &self.crate_version)
             .field("render_options", &self.render_options)
             .field("runtool", &self.runtool)
@@ -201,6 +212,7 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
             .field("run_check", &self.run_check)
             .field("no_run", &self.no_run)
             .field("nocapture", &self.nocapture)
+            .field("merge_doctests", &self.merge_doctests)
             .field("scrape_examples_options", &self.scrape_examples_options)
             .finish()
     }
@@ -267,6 +279,10 @@ pub(crate) struct RenderOptions {
     pub(crate) generate_redirect_map: bool,
     /// Show the memory layout of types in the docs.
     pub(crate) show_type_layout: bool,
+    /// List the functions, methods and fields using a type or a trait on its page.
+    pub(crate) show_used_by: bool,
+    /// Index the text of the documentation, not only the names of items, in the search index.
+    pub(crate) full_text_search: bool,
     pub(crate) unstable_features: rustc_feature::UnstableFeatures,
     pub(crate) emit: Vec<EmitType>,
     /// If `true`, HTML source pages will generate links for items to their definition.
@@ -380,6 +396,7 @@ fn println_condition(condition: Condition) {
                     WhenDocumentPrivate => println!("  (when --document-private-items)"),
                     WhenNotDocumentPrivate => println!("  (when not --document-private-items)"),
                     WhenNotDocumentHidden => println!("  (when not --document-hidden-items)"),
+                    WhenCoverageReport => println!("  (when --coverage-report)"),
                 }
             }
 
@@ -621,6 +638,7 @@ fn println_condition(condition: Condition) {
         let target = parse_target_triple(matches, error_format);
 
         let show_coverage = matches.opt_present("show-coverage");
+        let coverage_report = matches.opt_present("coverage-report");
 
         let crate_types = match parse_crate_types_from_list(matches.opt_strs("crate-type")) {
             Ok(types) => types,
@@ -634,9 +652,10 @@ fn println_condition(condition: Condition) {
             Some(s) => match OutputFormat::try_from(s.as_str()) {
                 Ok(out_fmt) => {
                     if !out_fmt.is_json() && show_coverage {
-                        diag.struct_err(
-                            "html output format isn't supported for the --show-coverage option",
-                        )
+                        diag.struct_err(&format!(
+                            "{} output format isn't supported for the --show-coverage option",
+                            s
+                        ))
                         .emit();
                         return Err(1);
                     }
@@ -681,11 +700,23 @@ fn println_condition(condition: Condition) {
         let run_check = matches.opt_present("check");
         let generate_redirect_map = matches.opt_present("generate-redirect-map");
         let show_type_layout = matches.opt_present("show-type-layout");
+        let show_used_by = matches.opt_present("show-used-by");
+        let full_text_search = matches.opt_present("full-text-search");
         let nocapture = matches.opt_present("nocapture");
+        let merge_doctests = matches.opt_present("merge-doctests");
         let generate_link_to_definition = matches.opt_present("generate-link-to-definition");
         let extern_html_root_takes_precedence =
             matches.opt_present("extern-html-root-takes-precedence");
 
+        if coverage_report && (!show_coverage || !output_format.is_json()) {
+            diag.struct_err(
+                "--coverage-report option can only be used with --show-coverage and the JSON \
+                 output format",
+            )
+            .emit();
+            return Err(1);
+        }
+
         if generate_link_to_definition && (show_coverage || output_format != OutputFormat::Html) {
             diag.struct_err(
                 "--generate-link-to-definition option can only be used with HTML output format",
@@ -722,6 +753,7 @@ fn println_condition(condition: Condition) {
             should_test,
             test_args,
             show_coverage,
+            coverage_report,
             crate_version,
             test_run_directory,
             persist_doctests,
@@ -732,6 +764,7 @@ fn println_condition(condition: Condition) {
             run_check,
             no_run,
             nocapture,
+            merge_doctests,
             render_options: RenderOptions {
                 output,
                 external_html,
@@ -755,6 +788,8 @@ fn println_condition(condition: Condition) {
                 document_hidden,
                 generate_redirect_map,
                 show_type_layout,
+                show_used_by,
+                full_text_search,
                 unstable_features: rustc_feature::UnstableFeatures::from_environment(
                     crate_name.as_deref(),
                 ),
@@ -24,9 +24,10 @@
 use tempfile::Builder as TempFileBuilder;
 
 use std::env;
+use std::fs::File;
 use std::io::{self, Write};
 use std::panic;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::{self, Command, Stdio};
 use std::str;
 use std::sync::atomic::{AtomicUsize, Ordering};
@@ -38,6 +39,10 @@
 use crate::lint::init_lints;
 use crate::passes::span_of_attrs;
 
+use self::merged::MergedDoctests;
+
+mod merged;
+
 /// Options that apply to all doctests in a crate or Markdown file (for `rustdoc foo.md`).
 #[derive(Clone, Default)]
 pub(crate) struct GlobalTestOptions {
@@ -316,26 +321,8 @@ fn run_test(
 
     let output_file = outdir.path().join("rust_out");
 
-    let rustc_binary = rustdoc_options
-        .test_builder
-        .as_deref()
-        .unwrap_or_else(|| rustc_interface::util::rustc_path().expect("found rustc"));
-    let mut compiler = Command::new(&rustc_binary);
-    compiler.arg("--crate-type").arg("bin");
-    for cfg in &rustdoc_options.cfgs {
-        compiler.arg("--cfg").arg(&cfg);
-    }
-    if !rustdoc_options.check_cfgs.is_empty() {
-        compiler.arg("-Z").arg("unstable-options");
-        for check_cfg in &rustdoc_options.check_cfgs {
-            compiler.arg("--check-cfg").arg(&check_cfg);
-        }
-    }
-    if let Some(sysroot) = rustdoc_options.maybe_sysroot {
-        compiler.arg("--sysroot").arg(sysroot);
-    }
-    compiler.arg("--edition").arg(&edition.to_string());
-    compiler.env("UNSTABLE_RUSTDOC_TEST_PATH", path);
+    let mut compiler = make_compiler(&rustdoc_options, edition, &target, supports_color);
+    compiler.env("UNSTABLE_RUSTDOC_TEST_PATH", &path);
     compiler.env("UNSTABLE_RUSTDOC_TEST_LINE", format!("{}", line as isize - line_offset as isize));
     compiler.arg("-o").arg(&output_file);
     if lang_string.test_harness {
@@ -347,47 +334,9 @@ fn run_test(
         compiler.arg("-Z").arg("unstable-options");
         compiler.arg("-W").arg("unused_crate_dependencies");
     }
-    for lib_str in &rustdoc_options.lib_strs {
-        compiler.arg("-L").arg(&lib_str);
-    }
-    for extern_str in &rustdoc_options.extern_strs {
-        compiler.arg("--extern").arg(&extern_str);
-    }
-    compiler.arg("-Ccodegen-units=1");
-    for codegen_options_str in &rustdoc_options.codegen_options_strs {
-        compiler.arg("-C").arg(&codegen_options_str);
-    }
-    for unstable_option_str in &rustdoc_options.unstable_opts_strs {
-        compiler.arg("-Z").arg(&unstable_option_str);
-    }
     if no_run && !lang_string.compile_fail && rustdoc_options.persist_doctests.is_none() {
         compiler.arg("--emit=metadata");
     }
-    compiler.arg("--target").arg(match target {
-        TargetTriple::TargetTriple(s) => s,
-        TargetTriple::TargetJson { path_for_rustdoc, .. } => {
-            path_for_rustdoc.to_str().expect("target path must be valid unicode").to_string()
-        }
-    });
-    if let ErrorOutputType::HumanReadable(kind) = rustdoc_options.error_format {
-        let (short, color_config) = kind.unzip();
-
-        if short {
-            compiler.arg("--error-format").arg("short");
-        }
-
-        match color_config {
-            ColorConfig::Never => {
-                compiler.arg("--color").arg("never");
-            }
-            ColorConfig::Always => {
-                compiler.arg("--color").arg("always");
-            }
-            ColorConfig::Auto => {
-                compiler.arg("--color").arg(if supports_color { "always" } else { "never" });
-            }
-        }
-    }
 
     compiler.arg("-");
     compiler.stdin(Stdio::piped());
@@ -454,6 +403,98 @@ fn drop(&mut self) {
     }
 
     // Run the code!
+    run_test_binary(
+        &output_file,
+        None,
+        runtool,
+        runtool_args,
+        &rustdoc_options,
+        &lang_string,
+        &path,
+    )
+}
+
+/// Creates the `rustc` invocation used to compile doctests, with all the flags that were passed
+/// to rustdoc for the crate. Callers are expected to add the input and output themselves.
+fn make_compiler(
+    rustdoc_options: &RustdocOptions,
+    edition: Edition,
+    target: &TargetTriple,
+    supports_color: bool,
+) -> Command {
+    let rustc_binary = rustdoc_options
+        .test_builder
+        .as_deref()
+        .unwrap_or_else(|| rustc_interface::util::rustc_path().expect("found rustc"));
+    let mut compiler = Command::new(&rustc_binary);
+    compiler.arg("--crate-type").arg("bin");
+    for cfg in &rustdoc_options.cfgs {
+        compiler.arg("--cfg").arg(&cfg);
+    }
+    if !rustdoc_options.check_cfgs.is_empty() {
+        compiler.arg("-Z").arg("unstable-options");
+        for check_cfg in &rustdoc_options.check_cfgs {
+            compiler.arg("--check-cfg").arg(&check_cfg);
+        }
+    }
+    if let Some(sysroot) = &rustdoc_options.maybe_sysroot {
+        compiler.arg("--sysroot").arg(sysroot);
+    }
+    compiler.arg("--edition").arg(&edition.to_string());
+    for lib_str in &rustdoc_options.lib_strs {
+        compiler.arg("-L").arg(&lib_str);
+    }
+    for extern_str in &rustdoc_options.extern_strs {
+        compiler.arg("--extern").arg(&extern_str);
+    }
+    compiler.arg("-Ccodegen-units=1");
+    for codegen_options_str in &rustdoc_options.codegen_options_strs {
+        compiler.arg("-C").arg(&codegen_options_str);
+    }
+    for unstable_option_str in &rustdoc_options.unstable_opts_strs {
+        compiler.arg("-Z").arg(&unstable_option_str);
+    }
+    compiler.arg("--target").arg(match target {
+        TargetTriple::TargetTriple(s) => s.clone(),
+        TargetTriple::TargetJson { path_for_rustdoc, .. } => {
+            path_for_rustdoc.to_str().expect("target path must be valid unicode").to_string()
+        }
+    });
+    if let ErrorOutputType::HumanReadable(kind) = rustdoc_options.error_format {
+        let (short, color_config) = kind.unzip();
+
+        if short {
+            compiler.arg("--error-format").arg("short");
+        }
+
+        match color_config {
+            ColorConfig::Never => {
+                compiler.arg("--color").arg("never");
+            }
+            ColorConfig::Always => {
+                compiler.arg("--color").arg("always");
+            }
+            ColorConfig::Auto => {
+                compiler.arg("--color").arg(if supports_color { "always" } else { "never" });
+            }
+        }
+    }
+    compiler
+}
+
+/// Runs a compiled doctest binary, passing it `arg` if there is one, and checks its exit status.
+///
+/// The `env=`, `stdin=` and `cwd=` attributes of the code block are applied to the process, the
+/// paths being relative to the directory of `path`, the source file of the doctest.
+fn run_test_binary(
+    output_file: &Path,
+    arg: Option<&str>,
+    runtool: Option<String>,
+    runtool_args: Vec<String>,
+    rustdoc_options: &RustdocOptions,
+    lang_string: &LangString,
+    path: &Path,
+) -> Result<(), TestFailure> {
     let mut cmd;
 
     if let Some(tool) = runtool {
@@ -463,9 +504,20 @@ fn drop(&mut self) {
     } else {
         cmd = Command::new(output_file);
     }
-    if let Some(run_directory) = rustdoc_options.test_run_directory {
+    if let Some(arg) = arg {
+        cmd.arg(arg);
+    }
+    let source_dir = path.parent().unwrap_or_else(|| Path::new(""));
+    if let Some(cwd) = &lang_string.cwd {
+        cmd.current_dir(source_dir.join(cwd));
+    } else if let Some(run_directory) = &rustdoc_options.test_run_directory {
         cmd.current_dir(run_directory);
     }
+    cmd.envs(lang_string.env.iter().map(|(key, value)| (key, value)));
+    if let Some(stdin) = &lang_string.stdin {
+        let stdin = File::open(source_dir.join(stdin)).map_err(TestFailure::ExecutionError)?;
+        cmd.stdin(stdin);
+    }
 
     let result = if rustdoc_options.nocapture {
         cmd.status().map(|status| process::Output {
@@ -917,6 +969,7 @@ pub(crate) struct Collector {
     visited_tests: FxHashMap<(String, usize), usize>,
     unused_extern_reports: Arc<Mutex<Vec<UnusedExterns>>>,
     compiling_test_count: AtomicUsize,
+    merged_doctests: Option<Arc<MergedDoctests>>,
 }
 
 impl Collector {
@@ -929,6 +982,8 @@ pub(crate) fn new(
         filename: Option<PathBuf>,
         enable_per_target_ignores: bool,
     ) -> Collector {
+        let merged_doctests =
+            MergedDoctests::new(&rustdoc_options, crate_name.as_str(), &opts).map(Arc::new);
         Collector {
             tests: Vec::new(),
             names: Vec::new(),
@@ -943,6 +998,7 @@ pub(crate) fn new(
             visited_tests: FxHashMap::default(),
             unused_extern_reports: Default::default(),
             compiling_test_count: AtomicUsize::new(0),
+            merged_doctests,
         }
     }
 
@@ -980,6 +1036,28 @@ fn get_filename(&self) -> FileName {
     }
 }
 
+/// Creates the directory the doctest executable named `name` is compiled into, which is only
+/// kept around after the tests ran if `--persist-doctests` is used.
+fn make_outdir(rustdoc_options: &RustdocOptions, name: &str) -> DirState {
+    if let Some(mut path) = rustdoc_options.persist_doctests.clone() {
+        path.push(name);
+
+        if let Err(err) = std::fs::create_dir_all(&path) {
+            eprintln!("Couldn't create directory for doctest executables: {}", err);
+            panic::resume_unwind(Box::new(()));
+        }
+
+        DirState::Perm(path)
+    } else {
+        DirState::Temp(
+            TempFileBuilder::new()
+                .prefix("rustdoctest")
+                .tempdir()
+                .expect("rustdoc needs a tempdir"),
+        )
+    }
+}
+
 impl Tester for Collector {
     fn add_test(&mut self, test: String, config: LangString, line: usize) {
         let filename = self.get_filename();
@@ -1027,33 +1105,22 @@ fn add_test(&mut self, test: String, config: LangString, line: usize) {
                 self.visited_tests.entry((file.clone(), line)).and_modify(|v| *v += 1).or_insert(0)
             },
         );
-        let outdir = if let Some(mut path) = rustdoc_options.persist_doctests.clone() {
-            path.push(&test_id);
-
-            if let Err(err) = std::fs::create_dir_all(&path) {
-                eprintln!("Couldn't create directory for doctest executables: {}", err);
-                panic::resume_unwind(Box::new(()));
-            }
-
-            DirState::Perm(path)
-        } else {
-            DirState::Temp(
-                TempFileBuilder::new()
-                    .prefix("rustdoctest")
-                    .tempdir()
-                    .expect("rustdoc needs a tempdir"),
-            )
+        let outdir = make_outdir(&rustdoc_options, &test_id);
+        let ignore = match config.ignore {
+            Ignore::All => true,
+            Ignore::None => false,
+            Ignore::Some(ref ignores) => ignores.iter().any(|s| target_str.contains(s)),
         };
+        let merged = self
+            .merged_doctests
+            .clone()
+            .filter(|merged| !ignore && merged.add(&test, &config, line, &path, &test_id));
 
         debug!("creating test {name}: {test}");
         self.tests.push(test::TestDescAndFn {
             desc: test::TestDesc {
                 name: test::DynTestName(name),
-                ignore: match config.ignore {
-                    Ignore::All => true,
-                    Ignore::None => false,
-                    Ignore::Some(ref ignores) => ignores.iter().any(|s| target_str.contains(s)),
-                },
+                ignore,
                 ignore_message: None,
                 // compiler failures are test failures
                 should_panic: test::ShouldPanic::No,
@@ -1065,23 +1132,36 @@ fn add_test(&mut self, test: String, config: LangString, line: usize) {
                 let report_unused_externs = |uext| {
                     unused_externs.lock().unwrap().push(uext);
                 };
-                let res = run_test(
-                    &test,
-                    &crate_name,
-                    line,
-                    rustdoc_options,
-                    config,
-                    no_run,
-                    runtool,
-                    runtool_args,
-                    target,
-                    &opts,
-                    edition,
-                    outdir,
-                    path,
-                    &test_id,
-                    report_unused_externs,
-                );
+                // Merged doctests only fall back to being compiled on their own if they failed
+                // to compile in the merged binary.
+                let res = match merged.and_then(|merged| merged.binary(&test_id)) {
+                    Some(binary) => run_test_binary(
+                        &binary,
+                        Some(&test_id),
+                        runtool,
+                        runtool_args,
+                        &rustdoc_options,
+                        &config,
+                        &path,
+                    ),
+                    None => run_test(
+                        &test,
+                        &crate_name,
+                        line,
+                        rustdoc_options,
+                        config,
+                        no_run,
+                        runtool,
+                        runtool_args,
+                        target,
+                        &opts,
+                        edition,
+                        outdir,
+                        path,
+                        &test_id,
+                        report_unused_externs,
+                    ),
+                };
 
                 if let Err(err) = res {
                     match err {
@@ -0,0 +1,262 @@
+//! Support for `--merge-doctests`, which compiles the doctests of a crate into a single binary.
+//!
+//! Every merged doctest is written to its own file and included as a module of the merged crate.
+//! The `main` function of the merged binary takes the id of a doctest as its only argument and
+//! runs that doctest, so that libtest can still run each doctest as a separate test, in its own
+//! process. Doctests which can't be merged, or which fail to compile in the merged binary, are
+//! compiled on their own as usual.
+
+use rustc_data_structures::fx::FxHashSet;
+use rustc_span::edition::Edition;
+
+use std::fmt::Write as _;
+use std::fs;
+use std::io::{self, Write as _};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::Mutex;
+
+use super::{make_compiler, make_outdir, make_test, partition_source, DirState, GlobalTestOptions};
+use crate::config::Options as RustdocOptions;
+use crate::html::markdown::LangString;
+
+/// Doctests of the current crate that are compiled together into a single binary.
+pub(super) struct MergedDoctests {
+    rustdoc_options: RustdocOptions,
+    crate_name: String,
+    opts: GlobalTestOptions,
+    outdir: DirState,
+    state: Mutex<MergedDoctestsState>,
+}
+
+struct MergedDoctestsState {
+    doctests: Vec<MergedDoctest>,
+    binary: MergedBinary,
+}
+
+/// A doctest turned into the module of the merged crate.
+struct MergedDoctest {
+    test_id: String,
+    /// The source of the module, padded so that line numbers match the original file.
+    source: String,
+    /// The file the doctest comes from, which is what the module file is remapped to.
+    path: PathBuf,
+    /// The lines of `path` the module spans, which its errors point at.
+    lines: Range<usize>,
+}
+
+enum MergedBinary {
+    NotBuilt,
+    Built(PathBuf),
+    Failed,
+}
+
+impl MergedDoctests {
+    /// Returns `None` if doctests of this crate can't be merged at all.
+    pub(super) fn new(
+        rustdoc_options: &RustdocOptions,
+        crate_name: &str,
+        opts: &GlobalTestOptions,
+    ) -> Option<MergedDoctests> {
+        if !rustdoc_options.merge_doctests
+            || rustdoc_options.no_run
+            || rustdoc_options.json_unused_externs.is_enabled()
+            // `extern crate` items outside of the crate root don't add the crate to the extern
+            // prelude, so paths to the documented crate would break in the 2015 edition.
+            || rustdoc_options.edition == Edition::Edition2015
+            // Attributes from `#![doc(test(attr(...)))]` end up at the top of each module, where
+            // only lint attributes are guaranteed to behave as they would in a crate root.
+            || !opts.attrs.iter().all(|attr| is_lint_attribute(attr))
+        {
+            return None;
+        }
+        Some(MergedDoctests {
+            rustdoc_options: rustdoc_options.clone(),
+            crate_name: crate_name.to_owned(),
+            opts: opts.clone(),
+            outdir: make_outdir(rustdoc_options, "merged_doctests"),
+            state: Mutex::new(MergedDoctestsState {
+                doctests: Vec::new(),
+                binary: MergedBinary::NotBuilt,
+            }),
+        })
+    }
+
+    /// Adds a doctest that isn't ignored to the merged binary if it can run unmodified next to the
+    /// other doctests, returning whether it was added.
+    pub(super) fn add(
+        &self,
+        test: &str,
+        config: &LangString,
+        line: usize,
+        path: &Path,
+        test_id: &str,
+    ) -> bool {
+        let edition = self.rustdoc_options.edition;
+        if config.compile_fail
+            || config.no_run
+            || config.should_panic
+            || config.test_harness
+            || config.edition.map_or(false, |test_edition| test_edition != edition)
+            // Exported macros are all defined at the crate root and may conflict with each other.
+            || test.contains("macro_export")
+            || !partition_source(test, edition).0.trim().is_empty()
+        {
+            return false;
+        }
+
+        let (prog, line_offset, _) =
+            make_test(test, Some(&self.crate_name), false, &self.opts, edition, Some(test_id));
+        let padding = (line as isize - line_offset as isize).max(0) as usize;
+        let mut source = "\n".repeat(padding);
+        source.push_str(&prog);
+        source.push_str(
+            "\npub(crate) fn __rustdoc_run() -> impl ::std::process::Termination { main() }\n",
+        );
+        let lines = padding + 1..source.lines().count() + 1;
+
+        let mut state = self.state.lock().unwrap();
+        state.doctests.push(MergedDoctest {
+            test_id: test_id.to_owned(),
+            source,
+            path: path.to_owned(),
+            lines,
+        });
+        true
+    }
+
+    /// Returns the path of the merged binary, compiling it first if needed. Returns `None` if
+    /// the doctest `test_id` isn't part of the merged binary because it failed to compile in it,
+    /// in which case it should be compiled on its own instead.
+    pub(super) fn binary(&self, test_id: &str) -> Option<PathBuf> {
+        let mut state = self.state.lock().unwrap();
+        if let MergedBinary::NotBuilt = state.binary {
+            let mut doctests = std::mem::take(&mut state.doctests);
+            state.binary = match self.compile_without_failing(&mut doctests) {
+                Some(binary) => MergedBinary::Built(binary),
+                None => MergedBinary::Failed,
+            };
+            state.doctests = doctests;
+        }
+        match &state.binary {
+            MergedBinary::Built(binary)
+                if state.doctests.iter().any(|doctest| doctest.test_id == test_id) =>
+            {
+                Some(binary.clone())
+            }
+            MergedBinary::NotBuilt | MergedBinary::Built(_) | MergedBinary::Failed => None,
+        }
+    }
+
+    /// Compiles the merged binary, leaving out the doctests which fail to compile in it until it
+    /// compiles. The doctests which are left out are removed from `doctests`.
+    fn compile_without_failing(&self, doctests: &mut Vec<MergedDoctest>) -> Option<PathBuf> {
+        while !doctests.is_empty() {
+            let stderr = match self.compile(doctests) {
+                Ok(binary) => return Some(binary),
+                Err(stderr) => stderr,
+            };
+            let failing = failing_doctests(&stderr, doctests);
+            if failing.is_empty() {
+                // The errors can't be blamed on some of the doctests, so none of them is merged.
+                let _ = writeln!(
+                    io::stderr(),
+                    "warning: the merged doctests failed to compile, \
+                     compiling each of them on its own instead\n{stderr}"
+                );
+                return None;
+            }
+            debug!("leaving failing doctests out of the merged binary: {failing:?}");
+            doctests.retain(|doctest| !failing.contains(&doctest.test_id));
+        }
+        None
+    }
+
+    /// Compiles the merged binary, returning its path, or the output of rustc if it failed.
+    fn compile(&self, doctests: &[MergedDoctest]) -> Result<PathBuf, String> {
+        let outdir = self.outdir.path();
+        let main_file = outdir.join("main.rs");
+        let output_file = outdir.join("rust_out");
+
+        let options = &self.rustdoc_options;
+        let mut compiler = make_compiler(options, options.edition, &options.target, false);
+        compiler.arg("--crate-name").arg("rust_out");
+        compiler.arg("-o").arg(&output_file);
+
+        let mut main = String::new();
+        for doctest in doctests {
+            let file_name = format!("{}.rs", doctest.test_id);
+            let module_file = outdir.join(&file_name);
+            if let Err(err) = fs::write(&module_file, &doctest.source) {
+                return Err(format!("couldn't write {}: {err}", module_file.display()));
+            }
+            // Make diagnostics and panic locations point at the documented file instead.
+            let mut remap = module_file.into_os_string();
+            remap.push("=");
+            remap.push(&doctest.path);
+            compiler.arg("--remap-path-prefix").arg(remap);
+
+            writeln!(main, "#[path = {file_name:?}]\nmod __doctest_{};", doctest.test_id).unwrap();
+        }
+
+        main.push_str("\nfn main() -> ::std::process::ExitCode {\n");
+        main.push_str("    match ::std::env::args().nth(1).as_deref() {\n");
+        for doctest in doctests {
+            writeln!(
+                main,
+                "        Some({id:?}) => \
+                 ::std::process::Termination::report(__doctest_{id}::__rustdoc_run()),",
+                id = doctest.test_id,
+            )
+            .unwrap();
+        }
+        main.push_str("        _ => {\n");
+        main.push_str("            ::std::eprintln!(\"unknown doctest\");\n");
+        main.push_str("            ::std::process::ExitCode::FAILURE\n");
+        main.push_str("        }\n");
+        main.push_str("    }\n");
+        main.push_str("}\n");
+
+        if let Err(err) = fs::write(&main_file, &main) {
+            return Err(format!("couldn't write {}: {err}", main_file.display()));
+        }
+        debug!("merged doctests:\n{main}");
+
+        compiler.arg(&main_file);
+        compiler.stdin(Stdio::null());
+        compiler.stdout(Stdio::null());
+        compiler.stderr(Stdio::piped());
+        let output = compiler.output().expect("Failed to spawn rustc process");
+        if output.status.success() {
+            Ok(output_file)
+        } else {
+            Err(String::from_utf8_lossy(&output.stderr).into_owned())
+        }
+    }
+}
+
+/// Returns the ids of the doctests the errors in `stderr` point at. The failing doctests are
+/// compiled again on their own, which reports their errors properly.
+fn failing_doctests(stderr: &str, doctests: &[MergedDoctest]) -> FxHashSet<String> {
+    let mut failing = FxHashSet::default();
+    for line in stderr.lines() {
+        // The primary span of each error is shown as ` --> path:line:column`.
+        let Some(location) = line.trim_start().strip_prefix("--> ") else { continue };
+        let Some((location, _column)) = location.rsplit_once(':') else { continue };
+        let Some((path, line)) = location.rsplit_once(':') else { continue };
+        let Ok(line) = line.parse::<usize>() else { continue };
+        for doctest in doctests {
+            if doctest.lines.contains(&line) && doctest.path.to_string_lossy() == path {
+                failing.insert(doctest.test_id.clone());
+            }
+        }
+    }
+    failing
+}
+
+/// Returns whether `attr`, as given in `#![doc(test(attr(...)))]`, is a lint attribute.
+fn is_lint_attribute(attr: &str) -> bool {
+    let name = attr.split(|c: char| c == '(' || c.is_whitespace()).next().unwrap_or_default();
+    matches!(name, "allow" | "warn" | "deny" | "forbid" | "expect")
+}
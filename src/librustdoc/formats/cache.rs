@@ -1,6 +1,7 @@
 use std::mem;
 
 use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_hir::def::DefKind;
 use rustc_hir::def_id::{CrateNum, DefId};
 use rustc_middle::middle::privacy::AccessLevels;
 use rustc_middle::ty::{self, TyCtxt};
@@ -13,7 +14,7 @@
 use crate::formats::Impl;
 use crate::html::format::join_with_double_colon;
 use crate::html::markdown::short_markdown_summary;
-use crate::html::render::search_index::get_function_type_for_search;
+use crate::html::render::search_index::{get_doc_words_for_search, get_function_type_for_search};
 use crate::html::render::IndexItem;
 
 /// This cache is used to store information about the [`clean::Crate`] being
@@ -69,6 +70,11 @@ pub(crate) struct Cache {
     /// of trait ids to the list of known implementors of the trait
     pub(crate) implementors: FxHashMap<DefId, Vec<Impl>>,
 
+    /// Maps the `DefId` of a type or a trait to the local functions, methods and
+    /// fields mentioning it in their signature or in their bounds. This is used
+    /// to render the "Used by" section of its page with `--show-used-by`.
+    pub(crate) used_by: FxHashMap<DefId, Vec<UsedBy>>,
+
     /// Cache of where external crate documentation can be found.
     pub(crate) extern_locations: FxHashMap<CrateNum, ExternalLocation>,
 
@@ -87,6 +93,10 @@ pub(crate) struct Cache {
     /// This is stored in `Cache` so it doesn't need to be passed through all rustdoc functions.
     pub(crate) document_private: bool,
 
+    /// Whether to index the text of the documentation for the search.
+    /// This is stored in `Cache` since the search index is built while populating it.
+    pub(crate) full_text_search: bool,
+
     /// Crates marked with [`#[doc(masked)]`][doc_masked].
     ///
     /// [doc_masked]: https://doc.rust-lang.org/nightly/unstable-book/language-features/doc-masked.html
@@ -119,6 +129,10 @@ pub(crate) struct Cache {
     ///
     /// Links are indexed by the DefId of the item they document.
     pub(crate) intra_doc_links: FxHashMap<ItemId, Vec<clean::ItemLink>>,
+    /// The intra-doc links which failed to resolve, as written in the documentation.
+    ///
+    /// Only used for the `--coverage-report` option.
+    pub(crate) unresolved_intra_doc_links: FxHashMap<ItemId, Vec<String>>,
     /// Cfg that have been hidden via #![doc(cfg_hide(...))]
     pub(crate) hidden_cfg: FxHashSet<clean::cfg::Cfg>,
 }
@@ -132,8 +146,12 @@ struct CacheBuilder<'a, 'tcx> {
 }
 
 impl Cache {
-    pub(crate) fn new(access_levels: AccessLevels<DefId>, document_private: bool) -> Self {
-        Cache { access_levels, document_private, ..Cache::default() }
+    pub(crate) fn new(
+        access_levels: AccessLevels<DefId>,
+        document_private: bool,
+        full_text_search: bool,
+    ) -> Self {
+        Cache { access_levels, document_private, full_text_search, ..Cache::default() }
     }
 
     /// Populates the `Cache` with more data. The returned `Crate` will be missing some data that was
@@ -246,6 +264,11 @@ fn fold_item(&mut self, item: clean::Item) -> Option<clean::Item> {
             }
         }
 
+        // Collect the types and traits used by the functions, methods and fields.
+        if item.item_id.is_local() && !self.cache.stripped_mod {
+            self.add_used_by(&item);
+        }
+
         // Index this method for searching later on.
         if let Some(ref s) = item.name.or_else(|| {
             if item.is_stripped() {
@@ -335,6 +358,7 @@ fn fold_item(&mut self, item: clean::Item) -> Option<clean::Item> {
                                 self.cache,
                             ),
                             aliases: item.attrs.get_doc_aliases(),
+                            doc_words: get_doc_words_for_search(&item, self.cache),
                         });
                     }
                 }
@@ -510,6 +534,129 @@ fn fold_item(&mut self, item: clean::Item) -> Option<clean::Item> {
     }
 }
 
+impl<'a, 'tcx> CacheBuilder<'a, 'tcx> {
+    /// Adds `item` to [`Cache::used_by`] for every type and trait mentioned in its signature.
+    fn add_used_by(&mut self, item: &clean::Item) {
+        let parent = match self.cache.parent_stack.last() {
+            // The methods of trait impls would only repeat the signatures of the trait.
+            Some(parent) if parent.is_trait_impl() => return,
+            Some(ParentStackItem::Impl { for_, .. }) => for_.def_id(self.cache),
+            Some(ParentStackItem::Type(item_id)) => item_id.as_def_id(),
+            None => None,
+        };
+        let mut dids = FxHashSet::default();
+        let parent = match *item.kind {
+            clean::FunctionItem(ref f) => {
+                add_fn_def_ids(&f.decl, &f.generics, &mut dids);
+                None
+            }
+            clean::MethodItem(ref f, _) | clean::TyMethodItem(ref f) if parent.is_some() => {
+                add_fn_def_ids(&f.decl, &f.generics, &mut dids);
+                parent
+            }
+            // The fields of enum variants don't have an anchor of their own.
+            clean::StructFieldItem(ref ty)
+                if parent.map_or(false, |did| {
+                    matches!(self.tcx.def_kind(did), DefKind::Struct | DefKind::Union)
+                }) =>
+            {
+                add_type_def_ids(ty, &mut dids);
+                parent
+            }
+            _ => return,
+        };
+        let Some(name) = item.name else { return };
+        let item_id = item.item_id.expect_def_id();
+        for did in dids {
+            // A type using itself in its own methods or fields isn't worth listing.
+            if Some(did) == parent {
+                continue;
+            }
+            self.cache.used_by.entry(did).or_default().push(UsedBy {
+                item_id,
+                name,
+                ty: item.type_(),
+                parent,
+            });
+        }
+    }
+}
+
+/// A local function, method or field mentioning a type or a trait, see [`Cache::used_by`].
+pub(crate) struct UsedBy {
+    pub(crate) item_id: DefId,
+    pub(crate) name: Symbol,
+    pub(crate) ty: ItemType,
+    /// The type or trait of a method or a field, `None` for a function.
+    pub(crate) parent: Option<DefId>,
+}
+
+fn add_fn_def_ids(decl: &clean::FnDecl, generics: &clean::Generics, dids: &mut FxHashSet<DefId>) {
+    add_fn_decl_def_ids(decl, dids);
+    for param in &generics.params {
+        if let clean::GenericParamDefKind::Type { ref bounds, .. } = param.kind {
+            add_bounds_def_ids(bounds, dids);
+        }
+    }
+    for predicate in &generics.where_predicates {
+        if let clean::WherePredicate::BoundPredicate { ref ty, ref bounds, .. } = *predicate {
+            add_type_def_ids(ty, dids);
+            add_bounds_def_ids(bounds, dids);
+        }
+    }
+}
+
+fn add_fn_decl_def_ids(decl: &clean::FnDecl, dids: &mut FxHashSet<DefId>) {
+    for arg in &decl.inputs.values {
+        add_type_def_ids(&arg.type_, dids);
+    }
+    if let clean::FnRetTy::Return(ref ty) = decl.output {
+        add_type_def_ids(ty, dids);
+    }
+}
+
+fn add_bounds_def_ids(bounds: &[clean::GenericBound], dids: &mut FxHashSet<DefId>) {
+    for bound in bounds {
+        if let clean::GenericBound::TraitBound(ref poly_trait, _) = *bound {
+            add_path_def_ids(&poly_trait.trait_, dids);
+        }
+    }
+}
+
+fn add_path_def_ids(path: &clean::Path, dids: &mut FxHashSet<DefId>) {
+    dids.insert(path.def_id());
+    for ty in path.generics().into_iter().flatten() {
+        add_type_def_ids(ty, dids);
+    }
+}
+
+fn add_type_def_ids(ty: &clean::Type, dids: &mut FxHashSet<DefId>) {
+    match *ty {
+        clean::Type::Path { ref path } => add_path_def_ids(path, dids),
+        clean::DynTrait(ref bounds, _) => {
+            for poly_trait in bounds {
+                add_path_def_ids(&poly_trait.trait_, dids);
+            }
+        }
+        clean::ImplTrait(ref bounds) => add_bounds_def_ids(bounds, dids),
+        clean::BareFunction(ref f) => add_fn_decl_def_ids(&f.decl, dids),
+        clean::Tuple(ref tys) => {
+            for ty in tys {
+                add_type_def_ids(ty, dids);
+            }
+        }
+        clean::Slice(ref ty)
+        | clean::Array(ref ty, _)
+        | clean::RawPointer(_, ref ty)
+        | clean::BorrowedRef { type_: ref ty, .. } => add_type_def_ids(ty, dids),
+        clean::QPath { ref self_type, ref trait_, .. } => {
+            add_type_def_ids(self_type, dids);
+            add_path_def_ids(trait_, dids);
+        }
+        clean::Generic(_) | clean::Primitive(_) | clean::Infer => {}
+    }
+}
+
 pub(crate) struct OrphanImplItem {
     pub(crate) parent: DefId,
     pub(crate) item: clean::Item,
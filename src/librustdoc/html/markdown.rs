@@ -147,13 +147,13 @@ pub(crate) fn as_bool(self) -> bool {
 /// Controls whether a line will be hidden or shown in HTML output.
 ///
 /// All lines are used in documentation tests.
-enum Line<'a> {
+pub(crate) enum Line<'a> {
     Hidden(&'a str),
     Shown(Cow<'a, str>),
 }
 
 impl<'a> Line<'a> {
-    fn for_html(self) -> Option<Cow<'a, str>> {
+    pub(crate) fn for_html(self) -> Option<Cow<'a, str>> {
         match self {
             Line::Shown(l) => Some(l),
             Line::Hidden(_) => None,
@@ -173,7 +173,7 @@ fn for_code(self) -> Cow<'a, str> {
 // is done in the single # case. This inconsistency seems okay, if non-ideal. In
 // order to fix it we'd have to iterate to find the first non-# character, and
 // then reallocate to remove it; which would make us return a String.
-fn map_line(s: &str) -> Line<'_> {
+pub(crate) fn map_line(s: &str) -> Line<'_> {
     let trimmed = s.trim();
     if trimmed.starts_with("##") {
         Line::Shown(Cow::Owned(s.replacen("##", "#", 1)))
@@ -845,6 +845,14 @@ pub(crate) struct LangString {
     pub(crate) compile_fail: bool,
     pub(crate) error_codes: Vec<String>,
     pub(crate) edition: Option<Edition>,
+    /// The environment variables set with `env=KEY=VALUE` when running the doctest.
+    pub(crate) env: Vec<(String, String)>,
+    /// The file given with `stdin=PATH` as the standard input of the doctest, relative to the
+    /// directory of the documented source file.
+    pub(crate) stdin: Option<String>,
+    /// The working directory given with `cwd=PATH` to run the doctest in, relative to the
+    /// directory of the documented source file.
+    pub(crate) cwd: Option<String>,
 }
 
 #[derive(Eq, PartialEq, Clone, Debug)]
@@ -866,12 +874,15 @@ fn default() -> Self {
             compile_fail: false,
             error_codes: Vec::new(),
             edition: None,
+            env: Vec::new(),
+            stdin: None,
+            cwd: None,
         }
     }
 }
 
 impl LangString {
-    fn parse_without_check(
+    pub(crate) fn parse_without_check(
         string: &str,
         allow_error_code_check: ErrorCodes,
         enable_per_target_ignores: bool,
@@ -954,6 +965,39 @@ fn parse(
                 x if x.starts_with("edition") => {
                     data.edition = x[7..].parse::<Edition>().ok();
                 }
+                x if x.starts_with("env=") => {
+                    if let Some((key, value)) = x[4..].split_once('=') && !key.is_empty() {
+                        data.env.push((key.to_owned(), value.to_owned()));
+                    } else if let Some(extra) = extra {
+                        extra.error_invalid_codeblock_attr(
+                            &format!("invalid attribute `{}`", x),
+                            "environment variables are set with `env=KEY=VALUE`",
+                        );
+                    }
+                    seen_rust_tags = !seen_other_tags || seen_rust_tags;
+                }
+                x if x.starts_with("stdin=") || x.starts_with("cwd=") => {
+                    let (name, path) = x.split_once('=').unwrap();
+                    if path.is_empty() {
+                        if let Some(extra) = extra {
+                            let example =
+                                if name == "stdin" { "stdin=data/input.txt" } else { "cwd=data" };
+                            extra.error_invalid_codeblock_attr(
+                                &format!("invalid attribute `{}`", x),
+                                &format!(
+                                    "`{}` expects a path relative to the directory of the source \
+                                     file, like `{}`",
+                                    name, example
+                                ),
+                            );
+                        }
+                    } else if name == "stdin" {
+                        data.stdin = Some(path.to_owned());
+                    } else {
+                        data.cwd = Some(path.to_owned());
+                    }
+                    seen_rust_tags = !seen_other_tags || seen_rust_tags;
+                }
                 x if allow_error_code_check && x.starts_with('E') && x.len() == 5 => {
                     if x[1..].parse::<u32>().is_ok() {
                         data.error_codes.push(x.to_owned());
@@ -1249,6 +1293,32 @@ pub(crate) fn plain_text_summary(md: &str) -> String {
     s
 }
 
+/// Renders all of the text of the Markdown `md`, for the full-text search.
+///
+/// - Headings, links, and formatting are stripped.
+/// - Inline code is rendered as-is.
+/// - HTML and code blocks are ignored.
+pub(crate) fn plain_text(md: &str) -> String {
+    let mut s = String::with_capacity(md.len());
+    let mut in_code_block = false;
+
+    for event in Parser::new_ext(md, summary_opts()) {
+        match &event {
+            Event::Text(text) if !in_code_block => s.push_str(text),
+            Event::Code(code) => s.push_str(code),
+            Event::HardBreak | Event::SoftBreak => s.push(' '),
+            Event::Start(Tag::CodeBlock(..)) => in_code_block = true,
+            Event::End(Tag::CodeBlock(..)) => in_code_block = false,
+            Event::End(Tag::Paragraph | Tag::Heading(..) | Tag::Item | Tag::TableCell) => {
+                s.push(' ')
+            }
+            _ => (),
+        }
+    }
+
+    s
+}
+
 #[derive(Debug)]
 pub(crate) struct MarkdownLink {
     pub kind: LinkType,
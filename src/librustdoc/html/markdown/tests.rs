@@ -1,4 +1,4 @@
-use super::{find_testable_code, plain_text_summary, short_markdown_summary};
+use super::{find_testable_code, plain_text, plain_text_summary, short_markdown_summary};
 use super::{ErrorCodes, HeadingOffset, IdMap, Ignore, LangString, Markdown, MarkdownHtml};
 use rustc_span::edition::{Edition, DEFAULT_EDITION};
 
@@ -117,6 +117,28 @@ fn t(lg: LangString) {
         edition: Some(Edition::Edition2018),
         ..Default::default()
     });
+    t(LangString {
+        original: "rust,env=KEY=VAL,env=EMPTY=,env=A=B=C".into(),
+        env: vec![
+            ("KEY".to_string(), "VAL".to_string()),
+            ("EMPTY".to_string(), String::new()),
+            ("A".to_string(), "B=C".to_string()),
+        ],
+        ..Default::default()
+    });
+    t(LangString {
+        original: "stdin=input.txt,cwd=data".into(),
+        stdin: Some("input.txt".to_string()),
+        cwd: Some("data".to_string()),
+        ..Default::default()
+    });
+    t(LangString { original: "env=NOVALUE".into(), ..Default::default() });
+    t(LangString {
+        original: "sh,env=KEY=VAL".into(),
+        env: vec![("KEY".to_string(), "VAL".to_string())],
+        rust: false,
+        ..Default::default()
+    });
 }
 
 #[test]
@@ -275,6 +297,23 @@ fn t(input: &str, expect: &str) {
     );
 }
 
+#[test]
+fn test_plain_text() {
+    fn t(input: &str, expect: &str) {
+        let output = plain_text(input);
+        assert_eq!(output, expect, "original: {}", input);
+    }
+
+    t("", "");
+    t("hello [Rust](https://www.rust-lang.org) :)", "hello Rust :) ");
+    t("code `let x = i32;`", "code let x = i32; ");
+    t("# top header\n\nfollowed by some text", "top header followed by some text ");
+    t("first paragraph\n\nsecond paragraph", "first paragraph second paragraph ");
+    t("- first item\n- second item", "first item second item ");
+    t("before\n\n```\nfn main() {}\n```\n\nafter", "before after ");
+    t("<div>hello</div>", "");
+}
+
 #[test]
 fn test_markdown_html_escape() {
     fn t(input: &str, expect: &str) {
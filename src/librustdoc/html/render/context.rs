@@ -87,6 +87,8 @@ pub(crate) struct SharedContext<'tcx> {
     pub(crate) local_sources: FxHashMap<PathBuf, String>,
     /// Show the memory layout of types in the docs.
     pub(super) show_type_layout: bool,
+    /// List the functions, methods and fields using a type or a trait on its page.
+    pub(super) show_used_by: bool,
     /// The base-URL of the issue tracker for when an item has been tagged with
     /// an issue number.
     pub(super) issue_tracker_base_url: Option<String>,
@@ -406,6 +408,7 @@ fn init(
             unstable_features,
             generate_redirect_map,
             show_type_layout,
+            show_used_by,
             generate_link_to_definition,
             call_locations,
             no_emit_shared,
@@ -490,6 +493,7 @@ fn init(
             errors: receiver,
             redirections: if generate_redirect_map { Some(Default::default()) } else { None },
             show_type_layout,
+            show_used_by,
             span_correspondance_map: matches,
             cache,
             call_locations,
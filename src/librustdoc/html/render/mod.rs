@@ -64,7 +64,7 @@
 
 use crate::clean::{self, ItemId, RenderedLink, SelfTy};
 use crate::error::Error;
-use crate::formats::cache::Cache;
+use crate::formats::cache::{Cache, UsedBy};
 use crate::formats::item_type::ItemType;
 use crate::formats::{AssocItemRender, Impl, RenderMode};
 use crate::html::escape::Escape;
@@ -105,6 +105,8 @@ pub(crate) struct IndexItem {
     pub(crate) parent_idx: Option<usize>,
     pub(crate) search_type: Option<IndexItemFunctionType>,
     pub(crate) aliases: Box<[Symbol]>,
+    /// The stemmed words of the documentation, only filled with `--full-text-search`.
+    pub(crate) doc_words: Vec<String>,
 }
 
 /// A type used for the search index.
@@ -2371,7 +2373,7 @@ fn sidebar_enum(cx: &Context<'_>, buf: &mut Buffer, it: &clean::Item, e: &clean:
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-enum ItemSection {
+pub(crate) enum ItemSection {
     Reexports,
     PrimitiveTypes,
     Modules,
@@ -2400,7 +2402,7 @@ enum ItemSection {
 }
 
 impl ItemSection {
-    const ALL: &'static [Self] = {
+    pub(crate) const ALL: &'static [Self] = {
         use ItemSection::*;
         // NOTE: The order here affects the order in the UI.
         &[
@@ -2462,7 +2464,7 @@ fn id(self) -> &'static str {
         }
     }
 
-    fn name(self) -> &'static str {
+    pub(crate) fn name(self) -> &'static str {
         match self {
             Self::Reexports => "Re-exports",
             Self::Modules => "Modules",
@@ -2493,7 +2495,7 @@ fn name(self) -> &'static str {
     }
 }
 
-fn item_ty_to_section(ty: ItemType) -> ItemSection {
+pub(crate) fn item_ty_to_section(ty: ItemType) -> ItemSection {
     match ty {
         ItemType::ExternCrate | ItemType::Import => ItemSection::Reexports,
         ItemType::Module => ItemSection::Modules,
@@ -2624,6 +2626,39 @@ fn collect_paths_for_type(first_ty: clean::Type, cache: &Cache) -> Vec<String> {
     out
 }
 
+/// Returns the links to the local items using the type or trait `did`, relative to the current
+/// page and sorted, for its "Used by" section.
+fn used_by_links(did: DefId, cx: &Context<'_>) -> Vec<String> {
+    let Some(used_by) = cx.cache().used_by.get(&did) else { return Vec::new() };
+    let mut links =
+        used_by.iter().filter_map(|used_by| used_by_link(used_by, cx)).collect::<Vec<_>>();
+    links.sort();
+    links.dedup();
+    links
+}
+
+/// Renders the link to a function, method or field using a type or a trait. Returns `None` if
+/// the item isn't documented.
+fn used_by_link(used_by: &UsedBy, cx: &Context<'_>) -> Option<String> {
+    let (url, fqp) = match used_by.parent {
+        Some(parent) => {
+            let (url, _, mut fqp) = href(parent, cx).ok()?;
+            fqp.push(used_by.name);
+            (format!("{}#{}.{}", url, used_by.ty, used_by.name), fqp)
+        }
+        None => {
+            let (url, _, fqp) = href(used_by.item_id, cx).ok()?;
+            (url, fqp)
+        }
+    };
+    Some(format!(
+        "<a class=\"{}\" href=\"{}\">{}</a>",
+        used_by.ty,
+        url,
+        join_with_double_colon(&fqp)
+    ))
+}
+
 const MAX_FULL_EXAMPLES: usize = 5;
 const NUM_VISIBLE_LINES: usize = 10;
 
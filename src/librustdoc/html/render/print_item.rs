@@ -19,8 +19,8 @@
 use super::{
     collect_paths_for_type, document, ensure_trailing_slash, item_ty_to_section,
     notable_traits_decl, render_assoc_item, render_assoc_items, render_attributes_in_code,
-    render_attributes_in_pre, render_impl, render_stability_since_raw, write_srclink,
-    AssocItemLink, Context, ImplRenderingParameters,
+    render_attributes_in_pre, render_impl, render_stability_since_raw, used_by_links,
+    write_srclink, AssocItemLink, Context, ImplRenderingParameters,
 };
 use crate::clean;
 use crate::config::ModuleSorting;
@@ -1014,6 +1014,8 @@ fn trait_item(w: &mut Buffer, cx: &mut Context<'_>, m: &clean::Item, t: &clean::
         "<script type=\"text/javascript\" src=\"{src}\" data-ignore-extern-crates=\"{extern_crates}\" async></script>",
         src = js_src_path.finish(),
     );
+
+    document_used_by(w, cx, it);
 }
 
 fn item_trait_alias(w: &mut Buffer, cx: &mut Context<'_>, it: &clean::Item, t: &clean::TraitAlias) {
@@ -1139,6 +1141,7 @@ fn item_union(w: &mut Buffer, cx: &mut Context<'_>, it: &clean::Item, s: &clean:
     }
     let def_id = it.item_id.expect_def_id();
     render_assoc_items(w, cx, it, def_id, AssocItemRender::All);
+    document_used_by(w, cx, it);
     document_type_layout(w, cx, def_id);
 }
 
@@ -1304,6 +1307,7 @@ fn item_enum(w: &mut Buffer, cx: &mut Context<'_>, it: &clean::Item, e: &clean::
     }
     let def_id = it.item_id.expect_def_id();
     render_assoc_items(w, cx, it, def_id, AssocItemRender::All);
+    document_used_by(w, cx, it);
     document_type_layout(w, cx, def_id);
 }
 
@@ -1460,6 +1464,7 @@ fn item_struct(w: &mut Buffer, cx: &mut Context<'_>, it: &clean::Item, s: &clean
     }
     let def_id = it.item_id.expect_def_id();
     render_assoc_items(w, cx, it, def_id, AssocItemRender::All);
+    document_used_by(w, cx, it);
     document_type_layout(w, cx, def_id);
 }
 
@@ -1855,6 +1860,46 @@ struct update syntax will not work.",
     }
 }
 
+fn document_used_by(w: &mut Buffer, cx: &mut Context<'_>, it: &clean::Item) {
+    if !cx.shared.show_used_by {
+        return;
+    }
+
+    let id = cx.derive_id("used-by".to_owned());
+    write!(
+        w,
+        "<h2 id=\"{0}\" class=\"small-section-header\">\
+            Used by<a href=\"#{0}\" class=\"anchor\"></a>\
+         </h2><div class=\"item-list\" id=\"used-by-list\">",
+        id
+    );
+    let def_id = it.item_id.expect_def_id();
+    for link in used_by_links(def_id, cx) {
+        write!(w, "<div class=\"used-by\">{}</div>", link);
+    }
+    w.write_str("</div>");
+
+    // The items of the crates depending on this one are added by a JavaScript file, which works
+    // like the one listing the implementors of a trait (see `item_trait`).
+    let cache = cx.cache();
+    let mut js_src_path: UrlPartsBuilder =
+        std::iter::repeat("..").take(cx.current.len()).chain(std::iter::once("used-by")).collect();
+    if let Some(fqp) =
+        cache.exact_paths.get(&def_id).or_else(|| cache.external_paths.get(&def_id).map(|s| &s.0))
+    {
+        js_src_path.extend(fqp[..fqp.len() - 1].iter().copied());
+        js_src_path.push_fmt(format_args!("{}.{}.js", it.type_(), fqp.last().unwrap()));
+    } else {
+        js_src_path.extend(cx.current.iter().copied());
+        js_src_path.push_fmt(format_args!("{}.{}.js", it.type_(), it.name.unwrap()));
+    }
+    write!(
+        w,
+        "<script type=\"text/javascript\" src=\"{src}\" async></script>",
+        src = js_src_path.finish(),
+    );
+}
+
 fn document_type_layout(w: &mut Buffer, cx: &Context<'_>, ty_def_id: DefId) {
     fn write_size_of_layout(w: &mut Buffer, layout: Layout<'_>, tag_size: u64) {
         if layout.abi().is_unsized() {
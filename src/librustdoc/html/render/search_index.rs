@@ -14,7 +14,7 @@
 use crate::formats::cache::{Cache, OrphanImplItem};
 use crate::formats::item_type::ItemType;
 use crate::html::format::join_with_double_colon;
-use crate::html::markdown::short_markdown_summary;
+use crate::html::markdown::{plain_text, short_markdown_summary};
 use crate::html::render::{IndexItem, IndexItemFunctionType, RenderType, RenderTypeId};
 
 /// Builds the search index from the collected metadata
@@ -42,6 +42,7 @@ pub(crate) fn build_index<'tcx>(
                 parent_idx: None,
                 search_type: get_function_type_for_search(item, tcx, impl_generics.as_ref(), cache),
                 aliases: item.attrs.get_doc_aliases(),
+                doc_words: get_doc_words_for_search(item, cache),
             });
         }
     }
@@ -51,6 +52,8 @@ pub(crate) fn build_index<'tcx>(
         .doc_value()
         .map_or_else(String::new, |s| short_markdown_summary(&s, &krate.module.link_names(cache)));
 
+    let crate_doc_words = get_doc_words_for_search(&krate.module, cache);
+
     // Aliases added through `#[doc(alias = "...")]`. Since a few items can have the same alias,
     // we need the alias element to have an array of items.
     let mut aliases: BTreeMap<String, Vec<usize>> = BTreeMap::new();
@@ -70,6 +73,25 @@ pub(crate) fn build_index<'tcx>(
         }
     }
 
+    // Set up the full-text index, which maps every word of the documentation to the items it
+    // appears in. 0 is the crate itself, the items are one-indexed.
+    let mut doc_words: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+    for word in crate_doc_words {
+        doc_words.entry(word).or_default().push(0);
+    }
+    for (i, item) in cache.search_index.iter_mut().enumerate() {
+        for word in std::mem::take(&mut item.doc_words) {
+            doc_words.entry(word).or_default().push(i + 1);
+        }
+    }
+    // The indexes are increasing, so storing the differences between them keeps the numbers
+    // small.
+    for indexes in doc_words.values_mut() {
+        for i in (1..indexes.len()).rev() {
+            indexes[i] -= indexes[i - 1];
+        }
+    }
+
     // Reduce `DefId` in paths into smaller sequential numbers,
     // and prune the paths that do not appear in the index.
     let mut lastpath = "";
@@ -188,6 +210,9 @@ struct CrateData<'a> {
         //
         // To be noted: the `usize` elements are indexes to `items`.
         aliases: &'a BTreeMap<String, Vec<usize>>,
+        // The words of the documentation, with the differences between the indexes of the items
+        // they appear in. Empty unless `--full-text-search` is passed.
+        doc_words: &'a BTreeMap<String, Vec<usize>>,
     }
 
     impl<'a> Serialize for CrateData<'a> {
@@ -196,8 +221,9 @@ fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
             S: Serializer,
         {
             let has_aliases = !self.aliases.is_empty();
-            let mut crate_data =
-                serializer.serialize_struct("CrateData", if has_aliases { 9 } else { 8 })?;
+            let has_doc_words = !self.doc_words.is_empty();
+            let mut crate_data = serializer
+                .serialize_struct("CrateData", 8 + has_aliases as usize + has_doc_words as usize)?;
             crate_data.serialize_field("doc", &self.doc)?;
             crate_data.serialize_field(
                 "t",
@@ -269,6 +295,9 @@ fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
             if has_aliases {
                 crate_data.serialize_field("a", &self.aliases)?;
             }
+            if has_doc_words {
+                crate_data.serialize_field("w", &self.doc_words)?;
+            }
             crate_data.end()
         }
     }
@@ -282,6 +311,7 @@ fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
             items: crate_items,
             paths: crate_paths,
             aliases: &aliases,
+            doc_words: &doc_words,
         })
         .expect("failed serde conversion")
         // All these `replace` calls are because we have to go through JS string for JSON content.
@@ -292,6 +322,52 @@ fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     )
 }
 
+/// Words which are too common in documentation to be worth indexing.
+///
+/// This must be kept in sync with `STOP_WORDS` in `search.js`.
+const STOP_WORDS: &[&str] = &[
+    "an", "and", "are", "as", "at", "be", "by", "for", "from", "if", "in", "is", "it", "its", "of",
+    "on", "or", "that", "the", "this", "to", "was", "will", "with",
+];
+
+/// Returns the words of the documentation of `item` to put in the full-text index, if it's
+/// enabled.
+pub(crate) fn get_doc_words_for_search(item: &clean::Item, cache: &Cache) -> Vec<String> {
+    if !cache.full_text_search {
+        return Vec::new();
+    }
+    let Some(doc) = item.doc_value() else {
+        return Vec::new();
+    };
+    let text = plain_text(&doc).to_lowercase();
+    let mut words: Vec<String> = text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.chars().count() >= 2 && !STOP_WORDS.contains(word))
+        .map(stem)
+        .collect();
+    words.sort_unstable();
+    words.dedup();
+    words
+}
+
+/// Strips the most common English suffixes from a lowercase word, so that the different forms
+/// of a word are indexed together ("parse", "parses" and "parsing" all become "pars").
+///
+/// This must be kept in sync with `stem` in `search.js`.
+fn stem(word: &str) -> String {
+    const SUFFIXES: &[(&str, &str)] =
+        &[("ies", "y"), ("ing", ""), ("ed", ""), ("es", ""), ("ly", ""), ("s", ""), ("e", "")];
+    for &(suffix, replacement) in SUFFIXES {
+        if let Some(stem) = word.strip_suffix(suffix) {
+            // Keep words like "class" intact.
+            if stem.chars().count() >= 3 && !(suffix == "s" && stem.ends_with('s')) {
+                return format!("{}{}", stem, replacement);
+            }
+        }
+    }
+    word.to_owned()
+}
+
 pub(crate) fn get_function_type_for_search<'tcx>(
     item: &clean::Item,
     tcx: TyCtxt<'tcx>,
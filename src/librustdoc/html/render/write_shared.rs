@@ -12,11 +12,14 @@
 use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use serde::Serialize;
 
-use super::{collect_paths_for_type, ensure_trailing_slash, Context, BASIC_KEYWORDS};
+use super::{
+    collect_paths_for_type, ensure_trailing_slash, used_by_links, Context, BASIC_KEYWORDS,
+};
 use crate::clean::Crate;
 use crate::config::{EmitType, RenderOptions};
 use crate::docfs::PathError;
 use crate::error::Error;
+use crate::formats::item_type::ItemType;
 use crate::html::{layout, static_files};
 use crate::{try_err, try_none};
 
@@ -589,5 +592,68 @@ struct Implementor {
         v.push_str("})()");
         cx.shared.fs.write(mydst, v)?;
     }
+
+    // Update the list of the items using types and traits, in the same way as the implementors
+    if cx.shared.show_used_by {
+        let dst = cx.dst.join("used-by");
+        let has_used_by = |ty: ItemType| {
+            matches!(ty, ItemType::Struct | ItemType::Enum | ItemType::Union | ItemType::Trait)
+        };
+        // The pages of the local types and traits always load their file, so create it even if
+        // no item uses them.
+        let local_dids = cache
+            .paths
+            .iter()
+            .filter(|(did, (_, ty))| did.is_local() && has_used_by(*ty))
+            .map(|(&did, _)| did);
+        let dids = cache.used_by.keys().copied().chain(local_dids).collect::<FxHashSet<_>>();
+        for did in dids {
+            let (remote_path, remote_item_type) = match cache.exact_paths.get(&did) {
+                Some(p) => match cache.paths.get(&did).or_else(|| cache.external_paths.get(&did)) {
+                    Some((_, t)) => (p, t),
+                    None => continue,
+                },
+                None => match cache.external_paths.get(&did) {
+                    Some((p, t)) => (p, t),
+                    None => continue,
+                },
+            };
+            if !has_used_by(*remote_item_type) {
+                continue;
+            }
+
+            let used_by = format!(
+                r#"used_by["{}"] = {};"#,
+                krate.name(cx.tcx()),
+                serde_json::to_string(&used_by_links(did, cx)).unwrap()
+            );
+
+            let mut mydst = dst.clone();
+            for part in &remote_path[..remote_path.len() - 1] {
+                mydst.push(part.to_string());
+            }
+            cx.shared.ensure_dir(&mydst)?;
+            mydst.push(&format!("{}.{}.js", remote_item_type, remote_path[remote_path.len() - 1]));
+
+            let (mut all_used_by, _) =
+                try_err!(collect(&mydst, krate.name(cx.tcx()).as_str(), "used_by"), &mydst);
+            all_used_by.push(used_by);
+            all_used_by.sort();
+
+            let mut v = String::from("(function() {var used_by = {};\n");
+            for used_by in &all_used_by {
+                writeln!(v, "{}", *used_by).unwrap();
+            }
+            v.push_str(
+                "if (window.register_used_by) {\
+                     window.register_used_by(used_by);\
+                 } else {\
+                     window.pending_used_by = used_by;\
+                 }",
+            );
+            v.push_str("})()");
+            cx.shared.fs.write(mydst, v)?;
+        }
+    }
     Ok(())
 }
@@ -60,6 +60,7 @@ let Row;
  *    in_args: Array<Object>,
  *    returned: Array<Object>,
  *    others: Array<Object>,
+ *    in_docs: Array<Object>,
  *    query: ParsedQuery,
  * }}
  */
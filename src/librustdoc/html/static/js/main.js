@@ -227,9 +227,10 @@ function loadCss(cssFileName) {
         // 0 for "In Names"
         // 1 for "In Parameters"
         // 2 for "In Return Types"
+        // 3 for "In Documentation" (only for crates documented with `--full-text-search`)
         currentTab: 0,
         // tab and back preserves the element that was focused.
-        focusedByTab: [null, null, null],
+        focusedByTab: [null, null, null, null],
         clearInputTimeout: () => {
             if (searchState.timeout !== null) {
                 clearTimeout(searchState.timeout);
@@ -579,6 +580,35 @@ function loadCss(cssFileName) {
         window.register_implementors(window.pending_implementors);
     }
 
+    window.register_used_by = usedBy => {
+        const list = document.getElementById("used-by-list");
+        if (!list) {
+            return;
+        }
+        for (const lib of Object.getOwnPropertyNames(usedBy)) {
+            // The items of the current crate are already in the HTML.
+            if (lib === window.currentCrate) {
+                continue;
+            }
+            for (const text of usedBy[lib]) {
+                const entry = document.createElement("div");
+                addClass(entry, "used-by");
+                entry.innerHTML = text;
+                onEachLazy(entry.getElementsByTagName("a"), elem => {
+                    const href = elem.getAttribute("href");
+
+                    if (href && href.indexOf("http") !== 0) {
+                        elem.setAttribute("href", window.rootPath + href);
+                    }
+                });
+                list.appendChild(entry);
+            }
+        }
+    };
+    if (window.pending_used_by) {
+        window.register_used_by(window.pending_used_by);
+    }
+
     function addSidebarCrates() {
         if (!window.ALL_CRATES) {
             return;
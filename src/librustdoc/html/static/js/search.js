@@ -121,6 +121,56 @@ function initSearch(rawSearchIndex) {
     let searchIndex;
     let currentResults;
     const ALIASES = Object.create(null);
+    /**
+     * Maps the stemmed words of the documentation to the ids of the items they appear in, for
+     * the crates documented with `--full-text-search`.
+     */
+    const DOC_WORDS = Object.create(null);
+    // Words which are too common in documentation to be worth indexing. This must be kept in
+    // sync with `STOP_WORDS` in `search_index.rs`.
+    const STOP_WORDS = [
+        "an", "and", "are", "as", "at", "be", "by", "for", "from", "if", "in", "is", "it", "its",
+        "of", "on", "or", "that", "the", "this", "to", "was", "will", "with",
+    ];
+
+    /**
+     * Strips the most common English suffixes from a lowercase word. This must be kept in sync
+     * with `stem` in `search_index.rs`.
+     *
+     * @param {string} word
+     *
+     * @return {string}
+     */
+    function stem(word) {
+        const suffixes = [
+            ["ies", "y"], ["ing", ""], ["ed", ""], ["es", ""], ["ly", ""], ["s", ""], ["e", ""],
+        ];
+        for (const [suffix, replacement] of suffixes) {
+            if (word.endsWith(suffix)) {
+                const base = word.slice(0, word.length - suffix.length);
+                // Keep words like "class" intact.
+                if (Array.from(base).length >= 3 && !(suffix === "s" && base.endsWith("s"))) {
+                    return base + replacement;
+                }
+            }
+        }
+        return word;
+    }
+
+    /**
+     * Splits a text into the stemmed words used by the full-text index, the same way as
+     * `get_doc_words_for_search` in `search_index.rs`.
+     *
+     * @param {string} text
+     *
+     * @return {Array<string>}
+     */
+    function docWords(text) {
+        return text.toLowerCase()
+            .split(/[^\p{Alphabetic}\p{N}]+/u)
+            .filter(word => Array.from(word).length >= 2 && STOP_WORDS.indexOf(word) === -1)
+            .map(stem);
+    }
 
     function isWhitespace(c) {
         return " \t\n\r".indexOf(c) !== -1;
@@ -699,15 +749,23 @@ function initSearch(rawSearchIndex) {
      * @param {Array<Result>} results_in_args
      * @param {Array<Result>} results_returned
      * @param {Array<Result>} results_in_args
+     * @param {Array<Result>} results_in_docs
      * @param {ParsedQuery} parsedQuery
      *
      * @return {ResultsTable}
      */
-    function createQueryResults(results_in_args, results_returned, results_others, parsedQuery) {
+    function createQueryResults(
+        results_in_args,
+        results_returned,
+        results_others,
+        results_in_docs,
+        parsedQuery
+    ) {
         return {
             "in_args": results_in_args,
             "returned": results_returned,
             "others": results_others,
+            "in_docs": results_in_docs,
             "query": parsedQuery,
         };
     }
@@ -724,6 +782,7 @@ function initSearch(rawSearchIndex) {
      */
     function execQuery(parsedQuery, searchWords, filterCrates, currentCrate) {
         const results_others = {}, results_in_args = {}, results_returned = {};
+        const results_in_docs = {};
 
         function transformResults(results) {
             const duplicates = {};
@@ -1423,14 +1482,47 @@ function initSearch(rawSearchIndex) {
             }
         }
 
+        /**
+         * Looks for the items whose documentation contains all the words of the query. The query
+         * doesn't need to be valid for this search, since it's only split into words.
+         */
+        function searchDocs() {
+            const words = docWords(parsedQuery.original);
+            if (words.length === 0) {
+                return;
+            }
+            let ids = null;
+            for (const word of words) {
+                const wordIds = DOC_WORDS[word];
+                if (wordIds === undefined) {
+                    return;
+                }
+                if (ids === null) {
+                    ids = wordIds;
+                } else {
+                    const wordIdsSet = new Set(wordIds);
+                    ids = ids.filter(id => wordIdsSet.has(id));
+                }
+            }
+            for (const id of ids) {
+                const row = searchIndex[id];
+                if (filterCrates !== null && row.crate !== filterCrates) {
+                    continue;
+                }
+                addIntoResults(results_in_docs, row.id, id, -1, 0);
+            }
+        }
+
         if (parsedQuery.error === null) {
             innerRunQuery();
         }
+        searchDocs();
 
         const ret = createQueryResults(
             sortResults(results_in_args, true, currentCrate),
             sortResults(results_returned, true, currentCrate),
             sortResults(results_others, false, currentCrate),
+            sortResults(results_in_docs, true, currentCrate),
             parsedQuery);
         handleAliases(ret, parsedQuery.original.replace(/"/g, ""), filterCrates, currentCrate);
         if (parsedQuery.error !== null && ret.others.length !== 0) {
@@ -1479,7 +1571,9 @@ function initSearch(rawSearchIndex) {
     }
 
     function nextTab(direction) {
-        const next = (searchState.currentTab + direction + 3) % searchState.focusedByTab.length;
+        // The documentation tab is only rendered when a full-text index is available.
+        const nbTabs = document.querySelectorAll("#titles > button").length;
+        const next = (searchState.currentTab + direction + nbTabs) % nbTabs;
         searchState.focusedByTab[searchState.currentTab] = document.activeElement;
         printTab(next);
         focusSearchResult();
@@ -1690,22 +1784,7 @@ function initSearch(rawSearchIndex) {
         const ret_others = addTab(results.others, results.query, true);
         const ret_in_args = addTab(results.in_args, results.query, false);
         const ret_returned = addTab(results.returned, results.query, false);
-
-        // Navigate to the relevant tab if the current tab is empty, like in case users search
-        // for "-> String". If they had selected another tab previously, they have to click on
-        // it again.
-        let currentTab = searchState.currentTab;
-        if ((currentTab === 0 && ret_others[1] === 0) ||
-                (currentTab === 1 && ret_in_args[1] === 0) ||
-                (currentTab === 2 && ret_returned[1] === 0)) {
-            if (ret_others[1] !== 0) {
-                currentTab = 0;
-            } else if (ret_in_args[1] !== 0) {
-                currentTab = 1;
-            } else if (ret_returned[1] !== 0) {
-                currentTab = 2;
-            }
-        }
+        const ret_in_docs = addTab(results.in_docs, results.query, false);
 
         let crates = "";
         const crates_list = Object.keys(rawSearchIndex);
@@ -1726,34 +1805,49 @@ function initSearch(rawSearchIndex) {
         let output = "<div id=\"search-settings\">" +
             `<h1 class="search-results-title">Results for ${escape(results.query.userQuery)}` +
             `${typeFilter}</h1>${crates}</div>`;
+        let currentTab = searchState.currentTab;
+        // An array of [title, [results element, number of results]].
+        const tabs = [];
         if (results.query.error !== null) {
             output += `<h3>Query parser error: "${results.query.error}".</h3>`;
-            output += "<div id=\"titles\">" +
-                makeTabHeader(0, "In Names", ret_others[1]) +
-                "</div>";
+            tabs.push(["In Names", ret_others]);
             currentTab = 0;
         } else if (results.query.foundElems <= 1 && results.query.returned.length === 0) {
-            output += "<div id=\"titles\">" +
-                makeTabHeader(0, "In Names", ret_others[1]) +
-                makeTabHeader(1, "In Parameters", ret_in_args[1]) +
-                makeTabHeader(2, "In Return Types", ret_returned[1]) +
-                "</div>";
+            tabs.push(["In Names", ret_others]);
+            tabs.push(["In Parameters", ret_in_args]);
+            tabs.push(["In Return Types", ret_returned]);
         } else {
             const signatureTabTitle =
                 results.query.elems.length === 0 ? "In Function Return Types" :
                 results.query.returned.length === 0 ? "In Function Parameters" :
                 "In Function Signatures";
-            output += "<div id=\"titles\">" +
-                makeTabHeader(0, signatureTabTitle, ret_others[1]) +
-                "</div>";
+            tabs.push([signatureTabTitle, ret_others]);
             currentTab = 0;
         }
+        // The documentation tab is only displayed if at least one crate has a full-text index.
+        if (Object.keys(DOC_WORDS).length !== 0) {
+            tabs.push(["In Documentation", ret_in_docs]);
+        }
+
+        // Navigate to the relevant tab if the current tab is empty, like in case users search
+        // for "-> String". If they had selected another tab previously, they have to click on
+        // it again.
+        if (currentTab >= tabs.length || tabs[currentTab][1][1] === 0) {
+            const firstNonEmpty = tabs.findIndex(tab => tab[1][1] !== 0);
+            currentTab = firstNonEmpty === -1 ? 0 : firstNonEmpty;
+        }
+
+        output += "<div id=\"titles\">";
+        tabs.forEach(([title, ret], tabNb) => {
+            output += makeTabHeader(tabNb, title, ret[1]);
+        });
+        output += "</div>";
 
         const resultsElem = document.createElement("div");
         resultsElem.id = "results";
-        resultsElem.appendChild(ret_others[0]);
-        resultsElem.appendChild(ret_in_args[0]);
-        resultsElem.appendChild(ret_returned[0]);
+        for (const [, ret] of tabs) {
+            resultsElem.appendChild(ret[0]);
+        }
 
         search.innerHTML = output;
         const crateSearch = document.getElementById("crate-search");
@@ -1957,11 +2051,17 @@ function initSearch(rawSearchIndex) {
              *
              * `doc` contains the description of the crate.
              *
+             * `w` is only present when the crate was documented with `--full-text-search`. It
+             * maps the words of the documentation to the items they appear in: `0` is the crate
+             * itself and `1` the first item of the n/t/d/q/i/f arrays. To keep the index small,
+             * every offset but the first one is stored as the difference with the previous one.
+             *
              * `p` is a list of path/type pairs. It is used for parents and function parameters.
              *
              * @type {{
              *   doc: string,
              *   a: Object,
+             *   w: Object,
              *   n: Array<string>,
              *   t: Array<Number>,
              *   d: Array<string>,
@@ -2073,6 +2173,26 @@ function initSearch(rawSearchIndex) {
                     }
                 }
             }
+
+            const docWordsOffsets = crateCorpus.w;
+            if (docWordsOffsets) {
+                // `currentIndex` already accounts for the crate row, which is at offset 0.
+                const crateIndex = currentIndex - 1;
+                for (const doc_word in docWordsOffsets) {
+                    if (!hasOwnPropertyRustdoc(docWordsOffsets, doc_word)) {
+                        continue;
+                    }
+
+                    if (!hasOwnPropertyRustdoc(DOC_WORDS, doc_word)) {
+                        DOC_WORDS[doc_word] = [];
+                    }
+                    let offset = 0;
+                    for (const delta of docWordsOffsets[doc_word]) {
+                        offset += delta;
+                        DOC_WORDS[doc_word].push(crateIndex + offset);
+                    }
+                }
+            }
             currentIndex += crateSize;
         }
         return searchWords;
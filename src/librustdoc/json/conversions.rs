@@ -11,6 +11,7 @@
 use rustc_hir::{def::CtorKind, def_id::DefId};
 use rustc_middle::ty::{self, TyCtxt};
 use rustc_span::{Pos, Symbol};
+use rustc_target::abi;
 use rustc_target::spec::abi::Abi as RustcAbi;
 
 use rustdoc_json_types::*;
@@ -88,6 +89,53 @@ fn convert_span(&self, span: clean::Span) -> Option<Span> {
         }
     }
 
+    pub(super) fn convert_layout(&self, def_id: DefId) -> Option<Layout> {
+        let param_env = self.tcx.param_env(def_id);
+        let ty_layout = self.tcx.layout_of(param_env.and(self.tcx.type_of(def_id))).ok()?;
+        let size = |layout: abi::Layout<'_>, tag_size: u64| {
+            (!layout.abi().is_unsized()).then(|| layout.size().bytes().saturating_sub(tag_size))
+        };
+        let mut variants = Vec::new();
+        if let abi::Variants::Multiple { variants: layouts, tag, tag_encoding, .. } =
+            ty_layout.layout.variants()
+        {
+            let ty::Adt(adt, _) = ty_layout.ty.kind() else { return None };
+            let tag_size = match (tag_encoding, tag.primitive()) {
+                (abi::TagEncoding::Niche { .. }, _) => 0,
+                (_, abi::Primitive::Int(i, _)) => i.size().bytes(),
+                _ => return None,
+            };
+            for (index, layout) in layouts.iter_enumerated() {
+                variants.push(VariantLayout {
+                    id: from_item_id(adt.variant(index).def_id.into(), self.tcx),
+                    size: size(*layout, tag_size).unwrap_or_default(),
+                });
+            }
+        }
+        Some(Layout {
+            size: size(ty_layout.layout, 0),
+            align: ty_layout.layout.align().abi.bytes(),
+            variants,
+        })
+    }
+
+    /// Fills in the variance of the lifetime and type parameters of a struct, enum or union.
+    pub(super) fn add_variances(&self, generics: &mut Generics, def_id: DefId) {
+        let ty_generics = self.tcx.generics_of(def_id);
+        let variances = self.tcx.variances_of(def_id);
+        for param in &mut generics.params {
+            if let GenericParamDefKind::Const { .. } = param.kind {
+                continue;
+            }
+            param.variance = ty_generics
+                .params
+                .iter()
+                .find(|p| p.name.as_str() == param.name)
+                .and_then(|p| variances.get(p.index as usize))
+                .map(|&v| from_variance(v));
+        }
+    }
+
     fn convert_visibility(&self, v: clean::Visibility) -> Visibility {
         use clean::Visibility::*;
         match v {
@@ -298,6 +346,7 @@ fn from_tcx(struct_: clean::Struct, tcx: TyCtxt<'_>) -> Self {
             fields_stripped,
             fields: ids(fields, tcx),
             impls: Vec::new(), // Added in JsonRenderer::item
+            layout: None,      // Added in JsonRenderer::item
         }
     }
 }
@@ -311,6 +360,7 @@ fn from_tcx(union_: clean::Union, tcx: TyCtxt<'_>) -> Self {
             fields_stripped,
             fields: ids(fields, tcx),
             impls: Vec::new(), // Added in JsonRenderer::item
+            layout: None,      // Added in JsonRenderer::item
         }
     }
 }
@@ -323,6 +373,15 @@ pub(crate) fn from_ctor_kind(struct_type: CtorKind) -> StructType {
     }
 }
 
+fn from_variance(variance: ty::Variance) -> Variance {
+    match variance {
+        ty::Variance::Covariant => Variance::Covariant,
+        ty::Variance::Invariant => Variance::Invariant,
+        ty::Variance::Contravariant => Variance::Contravariant,
+        ty::Variance::Bivariant => Variance::Bivariant,
+    }
+}
+
 pub(crate) fn from_fn_header(header: &rustc_hir::FnHeader) -> Header {
     Header {
         async_: header.is_async(),
@@ -365,6 +424,7 @@ fn from_tcx(generic_param: clean::GenericParamDef, tcx: TyCtxt<'_>) -> Self {
         GenericParamDef {
             name: generic_param.name.to_string(),
             kind: generic_param.kind.into_tcx(tcx),
+            variance: None, // Added in JsonRenderer::item
         }
     }
 }
@@ -636,6 +696,7 @@ fn from_tcx(enum_: clean::Enum, tcx: TyCtxt<'_>) -> Self {
             variants_stripped,
             variants: ids(variants, tcx),
             impls: Vec::new(), // Added in JsonRenderer::item
+            layout: None,      // Added in JsonRenderer::item
         }
     }
 }
@@ -650,6 +711,7 @@ fn from_tcx(struct_: clean::VariantStruct, tcx: TyCtxt<'_>) -> Self {
             fields_stripped,
             fields: ids(fields, tcx),
             impls: Vec::new(),
+            layout: None,
         }
     }
 }
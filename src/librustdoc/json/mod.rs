@@ -196,15 +196,24 @@ fn item(&mut self, item: clean::Item) -> Result<(), Error> {
                     false
                 }
                 types::ItemEnum::Struct(ref mut s) => {
-                    s.impls = self.get_impls(item_id.expect_def_id());
+                    let def_id = item_id.expect_def_id();
+                    s.impls = self.get_impls(def_id);
+                    s.layout = self.convert_layout(def_id);
+                    self.add_variances(&mut s.generics, def_id);
                     false
                 }
                 types::ItemEnum::Enum(ref mut e) => {
-                    e.impls = self.get_impls(item_id.expect_def_id());
+                    let def_id = item_id.expect_def_id();
+                    e.impls = self.get_impls(def_id);
+                    e.layout = self.convert_layout(def_id);
+                    self.add_variances(&mut e.generics, def_id);
                     false
                 }
                 types::ItemEnum::Union(ref mut u) => {
-                    u.impls = self.get_impls(item_id.expect_def_id());
+                    let def_id = item_id.expect_def_id();
+                    u.impls = self.get_impls(def_id);
+                    u.layout = self.convert_layout(def_id);
+                    self.add_variances(&mut u.generics, def_id);
                     false
                 }
 
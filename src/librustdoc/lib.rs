@@ -120,6 +120,7 @@ macro_rules! map {
 mod markdown;
 mod passes;
 mod scrape_examples;
+mod text;
 mod theme;
 mod visit;
 mod visit_ast;
@@ -459,7 +460,7 @@ fn opts() -> Vec<RustcOptGroup> {
                 "",
                 "error-format",
                 "How errors and other messages are produced",
-                "human|json|short",
+                "human|json|short|sarif",
             )
         }),
         unstable("diagnostic-width", |o| {
@@ -528,6 +529,14 @@ fn opts() -> Vec<RustcOptGroup> {
                 "calculate percentage of public items with documentation",
             )
         }),
+        unstable("coverage-report", |o| {
+            o.optflagmulti(
+                "",
+                "coverage-report",
+                "with --show-coverage, list the items missing documentation or examples, \
+                 the unresolved intra-doc links and the ignored doctests",
+            )
+        }),
         unstable("enable-per-target-ignores", |o| {
             o.optflagmulti(
                 "",
@@ -576,9 +585,30 @@ fn opts() -> Vec<RustcOptGroup> {
         unstable("show-type-layout", |o| {
             o.optflagmulti("", "show-type-layout", "Include the memory layout of types in the docs")
         }),
+        unstable("show-used-by", |o| {
+            o.optflagmulti(
+                "",
+                "show-used-by",
+                "List the functions, methods and fields using a type or a trait in its docs",
+            )
+        }),
+        unstable("full-text-search", |o| {
+            o.optflagmulti(
+                "",
+                "full-text-search",
+                "Include the text of the documentation in the search index",
+            )
+        }),
         unstable("nocapture", |o| {
             o.optflag("", "nocapture", "Don't capture stdout and stderr of tests")
         }),
+        unstable("merge-doctests", |o| {
+            o.optflag(
+                "",
+                "merge-doctests",
+                "Compile compatible doctests together into a single test binary",
+            )
+        }),
         unstable("generate-link-to-definition", |o| {
             o.optflag(
                 "",
@@ -763,6 +793,7 @@ fn main_options(options: config::Options) -> MainResult {
     // need to move these items separately because we lose them by the time the closure is called,
     // but we can't create the Handler ahead of time because it's not Send
     let show_coverage = options.show_coverage;
+    let coverage_report = options.coverage_report;
     let run_check = options.run_check;
 
     // First, parse the crate and extract all relevant information.
@@ -833,6 +864,7 @@ fn main_options(options: config::Options) -> MainResult {
                         resolver,
                         resolver_caches,
                         show_coverage,
+                        coverage_report,
                         render_options,
                         output_format,
                     )
@@ -862,6 +894,12 @@ fn main_options(options: config::Options) -> MainResult {
                     config::OutputFormat::Json => sess.time("render_json", || {
                         run_renderer::<json::JsonRenderer<'_>>(krate, render_opts, cache, tcx)
                     }),
+                    config::OutputFormat::Markdown => sess.time("render_markdown", || {
+                        run_renderer::<text::MarkdownRenderer<'_>>(krate, render_opts, cache, tcx)
+                    }),
+                    config::OutputFormat::Man => sess.time("render_man", || {
+                        run_renderer::<text::ManRenderer<'_>>(krate, render_opts, cache, tcx)
+                    }),
                 }
             })
         })
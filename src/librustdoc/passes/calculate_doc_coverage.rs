@@ -8,6 +8,7 @@
 use rustc_hir as hir;
 use rustc_lint::builtin::MISSING_DOCS;
 use rustc_middle::lint::LintLevelSource;
+use rustc_middle::ty::print::with_no_trimmed_paths;
 use rustc_middle::ty::DefIdTree;
 use rustc_session::lint;
 use rustc_span::FileName;
@@ -23,9 +24,11 @@
 };
 
 fn calculate_doc_coverage(krate: clean::Crate, ctx: &mut DocContext<'_>) -> clean::Crate {
-    let mut calc = CoverageCalculator { items: Default::default(), ctx };
+    let mut calc =
+        CoverageCalculator { items: Default::default(), report: Default::default(), ctx };
     calc.visit_crate(&krate);
 
+    calc.report.sort();
     calc.print_results();
 
     krate
@@ -101,8 +104,43 @@ fn add_assign(&mut self, rhs: Self) {
     }
 }
 
+/// An item listed in the `--coverage-report` output.
+#[derive(Serialize, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct ReportItem {
+    path: String,
+    kind: &'static str,
+    file: String,
+}
+
+#[derive(Serialize, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct UnresolvedLink {
+    #[serde(flatten)]
+    item: ReportItem,
+    link: String,
+}
+
+/// The per-item details printed with `--coverage-report`. Items are sorted by path so that
+/// reports of different versions of a crate can be compared.
+#[derive(Default, Serialize, Debug)]
+struct CoverageReport {
+    missing_docs: Vec<ReportItem>,
+    missing_examples: Vec<ReportItem>,
+    unresolved_links: Vec<UnresolvedLink>,
+    ignored_doctests: Vec<ReportItem>,
+}
+
+impl CoverageReport {
+    fn sort(&mut self) {
+        self.missing_docs.sort();
+        self.missing_examples.sort();
+        self.unresolved_links.sort();
+        self.ignored_doctests.sort();
+    }
+}
+
 struct CoverageCalculator<'a, 'b> {
     items: BTreeMap<FileName, ItemCount>,
+    report: CoverageReport,
     ctx: &'a mut DocContext<'b>,
 }
 
@@ -118,14 +156,40 @@ fn limit_filename_len(filename: String) -> String {
 
 impl<'a, 'b> CoverageCalculator<'a, 'b> {
     fn to_json(&self) -> String {
-        serde_json::to_string(
-            &self
-                .items
-                .iter()
-                .map(|(k, v)| (k.prefer_local().to_string(), v))
-                .collect::<BTreeMap<String, &ItemCount>>(),
-        )
-        .expect("failed to convert JSON data to string")
+        #[derive(Serialize)]
+        struct Report<'a> {
+            files: BTreeMap<String, &'a ItemCount>,
+            #[serde(flatten)]
+            report: &'a CoverageReport,
+        }
+
+        let files = self
+            .items
+            .iter()
+            .map(|(k, v)| (k.prefer_local().to_string(), v))
+            .collect::<BTreeMap<String, &ItemCount>>();
+        let json = if self.ctx.coverage_report {
+            serde_json::to_string(&Report { files, report: &self.report })
+        } else {
+            serde_json::to_string(&files)
+        };
+        json.expect("failed to convert JSON data to string")
+    }
+
+    fn report_item(&self, i: &clean::Item) -> ReportItem {
+        let tcx = self.ctx.tcx;
+        let def_id = i.item_id.expect_def_id();
+        // Paths of local items are printed relative to the crate root, which is left empty.
+        let path = if def_id.is_crate_root() {
+            tcx.crate_name(def_id.krate).to_string()
+        } else {
+            with_no_trimmed_paths!(tcx.def_path_str(def_id))
+        };
+        ReportItem {
+            path,
+            kind: i.type_().as_str(),
+            file: i.span(tcx).filename(self.ctx.sess()).prefer_local().to_string(),
+        }
     }
 
     fn print_results(&self) {
@@ -205,7 +269,7 @@ fn visit_item(&mut self, i: &clean::Item) {
             clean::ImplItem(_) => {}
             _ => {
                 let has_docs = !i.attrs.doc_strings.is_empty();
-                let mut tests = Tests { found_tests: 0 };
+                let mut tests = Tests { found_tests: 0, ignored_tests: 0 };
 
                 find_testable_code(
                     &i.attrs.collapsed_doc_value().unwrap_or_default(),
@@ -261,13 +325,36 @@ fn visit_item(&mut self, i: &clean::Item) {
                 let should_have_docs = !should_be_ignored
                     && (level != lint::Level::Allow || matches!(source, LintLevelSource::Default));
 
+                let should_have_doc_example = should_have_doc_example(self.ctx, i);
+
                 debug!("counting {:?} {:?} in {:?}", i.type_(), i.name, filename);
                 self.items.entry(filename).or_default().count_item(
                     has_docs,
                     has_doc_example,
-                    should_have_doc_example(self.ctx, i),
+                    should_have_doc_example,
                     should_have_docs,
                 );
+
+                if self.ctx.coverage_report {
+                    if should_have_docs && !has_docs {
+                        self.report.missing_docs.push(self.report_item(i));
+                    }
+                    if should_have_doc_example && !has_doc_example {
+                        self.report.missing_examples.push(self.report_item(i));
+                    }
+                    if tests.ignored_tests != 0 {
+                        self.report.ignored_doctests.push(self.report_item(i));
+                    }
+                }
+            }
+        }
+
+        // Broken links are reported for every item, including the ones which aren't counted.
+        if self.ctx.coverage_report && i.item_id.as_def_id().is_some() {
+            let links = self.ctx.cache.unresolved_intra_doc_links.get(&i.item_id);
+            for link in links.into_iter().flatten() {
+                let link = UnresolvedLink { item: self.report_item(i), link: link.clone() };
+                self.report.unresolved_links.push(link);
             }
         }
 
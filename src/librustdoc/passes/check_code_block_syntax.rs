@@ -1,4 +1,5 @@
-//! Validates syntax inside Rust code blocks (\`\`\`rust).
+//! Validates syntax inside Rust code blocks (\`\`\`rust), and the files given to their
+//! `stdin=` and `cwd=` attributes.
 use rustc_data_structures::sync::{Lock, Lrc};
 use rustc_errors::{
     emitter::Emitter, Applicability, Diagnostic, Handler, LazyFallbackBundle, LintDiagnosticBuilder,
@@ -9,6 +10,8 @@
 use rustc_span::source_map::{FilePathMapping, SourceMap};
 use rustc_span::{FileName, InnerSpan, DUMMY_SP};
 
+use std::path::Path;
+
 use crate::clean;
 use crate::core::DocContext;
 use crate::html::markdown::{self, RustCodeBlock};
@@ -152,6 +155,53 @@ fn check_rust_syntax(&self, item: &clean::Item, dox: &str, code_block: RustCodeB
     }
 }
 
+impl<'a, 'tcx> SyntaxChecker<'a, 'tcx> {
+    /// Checks that the file given to `stdin=` and the directory given to `cwd=` exist, relative to
+    /// the directory of the source file, like when the doctest is run.
+    fn check_run_attributes(&self, item: &clean::Item, dox: &str, code_block: &RustCodeBlock) {
+        let lang_string = &code_block.lang_string;
+        if lang_string.stdin.is_none() && lang_string.cwd.is_none() {
+            return;
+        }
+        let Some(local_id) = item.item_id.as_def_id().and_then(|x| x.as_local())
+        else {
+            return;
+        };
+        let tcx = self.cx.tcx;
+        let attr_span = item.attr_span(tcx);
+        let FileName::Real(filename) = tcx.sess.source_map().span_to_filename(attr_span)
+        else {
+            return;
+        };
+        let Some(source_dir) = filename.local_path().and_then(Path::parent) else { return };
+
+        let hir_id = tcx.hir().local_def_id_to_hir_id(local_id);
+        let sp = super::source_span_for_markdown_range(tcx, dox, &code_block.range, &item.attrs)
+            .unwrap_or(attr_span);
+        let checks = [("stdin", &lang_string.stdin, false), ("cwd", &lang_string.cwd, true)];
+        for (attr, path, is_dir) in checks {
+            let Some(path) = path else { continue };
+            let full_path = source_dir.join(path);
+            if (is_dir && full_path.is_dir()) || (!is_dir && full_path.is_file()) {
+                continue;
+            }
+            let kind = if is_dir { "directory" } else { "file" };
+            let msg = format!("{} `{}` not found for the `{}` attribute", kind, path, attr);
+            tcx.struct_span_lint_hir(
+                crate::lint::INVALID_CODEBLOCK_ATTRIBUTES,
+                hir_id,
+                sp,
+                |lint| {
+                    lint.build(&msg)
+                        .note(&format!("looked for `{}`", full_path.display()))
+                        .help("the path is relative to the directory of the source file")
+                        .emit();
+                },
+            );
+        }
+    }
+}
+
 impl<'a, 'tcx> DocVisitor for SyntaxChecker<'a, 'tcx> {
     fn visit_item(&mut self, item: &clean::Item) {
         if let Some(dox) = &item.attrs.collapsed_doc_value() {
@@ -162,6 +212,7 @@ fn visit_item(&mut self, item: &clean::Item) {
                 sp,
             );
             for code_block in markdown::rust_code_blocks(dox, &extra) {
+                self.check_run_attributes(item, dox, &code_block);
                 self.check_rust_syntax(item, dox, code_block);
             }
         }
@@ -45,12 +45,19 @@ fn visit_item(&mut self, item: &Item) {
 
 pub(crate) struct Tests {
     pub(crate) found_tests: usize,
+    /// Rust code blocks marked `ignore`, which aren't counted in `found_tests`.
+    pub(crate) ignored_tests: usize,
 }
 
 impl crate::doctest::Tester for Tests {
     fn add_test(&mut self, _: String, config: LangString, _: usize) {
-        if config.rust && config.ignore == Ignore::None {
-            self.found_tests += 1;
+        if !config.rust {
+            return;
+        }
+        match config.ignore {
+            Ignore::None => self.found_tests += 1,
+            Ignore::All => self.ignored_tests += 1,
+            Ignore::Some(_) => {}
         }
     }
 }
@@ -113,7 +120,7 @@ pub(crate) fn look_for_tests<'tcx>(cx: &DocContext<'tcx>, dox: &str, item: &Item
         return;
     };
 
-    let mut tests = Tests { found_tests: 0 };
+    let mut tests = Tests { found_tests: 0, ignored_tests: 0 };
 
     find_testable_code(dox, &mut tests, ErrorCodes::No, false, None);
 
@@ -862,6 +862,13 @@ fn visit_item(&mut self, item: &Item) {
                 let link = self.resolve_link(item, &doc, parent_node, md_link);
                 if let Some(link) = link {
                     self.cx.cache.intra_doc_links.entry(item.item_id).or_default().push(link);
+                } else {
+                    self.cx
+                        .cache
+                        .unresolved_intra_doc_links
+                        .entry(item.item_id)
+                        .or_default()
+                        .push(md_link.1.link.clone());
                 }
             }
             self.cx.resolver_caches.markdown_links = Some(tmp_links);
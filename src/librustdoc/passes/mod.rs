@@ -72,6 +72,8 @@ pub(crate) enum Condition {
     WhenNotDocumentPrivate,
     /// When `--document-hidden-items` is not passed.
     WhenNotDocumentHidden,
+    /// When `--coverage-report` is passed.
+    WhenCoverageReport,
 }
 
 /// The full list of passes.
@@ -107,6 +109,7 @@ pub(crate) enum Condition {
 pub(crate) const COVERAGE_PASSES: &[ConditionalPass] = &[
     ConditionalPass::new(STRIP_HIDDEN, WhenNotDocumentHidden),
     ConditionalPass::new(STRIP_PRIVATE, WhenNotDocumentPrivate),
+    ConditionalPass::new(COLLECT_INTRA_DOC_LINKS, WhenCoverageReport),
     ConditionalPass::always(CALCULATE_DOC_COVERAGE),
 ];
 
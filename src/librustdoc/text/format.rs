@@ -0,0 +1,672 @@
+//! Plain-text rendering of item signatures.
+//!
+//! This mirrors what `html::format` prints with the `{:#}` flag, but doesn't need an HTML
+//! [`Context`](crate::html::render::Context), since there are no links to generate.
+
+use std::fmt::Write;
+
+use rustc_data_structures::fx::FxHashSet;
+use rustc_hir as hir;
+use rustc_hir::def::CtorKind;
+use rustc_middle::ty::{self, TyCtxt};
+use rustc_span::hygiene::MacroKind;
+use rustc_span::symbol::kw;
+
+use crate::clean::{self, PrimitiveType};
+use crate::html::format::{print_abi_with_space, PrintWithSpace};
+
+fn write_type(out: &mut String, ty: &clean::Type, tcx: TyCtxt<'_>) {
+    match *ty {
+        clean::Generic(name) => out.push_str(name.as_str()),
+        clean::Type::Path { ref path } => write_path(out, path, path.is_assoc_ty(), tcx),
+        clean::DynTrait(ref bounds, ref lt) => {
+            out.push_str("dyn ");
+            for (i, bound) in bounds.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(" + ");
+                }
+                write_poly_trait(out, bound, tcx);
+            }
+            if let Some(lt) = lt {
+                out.push_str(" + ");
+                out.push_str(lt.0.as_str());
+            }
+        }
+        clean::Infer => out.push('_'),
+        clean::Primitive(PrimitiveType::Never) => out.push('!'),
+        clean::Primitive(prim) => out.push_str(prim.as_sym().as_str()),
+        clean::BareFunction(ref decl) => {
+            if !decl.generic_params.is_empty() {
+                out.push_str("for<");
+                write_generic_params(out, &decl.generic_params, tcx);
+                out.push_str("> ");
+            }
+            out.push_str(decl.unsafety.print_with_space());
+            write!(out, "{:#}", print_abi_with_space(decl.abi)).unwrap();
+            out.push_str("fn");
+            write_fn_decl(out, &decl.decl, hir::IsAsync::NotAsync, tcx);
+        }
+        clean::Tuple(ref types) => {
+            out.push('(');
+            for (i, ty) in types.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                write_type(out, ty, tcx);
+            }
+            if types.len() == 1 {
+                out.push(',');
+            }
+            out.push(')');
+        }
+        clean::Slice(ref ty) => {
+            out.push('[');
+            write_type(out, ty, tcx);
+            out.push(']');
+        }
+        clean::Array(ref ty, ref n) => {
+            out.push('[');
+            write_type(out, ty, tcx);
+            write!(out, "; {}]", n).unwrap();
+        }
+        clean::RawPointer(mutability, ref ty) => {
+            out.push_str(match mutability {
+                hir::Mutability::Mut => "*mut ",
+                hir::Mutability::Not => "*const ",
+            });
+            write_type(out, ty, tcx);
+        }
+        clean::BorrowedRef { ref lifetime, mutability, ref type_ } => {
+            out.push('&');
+            if let Some(lt) = lifetime {
+                write!(out, "{} ", lt.0).unwrap();
+            }
+            out.push_str(mutability.print_with_space());
+            match **type_ {
+                clean::DynTrait(ref bounds, ref trait_lt)
+                    if bounds.len() > 1 || trait_lt.is_some() =>
+                {
+                    out.push('(');
+                    write_type(out, type_, tcx);
+                    out.push(')');
+                }
+                _ => write_type(out, type_, tcx),
+            }
+        }
+        clean::ImplTrait(ref bounds) => {
+            out.push_str("impl ");
+            write_bounds(out, bounds, tcx);
+        }
+        clean::QPath { ref assoc, ref self_type, ref trait_, should_show_cast } => {
+            if should_show_cast {
+                out.push('<');
+                write_type(out, self_type, tcx);
+                out.push_str(" as ");
+                write_path(out, trait_, false, tcx);
+                out.push_str(">::");
+            } else {
+                write_type(out, self_type, tcx);
+                out.push_str("::");
+            }
+            out.push_str(assoc.name.as_str());
+            write_generic_args(out, &assoc.args, tcx);
+        }
+    }
+}
+
+fn write_path(out: &mut String, path: &clean::Path, print_all: bool, tcx: TyCtxt<'_>) {
+    let last = path.segments.last().unwrap();
+    if print_all {
+        for seg in &path.segments[..path.segments.len() - 1] {
+            if seg.name != kw::PathRoot {
+                out.push_str(seg.name.as_str());
+            }
+            out.push_str("::");
+        }
+    }
+    out.push_str(last.name.as_str());
+    write_generic_args(out, &last.args, tcx);
+}
+
+fn write_generic_args(out: &mut String, args: &clean::GenericArgs, tcx: TyCtxt<'_>) {
+    match args {
+        clean::GenericArgs::AngleBracketed { args, bindings } => {
+            if args.is_empty() && bindings.is_empty() {
+                return;
+            }
+            out.push('<');
+            let mut comma = false;
+            for arg in args.iter() {
+                if comma {
+                    out.push_str(", ");
+                }
+                comma = true;
+                match arg {
+                    clean::GenericArg::Lifetime(lt) => out.push_str(lt.0.as_str()),
+                    clean::GenericArg::Type(ty) => write_type(out, ty, tcx),
+                    clean::GenericArg::Const(ct) => out.push_str(&ct.kind.expr(tcx)),
+                    clean::GenericArg::Infer => out.push('_'),
+                }
+            }
+            for binding in bindings.iter() {
+                if comma {
+                    out.push_str(", ");
+                }
+                comma = true;
+                out.push_str(binding.assoc.name.as_str());
+                write_generic_args(out, &binding.assoc.args, tcx);
+                match binding.kind {
+                    clean::TypeBindingKind::Equality { ref term } => {
+                        out.push_str(" = ");
+                        write_term(out, term, tcx);
+                    }
+                    clean::TypeBindingKind::Constraint { ref bounds } => {
+                        if !bounds.is_empty() {
+                            out.push_str(": ");
+                            write_bounds(out, bounds, tcx);
+                        }
+                    }
+                }
+            }
+            out.push('>');
+        }
+        clean::GenericArgs::Parenthesized { inputs, output } => {
+            out.push('(');
+            for (i, ty) in inputs.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                write_type(out, ty, tcx);
+            }
+            out.push(')');
+            if let Some(ty) = output {
+                out.push_str(" -> ");
+                write_type(out, ty, tcx);
+            }
+        }
+    }
+}
+
+fn write_term(out: &mut String, term: &clean::Term, tcx: TyCtxt<'_>) {
+    match term {
+        clean::Term::Type(ty) => write_type(out, ty, tcx),
+        clean::Term::Constant(ct) => out.push_str(&ct.kind.expr(tcx)),
+    }
+}
+
+fn write_poly_trait(out: &mut String, poly_trait: &clean::PolyTrait, tcx: TyCtxt<'_>) {
+    if !poly_trait.generic_params.is_empty() {
+        out.push_str("for<");
+        write_generic_params(out, &poly_trait.generic_params, tcx);
+        out.push_str("> ");
+    }
+    write_path(out, &poly_trait.trait_, false, tcx);
+}
+
+fn write_bounds(out: &mut String, bounds: &[clean::GenericBound], tcx: TyCtxt<'_>) {
+    let mut bounds_dup = FxHashSet::default();
+    for (i, bound) in bounds.iter().filter(|b| bounds_dup.insert(*b)).enumerate() {
+        if i > 0 {
+            out.push_str(" + ");
+        }
+        match bound {
+            clean::GenericBound::Outlives(lt) => out.push_str(lt.0.as_str()),
+            clean::GenericBound::TraitBound(poly_trait, modifier) => {
+                if let hir::TraitBoundModifier::Maybe = modifier {
+                    out.push('?');
+                }
+                write_poly_trait(out, poly_trait, tcx);
+            }
+        }
+    }
+}
+
+fn write_generic_params(out: &mut String, params: &[clean::GenericParamDef], tcx: TyCtxt<'_>) {
+    let params = params.iter().filter(|p| !p.is_synthetic_type_param());
+    for (i, param) in params.enumerate() {
+        if i > 0 {
+            out.push_str(", ");
+        }
+        match &param.kind {
+            clean::GenericParamDefKind::Lifetime { outlives } => {
+                out.push_str(param.name.as_str());
+                for (i, lt) in outlives.iter().enumerate() {
+                    out.push_str(if i == 0 { ": " } else { " + " });
+                    out.push_str(lt.0.as_str());
+                }
+            }
+            clean::GenericParamDefKind::Type { bounds, default, .. } => {
+                out.push_str(param.name.as_str());
+                if !bounds.is_empty() {
+                    out.push_str(": ");
+                    write_bounds(out, bounds, tcx);
+                }
+                if let Some(ty) = default {
+                    out.push_str(" = ");
+                    write_type(out, ty, tcx);
+                }
+            }
+            clean::GenericParamDefKind::Const { ty, default, .. } => {
+                write!(out, "const {}: ", param.name).unwrap();
+                write_type(out, ty, tcx);
+                if let Some(default) = default {
+                    write!(out, " = {}", default).unwrap();
+                }
+            }
+        }
+    }
+}
+
+fn write_generics(out: &mut String, generics: &clean::Generics, tcx: TyCtxt<'_>) {
+    if generics.params.iter().all(|p| p.is_synthetic_type_param()) {
+        return;
+    }
+    out.push('<');
+    write_generic_params(out, &generics.params, tcx);
+    out.push('>');
+}
+
+/// Writes the `where` clause of `generics`, if there is one, with one predicate per line.
+fn write_where_clause(out: &mut String, generics: &clean::Generics, tcx: TyCtxt<'_>) {
+    let mut predicates = generics
+        .where_predicates
+        .iter()
+        .filter(|pred| match pred {
+            clean::WherePredicate::BoundPredicate { bounds, .. } => !bounds.is_empty(),
+            _ => true,
+        })
+        .peekable();
+    if predicates.peek().is_none() {
+        return;
+    }
+    out.push_str("\nwhere");
+    for pred in predicates {
+        out.push_str("\n    ");
+        match pred {
+            clean::WherePredicate::BoundPredicate { ty, bounds, bound_params } => {
+                if !bound_params.is_empty() {
+                    out.push_str("for<");
+                    for (i, lt) in bound_params.iter().enumerate() {
+                        if i > 0 {
+                            out.push_str(", ");
+                        }
+                        out.push_str(lt.0.as_str());
+                    }
+                    out.push_str("> ");
+                }
+                write_type(out, ty, tcx);
+                out.push_str(": ");
+                write_bounds(out, bounds, tcx);
+            }
+            clean::WherePredicate::RegionPredicate { lifetime, bounds } => {
+                write!(out, "{}: ", lifetime.0).unwrap();
+                write_bounds(out, bounds, tcx);
+            }
+            clean::WherePredicate::EqPredicate { lhs, rhs } => {
+                write_type(out, lhs, tcx);
+                out.push_str(" == ");
+                write_term(out, rhs, tcx);
+            }
+        }
+        out.push(',');
+    }
+}
+
+fn write_fn_decl(out: &mut String, decl: &clean::FnDecl, asyncness: hir::IsAsync, tcx: TyCtxt<'_>) {
+    out.push('(');
+    for (i, input) in decl.inputs.values.iter().enumerate() {
+        if i > 0 {
+            out.push_str(", ");
+        }
+        match input.to_self() {
+            Some(clean::SelfValue) => out.push_str("self"),
+            Some(clean::SelfBorrowed(lt, mutability)) => {
+                out.push('&');
+                if let Some(lt) = lt {
+                    write!(out, "{} ", lt.0).unwrap();
+                }
+                write!(out, "{}self", mutability.print_with_space()).unwrap();
+            }
+            Some(clean::SelfExplicit(ty)) => {
+                out.push_str("self: ");
+                write_type(out, &ty, tcx);
+            }
+            None => {
+                if input.is_const {
+                    out.push_str("const ");
+                }
+                if !input.name.is_empty() {
+                    write!(out, "{}: ", input.name).unwrap();
+                }
+                write_type(out, &input.type_, tcx);
+            }
+        }
+    }
+    if decl.c_variadic {
+        out.push_str(", ...");
+    }
+    out.push(')');
+
+    let output = match asyncness {
+        hir::IsAsync::Async => decl.sugared_async_return_type(),
+        hir::IsAsync::NotAsync => decl.output.clone(),
+    };
+    match output {
+        clean::Return(clean::Tuple(tys)) if tys.is_empty() => {}
+        clean::Return(ty) => {
+            out.push_str(" -> ");
+            write_type(out, &ty, tcx);
+        }
+        clean::DefaultReturn => {}
+    }
+}
+
+/// Prints the header of an impl block, e.g. `impl<T: Clone> Clone for Vec<T>`.
+pub(crate) fn print_impl(impl_: &clean::Impl, tcx: TyCtxt<'_>) -> String {
+    let mut out = String::new();
+    out.push_str(impl_.unsafety.print_with_space());
+    out.push_str("impl");
+    write_generics(&mut out, &impl_.generics, tcx);
+    out.push(' ');
+    if let Some(trait_) = &impl_.trait_ {
+        if let ty::ImplPolarity::Negative = impl_.polarity {
+            out.push('!');
+        }
+        write_path(&mut out, trait_, false, tcx);
+        out.push_str(" for ");
+    }
+    write_type(&mut out, impl_.kind.as_blanket_ty().unwrap_or(&impl_.for_), tcx);
+    write_where_clause(&mut out, &impl_.generics, tcx);
+    out
+}
+
+fn write_fields(
+    out: &mut String,
+    fields: &[clean::Item],
+    ctor_kind: CtorKind,
+    indent: &str,
+    tcx: TyCtxt<'_>,
+) {
+    match ctor_kind {
+        CtorKind::Fictive => {
+            out.push_str(" {\n");
+            for field in fields {
+                if let clean::StructFieldItem(ref ty) = *field.kind {
+                    write!(out, "{}    ", indent).unwrap();
+                    write_visibility(out, field, tcx);
+                    write!(out, "{}: ", field.name.unwrap()).unwrap();
+                    write_type(out, ty, tcx);
+                    out.push_str(",\n");
+                }
+            }
+            if fields.iter().any(|f| f.is_stripped()) {
+                writeln!(out, "{}    /* private fields */", indent).unwrap();
+            }
+            out.push_str(indent);
+            out.push('}');
+        }
+        CtorKind::Fn => {
+            out.push('(');
+            for (i, field) in fields.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                match *field.kind {
+                    clean::StructFieldItem(ref ty) => {
+                        write_visibility(out, field, tcx);
+                        write_type(out, ty, tcx);
+                    }
+                    _ => out.push('_'),
+                }
+            }
+            out.push(')');
+        }
+        CtorKind::Const => {}
+    }
+}
+
+fn write_visibility(out: &mut String, item: &clean::Item, tcx: TyCtxt<'_>) {
+    if let Some(did) = item.item_id.as_def_id() {
+        write!(out, "{}", item.visibility.to_src_with_space(tcx, did)).unwrap();
+    }
+}
+
+fn write_fn(out: &mut String, item: &clean::Item, f: &clean::Function, tcx: TyCtxt<'_>) {
+    let header = item.fn_header(tcx).expect("function items have a header");
+    write_visibility(out, item, tcx);
+    if let hir::Constness::Const = header.constness {
+        out.push_str("const ");
+    }
+    out.push_str(header.asyncness.print_with_space());
+    out.push_str(header.unsafety.print_with_space());
+    write!(out, "{:#}", print_abi_with_space(header.abi)).unwrap();
+    write!(out, "fn {}", item.name.unwrap()).unwrap();
+    write_generics(out, &f.generics, tcx);
+    write_fn_decl(out, &f.decl, header.asyncness, tcx);
+    write_where_clause(out, &f.generics, tcx);
+}
+
+/// Prints the declaration of `item` the way it would appear in source code, without bodies or
+/// private fields. Returns `None` for items without a meaningful declaration, like modules.
+pub(crate) fn print_item_signature(item: &clean::Item, tcx: TyCtxt<'_>) -> Option<String> {
+    let mut out = String::new();
+    let name = item.name.unwrap_or(kw::Empty);
+    match *item.kind {
+        clean::StructItem(ref s) => {
+            write_visibility(&mut out, item, tcx);
+            write!(out, "struct {}", name).unwrap();
+            write_generics(&mut out, &s.generics, tcx);
+            if s.struct_type == CtorKind::Fictive {
+                write_where_clause(&mut out, &s.generics, tcx);
+                write_fields(&mut out, &s.fields, s.struct_type, "", tcx);
+            } else {
+                write_fields(&mut out, &s.fields, s.struct_type, "", tcx);
+                write_where_clause(&mut out, &s.generics, tcx);
+                out.push(';');
+            }
+        }
+        clean::UnionItem(ref u) => {
+            write_visibility(&mut out, item, tcx);
+            write!(out, "union {}", name).unwrap();
+            write_generics(&mut out, &u.generics, tcx);
+            write_where_clause(&mut out, &u.generics, tcx);
+            write_fields(&mut out, &u.fields, CtorKind::Fictive, "", tcx);
+        }
+        clean::EnumItem(ref e) => {
+            write_visibility(&mut out, item, tcx);
+            write!(out, "enum {}", name).unwrap();
+            write_generics(&mut out, &e.generics, tcx);
+            write_where_clause(&mut out, &e.generics, tcx);
+            out.push_str(" {\n");
+            for variant in e.variants() {
+                out.push_str("    ");
+                write_variant(&mut out, variant, "    ", tcx);
+                out.push_str(",\n");
+            }
+            if e.has_stripped_entries() {
+                out.push_str("    // some variants omitted\n");
+            }
+            out.push('}');
+        }
+        clean::FunctionItem(ref f)
+        | clean::ForeignFunctionItem(ref f)
+        | clean::MethodItem(ref f, _) => {
+            write_fn(&mut out, item, f, tcx);
+        }
+        clean::TyMethodItem(ref f) => {
+            write_fn(&mut out, item, f, tcx);
+            out.push(';');
+        }
+        clean::TraitItem(ref t) => {
+            write_visibility(&mut out, item, tcx);
+            out.push_str(t.unsafety.print_with_space());
+            if t.is_auto {
+                out.push_str("auto ");
+            }
+            write!(out, "trait {}", name).unwrap();
+            write_generics(&mut out, &t.generics, tcx);
+            if !t.bounds.is_empty() {
+                out.push_str(": ");
+                write_bounds(&mut out, &t.bounds, tcx);
+            }
+            write_where_clause(&mut out, &t.generics, tcx);
+            out.push_str(" {\n");
+            for assoc in t.items.iter().filter(|i| !i.is_stripped()) {
+                if let Some(sig) = print_item_signature(assoc, tcx) {
+                    for line in sig.lines() {
+                        writeln!(out, "    {}", line).unwrap();
+                    }
+                    if let clean::MethodItem(..) = *assoc.kind {
+                        out.pop();
+                        out.push_str(" { ... }\n");
+                    }
+                }
+            }
+            out.push('}');
+        }
+        clean::TraitAliasItem(ref ta) => {
+            write!(out, "trait {}", name).unwrap();
+            write_generics(&mut out, &ta.generics, tcx);
+            out.push_str(" = ");
+            write_bounds(&mut out, &ta.bounds, tcx);
+            write_where_clause(&mut out, &ta.generics, tcx);
+            out.push(';');
+        }
+        clean::TypedefItem(ref t) => {
+            write_visibility(&mut out, item, tcx);
+            write!(out, "type {}", name).unwrap();
+            write_generics(&mut out, &t.generics, tcx);
+            write_where_clause(&mut out, &t.generics, tcx);
+            out.push_str(" = ");
+            write_type(&mut out, &t.type_, tcx);
+            out.push(';');
+        }
+        clean::OpaqueTyItem(ref t) => {
+            write!(out, "type {}", name).unwrap();
+            write_generics(&mut out, &t.generics, tcx);
+            write_where_clause(&mut out, &t.generics, tcx);
+            out.push_str(" = impl ");
+            write_bounds(&mut out, &t.bounds, tcx);
+            out.push(';');
+        }
+        clean::StaticItem(ref s) | clean::ForeignStaticItem(ref s) => {
+            write_visibility(&mut out, item, tcx);
+            write!(out, "static {}{}: ", s.mutability.print_with_space(), name).unwrap();
+            write_type(&mut out, &s.type_, tcx);
+            out.push(';');
+        }
+        clean::ConstantItem(ref c) => {
+            write_visibility(&mut out, item, tcx);
+            write!(out, "const {}: ", name).unwrap();
+            write_type(&mut out, &c.type_, tcx);
+            write!(out, " = {};", c.kind.expr(tcx)).unwrap();
+        }
+        clean::TyAssocConstItem(ref ty) => {
+            write!(out, "const {}: ", name).unwrap();
+            write_type(&mut out, ty, tcx);
+            out.push(';');
+        }
+        clean::AssocConstItem(ref ty, ref kind) => {
+            write_visibility(&mut out, item, tcx);
+            write!(out, "const {}: ", name).unwrap();
+            write_type(&mut out, ty, tcx);
+            write!(out, " = {};", kind.expr(tcx)).unwrap();
+        }
+        clean::TyAssocTypeItem(ref generics, ref bounds) => {
+            write!(out, "type {}", name).unwrap();
+            write_generics(&mut out, generics, tcx);
+            if !bounds.is_empty() {
+                out.push_str(": ");
+                write_bounds(&mut out, bounds, tcx);
+            }
+            write_where_clause(&mut out, generics, tcx);
+            out.push(';');
+        }
+        clean::AssocTypeItem(ref t, _) => {
+            write!(out, "type {}", name).unwrap();
+            write_generics(&mut out, &t.generics, tcx);
+            write_where_clause(&mut out, &t.generics, tcx);
+            out.push_str(" = ");
+            write_type(&mut out, &t.type_, tcx);
+            out.push(';');
+        }
+        clean::StructFieldItem(ref ty) => {
+            write_visibility(&mut out, item, tcx);
+            write!(out, "{}: ", name).unwrap();
+            write_type(&mut out, ty, tcx);
+        }
+        clean::VariantItem(_) => write_variant(&mut out, item, "", tcx),
+        clean::ForeignTypeItem => {
+            write_visibility(&mut out, item, tcx);
+            write!(out, "type {};", name).unwrap();
+        }
+        clean::MacroItem(ref m) => out.push_str(&m.source),
+        clean::ProcMacroItem(ref m) => {
+            match m.kind {
+                MacroKind::Bang => write!(out, "{}!() {{ /* proc-macro */ }}", name),
+                MacroKind::Attr => write!(out, "#[{}]", name),
+                MacroKind::Derive => write!(out, "#[derive({})]", name),
+            }
+            .unwrap();
+            if !m.helpers.is_empty() {
+                out.push_str("\n/* attributes: ");
+                for (i, helper) in m.helpers.iter().enumerate() {
+                    if i > 0 {
+                        out.push_str(", ");
+                    }
+                    out.push_str(helper.as_str());
+                }
+                out.push_str(" */");
+            }
+        }
+        clean::ImplItem(ref impl_) => out.push_str(&print_impl(impl_, tcx)),
+        clean::ExternCrateItem { ref src } => {
+            write_visibility(&mut out, item, tcx);
+            match *src {
+                Some(src) => write!(out, "extern crate {} as {};", src, name),
+                None => write!(out, "extern crate {};", name),
+            }
+            .unwrap();
+        }
+        clean::ImportItem(ref import) => {
+            write_visibility(&mut out, item, tcx);
+            out.push_str("use ");
+            let path = &import.source.path;
+            match import.kind {
+                clean::ImportKind::Simple(name) => {
+                    write_path(&mut out, path, true, tcx);
+                    if name != path.last() {
+                        write!(out, " as {}", name).unwrap();
+                    }
+                }
+                clean::ImportKind::Glob => {
+                    if !path.segments.is_empty() {
+                        write_path(&mut out, path, true, tcx);
+                        out.push_str("::");
+                    }
+                    out.push('*');
+                }
+            }
+            out.push(';');
+        }
+        clean::ModuleItem(_)
+        | clean::PrimitiveItem(_)
+        | clean::KeywordItem(_)
+        | clean::StrippedItem(_) => return None,
+    }
+    Some(out)
+}
+
+fn write_variant(out: &mut String, variant: &clean::Item, indent: &str, tcx: TyCtxt<'_>) {
+    out.push_str(variant.name.unwrap().as_str());
+    match *variant.kind {
+        clean::VariantItem(clean::Variant::CLike) => {}
+        clean::VariantItem(clean::Variant::Tuple(ref fields)) => {
+            write_fields(out, fields, CtorKind::Fn, indent, tcx)
+        }
+        clean::VariantItem(clean::Variant::Struct(ref s)) => {
+            write_fields(out, &s.fields, CtorKind::Fictive, indent, tcx)
+        }
+        _ => unreachable!("expected a variant"),
+    }
+}
@@ -0,0 +1,444 @@
+//! The man page backend, enabled with `--output-format man`.
+//!
+//! Every item that has its own page in the HTML output gets a man page in section 3, written to
+//! `man3/<page>.3` in the output directory. Pages are named after the full path of the item, joined
+//! with dots, and the type of the item, like `std.vec.Vec.struct`, so that items of different
+//! namespaces sharing a path get different pages and `MANPATH=<output> man std.vec.Vec.struct`
+//! works. Pages are written in the `man` macro package of roff, translated from the Markdown of
+//! the documentation.
+
+use std::fmt::Write;
+use std::fs::{self, create_dir_all};
+use std::path::PathBuf;
+use std::rc::Rc;
+
+use pulldown_cmark::{BrokenLink, CodeBlockKind, Event, LinkType, Parser, Tag};
+use rustc_middle::ty::TyCtxt;
+use rustc_span::Symbol;
+
+use super::{Entry, Page};
+use crate::clean::{self, RenderedLink};
+use crate::config::RenderOptions;
+use crate::docfs::PathError;
+use crate::error::Error;
+use crate::formats::cache::Cache;
+use crate::formats::item_type::ItemType;
+use crate::formats::FormatRenderer;
+use crate::html::format::join_with_double_colon;
+use crate::html::markdown::{main_body_opts, map_line, plain_text_summary, ErrorCodes, LangString};
+use crate::try_err;
+
+#[derive(Clone)]
+pub(crate) struct ManRenderer<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    cache: Rc<Cache>,
+    /// The `man3` directory all pages are written to.
+    dst: PathBuf,
+    /// The path of the module currently being rendered.
+    current: Vec<Symbol>,
+    /// Whether the current module is stripped, in which case nothing is rendered for it.
+    in_stripped_module: bool,
+}
+
+impl<'tcx> ManRenderer<'tcx> {
+    fn render_page(&self, item: &clean::Item, path: &[Symbol]) -> String {
+        let page = Page::new(item, &self.cache, self.tcx);
+        let name = join_with_double_colon(path);
+        let mut out = String::new();
+        let source = match self.cache.crate_version {
+            Some(ref version) => format!("{} {}", path[0], version),
+            None => path[0].to_string(),
+        };
+        let title = page_name(path, item.type_());
+        writeln!(out, ".TH {} 3 \"\" {} \"Rust Documentation\"", quote(&title), quote(&source))
+            .unwrap();
+
+        out.push_str(".SH NAME\n");
+        push_text(&mut out, &name);
+        if let Some(summary) = item.doc_value().map(|doc| plain_text_summary(&doc)) {
+            if !summary.is_empty() {
+                out.push_str(" \\- ");
+                push_text(&mut out, &summary);
+            }
+        }
+        out.push('\n');
+
+        out.push_str(".SH SYNOPSIS\n");
+        match page.signature {
+            Some(ref signature) => push_code(&mut out, signature),
+            None => writeln!(out, "{} \\fB{}\\fR", page.kind, escape(&name)).unwrap(),
+        }
+
+        if let Some(doc) = item.collapsed_doc_value() {
+            out.push_str(".SH DESCRIPTION\n");
+            push_docs(&mut out, &doc, &item.link_names(&self.cache), false);
+        }
+
+        for section in &page.sections {
+            writeln!(out, ".SH {}", quote(&section.title.to_uppercase())).unwrap();
+            for entry in &section.entries {
+                if item.is_mod() {
+                    self.write_module_entry(&mut out, entry);
+                } else {
+                    self.write_entry(&mut out, entry, false);
+                }
+            }
+        }
+
+        if path.len() > 1 {
+            out.push_str(".SH \"SEE ALSO\"\n");
+            let parent = page_name(&path[..path.len() - 1], ItemType::Module);
+            writeln!(out, ".BR {} (3)", quote(&parent)).unwrap();
+        }
+        out
+    }
+
+    /// Writes an item of a module, with a reference to its page and a summary of its
+    /// documentation.
+    fn write_module_entry(&self, out: &mut String, entry: &Entry<'_>) {
+        out.push_str(".TP\n");
+        match entry.page {
+            Some((item_type, name)) => {
+                let mut path = self.current.clone();
+                path.push(name);
+                writeln!(out, ".BR {} (3)", quote(&page_name(&path, item_type))).unwrap();
+            }
+            None => writeln!(out, ".B {}", quote(&entry.title)).unwrap(),
+        }
+        let summary = entry.item.doc_value().map(|doc| plain_text_summary(&doc));
+        if let Some(summary) = summary.filter(|summary| !summary.is_empty()) {
+            push_text(out, &summary);
+            out.push('\n');
+        }
+    }
+
+    fn write_entry(&self, out: &mut String, entry: &Entry<'_>, nested: bool) {
+        if nested {
+            writeln!(out, ".PP\n\\fB{}\\fR", escape(&entry.title)).unwrap();
+        } else {
+            writeln!(out, ".SS {}", quote(&entry.title)).unwrap();
+        }
+        if let Some(signature) = &entry.signature {
+            push_code(out, signature);
+        }
+        if let Some(doc) = entry.item.collapsed_doc_value() {
+            push_docs(out, &doc, &entry.item.link_names(&self.cache), true);
+        }
+        if !entry.children.is_empty() {
+            out.push_str(".RS 4\n");
+            for child in &entry.children {
+                self.write_entry(out, child, true);
+            }
+            out.push_str(".RE\n");
+        }
+    }
+
+    fn write_page(
+        &self,
+        path: &[Symbol],
+        item_type: ItemType,
+        contents: String,
+    ) -> Result<(), Error> {
+        try_err!(create_dir_all(&self.dst), &self.dst);
+        let file = self.dst.join(format!("{}.3", page_name(path, item_type)));
+        try_err!(fs::write(&file, contents), &file);
+        Ok(())
+    }
+}
+
+/// The name of the page of an item, which is also its file name without the section. `::` isn't
+/// used to join the path as it can't be part of a file name on all platforms.
+fn page_name(path: &[Symbol], item_type: ItemType) -> String {
+    let mut name = String::new();
+    for segment in path {
+        write!(name, "{}.", segment).unwrap();
+    }
+    name.push_str(item_type.as_str());
+    name
+}
+
+/// Escapes text for roff. This doesn't handle the start of lines, see [`push_text`].
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\e").replace('-', "\\-")
+}
+
+/// Quotes a macro argument.
+fn quote(text: &str) -> String {
+    format!("\"{}\"", escape(text).replace('"', "\\(dq"))
+}
+
+/// Pushes text to `out`, escaping what would be read as a request at the start of a line.
+fn push_text(out: &mut String, text: &str) {
+    for (i, line) in text.split('\n').enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        let line = if out.is_empty() || out.ends_with('\n') {
+            // Leading spaces break the line, and so do empty lines.
+            let line = line.trim_start();
+            if line.starts_with('.') || line.starts_with('\'') {
+                out.push_str("\\&");
+            }
+            line
+        } else {
+            line
+        };
+        out.push_str(&escape(line));
+    }
+}
+
+fn push_code(out: &mut String, code: &str) {
+    out.push_str(".PP\n.RS 4\n.nf\n");
+    for line in code.lines() {
+        if line.starts_with('.') || line.starts_with('\'') {
+            out.push_str("\\&");
+        }
+        out.push_str(&escape(line));
+        out.push('\n');
+    }
+    out.push_str(".fi\n.RE\n");
+}
+
+fn ensure_newline(out: &mut String) {
+    if !out.is_empty() && !out.ends_with('\n') {
+        out.push('\n');
+    }
+}
+
+/// Translates the Markdown documentation of an item to roff. The headings of entries, which
+/// already are subsections, are written in bold.
+fn push_docs(out: &mut String, md: &str, links: &[RenderedLink], nested: bool) {
+    let mut replacer = |broken_link: BrokenLink<'_>| {
+        links
+            .iter()
+            .find(|link| link.original_text.as_str() == &*broken_link.reference)
+            .map(|link| (link.original_text.as_str().into(), link.new_text.as_str().into()))
+    };
+    let p = Parser::new_with_broken_link_callback(md, main_body_opts(), Some(&mut replacer));
+
+    // The text of an intra-doc link, which replaces the text written in a shortcut link.
+    let mut link_text: Option<&RenderedLink> = None;
+    // The destination of a link, shown after its text.
+    let mut link_dest = None;
+    let mut code_block: Option<(bool, String)> = None;
+    let mut lists = Vec::new();
+    let mut item_start = false;
+    ensure_newline(out);
+    out.push_str(".PP\n");
+    let mut paragraph_start = true;
+    for event in p {
+        match event {
+            Event::Start(Tag::Paragraph) => {
+                ensure_newline(out);
+                if !paragraph_start && !item_start {
+                    out.push_str(if lists.is_empty() { ".PP\n" } else { ".sp\n" });
+                }
+                paragraph_start = false;
+                item_start = false;
+            }
+            Event::End(Tag::Paragraph) => ensure_newline(out),
+            Event::Start(Tag::Heading(level, ..)) => {
+                ensure_newline(out);
+                if !nested && level as usize == 1 {
+                    out.push_str(".SS ");
+                    paragraph_start = true;
+                } else {
+                    out.push_str(".PP\n\\fB");
+                    paragraph_start = false;
+                }
+            }
+            Event::End(Tag::Heading(level, ..)) => {
+                if nested || level as usize != 1 {
+                    out.push_str("\\fR");
+                }
+                out.push('\n');
+            }
+            Event::Start(Tag::BlockQuote) => {
+                ensure_newline(out);
+                out.push_str(".RS 4\n");
+                paragraph_start = true;
+            }
+            Event::End(Tag::BlockQuote) => {
+                ensure_newline(out);
+                out.push_str(".RE\n");
+            }
+            Event::Start(Tag::CodeBlock(kind)) => {
+                let is_rust = match kind {
+                    CodeBlockKind::Fenced(ref lang) => {
+                        LangString::parse_without_check(lang, ErrorCodes::No, false).rust
+                    }
+                    CodeBlockKind::Indented => true,
+                };
+                code_block = Some((is_rust, String::new()));
+            }
+            Event::End(Tag::CodeBlock(_)) => {
+                let (is_rust, code) = code_block.take().unwrap();
+                let code: Vec<_> = if is_rust {
+                    code.lines().filter_map(|line| map_line(line).for_html()).collect()
+                } else {
+                    code.lines().map(Into::into).collect()
+                };
+                ensure_newline(out);
+                push_code(out, &code.join("\n"));
+                paragraph_start = false;
+            }
+            Event::Start(Tag::List(start)) => {
+                ensure_newline(out);
+                if !lists.is_empty() {
+                    out.push_str(".RS 4\n");
+                }
+                lists.push(start);
+            }
+            Event::End(Tag::List(_)) => {
+                ensure_newline(out);
+                lists.pop();
+                if !lists.is_empty() {
+                    out.push_str(".RE\n");
+                }
+            }
+            Event::Start(Tag::Item) => {
+                ensure_newline(out);
+                match lists.last_mut() {
+                    Some(Some(number)) => {
+                        writeln!(out, ".IP {}. 4", number).unwrap();
+                        *number += 1;
+                    }
+                    _ => out.push_str(".IP \\(bu 2\n"),
+                }
+                item_start = true;
+            }
+            Event::End(Tag::Item) => ensure_newline(out),
+            Event::Start(Tag::Emphasis) => out.push_str("\\fI"),
+            Event::Start(Tag::Strong) => out.push_str("\\fB"),
+            Event::End(Tag::Emphasis | Tag::Strong) => out.push_str("\\fR"),
+            Event::Start(Tag::Link(kind, dest, _)) => {
+                let intra_doc_link = links.iter().find(|link| *link.original_text == *dest);
+                if let LinkType::ShortcutUnknown | LinkType::CollapsedUnknown = kind {
+                    link_text = intra_doc_link;
+                }
+                // The targets of intra-doc links have their own man pages, and autolinks already
+                // show their destination.
+                if intra_doc_link.is_none() && !matches!(kind, LinkType::Autolink | LinkType::Email)
+                {
+                    link_dest = Some(dest);
+                }
+            }
+            Event::End(Tag::Link(..)) => {
+                link_text = None;
+                if let Some(dest) = link_dest.take() {
+                    push_text(out, &format!(" <{}>", dest));
+                }
+            }
+            Event::Text(text) => match code_block {
+                Some((_, ref mut code)) => code.push_str(&text),
+                None => match link_text {
+                    Some(link) if *text == *link.original_text => push_text(out, &link.new_text),
+                    _ => push_text(out, &text),
+                },
+            },
+            Event::Code(code) => {
+                let code = match link_text {
+                    Some(link) if link.original_text.trim_matches('`') == &*code => {
+                        link.new_text.as_str()
+                    }
+                    _ => &*code,
+                };
+                write!(out, "\\fB{}\\fR", escape(code.trim_matches('`'))).unwrap();
+            }
+            Event::SoftBreak => out.push('\n'),
+            Event::HardBreak => {
+                ensure_newline(out);
+                out.push_str(".br\n");
+            }
+            Event::Rule => {
+                ensure_newline(out);
+                out.push_str(".PP\n");
+            }
+            Event::FootnoteReference(name) => push_text(out, &format!("[{}]", name)),
+            Event::Start(Tag::FootnoteDefinition(name)) => {
+                ensure_newline(out);
+                out.push_str(".PP\n");
+                push_text(out, &format!("[{}] ", name));
+                paragraph_start = true;
+            }
+            Event::TaskListMarker(checked) => push_text(out, if checked { "[x] " } else { "[ ] " }),
+            Event::Start(Tag::TableHead | Tag::TableRow) => {
+                ensure_newline(out);
+                out.push_str(".br\n");
+            }
+            Event::Start(Tag::TableCell) if !out.ends_with('\n') => out.push_str(" | "),
+            Event::Start(Tag::Table(_)) => {
+                ensure_newline(out);
+                out.push_str(".PP\n");
+            }
+            // Raw HTML means nothing in a man page.
+            Event::Html(_) => {}
+            Event::Start(_) | Event::End(_) => {}
+        }
+    }
+    ensure_newline(out);
+}
+
+impl<'tcx> FormatRenderer<'tcx> for ManRenderer<'tcx> {
+    fn descr() -> &'static str {
+        "man"
+    }
+
+    const RUN_ON_MODULE: bool = true;
+
+    fn init(
+        krate: clean::Crate,
+        options: RenderOptions,
+        cache: Cache,
+        tcx: TyCtxt<'tcx>,
+    ) -> Result<(Self, clean::Crate), Error> {
+        debug!("Initializing man page renderer");
+        Ok((
+            ManRenderer {
+                tcx,
+                cache: Rc::new(cache),
+                dst: options.output.join("man3"),
+                current: Vec::new(),
+                in_stripped_module: false,
+            },
+            krate,
+        ))
+    }
+
+    fn make_child_renderer(&self) -> Self {
+        self.clone()
+    }
+
+    fn item(&mut self, item: clean::Item) -> Result<(), Error> {
+        if self.in_stripped_module || item.is_stripped() {
+            return Ok(());
+        }
+        let mut path = self.current.clone();
+        path.push(item.name.unwrap());
+        let page = self.render_page(&item, &path);
+        self.write_page(&path, item.type_(), page)
+    }
+
+    fn mod_item_in(&mut self, item: &clean::Item) -> Result<(), Error> {
+        self.in_stripped_module |= item.is_stripped();
+        self.current.push(item.name.unwrap());
+        if self.in_stripped_module {
+            return Ok(());
+        }
+        let page = self.render_page(item, &self.current);
+        self.write_page(&self.current, ItemType::Module, page)
+    }
+
+    fn mod_item_out(&mut self) -> Result<(), Error> {
+        self.current.pop();
+        Ok(())
+    }
+
+    fn after_krate(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn cache(&self) -> &Cache {
+        &self.cache
+    }
+}
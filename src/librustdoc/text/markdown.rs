@@ -0,0 +1,366 @@
+//! The Markdown backend, enabled with `--output-format markdown`.
+//!
+//! Every page of the HTML output has a Markdown counterpart at the same location, ending in `.md`
+//! instead of `.html`. The documentation of items is included as written, except that intra-doc
+//! links are turned into relative links to the pages of their targets, headings are nested under
+//! the heading of the documented item and hidden lines of Rust code blocks are removed.
+
+use std::fmt::Write;
+use std::fs::{self, create_dir_all};
+use std::ops::Range;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+use pulldown_cmark::{BrokenLink, CodeBlockKind, Event, LinkType, Parser, Tag};
+use rustc_hir::def::DefKind;
+use rustc_hir::def_id::DefId;
+use rustc_middle::ty::TyCtxt;
+use rustc_span::Symbol;
+
+use super::{page_file_stem, Entry, Page};
+use crate::clean::types::ExternalLocation;
+use crate::clean::{self, ItemLink, RenderedLink};
+use crate::config::RenderOptions;
+use crate::docfs::PathError;
+use crate::error::Error;
+use crate::formats::cache::Cache;
+use crate::formats::item_type::ItemType;
+use crate::formats::FormatRenderer;
+use crate::html::format::{href_relative_parts, join_with_double_colon};
+use crate::html::markdown::{main_body_opts, map_line, plain_text_summary, ErrorCodes, LangString};
+use crate::try_err;
+
+#[derive(Clone)]
+pub(crate) struct MarkdownRenderer<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    cache: Rc<Cache>,
+    /// The directory of the module currently being rendered.
+    dst: PathBuf,
+    /// The path of the module currently being rendered.
+    current: Vec<Symbol>,
+    /// Whether the current module is stripped. The HTML output only has redirections for the
+    /// items of stripped modules, so nothing is rendered for them here.
+    in_stripped_module: bool,
+}
+
+impl<'tcx> MarkdownRenderer<'tcx> {
+    fn render_page(&self, item: &clean::Item, path: &[Symbol]) -> String {
+        let page = Page::new(item, &self.cache, self.tcx);
+        let mut out = String::new();
+        writeln!(out, "# {} `{}`\n", page.kind, join_with_double_colon(path)).unwrap();
+        if let Some(signature) = &page.signature {
+            writeln!(out, "```rust\n{}\n```\n", signature).unwrap();
+        }
+        self.write_docs(&mut out, item, 1);
+
+        for section in &page.sections {
+            writeln!(out, "## {}\n", section.title).unwrap();
+            if item.is_mod() {
+                for entry in &section.entries {
+                    self.write_module_entry(&mut out, entry);
+                }
+                out.push('\n');
+            } else {
+                for entry in &section.entries {
+                    self.write_entry(&mut out, entry, 3);
+                }
+            }
+        }
+        out.truncate(out.trim_end().len());
+        out.push('\n');
+        out
+    }
+
+    /// Writes an item of a module as a list item, with the summary of its documentation.
+    fn write_module_entry(&self, out: &mut String, entry: &Entry<'_>) {
+        match entry.page {
+            Some((item_type, name)) => {
+                let file = page_file_stem(item_type, name);
+                write!(out, "- [`{}`]({}.md)", entry.title, file).unwrap();
+            }
+            None => write!(out, "- `{}`", entry.title).unwrap(),
+        }
+        let summary = entry.item.doc_value().map(|doc| plain_text_summary(&doc));
+        if let Some(summary) = summary.filter(|summary| !summary.is_empty()) {
+            write!(out, ": {}", summary).unwrap();
+        }
+        out.push('\n');
+    }
+
+    fn write_entry(&self, out: &mut String, entry: &Entry<'_>, level: usize) {
+        out.push_str(&"#".repeat(level));
+        out.push(' ');
+        // The anchor is inline, since an HTML block would swallow the heading after it.
+        if let Some(id) = &entry.id {
+            write!(out, "<a id=\"{}\"></a>", id).unwrap();
+        }
+        writeln!(out, "`{}`\n", entry.title).unwrap();
+        if let Some(signature) = &entry.signature {
+            writeln!(out, "```rust\n{}\n```\n", signature).unwrap();
+        }
+        self.write_docs(out, entry.item, level);
+        for child in &entry.children {
+            self.write_entry(out, child, (level + 1).min(6));
+        }
+    }
+
+    fn write_docs(&self, out: &mut String, item: &clean::Item, level: usize) {
+        if let Some(doc) = item.collapsed_doc_value() {
+            out.push_str(rewrite_docs(&doc, &self.links(item), level).trim());
+            out.push_str("\n\n");
+        }
+    }
+
+    /// Resolves the intra-doc links of `item` to the Markdown pages of their targets.
+    fn links(&self, item: &clean::Item) -> Vec<RenderedLink> {
+        self.cache
+            .intra_doc_links
+            .get(&item.item_id)
+            .map_or(&[][..], |v| v.as_slice())
+            .iter()
+            .filter_map(|ItemLink { link, link_text, did, fragment }| {
+                let mut href = href(*did, &self.cache, self.tcx, &self.current)?;
+                if let Some(fragment) = fragment {
+                    fragment.render(&mut href, self.tcx);
+                }
+                Some(RenderedLink {
+                    original_text: link.clone(),
+                    new_text: link_text.clone(),
+                    href,
+                })
+            })
+            .collect()
+    }
+
+    fn write_page(&self, file_name: &str, contents: String) -> Result<(), Error> {
+        try_err!(create_dir_all(&self.dst), &self.dst);
+        let path = self.dst.join(file_name);
+        try_err!(fs::write(&path, contents), &path);
+        Ok(())
+    }
+}
+
+/// Returns the link to the Markdown page of `did`, relative to the module `current`. Items from
+/// other crates whose documentation isn't rendered next to this one link to their HTML pages.
+fn href(did: DefId, cache: &Cache, tcx: TyCtxt<'_>, current: &[Symbol]) -> Option<String> {
+    let did = match tcx.def_kind(did) {
+        DefKind::AssocTy | DefKind::AssocFn | DefKind::AssocConst | DefKind::Variant => {
+            // documented on their parent's page
+            tcx.parent(did)
+        }
+        _ => did,
+    };
+    if !did.is_local()
+        && !cache.access_levels.is_public(did)
+        && !cache.document_private
+        && !cache.primitive_locations.values().any(|&id| id == did)
+    {
+        return None;
+    }
+
+    let (fqp, item_type) = cache.paths.get(&did).or_else(|| cache.external_paths.get(&did))?;
+    let module_fqp = if *item_type == ItemType::Module { &fqp[..] } else { &fqp[..fqp.len() - 1] };
+    let file_stem = match item_type {
+        ItemType::Module => "index".to_owned(),
+        _ => format!("{}.{}", item_type, fqp.last().unwrap()),
+    };
+    if !cache.paths.contains_key(&did) {
+        match cache.extern_locations.get(&did.krate)? {
+            ExternalLocation::Remote(url) => {
+                let mut href = url.trim_end_matches('/').to_owned();
+                for part in module_fqp {
+                    write!(href, "/{}", part).unwrap();
+                }
+                write!(href, "/{}.html", file_stem).unwrap();
+                return Some(href);
+            }
+            ExternalLocation::Local => {}
+            ExternalLocation::Unknown => return None,
+        }
+    }
+    let mut href = String::new();
+    for part in href_relative_parts(module_fqp, current) {
+        write!(href, "{}/", part).unwrap();
+    }
+    write!(href, "{}.md", file_stem).unwrap();
+    Some(href)
+}
+
+/// Turns documentation written in rustdoc's flavor of Markdown into plain Markdown, nested under
+/// a heading of the given level. See the module documentation for what is rewritten.
+fn rewrite_docs(md: &str, links: &[RenderedLink], level: usize) -> String {
+    let mut replacer = |broken_link: BrokenLink<'_>| {
+        links
+            .iter()
+            .find(|link| link.original_text.as_str() == &*broken_link.reference)
+            .map(|link| (link.href.as_str().into(), link.new_text.as_str().into()))
+    };
+    let p = Parser::new_with_broken_link_callback(md, main_body_opts(), Some(&mut replacer));
+
+    // Replacements of ranges of `md`, in the order of the ranges.
+    let mut edits: Vec<(Range<usize>, String)> = Vec::new();
+    // The intra-doc link being rewritten, with the range of its text.
+    let mut current_link: Option<(Range<usize>, &RenderedLink, bool, Option<Range<usize>>)> = None;
+    let mut in_rust_code_block = false;
+    for (event, range) in p.into_offset_iter() {
+        match event {
+            Event::Start(Tag::Link(kind, dest, _)) => {
+                // Shortcut links were resolved by the broken link callback, so they already
+                // point to the right page.
+                let shortcut =
+                    matches!(kind, LinkType::ShortcutUnknown | LinkType::CollapsedUnknown);
+                let link = links.iter().find(|link| {
+                    if shortcut { *link.href == *dest } else { *link.original_text == *dest }
+                });
+                current_link = link.map(|link| (range, link, shortcut, None));
+            }
+            Event::End(Tag::Link(..)) => {
+                if let Some((range, link, shortcut, text)) = current_link.take() {
+                    let mut text = text.map_or("", |text| &md[text]).to_owned();
+                    if shortcut {
+                        // Remove the disambiguator: [`fn@f`]
+                        let original_text = link.original_text.trim_matches('`');
+                        text = text.replacen(original_text, &link.new_text, 1);
+                    }
+                    edits.push((range, format!("[{}]({})", text, link.href)));
+                }
+            }
+            Event::Start(Tag::Heading(heading_level, ..)) => {
+                let heading_level = heading_level as usize;
+                let new_level = (heading_level + level).min(6);
+                let source = &md[range.clone()];
+                let start = range.start + (source.len() - source.trim_start().len());
+                if source.trim_start().starts_with('#') {
+                    edits.push((start..start, "#".repeat(new_level - heading_level)));
+                } else if let Some(underline) = source.trim_end().rfind('\n') {
+                    // Setext headings can't be nested, so they are turned into ATX headings.
+                    edits.push((start..start, format!("{} ", "#".repeat(new_level))));
+                    let end = range.start + source.trim_end().len();
+                    edits.push((range.start + underline..end, String::new()));
+                }
+            }
+            Event::Start(Tag::CodeBlock(kind)) => {
+                in_rust_code_block = match kind {
+                    CodeBlockKind::Fenced(ref lang) => {
+                        LangString::parse_without_check(lang, ErrorCodes::No, false).rust
+                    }
+                    CodeBlockKind::Indented => true,
+                };
+                // Rustdoc's code block attributes mean nothing to other Markdown renderers.
+                if let (true, CodeBlockKind::Fenced(lang)) = (in_rust_code_block, kind) {
+                    if &*lang != "rust" {
+                        let first_line = md[range.clone()].lines().next().unwrap_or_default();
+                        let info = first_line.trim_start().trim_start_matches(&['`', '~'][..]);
+                        let info_start = range.start + first_line.len() - info.len();
+                        let info_end = range.start + first_line.trim_end().len();
+                        edits.push((info_start..info_end, "rust".to_owned()));
+                    }
+                }
+            }
+            Event::End(Tag::CodeBlock(_)) => in_rust_code_block = false,
+            Event::Text(text) if in_rust_code_block => {
+                let mut visible: String = text
+                    .lines()
+                    .filter_map(|line| map_line(line).for_html())
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                if visible.is_empty() {
+                    // Remove the whole line, including the indentation of the code block.
+                    let line_start = md[..range.start].rfind('\n').map_or(0, |i| i + 1);
+                    edits.push((line_start..range.end, String::new()));
+                } else if visible != *text.trim_end_matches('\n') {
+                    if text.ends_with('\n') {
+                        visible.push('\n');
+                    }
+                    edits.push((range, visible));
+                }
+            }
+            _ => {
+                if let Some((_, _, _, text)) = &mut current_link {
+                    let text = text.get_or_insert(range.clone());
+                    text.start = text.start.min(range.start);
+                    text.end = text.end.max(range.end);
+                }
+            }
+        }
+    }
+
+    edits.sort_by_key(|(range, _)| range.start);
+    let mut out = String::with_capacity(md.len());
+    let mut pos = 0;
+    for (range, replacement) in edits {
+        out.push_str(&md[pos..range.start]);
+        out.push_str(&replacement);
+        pos = range.end;
+    }
+    out.push_str(&md[pos..]);
+    out
+}
+
+impl<'tcx> FormatRenderer<'tcx> for MarkdownRenderer<'tcx> {
+    fn descr() -> &'static str {
+        "markdown"
+    }
+
+    const RUN_ON_MODULE: bool = true;
+
+    fn init(
+        krate: clean::Crate,
+        options: RenderOptions,
+        cache: Cache,
+        tcx: TyCtxt<'tcx>,
+    ) -> Result<(Self, clean::Crate), Error> {
+        debug!("Initializing markdown renderer");
+        Ok((
+            MarkdownRenderer {
+                tcx,
+                cache: Rc::new(cache),
+                dst: options.output,
+                current: Vec::new(),
+                in_stripped_module: false,
+            },
+            krate,
+        ))
+    }
+
+    fn make_child_renderer(&self) -> Self {
+        self.clone()
+    }
+
+    fn item(&mut self, item: clean::Item) -> Result<(), Error> {
+        if self.in_stripped_module || item.is_stripped() {
+            return Ok(());
+        }
+        let name = item.name.unwrap();
+        let mut path = self.current.clone();
+        path.push(name);
+        let page = self.render_page(&item, &path);
+        self.write_page(&format!("{}.md", page_file_stem(item.type_(), name)), page)
+    }
+
+    fn mod_item_in(&mut self, item: &clean::Item) -> Result<(), Error> {
+        self.in_stripped_module |= item.is_stripped();
+        let name = item.name.unwrap();
+        self.dst.push(name.as_str());
+        self.current.push(name);
+        if self.in_stripped_module {
+            return Ok(());
+        }
+        let page = self.render_page(item, &self.current);
+        self.write_page("index.md", page)
+    }
+
+    fn mod_item_out(&mut self) -> Result<(), Error> {
+        self.dst.pop();
+        self.current.pop();
+        Ok(())
+    }
+
+    fn after_krate(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn cache(&self) -> &Cache {
+        &self.cache
+    }
+}
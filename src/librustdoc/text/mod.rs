@@ -0,0 +1,253 @@
+//! Rustdoc's text backends: `--output-format markdown` and `--output-format man`.
+//!
+//! Both backends render the same pages as the HTML backend, from the cleaned crate and the
+//! intra-doc links resolved by the `collect-intra-doc-links` pass. This module collects what goes
+//! on the page of an item, while the [`markdown`] and [`man`] modules lay it out.
+
+mod format;
+mod man;
+mod markdown;
+
+use rustc_middle::ty::TyCtxt;
+use rustc_span::hygiene::MacroKind;
+use rustc_span::Symbol;
+
+use crate::clean;
+use crate::formats::cache::Cache;
+use crate::formats::item_type::ItemType;
+use crate::formats::Impl;
+use crate::html::render::{item_ty_to_section, ItemSection};
+
+pub(crate) use self::man::ManRenderer;
+pub(crate) use self::markdown::MarkdownRenderer;
+
+/// The contents of the page of an item.
+struct Page<'a> {
+    /// What the item is, e.g. `Struct`.
+    kind: &'static str,
+    /// The declaration of the item, for items which have one.
+    signature: Option<String>,
+    sections: Vec<Section<'a>>,
+}
+
+/// A group of entries on the page of an item, like the fields of a struct.
+struct Section<'a> {
+    title: &'static str,
+    entries: Vec<Entry<'a>>,
+}
+
+/// An item shown on the page of another item, e.g. a method on the page of its type.
+struct Entry<'a> {
+    item: &'a clean::Item,
+    /// The anchor of the entry, the same as in the HTML output.
+    id: Option<String>,
+    title: String,
+    /// The full declaration of the item, when it doesn't fit in the title.
+    signature: Option<String>,
+    /// For items which have their own page, the type and name they are documented under in the
+    /// current module. Only a summary of their documentation is shown in that case.
+    page: Option<(ItemType, Symbol)>,
+    /// Entries nested under this one, e.g. the methods of an impl block.
+    children: Vec<Entry<'a>>,
+}
+
+impl<'a> Page<'a> {
+    fn new(item: &'a clean::Item, cache: &'a Cache, tcx: TyCtxt<'_>) -> Page<'a> {
+        let mut sections = Vec::new();
+        match *item.kind {
+            clean::ModuleItem(ref m) => module_sections(&m.items, &mut sections, tcx),
+            clean::StructItem(clean::Struct { ref fields, .. })
+            | clean::UnionItem(clean::Union { ref fields, .. }) => {
+                let fields = fields.iter().filter(|f| !f.is_stripped());
+                push_section(&mut sections, "Fields", fields.map(|f| member_entry(f, tcx)));
+            }
+            clean::EnumItem(ref e) => {
+                let variants = e.variants().map(|variant| {
+                    let mut entry = member_entry(variant, tcx);
+                    if let clean::VariantItem(clean::Variant::Struct(ref s)) = *variant.kind {
+                        entry.children = s
+                            .fields
+                            .iter()
+                            .filter(|f| !f.is_stripped())
+                            .map(|f| {
+                                let mut field = member_entry(f, tcx);
+                                field.id = Some(format!(
+                                    "variant.{}.field.{}",
+                                    variant.name.unwrap(),
+                                    f.name.unwrap()
+                                ));
+                                field
+                            })
+                            .collect();
+                    }
+                    entry
+                });
+                push_section(&mut sections, "Variants", variants);
+            }
+            clean::TraitItem(ref t) => {
+                let items = || t.items.iter().filter(|i| !i.is_stripped());
+                for (title, item_type) in [
+                    ("Associated Types", ItemType::AssocType),
+                    ("Associated Constants", ItemType::AssocConst),
+                    ("Required Methods", ItemType::TyMethod),
+                    ("Provided Methods", ItemType::Method),
+                ] {
+                    let entries = items()
+                        .filter(|i| i.type_() == item_type)
+                        .map(|i| member_entry(i, tcx));
+                    push_section(&mut sections, title, entries);
+                }
+            }
+            _ => {}
+        }
+
+        if let Some(did) = item.item_id.as_def_id() {
+            if let Some(impls) = cache.impls.get(&did) {
+                impl_sections(impls, &mut sections, tcx);
+            }
+            if let Some(implementors) = cache.implementors.get(&did) {
+                let entries = implementors.iter().map(|i| impl_entry(i, false, tcx));
+                push_section(&mut sections, "Implementors", entries);
+            }
+        }
+
+        Page { kind: item_kind(item), signature: format::print_item_signature(item, tcx), sections }
+    }
+}
+
+fn push_section<'a>(
+    sections: &mut Vec<Section<'a>>,
+    title: &'static str,
+    entries: impl Iterator<Item = Entry<'a>>,
+) {
+    let entries: Vec<_> = entries.collect();
+    if !entries.is_empty() {
+        sections.push(Section { title, entries });
+    }
+}
+
+/// Lists the items of a module, grouped and ordered like on the HTML page of the module.
+fn module_sections<'a>(
+    items: &'a [clean::Item],
+    sections: &mut Vec<Section<'a>>,
+    tcx: TyCtxt<'_>,
+) {
+    let mut items: Vec<_> = items
+        .iter()
+        .filter(|item| match *item.kind {
+            clean::StrippedItem(_) => false,
+            clean::ImportItem(ref import) => import.should_be_displayed,
+            _ => true,
+        })
+        .collect();
+    items.sort_by_key(|&item| item.name.as_ref().map(Symbol::as_str));
+
+    for &section in ItemSection::ALL {
+        let entries = items.iter().filter(|item| item_ty_to_section(item.type_()) == section).map(
+            |&item| match (item.name, &*item.kind) {
+                (Some(name), kind) if !matches!(kind, clean::ExternCrateItem { .. }) => Entry {
+                    item,
+                    id: None,
+                    title: name.to_string(),
+                    signature: None,
+                    page: Some((item.type_(), name)),
+                    children: Vec::new(),
+                },
+                _ => Entry {
+                    item,
+                    id: None,
+                    title: format::print_item_signature(item, tcx).unwrap_or_default(),
+                    signature: None,
+                    page: None,
+                    children: Vec::new(),
+                },
+            },
+        );
+        push_section(sections, section.name(), entries);
+    }
+}
+
+fn impl_sections<'a>(impls: &'a [Impl], sections: &mut Vec<Section<'a>>, tcx: TyCtxt<'_>) {
+    let (inherent, trait_impls): (Vec<_>, Vec<_>) =
+        impls.iter().partition(|i| i.inner_impl().trait_.is_none());
+    let inherent = inherent.into_iter().map(|i| impl_entry(i, true, tcx));
+    push_section(sections, "Implementations", inherent);
+
+    let (synthetic, concrete): (Vec<_>, Vec<_>) =
+        trait_impls.into_iter().partition(|i| i.inner_impl().kind.is_auto());
+    let (blanket, concrete): (Vec<_>, Vec<_>) =
+        concrete.into_iter().partition(|i| i.inner_impl().kind.is_blanket());
+    for (title, impls) in [
+        ("Trait Implementations", concrete),
+        ("Auto Trait Implementations", synthetic),
+        ("Blanket Implementations", blanket),
+    ] {
+        push_section(sections, title, impls.into_iter().map(|i| impl_entry(i, false, tcx)));
+    }
+}
+
+/// An entry for an impl block. The items of inherent impls are listed as well, since that's
+/// where intra-doc links to methods point to.
+fn impl_entry<'a>(i: &'a Impl, inherent: bool, tcx: TyCtxt<'_>) -> Entry<'a> {
+    let impl_ = i.inner_impl();
+    let header = format::print_impl(impl_, tcx);
+    let (title, signature) = match header.split_once('\n') {
+        Some((first_line, _)) => (first_line.to_owned(), Some(header)),
+        None => (header, None),
+    };
+    let children = if inherent {
+        impl_.items.iter().filter(|i| !i.is_stripped()).map(|i| member_entry(i, tcx)).collect()
+    } else {
+        Vec::new()
+    };
+    Entry { item: &i.impl_item, id: None, title, signature, page: None, children }
+}
+
+/// An entry for an item documented on the page of its parent, like a field or a method.
+fn member_entry<'a>(item: &'a clean::Item, tcx: TyCtxt<'_>) -> Entry<'a> {
+    let name = item.name.unwrap();
+    Entry {
+        item,
+        id: Some(format!("{}.{}", item.type_(), name)),
+        title: name.to_string(),
+        signature: format::print_item_signature(item, tcx),
+        page: None,
+        children: Vec::new(),
+    }
+}
+
+/// The kind of item used in the title of its page, like the HTML output does.
+fn item_kind(item: &clean::Item) -> &'static str {
+    match *item.kind {
+        clean::ModuleItem(_) if item.is_crate() => "Crate",
+        clean::ModuleItem(_) => "Module",
+        clean::FunctionItem(..) | clean::ForeignFunctionItem(..) => "Function",
+        clean::TraitItem(..) => "Trait",
+        clean::StructItem(..) => "Struct",
+        clean::UnionItem(..) => "Union",
+        clean::EnumItem(..) => "Enum",
+        clean::TypedefItem(..) => "Type Definition",
+        clean::MacroItem(..) => "Macro",
+        clean::ProcMacroItem(ref mac) => match mac.kind {
+            MacroKind::Bang => "Macro",
+            MacroKind::Attr => "Attribute Macro",
+            MacroKind::Derive => "Derive Macro",
+        },
+        clean::PrimitiveItem(..) => "Primitive Type",
+        clean::StaticItem(..) | clean::ForeignStaticItem(..) => "Static",
+        clean::ConstantItem(..) => "Constant",
+        clean::ForeignTypeItem => "Foreign Type",
+        clean::KeywordItem(..) => "Keyword",
+        clean::OpaqueTyItem(..) => "Opaque Type",
+        clean::TraitAliasItem(..) => "Trait Alias",
+        _ => "Item",
+    }
+}
+
+/// The file name of the page of an item in the directory of its module, without extension.
+fn page_file_stem(item_type: ItemType, name: Symbol) -> String {
+    match item_type {
+        ItemType::Module => format!("{}/index", name),
+        _ => format!("{}.{}", item_type, name),
+    }
+}
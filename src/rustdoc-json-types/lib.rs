@@ -9,7 +9,7 @@
 use serde::{Deserialize, Serialize};
 
 /// rustdoc format-version.
-pub const FORMAT_VERSION: u32 = 16;
+pub const FORMAT_VERSION: u32 = 17;
 
 /// A `Crate` is the root of the emitted JSON blob. It contains all type/documentation information
 /// about the language items in the local crate, as well as info about external items to allow
@@ -256,6 +256,7 @@ pub struct Union {
     pub fields_stripped: bool,
     pub fields: Vec<Id>,
     pub impls: Vec<Id>,
+    pub layout: Option<Layout>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -265,6 +266,7 @@ pub struct Struct {
     pub fields_stripped: bool,
     pub fields: Vec<Id>,
     pub impls: Vec<Id>,
+    pub layout: Option<Layout>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -273,6 +275,30 @@ pub struct Enum {
     pub variants_stripped: bool,
     pub variants: Vec<Id>,
     pub impls: Vec<Id>,
+    pub layout: Option<Layout>,
+}
+
+/// The memory layout of a type, as computed for the target rustdoc was invoked with. Absent if
+/// the layout couldn't be computed, for example because the type has generic parameters.
+///
+/// Most layout information is unstable and may even differ between compilations. The only
+/// exception is types with certain `repr(...)` attributes.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Layout {
+    /// The size in bytes, or `None` if the type is unsized.
+    pub size: Option<u64>,
+    /// The alignment in bytes.
+    pub align: u64,
+    /// For enums whose variants have different layouts, the size in bytes of each variant,
+    /// without the tag.
+    pub variants: Vec<VariantLayout>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct VariantLayout {
+    /// The id of the [`Variant`] item.
+    pub id: Id,
+    pub size: u64,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -344,6 +370,22 @@ pub struct Generics {
 pub struct GenericParamDef {
     pub name: String,
     pub kind: GenericParamDefKind,
+    /// The variance of a lifetime or type parameter of a struct, enum or union. Absent for other
+    /// parameters.
+    pub variance: Option<Variance>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Variance {
+    /// `T<'a>` is a subtype of `T<'b>` if `'a` outlives `'b`, like in `&'a u8`.
+    Covariant,
+    /// `T<'a>` is a subtype of `T<'b>` only if they are the same, like in `&'a mut &'a u8`.
+    Invariant,
+    /// `T<'a>` is a subtype of `T<'b>` if `'b` outlives `'a`, like in `fn(&'a u8)`.
+    Contravariant,
+    /// The parameter doesn't affect subtyping.
+    Bivariant,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -8,6 +8,7 @@ fn test_struct_info_roundtrip() {
         fields_stripped: false,
         fields: vec![],
         impls: vec![],
+        layout: None,
     });
 
     let struct_json = serde_json::to_string(&s).unwrap();
@@ -24,6 +25,7 @@ fn test_union_info_roundtrip() {
         fields_stripped: false,
         fields: vec![],
         impls: vec![],
+        layout: None,
     });
 
     let union_json = serde_json::to_string(&u).unwrap();
@@ -0,0 +1,21 @@
+#![crate_type = "lib"]
+
+/// A thing that can be greeted.
+pub trait Greet {
+    fn greet(&self) -> String;
+}
+
+pub struct World;
+
+impl Greet for World {
+    fn greet(&self) -> String {
+        let greeting = "hello";
+        format!("{} world", greeting)
+    }
+}
+
+pub fn greet_world() -> String {
+    World.greet()
+}
+
+pub static CRAB: &str = "🦀"; pub fn after_crab() {}
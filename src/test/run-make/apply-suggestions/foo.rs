@@ -0,0 +1,8 @@
+fn main() {
+    let mut x = 1;
+    let y = (2);
+    println!("{}", x);
+}
+
+#[allow(dead_code)]
+fn CamelCase() {}
@@ -0,0 +1,7 @@
+fn double(x: u32) -> u32 {
+    x + x
+}
+
+fn main() {
+    println!("{}", double(21));
+}
@@ -0,0 +1,8 @@
+// The code of the lints recorded in the baseline moved, but they are still suppressed.
+
+fn unused() {}
+
+pub fn foo() {
+    let known = 1;
+    let added = 2;
+}
@@ -0,0 +1,7 @@
+pub fn foo() {
+    let known = 1;
+}
+
+struct Fixed;
+
+fn unused() {}
@@ -0,0 +1,10 @@
+mod inner {
+    pub fn double<T: Copy + std::ops::Add<Output = T>>(x: T) -> T {
+        x + x
+    }
+}
+
+fn main() {
+    let triple = |x: u32| x * 3;
+    println!("{} {} {}", inner::double(1u8), inner::double(2u64), triple(3));
+}
@@ -0,0 +1,9 @@
+mod inner {
+    pub fn compute<T: Copy + std::ops::Add<Output = T>>(x: T) -> T {
+        x + x
+    }
+}
+
+fn main() {
+    println!("{}", inner::compute(1u8));
+}
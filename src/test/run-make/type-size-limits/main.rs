@@ -0,0 +1,19 @@
+pub struct Buffer {
+    pub header: u32,
+    pub data: [u8; 1024],
+}
+
+async fn wait() {}
+
+async fn process() -> u8 {
+    let buf = [0u8; 512];
+    wait().await;
+    buf[0]
+}
+
+fn main() {
+    let buffer = Buffer { header: 0, data: [0; 1024] };
+    println!("{}", std::mem::size_of_val(&buffer));
+    let future = process();
+    println!("{}", std::mem::size_of_val(&future));
+}
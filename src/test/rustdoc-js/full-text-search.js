@@ -0,0 +1,27 @@
+// exact-check
+
+const QUERY = [
+    'parsing numbers',
+    'settings',
+    'configuration',
+];
+
+const EXPECTED = [
+    {
+        'in_docs': [
+            { 'path': 'full_text_search', 'name': 'from_str' },
+        ],
+    },
+    {
+        'in_docs': [
+            { 'path': 'full_text_search::Config', 'name': 'load' },
+            { 'path': 'full_text_search', 'name': 'Config' },
+        ],
+    },
+    {
+        'in_docs': [
+            { 'path': 'full_text_search', 'name': 'Config' },
+            { 'path': '', 'name': 'full_text_search' },
+        ],
+    },
+];
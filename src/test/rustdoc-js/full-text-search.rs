@@ -0,0 +1,18 @@
+// compile-flags: -Z unstable-options --full-text-search
+
+//! Utilities for reading configuration files.
+
+/// Parses a number from the given string.
+pub fn from_str(s: &str) -> u32 {
+    s.len() as u32
+}
+
+/// A configuration file, holding the settings of the application.
+pub struct Config;
+
+impl Config {
+    /// Reads the settings from the environment.
+    pub fn load() -> Config {
+        Config
+    }
+}
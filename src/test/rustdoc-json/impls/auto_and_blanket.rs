@@ -0,0 +1,17 @@
+// ignore-tidy-linelength
+
+#![no_std]
+
+use core::cell::Cell;
+
+// @has auto_and_blanket.json "$.index[*][?(@.name=='NotSync')]"
+pub struct NotSync(Cell<u8>);
+
+// @is - "$.index[*][?(@.kind=='impl' && @.inner.for.inner.name=='NotSync' && @.inner.trait.inner.name=='Send')].inner.synthetic" true
+// @is - "$.index[*][?(@.kind=='impl' && @.inner.for.inner.name=='NotSync' && @.inner.trait.inner.name=='Send')].inner.negative" false
+// @is - "$.index[*][?(@.kind=='impl' && @.inner.for.inner.name=='NotSync' && @.inner.trait.inner.name=='Sync')].inner.synthetic" true
+// @is - "$.index[*][?(@.kind=='impl' && @.inner.for.inner.name=='NotSync' && @.inner.trait.inner.name=='Sync')].inner.negative" true
+
+// @is - "$.index[*][?(@.kind=='impl' && @.inner.for.inner.name=='NotSync' && @.inner.trait.inner.name=='From')].inner.synthetic" false
+// @is - "$.index[*][?(@.kind=='impl' && @.inner.for.inner.name=='NotSync' && @.inner.trait.inner.name=='From')].inner.blanket_impl.kind" \"generic\"
+// @is - "$.index[*][?(@.kind=='impl' && @.inner.for.inner.name=='NotSync' && @.inner.trait.inner.name=='From')].inner.blanket_impl.inner" \"T\"
@@ -0,0 +1,44 @@
+// ignore-tidy-linelength
+
+#![no_std]
+
+// @is layout.json "$.index[*][?(@.name=='Aligned')].inner.layout.size" 8
+// @is - "$.index[*][?(@.name=='Aligned')].inner.layout.align" 8
+// @is - "$.index[*][?(@.name=='Aligned')].inner.layout.variants" []
+#[repr(C, align(8))]
+pub struct Aligned {
+    pub byte: u8,
+}
+
+// @is - "$.index[*][?(@.name=='Unsized')].inner.layout.size" null
+// @is - "$.index[*][?(@.name=='Unsized')].inner.layout.align" 1
+pub struct Unsized {
+    pub len: u8,
+    pub data: [u8],
+}
+
+// @is - "$.index[*][?(@.name=='Generic')].inner.layout" null
+pub struct Generic<T>(pub T);
+
+// @is - "$.index[*][?(@.name=='Bytes')].inner.layout.size" 4
+// @is - "$.index[*][?(@.name=='Bytes')].inner.layout.align" 1
+// @set none = - "$.index[*][?(@.name=='NoBytes')].id"
+// @set three = - "$.index[*][?(@.name=='ThreeBytes')].id"
+// @count - "$.index[*][?(@.name=='Bytes')].inner.layout.variants[*]" 2
+// @is - "$.index[*][?(@.name=='Bytes')].inner.layout.variants[0].id" $none
+// @is - "$.index[*][?(@.name=='Bytes')].inner.layout.variants[0].size" 0
+// @is - "$.index[*][?(@.name=='Bytes')].inner.layout.variants[1].id" $three
+// @is - "$.index[*][?(@.name=='Bytes')].inner.layout.variants[1].size" 3
+#[repr(u8)]
+pub enum Bytes {
+    NoBytes,
+    ThreeBytes([u8; 3]),
+}
+
+// @is - "$.index[*][?(@.name=='Either')].inner.layout.size" 4
+// @is - "$.index[*][?(@.name=='Either')].inner.layout.align" 4
+#[repr(C)]
+pub union Either {
+    pub int: u32,
+    pub float: f32,
+}
@@ -0,0 +1,30 @@
+// ignore-tidy-linelength
+
+#![no_std]
+
+use core::cell::Cell;
+
+// @is variance.json "$.index[*][?(@.name=='Variances')].inner.generics.params[0].name" \"\'a\"
+// @is - "$.index[*][?(@.name=='Variances')].inner.generics.params[0].variance" \"covariant\"
+// @is - "$.index[*][?(@.name=='Variances')].inner.generics.params[1].name" \"T\"
+// @is - "$.index[*][?(@.name=='Variances')].inner.generics.params[1].variance" \"invariant\"
+// @is - "$.index[*][?(@.name=='Variances')].inner.generics.params[2].name" \"U\"
+// @is - "$.index[*][?(@.name=='Variances')].inner.generics.params[2].variance" \"contravariant\"
+// @is - "$.index[*][?(@.name=='Variances')].inner.generics.params[3].name" \"N\"
+// @is - "$.index[*][?(@.name=='Variances')].inner.generics.params[3].variance" null
+pub struct Variances<'a, T, U, const N: usize> {
+    pub shared: &'a u8,
+    pub cell: Cell<T>,
+    pub callback: fn(U),
+    pub array: [u8; N],
+}
+
+// @is - "$.index[*][?(@.name=='Either')].inner.generics.params[0].variance" \"covariant\"
+// @is - "$.index[*][?(@.name=='Either')].inner.generics.params[1].variance" \"contravariant\"
+pub enum Either<L, R> {
+    Left(L),
+    Right(fn(R)),
+}
+
+// @is - "$.index[*][?(@.name=='generic_fn')].inner.generics.params[0].variance" null
+pub fn generic_fn<T>(_: T) {}
@@ -0,0 +1,23 @@
+// check-pass
+// compile-flags:-Z unstable-options --output-format json --show-coverage --coverage-report
+
+// This check ensures that the items missing docs or examples, the unresolved intra-doc links and
+// the ignored doctests are listed.
+
+#![allow(rustdoc::broken_intra_doc_links)]
+
+//! Crate docs, linking to [Missing].
+//!
+//! ```ignore
+//! let x = 12;
+//! ```
+
+/// ```
+/// let x = 12;
+/// ```
+pub fn with_example() {}
+
+/// No example here.
+pub fn without_example() {}
+
+pub struct Undocumented;
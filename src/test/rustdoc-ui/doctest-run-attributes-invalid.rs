@@ -0,0 +1,25 @@
+#![deny(rustdoc::invalid_codeblock_attributes)]
+
+/// ```env=NOVALUE
+//~^ ERROR invalid attribute `env=NOVALUE`
+/// let x = 1;
+/// ```
+pub fn env() {}
+
+/// ```stdin=
+//~^ ERROR invalid attribute `stdin=`
+/// let x = 1;
+/// ```
+pub fn stdin() {}
+
+/// ```stdin=missing.txt,cwd=missing
+//~^ ERROR file `missing.txt` not found for the `stdin` attribute
+//~| ERROR directory `missing` not found for the `cwd` attribute
+/// let x = 1;
+/// ```
+pub fn missing() {}
+
+/// ```stdin=doctest-run-attributes-invalid.rs,cwd=coverage
+/// let x = 1;
+/// ```
+pub fn found() {}
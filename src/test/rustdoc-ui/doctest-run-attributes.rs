@@ -0,0 +1,24 @@
+// Checks that the `env=`, `stdin=` and `cwd=` attributes are applied when running doctests.
+
+// check-pass
+// compile-flags:--test
+// normalize-stdout-test: "src/test/rustdoc-ui" -> "$$DIR"
+// normalize-stdout-test "finished in \d+\.\d+s" -> "finished in $$TIME"
+
+/// ```env=GREETING=hello,env=EMPTY=
+/// assert_eq!(std::env::var("GREETING").unwrap(), "hello");
+/// assert_eq!(std::env::var("EMPTY").unwrap(), "");
+/// ```
+pub fn env() {}
+
+/// ```stdin=doctest-run-attributes.rs
+/// let mut line = String::new();
+/// std::io::stdin().read_line(&mut line).unwrap();
+/// assert!(line.starts_with("// Checks that the `env=`"));
+/// ```
+pub fn stdin() {}
+
+/// ```cwd=coverage
+/// assert!(std::path::Path::new("basic.rs").is_file());
+/// ```
+pub fn cwd() {}
@@ -0,0 +1,33 @@
+// ignore-windows
+// Doctests without crate attributes are compiled together, while the others, and the ones which
+// fail to compile in the merged binary, are compiled on their own, and failures still point at the
+// original lines.
+
+// compile-flags:--test --test-args --test-threads=1 -Z unstable-options --merge-doctests
+// edition:2018
+// rustc-env:RUST_BACKTRACE=0
+// normalize-stdout-test: "src/test/rustdoc-ui" -> "$$DIR"
+// normalize-stdout-test "finished in \d+\.\d+s" -> "finished in $$TIME"
+// failure-status: 101
+
+/// ```
+/// assert_eq!(1 + 1, 2);
+/// ```
+pub struct Passes;
+
+/// ```
+/// println!("stdout");
+/// panic!("oh no");
+/// ```
+pub struct Panics;
+
+/// ```
+/// #![allow(unused_variables)]
+/// let x = 1;
+/// ```
+pub struct Standalone;
+
+/// ```
+/// no
+/// ```
+pub struct DoesNotCompile;
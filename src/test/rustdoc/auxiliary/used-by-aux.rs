@@ -0,0 +1,3 @@
+// compile-flags: -Z unstable-options --show-used-by
+
+pub struct Foo;
@@ -0,0 +1,41 @@
+// compile-flags: -Z unstable-options --output-format man
+
+#![crate_name = "foo"]
+
+// @has man3/foo.mod.3 '.TH "foo.mod" 3'
+// @has - '.SH NAME foo'
+// @has - '.SH "STRUCTS"'
+// @has - '.BR "foo.Foo.struct" (3)'
+// @has - '.BR "foo.bar.mod" (3)'
+// @has - '.BR "foo.bar.fn" (3)'
+
+// @has man3/foo.Foo.struct.3 '.SH NAME foo::Foo \- A struct.'
+// @has - '.SH SYNOPSIS'
+// @has - '.SH "FIELDS"'
+// @has - '.SS "x"'
+// @has - '.SS "impl Foo"'
+// @has - '.PP \fBnew\fR'
+// @has - 'Creates a \fBFoo\fR.'
+// @has - '.SH "SEE ALSO" .BR "foo.mod" (3)'
+/// A struct.
+///
+/// It has a field.
+pub struct Foo {
+    /// The field.
+    pub x: u32,
+}
+
+impl Foo {
+    /// Creates a [`Foo`].
+    pub fn new() -> Foo {
+        Foo { x: 0 }
+    }
+}
+
+// @has man3/foo.bar.mod.3 '.SH NAME foo::bar \- A module.'
+/// A module.
+pub mod bar {}
+
+// @has man3/foo.bar.fn.3 '.SH NAME foo::bar \- A function.'
+/// A function.
+pub fn bar() {}
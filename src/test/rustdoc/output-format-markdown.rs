@@ -0,0 +1,46 @@
+// compile-flags: -Z unstable-options --output-format markdown
+
+#![crate_name = "foo"]
+
+//! The crate, with a link to [`Foo::new`].
+//!
+//! # Examples
+//!
+//! ```
+//! # let hidden = 1;
+//! let foo = foo::Foo::new();
+//! ```
+
+// @has foo/index.md '# Crate `foo`'
+// @has - '[`Foo::new`](struct.Foo.md#method.new)'
+// @has - '## Examples'
+// @has - '```rust let foo = foo::Foo::new(); ```'
+// @!has - 'hidden'
+// @has - '- [`Foo`](struct.Foo.md): A struct.'
+// @has - '- [`bar`](bar/index.md)'
+
+// @has foo/struct.Foo.md '# Struct `foo::Foo`'
+// @has - 'pub struct Foo { pub x: u32, }'
+// @has - '### <a id="structfield.x"></a>`x`'
+// @has - '### `impl Foo`'
+// @has - '#### <a id="method.new"></a>`new`'
+// @has - '## Auto Trait Implementations'
+/// A struct.
+pub struct Foo {
+    /// The field.
+    pub x: u32,
+}
+
+impl Foo {
+    /// Creates a `Foo`.
+    pub fn new() -> Foo {
+        Foo { x: 0 }
+    }
+}
+
+pub mod bar {
+    // @has foo/bar/fn.baz.md '# Function `foo::bar::baz`'
+    // @has - '[the struct](../struct.Foo.md)'
+    /// Links to [the struct](crate::Foo).
+    pub fn baz() {}
+}
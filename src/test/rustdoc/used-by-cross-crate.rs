@@ -0,0 +1,11 @@
+// aux-build:used-by-aux.rs
+// build-aux-docs
+// compile-flags: -Z unstable-options --show-used-by
+
+extern crate used_by_aux;
+
+// @has used-by/used_by_aux/struct.Foo.js 'used_by["used_by_cross_crate"]'
+// @has - 'used_by_cross_crate/fn.make_foo.html'
+pub fn make_foo() -> used_by_aux::Foo {
+    used_by_aux::Foo
+}
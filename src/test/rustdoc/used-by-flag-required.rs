@@ -0,0 +1,8 @@
+// Tests that `--show-used-by` is required in order to show the "Used by" section.
+
+// @!has used_by_flag_required/struct.Foo.html 'Used by'
+pub struct Foo;
+
+pub fn foo() -> Foo {
+    Foo
+}
@@ -0,0 +1,46 @@
+// compile-flags: -Z unstable-options --show-used-by
+
+#![crate_name = "foo"]
+
+// @has foo/struct.Config.html '//h2[@id="used-by"]' 'Used by'
+// @has - '//div[@id="used-by-list"]//a[@href="fn.load.html"]' 'foo::load'
+// @has - '//div[@id="used-by-list"]//a[@href="struct.App.html#structfield.config"]' \
+//      'foo::App::config'
+// @has - '//div[@id="used-by-list"]//a[@href="struct.App.html#method.with_config"]' \
+//      'foo::App::with_config'
+// @has - '//div[@id="used-by-list"]//a[@href="trait.Source.html#tymethod.read"]' \
+//      'foo::Source::read'
+// @!has - '//div[@id="used-by-list"]//a' 'foo::Config::new'
+// @has - '//script/@src' '../used-by/foo/struct.Config.js'
+pub struct Config;
+
+impl Config {
+    pub fn new() -> Config {
+        Config
+    }
+}
+
+pub struct App {
+    pub config: Config,
+}
+
+impl App {
+    pub fn with_config(config: &Config) -> App {
+        App { config: Config }
+    }
+}
+
+// @has foo/trait.Source.html '//div[@id="used-by-list"]//a[@href="fn.load.html"]' 'foo::load'
+pub trait Source {
+    fn read(&self) -> Option<Config>;
+}
+
+pub fn load<S: Source>(source: S) -> Config {
+    source.read().unwrap_or(Config)
+}
+
+// @has foo/enum.Unused.html '//h2[@id="used-by"]' 'Used by'
+// @count - '//div[@id="used-by-list"]/*' 0
+pub enum Unused {}
+
+// @has used-by/foo/enum.Unused.js 'used_by["foo"] = [];'
@@ -0,0 +1,107 @@
+// run-pass
+// Test that users are able to use stable mir APIs to retrieve information of the current crate
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+// edition: 2021
+
+#![feature(rustc_private)]
+
+extern crate rustc_smir;
+
+use rustc_smir::rustc_internal::{self, Compilation};
+use rustc_smir::stable_mir::mir::{Operand, StatementKind, TerminatorKind};
+use rustc_smir::stable_mir::ty::TyKind;
+use rustc_smir::stable_mir::{self, ItemKind};
+use std::io::Write;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir(cx: &mut stable_mir::Context<'_>) -> Compilation {
+    // Get the local crate using stable_mir API.
+    let local = cx.local_crate();
+    assert_eq!(&local.name, CRATE_NAME);
+    assert!(local.is_local);
+    assert!(cx.external_crates().iter().any(|krate| krate.name == "std"));
+
+    let items = cx.all_local_items();
+    let find = |path: &str| items.iter().find(|item| item.path == path).unwrap().clone();
+
+    let foo_bar = find("foo_bar");
+    assert_eq!(foo_bar.kind, ItemKind::Fn);
+    let body = cx.mir_body(&foo_bar);
+    assert_eq!(body.arg_count, 1);
+    assert!(matches!(body.arg_locals()[0].ty.kind(), TyKind::Uint(_)));
+    let has_branch = body.blocks.iter().any(|block| {
+        matches!(block.terminator.kind, TerminatorKind::SwitchInt { .. })
+            && block.terminator.kind.successors().len() == 2
+    });
+    assert!(has_branch);
+
+    let bar = find("bar");
+    let body = cx.mir_body(&bar);
+    assert!(matches!(body.ret_ty().kind(), TyKind::Tuple(fields) if fields.is_empty()));
+    let call = body
+        .blocks
+        .iter()
+        .find_map(|block| match &block.terminator.kind {
+            TerminatorKind::Call { func: Operand::Constant(constant), .. } => Some(constant),
+            _ => None,
+        })
+        .unwrap();
+    let TyKind::FnDef(callee, _) = call.ty.kind() else { panic!("expected a function item") };
+    assert_eq!(cx.def_path_str(*callee), "foo_bar");
+    assert!(cx.def_crate(*callee).is_local);
+    assert!(
+        body.blocks
+            .iter()
+            .flat_map(|block| &block.statements)
+            .any(|statement| matches!(statement.kind, StatementKind::Assign(..)))
+    );
+
+    let answer = find("ANSWER");
+    assert_eq!(answer.kind, ItemKind::Const);
+    assert!(matches!(cx.mir_body(&answer).ret_ty().kind(), TyKind::Int(_)));
+
+    // Nothing to do anymore; skip code generation.
+    Compilation::Stop
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// It will invoke the compiler using a custom Callback implementation, which will
+/// invoke Stable MIR APIs after the compiler has finished its analysis.
+fn main() {
+    let path = "input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    rustc_internal::run(&args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub const ANSWER: i32 = 42;
+
+    pub fn foo_bar(x: u8) -> u8 {{
+        if x > 10 {{ x - 10 }} else {{ x }}
+    }}
+
+    pub fn bar() {{
+        let mut value = foo_bar(3);
+        value += 1;
+        let _ = value;
+    }}"#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,17 @@
+// compile-flags: -Z print-type-sizes --crate-type lib
+// edition:2021
+// build-pass
+// ignore-pass
+// ^-- needed because `--pass check` does not emit the output needed.
+//     FIXME: consider using an attribute instead of side-effects.
+
+// This file illustrates how generators are handled: each state is listed
+// as a variant, with the upvars and the locals held across its suspension
+// point, and the futures of async functions are named after the function.
+
+async fn wait() {}
+
+pub async fn test(arg: [u8; 8192]) {
+    wait().await;
+    drop(arg);
+}
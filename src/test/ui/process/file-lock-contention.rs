@@ -0,0 +1,78 @@
+// run-pass
+// ignore-emscripten no processes
+// ignore-sgx no processes
+// ignore-vxworks no file locking
+
+// Checks that locks taken with `File::lock` and friends exclude other
+// processes, and that a blocked `lock` call returns once the lock is released.
+
+#![feature(file_lock)]
+
+use std::env;
+use std::fs::{File, OpenOptions};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::Duration;
+
+fn lock_file() -> PathBuf {
+    env::current_exe().unwrap().with_extension("lock")
+}
+
+fn open() -> File {
+    OpenOptions::new().read(true).write(true).create(true).open(lock_file()).unwrap()
+}
+
+fn child(mode: &str) -> Command {
+    let mut command = Command::new(env::current_exe().unwrap());
+    command.arg(mode);
+    command
+}
+
+fn main() {
+    let args = env::args().collect::<Vec<_>>();
+    if args.len() > 1 {
+        let file = open();
+        match &*args[1] {
+            "exclusive-held" => {
+                assert!(!file.try_lock().unwrap());
+                assert!(!file.try_lock_shared().unwrap());
+            }
+            "shared-held" => {
+                assert!(!file.try_lock().unwrap());
+                assert!(file.try_lock_shared().unwrap());
+                file.unlock().unwrap();
+            }
+            "unlocked" => {
+                assert!(file.try_lock().unwrap());
+                file.unlock().unwrap();
+            }
+            "wait" => file.lock().unwrap(),
+            mode => panic!("unknown mode {}", mode),
+        }
+        return;
+    }
+
+    let file = open();
+
+    file.lock().unwrap();
+    assert!(child("exclusive-held").status().unwrap().success());
+
+    file.unlock().unwrap();
+    file.lock_shared().unwrap();
+    assert!(child("shared-held").status().unwrap().success());
+
+    file.unlock().unwrap();
+    assert!(child("unlocked").status().unwrap().success());
+
+    // A child blocked in `lock` only finishes after we release our lock.
+    file.lock().unwrap();
+    let mut waiting = child("wait").stdout(Stdio::null()).spawn().unwrap();
+    thread::sleep(Duration::from_millis(200));
+    assert!(waiting.try_wait().unwrap().is_none());
+    file.unlock().unwrap();
+    assert!(waiting.wait().unwrap().success());
+
+    // Locks are released when the process holding them exits.
+    assert!(child("unlocked").status().unwrap().success());
+}
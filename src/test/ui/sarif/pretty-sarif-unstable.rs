@@ -0,0 +1,6 @@
+// compile-flags: --error-format pretty-sarif
+
+// `--error-format=pretty-sarif` requires `-Z unstable-options`; the error about it is
+// reported as JSON since no SARIF emitter is set up at that point.
+
+fn main() {}
@@ -0,0 +1,6 @@
+// compile-flags: --error-format sarif
+
+// `--error-format=sarif` requires `-Z unstable-options`; the error about it is
+// reported as JSON since no SARIF emitter is set up at that point.
+
+fn main() {}
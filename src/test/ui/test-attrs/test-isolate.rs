@@ -0,0 +1,40 @@
+// compile-flags: --test
+// run-flags: --test-threads=1 -Zunstable-options --isolate --test-timeout 2s
+// run-fail
+// check-run-results
+// exec-env:RUST_BACKTRACE=0
+// normalize-stdout-test "finished in \d+\.\d+s" -> "finished in $$TIME"
+
+// ignore-wasm no panic or subprocess support
+// ignore-emscripten no panic or subprocess support
+// ignore-sgx no subprocess support
+
+#![cfg(test)]
+
+use std::panic;
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn it_catches_panics() {
+    // Panics caught by the test itself don't fail it.
+    assert!(panic::catch_unwind(|| panic!("caught")).is_err());
+}
+
+#[test]
+#[should_panic]
+fn it_panics() {
+    assert_eq!(1 + 1, 4);
+}
+
+#[test]
+fn it_fails() {
+    assert_eq!(1 + 1, 5);
+}
+
+#[test]
+fn it_hangs() {
+    loop {
+        thread::sleep(Duration::from_secs(1));
+    }
+}
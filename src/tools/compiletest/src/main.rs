@@ -510,6 +510,10 @@ pub fn test_opts(config: &Config) -> test::TestOpts {
         list: false,
         options: test::Options::new(),
         time_options: None,
+        test_timeout: None,
+        isolate: false,
+        shard_count: None,
+        shard_index: None,
         force_run_in_process: false,
     }
 }
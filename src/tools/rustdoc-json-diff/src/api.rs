@@ -0,0 +1,129 @@
+//! Collection of the public API of a crate from its rustdoc JSON.
+
+use std::collections::{BTreeMap, HashSet};
+
+use rustdoc_json_types::{Crate, Id, Item, ItemEnum, Visibility};
+
+/// The namespaces of items, which items sharing a path are told apart by.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Namespace {
+    Type,
+    Value,
+    Macro,
+}
+
+impl Namespace {
+    pub fn of(item: &Item) -> Namespace {
+        match item.inner {
+            ItemEnum::Function(_)
+            | ItemEnum::Method(_)
+            | ItemEnum::Constant(_)
+            | ItemEnum::Static(_)
+            | ItemEnum::AssocConst { .. }
+            | ItemEnum::StructField(_) => Namespace::Value,
+            ItemEnum::Macro(_) | ItemEnum::ProcMacro(_) => Namespace::Macro,
+            // Imports and impls are never listed, as they can't be named.
+            _ => Namespace::Type,
+        }
+    }
+}
+
+/// The items of a crate which can be named from other crates.
+pub struct PublicApi<'a> {
+    /// Public items by the paths they can be named with and their namespace. Items which are
+    /// re-exported are listed under every path they are reachable from.
+    pub items: BTreeMap<(String, Namespace), &'a Item>,
+    /// Re-exports of items from other crates, which aren't in the index, by the path they are
+    /// re-exported as. The value is the path of the re-exported item.
+    pub external_reexports: BTreeMap<String, &'a str>,
+}
+
+impl<'a> PublicApi<'a> {
+    pub fn new(krate: &'a Crate) -> PublicApi<'a> {
+        let mut api = PublicApi { items: BTreeMap::new(), external_reexports: BTreeMap::new() };
+        let root = &krate.index[&krate.root];
+        let name = root.name.as_deref().unwrap_or_default();
+        api.walk_module(krate, root, name, &mut HashSet::new());
+        api
+    }
+
+    fn walk_module(
+        &mut self,
+        krate: &'a Crate,
+        module: &'a Item,
+        path: &str,
+        walking: &mut HashSet<&'a Id>,
+    ) {
+        let items = match &module.inner {
+            ItemEnum::Module(module) => &module.items,
+            _ => return,
+        };
+        // Modules can re-export each other, so only the modules which are being walked are
+        // skipped. The others are walked again for every path they are reachable from.
+        if !walking.insert(&module.id) {
+            return;
+        }
+        for item in items.iter().filter_map(|id| krate.index.get(id)) {
+            if item.visibility != Visibility::Public {
+                continue;
+            }
+            match &item.inner {
+                ItemEnum::Import(import) => {
+                    match import.id.as_ref().and_then(|id| krate.index.get(id)) {
+                        Some(target) if import.glob => match &target.inner {
+                            // Globs re-export the variants of enums.
+                            ItemEnum::Enum(enum_) => {
+                                let variants =
+                                    enum_.variants.iter().filter_map(|id| krate.index.get(id));
+                                for variant in variants {
+                                    if let Some(name) = &variant.name {
+                                        let variant_path = format!("{}::{}", path, name);
+                                        self.add(krate, variant_path, variant, walking);
+                                    }
+                                }
+                            }
+                            _ => self.walk_module(krate, target, path, walking),
+                        },
+                        Some(target) => {
+                            self.add(krate, format!("{}::{}", path, import.name), target, walking)
+                        }
+                        None if !import.glob => {
+                            self.external_reexports
+                                .insert(format!("{}::{}", path, import.name), &import.source);
+                        }
+                        // The items of modules from other crates aren't known.
+                        None => {}
+                    }
+                }
+                // Stripped modules aren't public, only the items they re-export are.
+                ItemEnum::Module(module) if module.is_stripped => {}
+                _ => {
+                    if let Some(name) = &item.name {
+                        self.add(krate, format!("{}::{}", path, name), item, walking);
+                    }
+                }
+            }
+        }
+        walking.remove(&module.id);
+    }
+
+    /// Whether an item is listed under `path`, in any namespace.
+    pub fn contains_path(&self, path: &str) -> bool {
+        [Namespace::Type, Namespace::Value, Namespace::Macro]
+            .into_iter()
+            .any(|ns| self.items.contains_key(&(path.to_owned(), ns)))
+    }
+
+    fn add(
+        &mut self,
+        krate: &'a Crate,
+        path: String,
+        item: &'a Item,
+        walking: &mut HashSet<&'a Id>,
+    ) {
+        self.items.insert((path.clone(), Namespace::of(item)), item);
+        if let ItemEnum::Module(_) = item.inner {
+            self.walk_module(krate, item, &path, walking);
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use getopts::Options;
+
+use crate::diff::Severity;
+
+#[derive(Debug)]
+pub struct Config {
+    /// The rustdoc JSON output for the old version of the crate
+    pub old: String,
+    /// The rustdoc JSON output for the new version of the crate
+    pub new: String,
+    /// Whether to print the changes as JSON rather than as text
+    pub json: bool,
+    /// The lowest severity of changes which makes the comparison fail
+    pub deny: Option<Severity>,
+}
+
+/// Create a Config from a vector of command-line arguments
+pub fn parse_config(args: Vec<String>) -> Config {
+    let mut opts = Options::new();
+    opts.optopt("", "format", "Output format, `text` (default) or `json`", "FORMAT")
+        .optopt("", "deny", "Fail if there is a change of this severity or above", "major|minor")
+        .optflag("h", "help", "show this message");
+
+    let (argv0, args_) = args.split_first().unwrap();
+    let matches = opts.parse(args_).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        usage(argv0, &opts)
+    });
+    if matches.opt_present("h") || matches.free.len() != 2 {
+        usage(argv0, &opts);
+    }
+
+    let json = match matches.opt_str("format").as_deref() {
+        None | Some("text") => false,
+        Some("json") => true,
+        Some(format) => {
+            eprintln!("unknown output format `{}`", format);
+            usage(argv0, &opts)
+        }
+    };
+    let deny = match matches.opt_str("deny").as_deref() {
+        None => None,
+        Some("major") => Some(Severity::Major),
+        Some("minor") => Some(Severity::Minor),
+        Some(severity) => {
+            eprintln!("unknown severity `{}`", severity);
+            usage(argv0, &opts)
+        }
+    };
+
+    Config { old: matches.free[0].clone(), new: matches.free[1].clone(), json, deny }
+}
+
+fn usage(argv0: &str, opts: &Options) -> ! {
+    let message = format!("Usage: {} [options] <old.json> <new.json>", argv0);
+    println!("{}", opts.usage(&message));
+    std::process::exit(1);
+}
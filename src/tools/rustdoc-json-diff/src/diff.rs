@@ -0,0 +1,583 @@
+//! Comparison of two versions of the public API of a crate.
+//!
+//! Changes are classified following the [Cargo SemVer compatibility guidelines][semver]: a major
+//! change may break code using the crate, while a minor change only adds to the API.
+//!
+//! [semver]: https://doc.rust-lang.org/cargo/reference/semver.html
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::mem;
+
+use rustdoc_json_types::{
+    Crate, Enum, FnDecl, GenericParamDef, GenericParamDefKind, Generics, Header, Id, Item,
+    ItemEnum, Struct, Trait, Variant, Visibility,
+};
+use serde::Serialize;
+
+use crate::api::PublicApi;
+use crate::print;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Minor,
+    Major,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Severity::Minor => "minor",
+            Severity::Major => "major",
+        })
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct Change {
+    /// The path of the changed item.
+    pub path: String,
+    pub severity: Severity,
+    pub description: String,
+}
+
+/// Returns the changes made to the public API of `old` in `new`, ordered by path.
+pub fn diff(old: &Crate, new: &Crate) -> Vec<Change> {
+    let old_api = PublicApi::new(old);
+    let new_api = PublicApi::new(new);
+    let mut cx = DiffCx { old, new, changes: Vec::new() };
+
+    // Items are compared within their namespace, so that e.g. removing one of a module and a
+    // function with the same path is reported as such.
+    for (key @ (path, _), old_item) in &old_api.items {
+        match new_api.items.get(key) {
+            Some(new_item) => cx.compare_items(path, old_item, new_item),
+            None => cx.push(path, Severity::Major, format!("{} removed", kind(old_item))),
+        }
+    }
+    for (key @ (path, _), new_item) in &new_api.items {
+        if !old_api.items.contains_key(key) {
+            cx.push(path, Severity::Minor, format!("{} added", kind(new_item)));
+        }
+    }
+
+    for (path, old_source) in &old_api.external_reexports {
+        match new_api.external_reexports.get(path) {
+            Some(new_source) if new_source != old_source => cx.push(
+                path,
+                Severity::Major,
+                format!("re-export changed from `{}` to `{}`", old_source, new_source),
+            ),
+            Some(_) => {}
+            // The re-export may have been replaced by a local item, which is compared above.
+            None if new_api.contains_path(path) => {}
+            None => cx.push(path, Severity::Major, "re-export removed".to_owned()),
+        }
+    }
+    for path in new_api.external_reexports.keys() {
+        if !old_api.external_reexports.contains_key(path) && !old_api.contains_path(path) {
+            cx.push(path, Severity::Minor, "re-export added".to_owned());
+        }
+    }
+
+    let mut changes = cx.changes;
+    changes.sort_by(|a, b| a.path.cmp(&b.path));
+    changes
+}
+
+struct DiffCx<'a> {
+    old: &'a Crate,
+    new: &'a Crate,
+    changes: Vec<Change>,
+}
+
+impl<'a> DiffCx<'a> {
+    fn push(&mut self, path: &str, severity: Severity, description: String) {
+        self.changes.push(Change { path: path.to_owned(), severity, description });
+    }
+
+    fn compare_items(&mut self, path: &str, old: &'a Item, new: &'a Item) {
+        if mem::discriminant(&old.inner) != mem::discriminant(&new.inner) {
+            let description = format!("changed from {} to {}", kind(old), kind(new));
+            self.push(path, Severity::Major, description);
+            return;
+        }
+        match (&old.inner, &new.inner) {
+            (ItemEnum::Function(o), ItemEnum::Function(n)) => {
+                self.compare_fn(path, (&o.header, &o.decl), (&n.header, &n.decl));
+                self.compare_generics(path, &o.generics, &n.generics);
+            }
+            (ItemEnum::Struct(o), ItemEnum::Struct(n)) => self.compare_struct(path, old, new, o, n),
+            (ItemEnum::Union(o), ItemEnum::Union(n)) => {
+                self.compare_generics(path, &o.generics, &n.generics);
+                // Union fields can't be initialized all at once, so adding one is fine.
+                self.compare_fields(path, (&o.fields, o.fields_stripped), &n.fields, false);
+                self.compare_impls(path, &o.impls, &n.impls);
+            }
+            (ItemEnum::Enum(o), ItemEnum::Enum(n)) => self.compare_enum(path, old, new, o, n),
+            (ItemEnum::Trait(o), ItemEnum::Trait(n)) => self.compare_trait(path, o, n),
+            (ItemEnum::Typedef(o), ItemEnum::Typedef(n)) => {
+                self.compare_type(path, "type", &print::ty(&o.type_), &print::ty(&n.type_));
+                self.compare_generics(path, &o.generics, &n.generics);
+            }
+            (ItemEnum::Constant(o), ItemEnum::Constant(n)) => {
+                self.compare_type(path, "type", &print::ty(&o.type_), &print::ty(&n.type_));
+            }
+            (ItemEnum::Static(o), ItemEnum::Static(n)) => {
+                self.compare_type(path, "type", &print::ty(&o.type_), &print::ty(&n.type_));
+                if o.mutable != n.mutable {
+                    let description = if n.mutable { "now mutable" } else { "no longer mutable" };
+                    self.push(path, Severity::Major, description.to_owned());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn compare_type(&mut self, path: &str, what: &str, old: &str, new: &str) {
+        if old != new {
+            let description = format!("{} changed from `{}` to `{}`", what, old, new);
+            self.push(path, Severity::Major, description);
+        }
+    }
+
+    fn compare_fn(&mut self, path: &str, old: (&Header, &FnDecl), new: (&Header, &FnDecl)) {
+        // `const` and `unsafe` are compared separately, since only removing them can break code.
+        let normalize =
+            |header: &Header| Header { const_: false, unsafe_: false, ..header.clone() };
+        let old_signature = print::fn_signature(&normalize(old.0), old.1);
+        let new_signature = print::fn_signature(&normalize(new.0), new.1);
+        if old_signature != new_signature {
+            let description = format!(
+                "signature changed from `{}` to `{}`",
+                print::fn_signature(old.0, old.1),
+                print::fn_signature(new.0, new.1),
+            );
+            self.push(path, Severity::Major, description);
+            return;
+        }
+        match (old.0.const_, new.0.const_) {
+            (true, false) => self.push(path, Severity::Major, "no longer const".to_owned()),
+            (false, true) => self.push(path, Severity::Minor, "now const".to_owned()),
+            _ => {}
+        }
+        match (old.0.unsafe_, new.0.unsafe_) {
+            (false, true) => self.push(path, Severity::Major, "now unsafe".to_owned()),
+            (true, false) => self.push(path, Severity::Minor, "no longer unsafe".to_owned()),
+            _ => {}
+        }
+    }
+
+    fn compare_generics(&mut self, path: &str, old: &Generics, new: &Generics) {
+        // Parameters for `impl Trait` arguments are part of the signature.
+        fn params(generics: &Generics) -> Vec<&GenericParamDef> {
+            generics
+                .params
+                .iter()
+                .filter(|param| {
+                    !matches!(param.kind, GenericParamDefKind::Type { synthetic: true, .. })
+                })
+                .collect()
+        }
+        let (old_params, new_params) = (params(old), params(new));
+        let print_params = |params: &[&GenericParamDef]| {
+            params.iter().map(|param| print::generic_param(param)).collect::<Vec<_>>()
+        };
+        let (old_printed, new_printed) = (print_params(&old_params), print_params(&new_params));
+        if old_printed != new_printed {
+            // Adding parameters with defaults at the end doesn't break anything.
+            let defaulted_added = new_printed.starts_with(&old_printed)
+                && new_params[old_params.len()..].iter().all(|param| match &param.kind {
+                    GenericParamDefKind::Type { default, .. } => default.is_some(),
+                    GenericParamDefKind::Const { default, .. } => default.is_some(),
+                    GenericParamDefKind::Lifetime { .. } => false,
+                });
+            let severity = if defaulted_added { Severity::Minor } else { Severity::Major };
+            let description = format!(
+                "generic parameters changed from `<{}>` to `<{}>`",
+                old_printed.join(", "),
+                new_printed.join(", ")
+            );
+            self.push(path, severity, description);
+        }
+
+        let (old_bounds, new_bounds) = (bounds(old), bounds(new));
+        let added: Vec<_> =
+            new_bounds.difference(&old_bounds).map(|b| format!("`{}`", b)).collect();
+        if !added.is_empty() {
+            let description = format!("bounds added: {}", added.join(", "));
+            self.push(path, Severity::Major, description);
+        }
+        let removed: Vec<_> =
+            old_bounds.difference(&new_bounds).map(|b| format!("`{}`", b)).collect();
+        if !removed.is_empty() {
+            let description = format!("bounds removed: {}", removed.join(", "));
+            self.push(path, Severity::Minor, description);
+        }
+    }
+
+    fn compare_struct(
+        &mut self,
+        path: &str,
+        old_item: &Item,
+        new_item: &Item,
+        old: &'a Struct,
+        new: &'a Struct,
+    ) {
+        if old.struct_type != new.struct_type {
+            let description = format!(
+                "changed from a {:?} struct to a {:?} struct",
+                old.struct_type, new.struct_type
+            )
+            .to_lowercase();
+            self.push(path, Severity::Major, description);
+        }
+        let (old_non_exhaustive, new_non_exhaustive) =
+            (is_non_exhaustive(old_item), is_non_exhaustive(new_item));
+        self.compare_non_exhaustive(path, old_non_exhaustive, new_non_exhaustive);
+        self.compare_generics(path, &old.generics, &new.generics);
+        let constructible = !old.fields_stripped && !old_non_exhaustive;
+        self.compare_fields(path, (&old.fields, old.fields_stripped), &new.fields, constructible);
+        if constructible && new.fields_stripped {
+            let description = "private field added to a struct without private fields".to_owned();
+            self.push(path, Severity::Major, description);
+        }
+        self.compare_impls(path, &old.impls, &new.impls);
+    }
+
+    /// Compares the public fields of a struct, union or enum variant. Adding a field breaks code
+    /// constructing the type if all its fields were public.
+    fn compare_fields(
+        &mut self,
+        path: &str,
+        (old, old_stripped): (&[Id], bool),
+        new: &[Id],
+        constructible: bool,
+    ) {
+        let old_fields = fields(self.old, old);
+        let new_fields = fields(self.new, new);
+        for (name, old_ty) in &old_fields {
+            let field_path = format!("{}::{}", path, name);
+            match new_fields.get(name) {
+                Some(new_ty) => self.compare_type(&field_path, "field type", old_ty, new_ty),
+                None => self.push(&field_path, Severity::Major, "field removed".to_owned()),
+            }
+        }
+        for name in new_fields.keys().filter(|name| !old_fields.contains_key(*name)) {
+            let severity =
+                if constructible && !old_stripped { Severity::Major } else { Severity::Minor };
+            self.push(&format!("{}::{}", path, name), severity, "field added".to_owned());
+        }
+    }
+
+    fn compare_non_exhaustive(&mut self, path: &str, old: bool, new: bool) {
+        match (old, new) {
+            (false, true) => {
+                self.push(path, Severity::Major, "now `#[non_exhaustive]`".to_owned());
+            }
+            (true, false) => {
+                self.push(path, Severity::Minor, "no longer `#[non_exhaustive]`".to_owned());
+            }
+            _ => {}
+        }
+    }
+
+    fn compare_enum(
+        &mut self,
+        path: &str,
+        old_item: &Item,
+        new_item: &Item,
+        old: &'a Enum,
+        new: &'a Enum,
+    ) {
+        let old_non_exhaustive = is_non_exhaustive(old_item);
+        self.compare_non_exhaustive(path, old_non_exhaustive, is_non_exhaustive(new_item));
+        self.compare_generics(path, &old.generics, &new.generics);
+
+        let old_variants = variants(self.old, &old.variants);
+        let new_variants = variants(self.new, &new.variants);
+        for (name, old_variant) in &old_variants {
+            let variant_path = format!("{}::{}", path, name);
+            let new_variant = match new_variants.get(name) {
+                Some(new_variant) => new_variant,
+                None => {
+                    self.push(&variant_path, Severity::Major, "variant removed".to_owned());
+                    continue;
+                }
+            };
+            match (old_variant.kind, new_variant.kind) {
+                (Variant::Struct(old_fields), Variant::Struct(new_fields)) => {
+                    let old_non_exhaustive = is_non_exhaustive(old_variant.item);
+                    let new_non_exhaustive = is_non_exhaustive(new_variant.item);
+                    self.compare_non_exhaustive(
+                        &variant_path,
+                        old_non_exhaustive,
+                        new_non_exhaustive,
+                    );
+                    self.compare_fields(
+                        &variant_path,
+                        (old_fields, false),
+                        new_fields,
+                        !old_non_exhaustive,
+                    );
+                }
+                _ => self.compare_type(
+                    &variant_path,
+                    "variant",
+                    &old_variant.shape,
+                    &new_variant.shape,
+                ),
+            }
+        }
+        for name in new_variants.keys().filter(|name| !old_variants.contains_key(*name)) {
+            // Matches on exhaustive enums must handle every variant.
+            let severity = if old_non_exhaustive { Severity::Minor } else { Severity::Major };
+            self.push(&format!("{}::{}", path, name), severity, "variant added".to_owned());
+        }
+        self.compare_impls(path, &old.impls, &new.impls);
+    }
+
+    fn compare_trait(&mut self, path: &str, old: &'a Trait, new: &'a Trait) {
+        if !old.is_unsafe && new.is_unsafe {
+            self.push(path, Severity::Major, "now unsafe to implement".to_owned());
+        }
+        self.compare_generics(path, &old.generics, &new.generics);
+
+        let supertraits =
+            |t: &Trait| -> BTreeSet<String> { t.bounds.iter().map(print::bound).collect() };
+        let (old_supertraits, new_supertraits) = (supertraits(old), supertraits(new));
+        for added in new_supertraits.difference(&old_supertraits) {
+            self.push(path, Severity::Major, format!("supertrait `{}` added", added));
+        }
+        for removed in old_supertraits.difference(&new_supertraits) {
+            self.push(path, Severity::Minor, format!("supertrait `{}` removed", removed));
+        }
+
+        let old_items = named_items(self.old, &old.items, false);
+        let new_items = named_items(self.new, &new.items, false);
+        self.compare_assoc_items(path, &old_items, &new_items);
+        for (name, item) in &new_items {
+            if old_items.contains_key(name) {
+                continue;
+            }
+            // Implementations of the trait must provide items without a default.
+            let (severity, description) = match &item.inner {
+                ItemEnum::Method(m) if !m.has_body => (Severity::Major, "required method added"),
+                ItemEnum::AssocType { default: None, .. } => {
+                    (Severity::Major, "required associated type added")
+                }
+                ItemEnum::AssocConst { default: None, .. } => {
+                    (Severity::Major, "required associated constant added")
+                }
+                _ => (Severity::Minor, "provided item added"),
+            };
+            self.push(&format!("{}::{}", path, name), severity, description.to_owned());
+        }
+    }
+
+    /// Compares the items of a trait or of inherent impls which exist in both versions, and
+    /// reports the removed ones.
+    fn compare_assoc_items(
+        &mut self,
+        path: &str,
+        old: &BTreeMap<String, &'a Item>,
+        new: &BTreeMap<String, &'a Item>,
+    ) {
+        for (name, old_item) in old {
+            let item_path = format!("{}::{}", path, name);
+            let new_item = match new.get(name) {
+                Some(new_item) => new_item,
+                None => {
+                    self.push(&item_path, Severity::Major, format!("{} removed", kind(old_item)));
+                    continue;
+                }
+            };
+            match (&old_item.inner, &new_item.inner) {
+                (ItemEnum::Method(o), ItemEnum::Method(n)) => {
+                    self.compare_fn(&item_path, (&o.header, &o.decl), (&n.header, &n.decl));
+                    self.compare_generics(&item_path, &o.generics, &n.generics);
+                }
+                (
+                    ItemEnum::AssocConst { type_: old_ty, .. },
+                    ItemEnum::AssocConst { type_: new_ty, .. },
+                ) => {
+                    self.compare_type(&item_path, "type", &print::ty(old_ty), &print::ty(new_ty));
+                }
+                (
+                    ItemEnum::AssocType { bounds: old_bounds, .. },
+                    ItemEnum::AssocType { bounds: new_bounds, .. },
+                ) => {
+                    let print_bounds = |bounds: &[_]| -> BTreeSet<String> {
+                        bounds.iter().map(print::bound).collect()
+                    };
+                    let (old_bounds, new_bounds) =
+                        (print_bounds(old_bounds), print_bounds(new_bounds));
+                    if old_bounds != new_bounds {
+                        let description = format!(
+                            "bounds changed from `{}` to `{}`",
+                            old_bounds.into_iter().collect::<Vec<_>>().join(" + "),
+                            new_bounds.into_iter().collect::<Vec<_>>().join(" + "),
+                        );
+                        self.push(&item_path, Severity::Major, description);
+                    }
+                }
+                _ => {
+                    let description =
+                        format!("changed from {} to {}", kind(old_item), kind(new_item));
+                    self.push(&item_path, Severity::Major, description);
+                }
+            }
+        }
+    }
+
+    /// Compares the inherent items and the implemented traits of a type.
+    fn compare_impls(&mut self, path: &str, old: &[Id], new: &[Id]) {
+        let (old_items, old_traits) = impls(self.old, old);
+        let (new_items, new_traits) = impls(self.new, new);
+        self.compare_assoc_items(path, &old_items, &new_items);
+        for (name, item) in &new_items {
+            if !old_items.contains_key(name) {
+                let item_path = format!("{}::{}", path, name);
+                self.push(&item_path, Severity::Minor, format!("{} added", kind(item)));
+            }
+        }
+        for removed in old_traits.difference(&new_traits) {
+            self.push(path, Severity::Major, format!("no longer implements `{}`", removed));
+        }
+        for added in new_traits.difference(&old_traits) {
+            self.push(path, Severity::Minor, format!("now implements `{}`", added));
+        }
+    }
+}
+
+fn kind(item: &Item) -> &'static str {
+    match item.inner {
+        ItemEnum::Module(_) => "module",
+        ItemEnum::ExternCrate { .. } => "extern crate",
+        ItemEnum::Import(_) => "import",
+        ItemEnum::Union(_) => "union",
+        ItemEnum::Struct(_) => "struct",
+        ItemEnum::StructField(_) => "field",
+        ItemEnum::Enum(_) => "enum",
+        ItemEnum::Variant(_) => "variant",
+        ItemEnum::Function(_) => "function",
+        ItemEnum::Trait(_) => "trait",
+        ItemEnum::TraitAlias(_) => "trait alias",
+        ItemEnum::Method(_) => "method",
+        ItemEnum::Impl(_) => "impl",
+        ItemEnum::Typedef(_) => "type alias",
+        ItemEnum::OpaqueTy(_) => "opaque type",
+        ItemEnum::Constant(_) => "constant",
+        ItemEnum::Static(_) => "static",
+        ItemEnum::ForeignType => "foreign type",
+        ItemEnum::Macro(_) | ItemEnum::ProcMacro(_) => "macro",
+        ItemEnum::PrimitiveType(_) => "primitive type",
+        ItemEnum::AssocConst { .. } => "associated constant",
+        ItemEnum::AssocType { .. } => "associated type",
+    }
+}
+
+fn is_non_exhaustive(item: &Item) -> bool {
+    item.attrs.iter().any(|attr| attr == "#[non_exhaustive]")
+}
+
+/// Returns the bounds of all generic parameters, including those in the `where` clause.
+fn bounds(generics: &Generics) -> BTreeSet<String> {
+    let mut bounds = BTreeSet::new();
+    for param in &generics.params {
+        match &param.kind {
+            GenericParamDefKind::Lifetime { outlives } => {
+                bounds.extend(
+                    outlives.iter().map(|lifetime| format!("{}: {}", param.name, lifetime)),
+                );
+            }
+            GenericParamDefKind::Type { bounds: param_bounds, .. } => {
+                bounds.extend(
+                    param_bounds
+                        .iter()
+                        .map(|bound| format!("{}: {}", param.name, print::bound(bound))),
+                );
+            }
+            GenericParamDefKind::Const { .. } => {}
+        }
+    }
+    bounds.extend(generics.where_predicates.iter().map(print::where_predicate));
+    bounds
+}
+
+/// Returns the public fields among `ids`, with their types. The fields of enum variants have the
+/// default visibility, which makes them as public as the enum.
+fn fields(krate: &Crate, ids: &[Id]) -> BTreeMap<String, String> {
+    ids.iter()
+        .filter_map(|id| krate.index.get(id))
+        .filter(|field| matches!(field.visibility, Visibility::Public | Visibility::Default))
+        .filter_map(|field| match (&field.name, &field.inner) {
+            (Some(name), ItemEnum::StructField(ty)) => Some((name.clone(), print::ty(ty))),
+            _ => None,
+        })
+        .collect()
+}
+
+struct VariantInfo<'a> {
+    item: &'a Item,
+    kind: &'a Variant,
+    /// The variant as it would be written in the enum, with the fields of struct variants elided.
+    shape: String,
+}
+
+/// Returns the variants among `ids` by name.
+fn variants<'a>(krate: &'a Crate, ids: &[Id]) -> BTreeMap<String, VariantInfo<'a>> {
+    ids.iter()
+        .filter_map(|id| krate.index.get(id))
+        .filter_map(|item| match (&item.name, &item.inner) {
+            (Some(name), ItemEnum::Variant(kind)) => {
+                let shape = match kind {
+                    Variant::Plain => name.clone(),
+                    Variant::Tuple(types) => format!(
+                        "{}({})",
+                        name,
+                        types.iter().map(print::ty).collect::<Vec<_>>().join(", ")
+                    ),
+                    Variant::Struct(_) => format!("{} {{ .. }}", name),
+                };
+                Some((name.clone(), VariantInfo { item, kind, shape }))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Returns the named items among `ids`. Only public items are returned if `public_only` is set,
+/// which is needed for items of inherent impls, whose visibility can be restricted.
+fn named_items<'a>(krate: &'a Crate, ids: &[Id], public_only: bool) -> BTreeMap<String, &'a Item> {
+    ids.iter()
+        .filter_map(|id| krate.index.get(id))
+        .filter(|item| !public_only || item.visibility == Visibility::Public)
+        .filter_map(|item| Some((item.name.clone()?, item)))
+        .collect()
+}
+
+/// Returns the public items of the inherent impls among `impls` and the traits implemented by the
+/// other ones.
+fn impls<'a>(krate: &'a Crate, impls: &[Id]) -> (BTreeMap<String, &'a Item>, BTreeSet<String>) {
+    let mut items = BTreeMap::new();
+    let mut traits = BTreeSet::new();
+    for item in impls.iter().filter_map(|id| krate.index.get(id)) {
+        let impl_ = match &item.inner {
+            ItemEnum::Impl(impl_) => impl_,
+            _ => continue,
+        };
+        match &impl_.trait_ {
+            None => items.extend(named_items(krate, &impl_.items, true)),
+            Some(trait_) if !impl_.negative => {
+                traits.insert(print::ty(trait_));
+            }
+            Some(_) => {}
+        }
+    }
+    (items, traits)
+}
+
+#[cfg(test)]
+mod tests;
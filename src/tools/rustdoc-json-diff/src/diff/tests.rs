@@ -0,0 +1,612 @@
+use std::collections::HashMap;
+use std::mem;
+
+use rustdoc_json_types::{
+    Abi, Constant, Crate, Enum, FORMAT_VERSION, FnDecl, Function, GenericBound, GenericParamDef,
+    GenericParamDefKind, Generics, Header, Id, Impl, Import, Item, ItemEnum, Method, Module,
+    Static, Struct, StructType, Trait, TraitBoundModifier, Type, Typedef, Union, Variant,
+    Visibility,
+};
+
+use super::{Severity, diff};
+use Severity::{Major, Minor};
+
+/// The rustdoc JSON of a crate named `krate`, built item by item.
+#[derive(Default)]
+struct TestCrate {
+    index: HashMap<Id, Item>,
+    root_items: Vec<Id>,
+}
+
+impl TestCrate {
+    /// Adds a public item to the root module.
+    fn item(&mut self, name: &str, inner: ItemEnum) -> Id {
+        let id = self.add(Some(name), Visibility::Public, inner);
+        self.root_items.push(id.clone());
+        id
+    }
+
+    /// Adds an item which isn't in the root module, like a field or an associated item.
+    fn add(&mut self, name: Option<&str>, visibility: Visibility, inner: ItemEnum) -> Id {
+        let id = Id(format!("0:{}", self.index.len()));
+        let item = Item {
+            id: id.clone(),
+            crate_id: 0,
+            name: name.map(str::to_owned),
+            span: None,
+            visibility,
+            docs: None,
+            links: HashMap::new(),
+            attrs: Vec::new(),
+            deprecation: None,
+            inner,
+        };
+        self.index.insert(id.clone(), item);
+        id
+    }
+
+    fn non_exhaustive(&mut self, id: &Id) {
+        self.index.get_mut(id).unwrap().attrs.push("#[non_exhaustive]".to_owned());
+    }
+
+    fn field(&mut self, name: &str, ty: &str) -> Id {
+        self.add(Some(name), Visibility::Public, ItemEnum::StructField(prim(ty)))
+    }
+
+    fn variant(&mut self, name: &str, variant: Variant) -> Id {
+        self.add(Some(name), Visibility::Default, ItemEnum::Variant(variant))
+    }
+
+    /// Adds an impl of `trait_`, or an inherent impl if it's `None`.
+    fn impl_(&mut self, trait_: Option<&str>, items: Vec<Id>) -> Id {
+        let impl_ = Impl {
+            is_unsafe: false,
+            generics: generics(Vec::new()),
+            provided_trait_methods: Vec::new(),
+            trait_: trait_.map(path),
+            for_: Type::Infer,
+            items,
+            negative: false,
+            synthetic: false,
+            blanket_impl: None,
+        };
+        self.add(None, Visibility::Default, ItemEnum::Impl(impl_))
+    }
+
+    fn into_crate(mut self) -> Crate {
+        let items = mem::take(&mut self.root_items);
+        let module = ItemEnum::Module(Module { is_crate: true, items, is_stripped: false });
+        let root = self.add(Some("krate"), Visibility::Public, module);
+        Crate {
+            root,
+            crate_version: None,
+            includes_private: false,
+            index: self.index,
+            paths: HashMap::new(),
+            external_crates: HashMap::new(),
+            format_version: FORMAT_VERSION,
+        }
+    }
+}
+
+/// Checks the changes from the crate built by `old` to the one built by `new`, given as their
+/// path, severity and description.
+fn assert_changes(
+    old: impl FnOnce(&mut TestCrate),
+    new: impl FnOnce(&mut TestCrate),
+    expected: &[(&str, Severity, &str)],
+) {
+    fn build(f: impl FnOnce(&mut TestCrate)) -> Crate {
+        let mut krate = TestCrate::default();
+        f(&mut krate);
+        krate.into_crate()
+    }
+    let changes = diff(&build(old), &build(new));
+    let changes: Vec<_> = changes
+        .iter()
+        .map(|change| (change.path.as_str(), change.severity, change.description.as_str()))
+        .collect();
+    assert_eq!(changes, expected);
+}
+
+fn prim(name: &str) -> Type {
+    Type::Primitive(name.to_owned())
+}
+
+fn path(name: &str) -> Type {
+    Type::ResolvedPath {
+        name: name.to_owned(),
+        id: Id(name.to_owned()),
+        args: None,
+        param_names: Vec::new(),
+    }
+}
+
+fn bound(trait_: &str) -> GenericBound {
+    GenericBound::TraitBound {
+        trait_: path(trait_),
+        generic_params: Vec::new(),
+        modifier: TraitBoundModifier::None,
+    }
+}
+
+fn generics(params: Vec<GenericParamDef>) -> Generics {
+    Generics { params, where_predicates: Vec::new() }
+}
+
+fn type_param(name: &str, bounds: Vec<GenericBound>, default: Option<Type>) -> GenericParamDef {
+    let kind = GenericParamDefKind::Type { bounds, default, synthetic: false };
+    GenericParamDef { name: name.to_owned(), kind, variance: None }
+}
+
+fn header(const_: bool, unsafe_: bool) -> Header {
+    Header { const_, unsafe_, async_: false, abi: Abi::Rust }
+}
+
+fn decl(inputs: &[&str]) -> FnDecl {
+    let inputs = inputs.iter().map(|ty| ("_".to_owned(), prim(ty))).collect();
+    FnDecl { inputs, output: None, c_variadic: false }
+}
+
+fn function(header: Header, inputs: &[&str]) -> ItemEnum {
+    ItemEnum::Function(Function { decl: decl(inputs), generics: generics(Vec::new()), header })
+}
+
+fn generic_function(params: Vec<GenericParamDef>) -> ItemEnum {
+    ItemEnum::Function(Function {
+        decl: decl(&[]),
+        generics: generics(params),
+        header: header(false, false),
+    })
+}
+
+fn method(inputs: &[&str], has_body: bool) -> ItemEnum {
+    let generics = generics(Vec::new());
+    ItemEnum::Method(Method {
+        decl: decl(inputs),
+        generics,
+        header: header(false, false),
+        has_body,
+    })
+}
+
+fn struct_(
+    struct_type: StructType,
+    fields: Vec<Id>,
+    fields_stripped: bool,
+    impls: Vec<Id>,
+) -> ItemEnum {
+    let generics = generics(Vec::new());
+    ItemEnum::Struct(Struct { struct_type, generics, fields_stripped, fields, impls, layout: None })
+}
+
+fn enum_(variants: Vec<Id>) -> ItemEnum {
+    let generics = generics(Vec::new());
+    let impls = Vec::new();
+    ItemEnum::Enum(Enum { generics, variants_stripped: false, variants, impls, layout: None })
+}
+
+fn trait_(is_unsafe: bool, items: Vec<Id>, bounds: Vec<GenericBound>) -> ItemEnum {
+    ItemEnum::Trait(Trait {
+        is_auto: false,
+        is_unsafe,
+        items,
+        generics: generics(Vec::new()),
+        bounds,
+        implementations: Vec::new(),
+    })
+}
+
+fn constant(ty: &str) -> ItemEnum {
+    let expr = "0".to_owned();
+    ItemEnum::Constant(Constant { type_: prim(ty), expr, value: None, is_literal: true })
+}
+
+fn static_(ty: &str, mutable: bool) -> ItemEnum {
+    ItemEnum::Static(Static { type_: prim(ty), mutable, expr: "0".to_owned() })
+}
+
+fn import(source: &str, name: &str, id: Option<Id>) -> ItemEnum {
+    ItemEnum::Import(Import { source: source.to_owned(), name: name.to_owned(), id, glob: false })
+}
+
+#[test]
+fn items_added_and_removed() {
+    assert_changes(
+        |krate| {
+            krate.item("a", function(header(false, false), &[]));
+            krate.item("b", constant("u32"));
+        },
+        |krate| {
+            krate.item("b", static_("u32", false));
+            krate.item("c", function(header(false, false), &[]));
+        },
+        &[
+            ("krate::a", Major, "function removed"),
+            ("krate::b", Major, "changed from constant to static"),
+            ("krate::c", Minor, "function added"),
+        ],
+    );
+}
+
+#[test]
+fn reexported_items_are_listed_under_every_path() {
+    assert_changes(
+        |krate| {
+            let f = krate.add(Some("f"), Visibility::Public, function(header(false, false), &[]));
+            let module =
+                ItemEnum::Module(Module { is_crate: false, items: vec![f], is_stripped: false });
+            let a = krate.item("a", module);
+            krate.item("b", import("krate::a", "b", Some(a)));
+        },
+        |krate| {
+            let module =
+                ItemEnum::Module(Module { is_crate: false, items: vec![], is_stripped: false });
+            let a = krate.item("a", module);
+            krate.item("b", import("krate::a", "b", Some(a)));
+        },
+        &[("krate::a::f", Major, "function removed"), ("krate::b::f", Major, "function removed")],
+    );
+}
+
+#[test]
+fn items_sharing_a_path() {
+    assert_changes(
+        |krate| {
+            let module =
+                ItemEnum::Module(Module { is_crate: false, items: vec![], is_stripped: false });
+            krate.item("a", module);
+            krate.item("a", function(header(false, false), &[]));
+            krate.item("b", ItemEnum::Macro("macro_rules! b {}".to_owned()));
+            krate.item("b", trait_(false, Vec::new(), Vec::new()));
+        },
+        |krate| {
+            krate.item("a", function(header(false, false), &[]));
+            krate.item("b", trait_(false, Vec::new(), Vec::new()));
+        },
+        &[("krate::a", Major, "module removed"), ("krate::b", Major, "macro removed")],
+    );
+}
+
+#[test]
+fn glob_reexported_variants() {
+    assert_changes(
+        |krate| {
+            let variants =
+                vec![krate.variant("A", Variant::Plain), krate.variant("B", Variant::Plain)];
+            let e = krate.item("E", enum_(variants));
+            let glob = Import {
+                source: "krate::E".to_owned(),
+                name: "E".to_owned(),
+                id: Some(e),
+                glob: true,
+            };
+            krate.item("E", ItemEnum::Import(glob));
+        },
+        |krate| {
+            let variants =
+                vec![krate.variant("A", Variant::Plain), krate.variant("C", Variant::Plain)];
+            let e = krate.item("E", enum_(variants));
+            let glob = Import {
+                source: "krate::E".to_owned(),
+                name: "E".to_owned(),
+                id: Some(e),
+                glob: true,
+            };
+            krate.item("E", ItemEnum::Import(glob));
+        },
+        &[
+            ("krate::B", Major, "variant removed"),
+            ("krate::C", Minor, "variant added"),
+            ("krate::E::B", Major, "variant removed"),
+            ("krate::E::C", Major, "variant added"),
+        ],
+    );
+}
+
+#[test]
+fn external_reexports() {
+    assert_changes(
+        |krate| {
+            krate.item("a", import("other::a", "a", None));
+            krate.item("b", import("other::b", "b", None));
+            krate.item("c", import("other::c", "c", None));
+        },
+        |krate| {
+            krate.item("a", import("other::v2::a", "a", None));
+            krate.item("c", function(header(false, false), &[]));
+            krate.item("d", import("other::d", "d", None));
+        },
+        &[
+            ("krate::a", Major, "re-export changed from `other::a` to `other::v2::a`"),
+            ("krate::b", Major, "re-export removed"),
+            ("krate::c", Minor, "function added"),
+            ("krate::d", Minor, "re-export added"),
+        ],
+    );
+}
+
+#[test]
+fn function_signatures() {
+    assert_changes(
+        |krate| {
+            krate.item("f", function(header(false, false), &["u32"]));
+            krate.item("g", function(header(true, false), &[]));
+            krate.item("h", function(header(false, false), &[]));
+            krate.item("i", function(header(false, false), &[]));
+            krate.item("j", function(header(false, true), &[]));
+        },
+        |krate| {
+            krate.item("f", function(header(false, false), &["u64"]));
+            krate.item("g", function(header(false, false), &[]));
+            krate.item("h", function(header(true, false), &[]));
+            krate.item("i", function(header(false, true), &[]));
+            krate.item("j", function(header(false, false), &[]));
+        },
+        &[
+            ("krate::f", Major, "signature changed from `fn(u32)` to `fn(u64)`"),
+            ("krate::g", Major, "no longer const"),
+            ("krate::h", Minor, "now const"),
+            ("krate::i", Major, "now unsafe"),
+            ("krate::j", Minor, "no longer unsafe"),
+        ],
+    );
+}
+
+#[test]
+fn generic_parameters_and_bounds() {
+    let t = || type_param("T", vec![], None);
+    let t_clone = || type_param("T", vec![bound("Clone")], None);
+    assert_changes(
+        |krate| {
+            krate.item("f", generic_function(vec![t()]));
+            krate.item("g", generic_function(vec![t()]));
+            krate.item("h", generic_function(vec![t()]));
+            krate.item("i", generic_function(vec![t_clone()]));
+        },
+        |krate| {
+            krate.item("f", generic_function(vec![t(), type_param("U", vec![], None)]));
+            krate
+                .item("g", generic_function(vec![t(), type_param("U", vec![], Some(prim("u32")))]));
+            krate.item("h", generic_function(vec![t_clone()]));
+            krate.item("i", generic_function(vec![t()]));
+        },
+        &[
+            ("krate::f", Major, "generic parameters changed from `<T>` to `<T, U>`"),
+            ("krate::g", Minor, "generic parameters changed from `<T>` to `<T, U>`"),
+            ("krate::h", Major, "bounds added: `T: Clone`"),
+            ("krate::i", Minor, "bounds removed: `T: Clone`"),
+        ],
+    );
+}
+
+#[test]
+fn struct_fields() {
+    assert_changes(
+        |krate| {
+            let fields = vec![krate.field("a", "u32"), krate.field("b", "u32")];
+            krate.item("S", struct_(StructType::Plain, fields, false, vec![]));
+            krate.item("P", struct_(StructType::Plain, vec![], true, vec![]));
+            krate.item("Q", struct_(StructType::Plain, vec![], false, vec![]));
+            krate.item("T", struct_(StructType::Plain, vec![], false, vec![]));
+        },
+        |krate| {
+            let fields = vec![krate.field("a", "u64"), krate.field("c", "u32")];
+            krate.item("S", struct_(StructType::Plain, fields, false, vec![]));
+            let fields = vec![krate.field("x", "u32")];
+            krate.item("P", struct_(StructType::Plain, fields, true, vec![]));
+            krate.item("Q", struct_(StructType::Plain, vec![], true, vec![]));
+            krate.item("T", struct_(StructType::Tuple, vec![], false, vec![]));
+        },
+        &[
+            ("krate::P::x", Minor, "field added"),
+            ("krate::Q", Major, "private field added to a struct without private fields"),
+            ("krate::S::a", Major, "field type changed from `u32` to `u64`"),
+            ("krate::S::b", Major, "field removed"),
+            ("krate::S::c", Major, "field added"),
+            ("krate::T", Major, "changed from a plain struct to a tuple struct"),
+        ],
+    );
+}
+
+#[test]
+fn non_exhaustive_structs() {
+    assert_changes(
+        |krate| {
+            krate.item("A", struct_(StructType::Plain, vec![], false, vec![]));
+            let b = krate.item("B", struct_(StructType::Plain, vec![], false, vec![]));
+            krate.non_exhaustive(&b);
+            let c = krate.item("C", struct_(StructType::Plain, vec![], false, vec![]));
+            krate.non_exhaustive(&c);
+        },
+        |krate| {
+            let a = krate.item("A", struct_(StructType::Plain, vec![], false, vec![]));
+            krate.non_exhaustive(&a);
+            krate.item("B", struct_(StructType::Plain, vec![], false, vec![]));
+            let fields = vec![krate.field("x", "u32")];
+            let c = krate.item("C", struct_(StructType::Plain, fields, false, vec![]));
+            krate.non_exhaustive(&c);
+        },
+        &[
+            ("krate::A", Major, "now `#[non_exhaustive]`"),
+            ("krate::B", Minor, "no longer `#[non_exhaustive]`"),
+            ("krate::C::x", Minor, "field added"),
+        ],
+    );
+}
+
+#[test]
+fn union_fields() {
+    let union = |fields| {
+        let generics = generics(Vec::new());
+        let impls = Vec::new();
+        ItemEnum::Union(Union { generics, fields_stripped: false, fields, impls, layout: None })
+    };
+    assert_changes(
+        |krate| {
+            let fields = vec![krate.field("a", "u32")];
+            krate.item("U", union(fields));
+        },
+        |krate| {
+            let fields = vec![krate.field("a", "u32"), krate.field("b", "u32")];
+            krate.item("U", union(fields));
+        },
+        &[("krate::U::b", Minor, "field added")],
+    );
+}
+
+#[test]
+fn enum_variants() {
+    assert_changes(
+        |krate| {
+            let x = krate.add(Some("x"), Visibility::Default, ItemEnum::StructField(prim("u32")));
+            let variants = vec![
+                krate.variant("A", Variant::Plain),
+                krate.variant("B", Variant::Tuple(vec![prim("u32")])),
+                krate.variant("C", Variant::Struct(vec![x])),
+                krate.variant("D", Variant::Plain),
+            ];
+            krate.item("E", enum_(variants));
+            let variants = vec![krate.variant("A", Variant::Plain)];
+            let n = krate.item("N", enum_(variants));
+            krate.non_exhaustive(&n);
+        },
+        |krate| {
+            let x = krate.add(Some("x"), Visibility::Default, ItemEnum::StructField(prim("u32")));
+            let y = krate.add(Some("y"), Visibility::Default, ItemEnum::StructField(prim("u32")));
+            let variants = vec![
+                krate.variant("A", Variant::Plain),
+                krate.variant("B", Variant::Tuple(vec![prim("u64")])),
+                krate.variant("C", Variant::Struct(vec![x, y])),
+                krate.variant("F", Variant::Plain),
+            ];
+            krate.item("E", enum_(variants));
+            let variants =
+                vec![krate.variant("A", Variant::Plain), krate.variant("B", Variant::Plain)];
+            let n = krate.item("N", enum_(variants));
+            krate.non_exhaustive(&n);
+        },
+        &[
+            ("krate::E::B", Major, "variant changed from `B(u32)` to `B(u64)`"),
+            ("krate::E::C::y", Major, "field added"),
+            ("krate::E::D", Major, "variant removed"),
+            ("krate::E::F", Major, "variant added"),
+            ("krate::N::B", Minor, "variant added"),
+        ],
+    );
+}
+
+#[test]
+fn trait_items() {
+    let assoc_type =
+        |bounds| ItemEnum::AssocType { generics: generics(Vec::new()), bounds, default: None };
+    assert_changes(
+        |krate| {
+            let items = vec![
+                krate.add(Some("Out"), Visibility::Default, assoc_type(vec![bound("Clone")])),
+                krate.add(Some("provided"), Visibility::Default, method(&[], true)),
+                krate.add(Some("required"), Visibility::Default, method(&[], false)),
+            ];
+            krate.item("T", trait_(false, items, vec![]));
+            krate.item("U", trait_(false, vec![], vec![bound("Send")]));
+        },
+        |krate| {
+            let assoc_const = ItemEnum::AssocConst { type_: prim("u32"), default: None };
+            let items = vec![
+                krate.add(Some("C"), Visibility::Default, assoc_const),
+                krate.add(Some("Item"), Visibility::Default, assoc_type(vec![])),
+                krate.add(Some("Out"), Visibility::Default, assoc_type(vec![bound("Copy")])),
+                krate.add(Some("extra"), Visibility::Default, method(&[], true)),
+                krate.add(Some("new_required"), Visibility::Default, method(&[], false)),
+                krate.add(Some("provided"), Visibility::Default, method(&[], true)),
+            ];
+            krate.item("T", trait_(true, items, vec![bound("Send")]));
+            krate.item("U", trait_(false, vec![], vec![]));
+        },
+        &[
+            ("krate::T", Major, "now unsafe to implement"),
+            ("krate::T", Major, "supertrait `Send` added"),
+            ("krate::T::C", Major, "required associated constant added"),
+            ("krate::T::Item", Major, "required associated type added"),
+            ("krate::T::Out", Major, "bounds changed from `Clone` to `Copy`"),
+            ("krate::T::extra", Minor, "provided item added"),
+            ("krate::T::new_required", Major, "required method added"),
+            ("krate::T::required", Major, "method removed"),
+            ("krate::U", Minor, "supertrait `Send` removed"),
+        ],
+    );
+}
+
+#[test]
+fn impls() {
+    assert_changes(
+        |krate| {
+            let assoc_const =
+                |ty| ItemEnum::AssocConst { type_: prim(ty), default: Some("0".to_owned()) };
+            let items = vec![
+                krate.add(Some("C"), Visibility::Public, assoc_const("u32")),
+                krate.add(Some("K"), Visibility::Public, assoc_const("u32")),
+                krate.add(Some("old"), Visibility::Public, method(&[], true)),
+                krate.add(Some("same"), Visibility::Public, method(&["u32"], true)),
+            ];
+            let impls = vec![
+                krate.impl_(None, items),
+                krate.impl_(Some("Clone"), vec![]),
+                krate.impl_(Some("Debug"), vec![]),
+            ];
+            krate.item("S", struct_(StructType::Unit, vec![], false, impls));
+        },
+        |krate| {
+            let assoc_const =
+                ItemEnum::AssocConst { type_: prim("u64"), default: Some("0".to_owned()) };
+            let items = vec![
+                krate.add(Some("C"), Visibility::Public, assoc_const),
+                krate.add(Some("K"), Visibility::Public, method(&[], true)),
+                krate.add(Some("new"), Visibility::Public, method(&[], true)),
+                krate.add(Some("private"), Visibility::Crate, method(&[], true)),
+                krate.add(Some("same"), Visibility::Public, method(&["u64"], true)),
+            ];
+            let impls = vec![
+                krate.impl_(None, items),
+                krate.impl_(Some("Clone"), vec![]),
+                krate.impl_(Some("Default"), vec![]),
+            ];
+            krate.item("S", struct_(StructType::Unit, vec![], false, impls));
+        },
+        &[
+            ("krate::S", Major, "no longer implements `Debug`"),
+            ("krate::S", Minor, "now implements `Default`"),
+            ("krate::S::C", Major, "type changed from `u32` to `u64`"),
+            ("krate::S::K", Major, "changed from associated constant to method"),
+            ("krate::S::new", Minor, "method added"),
+            ("krate::S::old", Major, "method removed"),
+            ("krate::S::same", Major, "signature changed from `fn(u32)` to `fn(u64)`"),
+        ],
+    );
+}
+
+#[test]
+fn types_of_aliases_constants_and_statics() {
+    let typedef =
+        |ty| ItemEnum::Typedef(Typedef { type_: prim(ty), generics: generics(Vec::new()) });
+    assert_changes(
+        |krate| {
+            krate.item("A", typedef("u32"));
+            krate.item("C", constant("u32"));
+            krate.item("S", static_("u32", false));
+            krate.item("T", static_("u32", true));
+        },
+        |krate| {
+            krate.item("A", typedef("u64"));
+            krate.item("C", constant("u64"));
+            krate.item("S", static_("u32", true));
+            krate.item("T", static_("u32", false));
+        },
+        &[
+            ("krate::A", Major, "type changed from `u32` to `u64`"),
+            ("krate::C", Major, "type changed from `u32` to `u64`"),
+            ("krate::S", Major, "now mutable"),
+            ("krate::T", Major, "no longer mutable"),
+        ],
+    );
+}
@@ -0,0 +1,72 @@
+//! Compares the rustdoc JSON output of two versions of a crate and reports the changes made to
+//! its public API, along with the version bump they require.
+
+use std::{env, fs, process};
+
+use rustdoc_json_types::{Crate, FORMAT_VERSION};
+use serde::Serialize;
+
+mod api;
+mod config;
+mod diff;
+mod print;
+
+use config::parse_config;
+use diff::{Change, Severity};
+
+#[derive(Serialize)]
+struct Report<'a> {
+    required_bump: &'static str,
+    changes: &'a [Change],
+}
+
+fn main() {
+    if let Err(e) = run() {
+        eprintln!("error: {}", e);
+        process::exit(1);
+    }
+}
+
+fn run() -> Result<(), String> {
+    let config = parse_config(env::args().collect());
+
+    let old = read_crate(&config.old)?;
+    let new = read_crate(&config.new)?;
+    let changes = diff::diff(&old, &new);
+
+    let required_bump = match changes.iter().map(|change| change.severity).max() {
+        Some(Severity::Major) => "major",
+        Some(Severity::Minor) => "minor",
+        None => "patch",
+    };
+    if config.json {
+        let report = Report { required_bump, changes: &changes };
+        println!("{}", serde_json::to_string_pretty(&report).unwrap());
+    } else {
+        for change in &changes {
+            println!("{}: {}: {}", change.severity, change.path, change.description);
+        }
+        println!("required version bump: {}", required_bump);
+    }
+
+    match config.deny {
+        Some(deny) if changes.iter().any(|change| change.severity >= deny) => {
+            Err(format!("the public API has changes of {} severity or above", deny))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn read_crate(path: &str) -> Result<Crate, String> {
+    let contents =
+        fs::read_to_string(path).map_err(|e| format!("failed to read {}: {}", path, e))?;
+    let krate: Crate =
+        serde_json::from_str(&contents).map_err(|e| format!("failed to parse {}: {}", path, e))?;
+    if krate.format_version != FORMAT_VERSION {
+        return Err(format!(
+            "{} has format version {}, but only version {} is supported",
+            path, krate.format_version, FORMAT_VERSION
+        ));
+    }
+    Ok(krate)
+}
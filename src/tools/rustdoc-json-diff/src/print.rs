@@ -0,0 +1,276 @@
+//! Printing of types and signatures, used both to compare them and to describe changes.
+//!
+//! The ids in rustdoc JSON aren't stable between two runs of rustdoc, so types are compared by
+//! the paths they were written with instead.
+
+use std::fmt::Write;
+
+use rustdoc_json_types::{
+    Abi, FnDecl, GenericArg, GenericArgs, GenericBound, GenericParamDef, GenericParamDefKind,
+    Header, Term, TraitBoundModifier, Type, TypeBindingKind, WherePredicate,
+};
+
+pub fn ty(ty: &Type) -> String {
+    let mut out = String::new();
+    write_type(&mut out, ty);
+    out
+}
+
+pub fn bound(bound: &GenericBound) -> String {
+    let mut out = String::new();
+    write_bound(&mut out, bound);
+    out
+}
+
+pub fn where_predicate(pred: &WherePredicate) -> String {
+    let mut out = String::new();
+    match pred {
+        WherePredicate::BoundPredicate { type_, bounds, generic_params } => {
+            write_hrtb(&mut out, generic_params);
+            write_type(&mut out, type_);
+            out.push_str(": ");
+            write_bounds(&mut out, bounds);
+        }
+        WherePredicate::RegionPredicate { lifetime, bounds } => {
+            write!(out, "{}: ", lifetime).unwrap();
+            write_bounds(&mut out, bounds);
+        }
+        WherePredicate::EqPredicate { lhs, rhs } => {
+            write_type(&mut out, lhs);
+            out.push_str(" == ");
+            write_term(&mut out, rhs);
+        }
+    }
+    out
+}
+
+/// Prints the header, parameters and return type of a function, without its name and generics.
+pub fn fn_signature(header: &Header, decl: &FnDecl) -> String {
+    let mut out = String::new();
+    write_header(&mut out, header);
+    out.push_str("fn");
+    write_fn_decl(&mut out, decl);
+    out
+}
+
+fn write_header(out: &mut String, header: &Header) {
+    if header.const_ {
+        out.push_str("const ");
+    }
+    if header.async_ {
+        out.push_str("async ");
+    }
+    if header.unsafe_ {
+        out.push_str("unsafe ");
+    }
+    let (name, unwind) = match &header.abi {
+        Abi::Rust => return,
+        Abi::C { unwind } => ("C", *unwind),
+        Abi::Cdecl { unwind } => ("cdecl", *unwind),
+        Abi::Stdcall { unwind } => ("stdcall", *unwind),
+        Abi::Fastcall { unwind } => ("fastcall", *unwind),
+        Abi::Aapcs { unwind } => ("aapcs", *unwind),
+        Abi::Win64 { unwind } => ("win64", *unwind),
+        Abi::SysV64 { unwind } => ("sysv64", *unwind),
+        Abi::System { unwind } => ("system", *unwind),
+        Abi::Other(name) => (name.trim_matches('"'), false),
+    };
+    write!(out, "extern \"{}{}\" ", name, if unwind { "-unwind" } else { "" }).unwrap();
+}
+
+fn write_fn_decl(out: &mut String, decl: &FnDecl) {
+    out.push('(');
+    for (i, (_, input)) in decl.inputs.iter().enumerate() {
+        if i > 0 {
+            out.push_str(", ");
+        }
+        write_type(out, input);
+    }
+    if decl.c_variadic {
+        out.push_str(", ...");
+    }
+    out.push(')');
+    if let Some(output) = &decl.output {
+        out.push_str(" -> ");
+        write_type(out, output);
+    }
+}
+
+fn write_type(out: &mut String, ty: &Type) {
+    match ty {
+        Type::ResolvedPath { name, args, .. } => {
+            out.push_str(name);
+            if let Some(args) = args {
+                write_generic_args(out, args);
+            }
+        }
+        Type::Generic(name) | Type::Primitive(name) => out.push_str(name),
+        Type::FunctionPointer(f) => {
+            write_hrtb(out, &f.generic_params);
+            write_header(out, &f.header);
+            out.push_str("fn");
+            write_fn_decl(out, &f.decl);
+        }
+        Type::Tuple(types) => {
+            out.push('(');
+            for (i, ty) in types.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                write_type(out, ty);
+            }
+            if types.len() == 1 {
+                out.push(',');
+            }
+            out.push(')');
+        }
+        Type::Slice(ty) => {
+            out.push('[');
+            write_type(out, ty);
+            out.push(']');
+        }
+        Type::Array { type_, len } => {
+            out.push('[');
+            write_type(out, type_);
+            write!(out, "; {}]", len).unwrap();
+        }
+        Type::ImplTrait(bounds) => {
+            out.push_str("impl ");
+            write_bounds(out, bounds);
+        }
+        Type::Infer => out.push('_'),
+        Type::RawPointer { mutable, type_ } => {
+            out.push_str(if *mutable { "*mut " } else { "*const " });
+            write_type(out, type_);
+        }
+        Type::BorrowedRef { lifetime, mutable, type_ } => {
+            out.push('&');
+            if let Some(lifetime) = lifetime {
+                write!(out, "{} ", lifetime).unwrap();
+            }
+            if *mutable {
+                out.push_str("mut ");
+            }
+            write_type(out, type_);
+        }
+        Type::QualifiedPath { name, args, self_type, trait_ } => {
+            out.push('<');
+            write_type(out, self_type);
+            out.push_str(" as ");
+            write_type(out, trait_);
+            write!(out, ">::{}", name).unwrap();
+            write_generic_args(out, args);
+        }
+    }
+}
+
+fn write_generic_args(out: &mut String, args: &GenericArgs) {
+    match args {
+        GenericArgs::AngleBracketed { args, bindings } => {
+            if args.is_empty() && bindings.is_empty() {
+                return;
+            }
+            out.push('<');
+            let mut first = true;
+            let mut comma = |out: &mut String| {
+                if !std::mem::take(&mut first) {
+                    out.push_str(", ");
+                }
+            };
+            for arg in args {
+                comma(out);
+                match arg {
+                    GenericArg::Lifetime(lifetime) => out.push_str(lifetime),
+                    GenericArg::Type(ty) => write_type(out, ty),
+                    GenericArg::Const(c) => out.push_str(&c.expr),
+                    GenericArg::Infer => out.push('_'),
+                }
+            }
+            for binding in bindings {
+                comma(out);
+                out.push_str(&binding.name);
+                write_generic_args(out, &binding.args);
+                match &binding.binding {
+                    TypeBindingKind::Equality(term) => {
+                        out.push_str(" = ");
+                        write_term(out, term);
+                    }
+                    TypeBindingKind::Constraint(bounds) => {
+                        out.push_str(": ");
+                        write_bounds(out, bounds);
+                    }
+                }
+            }
+            out.push('>');
+        }
+        GenericArgs::Parenthesized { inputs, output } => {
+            out.push('(');
+            for (i, input) in inputs.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                write_type(out, input);
+            }
+            out.push(')');
+            if let Some(output) = output {
+                out.push_str(" -> ");
+                write_type(out, output);
+            }
+        }
+    }
+}
+
+fn write_term(out: &mut String, term: &Term) {
+    match term {
+        Term::Type(ty) => write_type(out, ty),
+        Term::Constant(c) => out.push_str(&c.expr),
+    }
+}
+
+fn write_hrtb(out: &mut String, params: &[GenericParamDef]) {
+    if params.is_empty() {
+        return;
+    }
+    out.push_str("for<");
+    for (i, param) in params.iter().enumerate() {
+        if i > 0 {
+            out.push_str(", ");
+        }
+        out.push_str(&param.name);
+    }
+    out.push_str("> ");
+}
+
+fn write_bound(out: &mut String, bound: &GenericBound) {
+    match bound {
+        GenericBound::TraitBound { trait_, generic_params, modifier } => {
+            write_hrtb(out, generic_params);
+            match modifier {
+                TraitBoundModifier::None => {}
+                TraitBoundModifier::Maybe => out.push('?'),
+                TraitBoundModifier::MaybeConst => out.push_str("~const "),
+            }
+            write_type(out, trait_);
+        }
+        GenericBound::Outlives(lifetime) => out.push_str(lifetime),
+    }
+}
+
+fn write_bounds(out: &mut String, bounds: &[GenericBound]) {
+    for (i, bound) in bounds.iter().enumerate() {
+        if i > 0 {
+            out.push_str(" + ");
+        }
+        write_bound(out, bound);
+    }
+}
+
+/// Prints a generic parameter the way it's declared, without its bounds.
+pub fn generic_param(param: &GenericParamDef) -> String {
+    match &param.kind {
+        GenericParamDefKind::Lifetime { .. } | GenericParamDefKind::Type { .. } => {
+            param.name.clone()
+        }
+        GenericParamDefKind::Const { type_, .. } => format!("const {}: {}", param.name, ty(type_)),
+    }
+}